@@ -0,0 +1,513 @@
+//! CI auto-fix watcher
+//!
+//! Opt-in per worktree. When the linked PR's checks fail, the watcher downloads
+//! the failed job logs of the head commit, starts a build-mode run on the chosen
+//! session with the logs as context, then commits and pushes the fix. Each new
+//! head commit gets at most one attempt, and the watcher stops after
+//! `max_attempts` fixes or once checks pass after a fix. Progress is emitted as
+//! `ci-fix:update` events.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::gh_cli::config::resolve_gh_binary;
+use crate::http_server::EmitExt;
+use crate::projects::github_actions::{
+    failed_run_log, failed_runs_for_commit, split_log_line, WorkflowRun,
+};
+use crate::projects::pr_status::{get_pr_status, CheckStatus};
+use crate::projects::storage::load_projects_data;
+
+/// Default number of fix attempts before the watcher gives up
+pub const DEFAULT_CI_FIX_ATTEMPTS: u32 = 3;
+
+/// Maximum number of fix attempts a watcher can be configured with
+pub const MAX_CI_FIX_ATTEMPTS: u32 = 10;
+
+/// Log lines kept from the end of each failed run
+const LOG_TAIL_LINES: usize = 150;
+
+/// Total log characters sent to the session across all failed runs
+const LOG_MAX_CHARS: usize = 40_000;
+
+/// Commit message used when the run left its fix uncommitted
+const CI_FIX_COMMIT_MESSAGE: &str = "Fix CI failures";
+
+/// Source of watcher generations
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+/// Watcher phase, reported in `ci-fix:update` events
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CiFixPhase {
+    /// Waiting for checks to fail
+    Watching,
+    /// Downloading failed job logs
+    FetchingLogs,
+    /// The session is working on a fix
+    Fixing,
+    /// Committing and pushing the fix
+    Pushing,
+    /// The fix was pushed; waiting for the new checks
+    Pushed,
+    /// Checks pass after a fix (watcher stopped)
+    Passed,
+    /// Out of attempts with checks still failing (watcher stopped)
+    Exhausted,
+    /// The attempt failed; the watcher keeps watching for a new head commit
+    Error,
+    /// Stopped by the user or by cancelling the fix run
+    Stopped,
+}
+
+impl CiFixPhase {
+    fn is_final(self) -> bool {
+        matches!(self, Self::Passed | Self::Exhausted | Self::Stopped)
+    }
+}
+
+/// State of a CI auto-fix watcher
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CiFixWatcher {
+    pub worktree_id: String,
+    /// Session the fix runs are sent to
+    pub session_id: String,
+    pub max_attempts: u32,
+    pub attempts: u32,
+    pub phase: CiFixPhase,
+    /// Head commit whose failure was last handled (never retried)
+    pub last_handled_sha: Option<String>,
+    /// Commit pushed by the last attempt
+    pub last_fix_sha: Option<String>,
+    pub message: Option<String>,
+    /// Timestamp of the last check (Unix seconds)
+    pub last_checked_at: u64,
+    /// An attempt or check is running
+    #[serde(skip)]
+    in_flight: bool,
+    /// Distinguishes a restarted watcher from the one it replaced, so a check
+    /// still running for the old watcher can't update the new one
+    #[serde(skip)]
+    generation: u64,
+}
+
+impl CiFixWatcher {
+    pub fn new(worktree_id: String, session_id: String, max_attempts: u32) -> Self {
+        Self {
+            worktree_id,
+            session_id,
+            max_attempts: max_attempts.clamp(1, MAX_CI_FIX_ATTEMPTS),
+            attempts: 0,
+            phase: CiFixPhase::Watching,
+            last_handled_sha: None,
+            last_fix_sha: None,
+            message: None,
+            last_checked_at: 0,
+            in_flight: false,
+            generation: NEXT_GENERATION.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+}
+
+/// CI auto-fix watchers by worktree ID
+pub type CiFixWatchers = Arc<Mutex<HashMap<String, CiFixWatcher>>>;
+
+/// `ci-fix:update` event payload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CiFixEvent<'a> {
+    #[serde(flatten)]
+    watcher: &'a CiFixWatcher,
+    /// Failed runs the current attempt is based on
+    run_ids: &'a [u64],
+}
+
+/// Emit the watcher state to the frontend
+pub fn emit_ci_fix_update(app: &AppHandle, watcher: &CiFixWatcher, run_ids: &[u64]) {
+    let event = CiFixEvent { watcher, run_ids };
+    if let Err(e) = app.emit_all("ci-fix:update", &event) {
+        log::error!("Failed to emit ci-fix:update event: {e}");
+    }
+}
+
+/// Update a watcher and emit its new state. Returns false when the watcher
+/// was stopped or restarted in the meantime (nothing is emitted then).
+fn transition(
+    app: &AppHandle,
+    watchers: &CiFixWatchers,
+    worktree_id: &str,
+    generation: u64,
+    run_ids: &[u64],
+    f: impl FnOnce(&mut CiFixWatcher),
+) -> bool {
+    let snapshot = {
+        let mut guard = watchers.lock().unwrap();
+        let Some(watcher) = guard
+            .get_mut(worktree_id)
+            .filter(|w| w.generation == generation)
+        else {
+            return false;
+        };
+        f(watcher);
+        let snapshot = watcher.clone();
+        if snapshot.phase.is_final() {
+            guard.remove(worktree_id);
+        }
+        snapshot
+    };
+    emit_ci_fix_update(app, &snapshot, run_ids);
+    true
+}
+
+/// Check every watcher that isn't busy, in the background.
+/// Called from the polling loop on the remote poll interval.
+pub fn poll_ci_fix_watchers(app: &AppHandle, watchers: &CiFixWatchers, now: u64) {
+    let due: Vec<(String, u64)> = {
+        let mut guard = watchers.lock().unwrap();
+        guard
+            .values_mut()
+            .filter(|w| !w.in_flight)
+            .map(|w| {
+                w.in_flight = true;
+                w.last_checked_at = now;
+                (w.worktree_id.clone(), w.generation)
+            })
+            .collect()
+    };
+
+    for (worktree_id, generation) in due {
+        let app = app.clone();
+        let watchers = Arc::clone(watchers);
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = check_worktree(&app, &watchers, &worktree_id, generation).await {
+                log::warn!("CI fix watcher for {worktree_id} failed: {e}");
+                transition(&app, &watchers, &worktree_id, generation, &[], |w| {
+                    w.phase = CiFixPhase::Error;
+                    w.message = Some(e);
+                });
+            }
+            let mut guard = watchers.lock().unwrap();
+            if let Some(w) = guard
+                .get_mut(&worktree_id)
+                .filter(|w| w.generation == generation)
+            {
+                w.in_flight = false;
+            }
+        });
+    }
+}
+
+/// One watcher tick: look at the PR checks and start a fix attempt if needed
+async fn check_worktree(
+    app: &AppHandle,
+    watchers: &CiFixWatchers,
+    worktree_id: &str,
+    generation: u64,
+) -> Result<(), String> {
+    let data = load_projects_data(app)?;
+    let worktree = data
+        .find_worktree(worktree_id)
+        .ok_or_else(|| format!("Worktree not found: {worktree_id}"))?;
+    let (Some(pr_number), Some(pr_url)) = (worktree.pr_number, worktree.pr_url.clone()) else {
+        return Err("This worktree has no linked pull request".to_string());
+    };
+    let worktree_path = worktree.path.clone();
+    let gh = resolve_gh_binary(app);
+
+    // The head commit comes from the same response as the checks, so a push
+    // between two queries can't pair one commit's failure with another's SHA
+    let status = tokio::task::spawn_blocking({
+        let worktree_path = worktree_path.clone();
+        let worktree_id = worktree_id.to_string();
+        let gh = gh.clone();
+        move || get_pr_status(&worktree_path, pr_number, &pr_url, &worktree_id, &gh)
+    })
+    .await
+    .map_err(|e| format!("CI status task failed: {e}"))??;
+
+    let Some(watcher) = watchers
+        .lock()
+        .unwrap()
+        .get(worktree_id)
+        .filter(|w| w.generation == generation)
+        .cloned()
+    else {
+        return Ok(());
+    };
+
+    match status.check_status {
+        Some(CheckStatus::Success) if watcher.attempts > 0 => {
+            transition(app, watchers, worktree_id, generation, &[], |w| {
+                w.phase = CiFixPhase::Passed;
+                w.message = Some("Checks pass".to_string());
+            });
+            return Ok(());
+        }
+        Some(CheckStatus::Failure) | Some(CheckStatus::Error) => {}
+        _ => return Ok(()),
+    }
+    let head_sha = status
+        .head_sha
+        .ok_or_else(|| "GitHub didn't report the PR's head commit".to_string())?;
+    if watcher.last_handled_sha.as_deref() == Some(head_sha.as_str()) {
+        return Ok(());
+    }
+    if watcher.attempts >= watcher.max_attempts {
+        transition(app, watchers, worktree_id, generation, &[], |w| {
+            w.phase = CiFixPhase::Exhausted;
+            w.message = Some(format!(
+                "Checks still failing after {} attempt(s)",
+                w.attempts
+            ));
+        });
+        return Ok(());
+    }
+    // Don't interrupt the user; try again on the next tick
+    if crate::chat::registry::is_session_actively_managed(&watcher.session_id) {
+        log::trace!("CI fix: session {} is busy, waiting", watcher.session_id);
+        return Ok(());
+    }
+
+    attempt_fix(
+        app,
+        watchers,
+        &watcher,
+        &gh,
+        &worktree_path,
+        pr_number,
+        &head_sha,
+    )
+    .await
+}
+
+/// Download the failed logs, run the session on them, and push the result.
+/// The attempt only counts (and the head commit is only marked as handled)
+/// once the logs are in and the fix run starts, so a failed download is
+/// retried on the next tick.
+async fn attempt_fix(
+    app: &AppHandle,
+    watchers: &CiFixWatchers,
+    watcher: &CiFixWatcher,
+    gh: &std::path::Path,
+    worktree_path: &str,
+    pr_number: u32,
+    head_sha: &str,
+) -> Result<(), String> {
+    let worktree_id = watcher.worktree_id.as_str();
+    let generation = watcher.generation;
+    let attempt = watcher.attempts + 1;
+    if !transition(app, watchers, worktree_id, generation, &[], |w| {
+        w.phase = CiFixPhase::FetchingLogs;
+        w.message = None;
+    }) {
+        return Ok(());
+    }
+
+    let (runs, logs) = tokio::task::spawn_blocking({
+        let gh = gh.to_path_buf();
+        let worktree_path = worktree_path.to_string();
+        let head_sha = head_sha.to_string();
+        move || -> Result<_, String> {
+            let runs = failed_runs_for_commit(&gh, &worktree_path, &head_sha)?;
+            let logs = runs
+                .iter()
+                .map(|run| {
+                    failed_run_log(&gh, &worktree_path, run.database_id)
+                        .unwrap_or_else(|e| format!("(failed to download log: {e})"))
+                })
+                .collect::<Vec<_>>();
+            Ok((runs, logs))
+        }
+    })
+    .await
+    .map_err(|e| format!("CI log task failed: {e}"))??;
+
+    if runs.is_empty() {
+        return Err(format!(
+            "No failed GitHub Actions runs for {}; the failing check may come from another CI",
+            short_sha(head_sha)
+        ));
+    }
+    let run_ids: Vec<u64> = runs.iter().map(|r| r.database_id).collect();
+
+    let metadata = crate::chat::storage::load_metadata(app, &watcher.session_id)?
+        .ok_or_else(|| format!("Session not found: {}", watcher.session_id))?;
+    let prefs = crate::load_preferences_sync(app).unwrap_or_default();
+    let parallel_execution_prompt = prefs.parallel_execution_prompt_enabled.then(|| {
+        prefs
+            .magic_prompts
+            .parallel_execution
+            .clone()
+            .unwrap_or_else(crate::default_parallel_execution_prompt)
+    });
+    let ai_language = Some(prefs.ai_language.clone()).filter(|l| !l.trim().is_empty());
+
+    let prompt = build_fix_prompt(&runs, &logs, pr_number, attempt, watcher.max_attempts);
+    if !transition(app, watchers, worktree_id, generation, &run_ids, |w| {
+        w.attempts = attempt;
+        w.last_handled_sha = Some(head_sha.to_string());
+        w.phase = CiFixPhase::Fixing;
+    }) {
+        return Ok(());
+    }
+    let response = crate::chat::send_chat_message(
+        app.clone(),
+        watcher.session_id.clone(),
+        worktree_id.to_string(),
+        worktree_path.to_string(),
+        prompt,
+        metadata.selected_model.clone(),
+        Some("build".to_string()),
+        metadata.selected_thinking_level.clone(),
+        None,
+        parallel_execution_prompt,
+        ai_language,
+        None,
+        None,
+        None,
+        Some(prefs.chrome_enabled),
+        None,
+        None,
+    )
+    .await?;
+    if response.cancelled {
+        transition(app, watchers, worktree_id, generation, &run_ids, |w| {
+            w.phase = CiFixPhase::Stopped;
+            w.message = Some("The fix run was cancelled".to_string());
+        });
+        return Ok(());
+    }
+
+    if !transition(app, watchers, worktree_id, generation, &run_ids, |w| {
+        w.phase = CiFixPhase::Pushing;
+    }) {
+        return Ok(());
+    }
+
+    let fix_sha = tokio::task::spawn_blocking({
+        let gh = gh.to_path_buf();
+        let worktree_path = worktree_path.to_string();
+        let head_sha = head_sha.to_string();
+        move || -> Result<String, String> {
+            use crate::projects::git;
+            if git::has_uncommitted_changes(&worktree_path) {
+                git::commit_changes(&worktree_path, CI_FIX_COMMIT_MESSAGE, true)?;
+            }
+            let local_head = git::rev_parse(&worktree_path, "HEAD")?;
+            if local_head == head_sha {
+                return Err("The fix run made no changes".to_string());
+            }
+            let push = git::git_push_to_pr(&worktree_path, pr_number, &gh)?;
+            if push.permission_denied {
+                return Err(format!("Push was rejected: {}", push.output.trim()));
+            }
+            Ok(local_head)
+        }
+    })
+    .await
+    .map_err(|e| format!("CI push task failed: {e}"))??;
+
+    transition(app, watchers, worktree_id, generation, &run_ids, |w| {
+        w.phase = CiFixPhase::Pushed;
+        w.last_fix_sha = Some(fix_sha.clone());
+        w.message = Some(format!(
+            "Pushed {} (attempt {attempt} of {})",
+            short_sha(&fix_sha),
+            w.max_attempts
+        ));
+    });
+    Ok(())
+}
+
+fn short_sha(sha: &str) -> &str {
+    &sha[..sha.len().min(7)]
+}
+
+/// Keep the last `max_lines` lines of a `gh run view --log-failed` log, with the
+/// job names and timestamps removed (the step names are kept)
+fn trim_failed_log(log: &str, max_lines: usize) -> String {
+    let lines: Vec<&str> = log.lines().collect();
+    let skipped = lines.len().saturating_sub(max_lines);
+    let mut out = String::new();
+    if skipped > 0 {
+        out.push_str(&format!("... ({skipped} earlier lines omitted)\n"));
+    }
+    for line in &lines[skipped..] {
+        let line = split_log_line(line);
+        match line.step {
+            Some(step) => out.push_str(&format!("{step} | {}\n", line.text)),
+            None => out.push_str(&format!("{}\n", line.text)),
+        }
+    }
+    out
+}
+
+/// Build the message asking the session to fix the failed runs
+fn build_fix_prompt(
+    runs: &[WorkflowRun],
+    logs: &[String],
+    pr_number: u32,
+    attempt: u32,
+    max_attempts: u32,
+) -> String {
+    let mut prompt = format!(
+        "CI failed on PR #{pr_number} (automatic fix attempt {attempt} of {max_attempts}). \
+         Find the cause in the logs below and fix it. Don't commit or push; that is done \
+         after you finish. If a failure is unrelated to the code (flaky test, outage, \
+         missing secret), say so and leave the code as is.\n"
+    );
+
+    let per_run_budget = LOG_MAX_CHARS / runs.len().max(1);
+    for (run, log) in runs.iter().zip(logs) {
+        let mut trimmed = trim_failed_log(log, LOG_TAIL_LINES);
+        if trimmed.len() > per_run_budget {
+            let mut cut = trimmed.len() - per_run_budget;
+            while !trimmed.is_char_boundary(cut) {
+                cut += 1;
+            }
+            trimmed = format!("...\n{}", &trimmed[cut..]);
+        }
+        prompt.push_str(&format!(
+            "\n## {} / {} (run {})\n{}\n\n```\n{}```\n",
+            run.workflow_name, run.name, run.database_id, run.url, trimmed
+        ));
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trim_failed_log() {
+        let log = "build\tRun tests\t2026-01-01T00:00:00.0000000Z running 3 tests\n\
+                   build\tRun tests\t2026-01-01T00:00:01.0000000Z test foo ... FAILED\n\
+                   build\tRun tests\t2026-01-01T00:00:02.0000000Z error: 1 test failed\n";
+        let trimmed = trim_failed_log(log, 2);
+        assert_eq!(
+            trimmed,
+            "... (1 earlier lines omitted)\n\
+             Run tests | test foo ... FAILED\n\
+             Run tests | error: 1 test failed\n"
+        );
+
+        assert_eq!(trim_failed_log("plain line", 10), "plain line\n");
+    }
+
+    #[test]
+    fn test_watcher_attempts_are_clamped() {
+        assert_eq!(CiFixWatcher::new("w".into(), "s".into(), 0).max_attempts, 1);
+        assert_eq!(
+            CiFixWatcher::new("w".into(), "s".into(), 50).max_attempts,
+            MAX_CI_FIX_ATTEMPTS
+        );
+        assert!(CiFixPhase::Passed.is_final());
+        assert!(!CiFixPhase::Error.is_final());
+    }
+}
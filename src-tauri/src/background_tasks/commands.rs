@@ -2,6 +2,7 @@
 
 use tauri::State;
 
+use super::ci_fix::{CiFixWatcher, DEFAULT_CI_FIX_ATTEMPTS};
 use super::{
     BackgroundTaskManager, MAX_POLL_INTERVAL, MAX_REMOTE_POLL_INTERVAL, MIN_POLL_INTERVAL,
     MIN_REMOTE_POLL_INTERVAL,
@@ -185,3 +186,41 @@ pub fn set_all_worktrees_for_polling(
     state.set_all_worktrees(infos);
     Ok(())
 }
+
+/// Start the CI auto-fix watcher for a worktree's linked PR.
+///
+/// When the PR's checks fail, failed job logs are sent to `session_id` in build
+/// mode and the fix is pushed, up to `max_attempts` times (default 3, max 10).
+/// Progress is emitted as `ci-fix:update` events.
+#[tauri::command]
+pub fn start_ci_fix_watcher(
+    state: State<'_, BackgroundTaskManager>,
+    worktree_id: String,
+    session_id: String,
+    max_attempts: Option<u32>,
+) -> Result<CiFixWatcher, String> {
+    let watcher = CiFixWatcher::new(
+        worktree_id,
+        session_id,
+        max_attempts.unwrap_or(DEFAULT_CI_FIX_ATTEMPTS),
+    );
+    Ok(state.start_ci_fix_watcher(watcher))
+}
+
+/// Stop the CI auto-fix watcher for a worktree
+#[tauri::command]
+pub fn stop_ci_fix_watcher(
+    state: State<'_, BackgroundTaskManager>,
+    worktree_id: String,
+) -> Result<(), String> {
+    state.stop_ci_fix_watcher(&worktree_id);
+    Ok(())
+}
+
+/// Get all active CI auto-fix watchers
+#[tauri::command]
+pub fn get_ci_fix_watchers(
+    state: State<'_, BackgroundTaskManager>,
+) -> Result<Vec<CiFixWatcher>, String> {
+    Ok(state.get_ci_fix_watchers())
+}
@@ -16,12 +16,17 @@ use std::time::Duration;
 
 use tauri::AppHandle;
 
+use self::ci_fix::{poll_ci_fix_watchers, CiFixWatcher, CiFixWatchers};
+use self::run_tracker::{poll_tracked_runs_if_due, TrackedRuns};
 use crate::gh_cli::config::resolve_gh_binary;
 use crate::http_server::EmitExt;
+use crate::projects::forge;
 use crate::projects::git_status::{get_branch_status, ActiveWorktreeInfo, GitBranchStatus};
-use crate::projects::pr_status::{get_pr_status, PrStatus};
+use crate::projects::pr_status::PrStatus;
 
+pub mod ci_fix;
 pub mod commands;
+pub mod run_tracker;
 
 // ============================================================================
 // Local polling constants (git commands that run locally)
@@ -137,6 +142,14 @@ pub struct BackgroundTaskManager {
     usage_poll_in_flight: Arc<AtomicBool>,
     /// Timestamp of last combined-context cleanup sweep
     last_cleanup_poll_time: Arc<AtomicU64>,
+    /// CI auto-fix watchers by worktree ID
+    ci_fix_watchers: CiFixWatchers,
+    /// Timestamp of last CI auto-fix check
+    last_ci_fix_poll_time: Arc<AtomicU64>,
+    /// Workflow runs started from Jean, followed until they complete
+    tracked_runs: TrackedRuns,
+    /// Timestamp of last tracked-run poll
+    last_run_track_time: Arc<AtomicU64>,
 }
 
 impl BackgroundTaskManager {
@@ -165,6 +178,10 @@ impl BackgroundTaskManager {
             // Initialize to "now" so startup does not trigger an immediate cleanup
             // (the startup cleanup in cleanup_old_archives already handles that).
             last_cleanup_poll_time: Arc::new(AtomicU64::new(now_unix_secs())),
+            ci_fix_watchers: Arc::new(Mutex::new(HashMap::new())),
+            last_ci_fix_poll_time: Arc::new(AtomicU64::new(0)),
+            tracked_runs: Arc::new(Mutex::new(HashMap::new())),
+            last_run_track_time: Arc::new(AtomicU64::new(0)),
         }
     }
 
@@ -198,6 +215,10 @@ impl BackgroundTaskManager {
         let last_usage_poll_time = Arc::clone(&self.last_usage_poll_time);
         let usage_poll_in_flight = Arc::clone(&self.usage_poll_in_flight);
         let last_cleanup_poll_time = Arc::clone(&self.last_cleanup_poll_time);
+        let ci_fix_watchers = Arc::clone(&self.ci_fix_watchers);
+        let last_ci_fix_poll_time = Arc::clone(&self.last_ci_fix_poll_time);
+        let tracked_runs = Arc::clone(&self.tracked_runs);
+        let last_run_track_time = Arc::clone(&self.last_run_track_time);
         let usage_poll_enabled = usage_polling_enabled();
 
         thread::spawn(move || {
@@ -292,6 +313,26 @@ impl BackgroundTaskManager {
                     }
                 }
 
+                // ================================================================
+                // CI auto-fix watchers (on the remote interval)
+                // Runs independently from app focus so fixes land while away.
+                // ================================================================
+                {
+                    let now = now_unix_secs();
+                    let last_ci_fix = last_ci_fix_poll_time.load(Ordering::Relaxed);
+                    let remote_interval = remote_poll_interval_secs.load(Ordering::Relaxed);
+
+                    if now.saturating_sub(last_ci_fix) >= remote_interval {
+                        last_ci_fix_poll_time.store(now, Ordering::Relaxed);
+                        poll_ci_fix_watchers(&app, &ci_fix_watchers, now);
+                    }
+                }
+
+                // ================================================================
+                // Tracked workflow runs (short interval until they complete)
+                // ================================================================
+                poll_tracked_runs_if_due(&app, &tracked_runs, &last_run_track_time);
+
                 // Only poll when app is focused
                 if !is_focused.load(Ordering::Relaxed) {
                     thread::sleep(Duration::from_secs(1));
@@ -394,7 +435,8 @@ impl BackgroundTaskManager {
                             }
 
                             let gh = resolve_gh_binary(&app);
-                            match get_pr_status(
+                            match forge::get_pr_status(
+                                &app,
                                 &info.worktree_path,
                                 *pr_number,
                                 pr_url,
@@ -458,7 +500,8 @@ impl BackgroundTaskManager {
                                 );
 
                                 let gh = resolve_gh_binary(&app);
-                                match get_pr_status(
+                                match forge::get_pr_status(
+                                    &app,
                                     &candidate.worktree_path,
                                     *pr_num,
                                     pr_url,
@@ -543,6 +586,8 @@ impl BackgroundTaskManager {
                     {
                         break;
                     }
+                    // Tracked runs are polled more often than the loop runs
+                    poll_tracked_runs_if_due(&app, &tracked_runs, &last_run_track_time);
                     thread::sleep(Duration::from_secs(1));
                 }
             }
@@ -682,6 +727,59 @@ impl BackgroundTaskManager {
         *guard = worktrees;
     }
 
+    /// Start (or restart) the CI auto-fix watcher for a worktree.
+    ///
+    /// The first check runs on the next loop iteration. A check or attempt still
+    /// running for a replaced watcher can't update the new one (see
+    /// `CiFixWatcher::generation`).
+    pub fn start_ci_fix_watcher(&self, watcher: CiFixWatcher) -> CiFixWatcher {
+        log::trace!(
+            "Starting CI fix watcher for {} (session {}, {} attempts)",
+            watcher.worktree_id,
+            watcher.session_id,
+            watcher.max_attempts
+        );
+        self.ci_fix_watchers
+            .lock()
+            .unwrap()
+            .insert(watcher.worktree_id.clone(), watcher.clone());
+        self.last_ci_fix_poll_time.store(0, Ordering::Relaxed);
+        ci_fix::emit_ci_fix_update(&self.app, &watcher, &[]);
+        watcher
+    }
+
+    /// Stop the CI auto-fix watcher for a worktree.
+    ///
+    /// A fix run already in progress is not cancelled, but nothing is pushed after it.
+    pub fn stop_ci_fix_watcher(&self, worktree_id: &str) {
+        let removed = self.ci_fix_watchers.lock().unwrap().remove(worktree_id);
+        if let Some(mut watcher) = removed {
+            log::trace!("Stopped CI fix watcher for {worktree_id}");
+            watcher.phase = ci_fix::CiFixPhase::Stopped;
+            ci_fix::emit_ci_fix_update(&self.app, &watcher, &[]);
+        }
+    }
+
+    /// Get all active CI auto-fix watchers
+    pub fn get_ci_fix_watchers(&self) -> Vec<CiFixWatcher> {
+        self.ci_fix_watchers
+            .lock()
+            .unwrap()
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// Follow a workflow run until it completes.
+    ///
+    /// The run is polled every 15 seconds and emitted as `workflow-run:update`.
+    pub fn track_workflow_run(&self, project_path: String, run_id: u64, min_attempt: Option<u32>) {
+        log::trace!("Tracking workflow run {run_id} in {project_path}");
+        run_tracker::track(&self.tracked_runs, project_path, run_id, min_attempt);
+        // Report the first state on the next loop iteration
+        self.last_run_track_time.store(0, Ordering::Relaxed);
+    }
+
     /// Trigger an immediate remote poll
     ///
     /// This bypasses the normal remote polling interval.
@@ -0,0 +1,173 @@
+//! Live tracking of workflow runs started from Jean
+//!
+//! Runs that were dispatched, re-run or cancelled from Jean are polled on a
+//! short interval until they complete, and each poll is emitted as a
+//! `workflow-run:update` event. Polls run on blocking tasks so a slow `gh`
+//! never holds up the polling loop.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::gh_cli::config::resolve_gh_binary;
+use crate::http_server::EmitExt;
+use crate::projects::github_actions::{fetch_workflow_run, WorkflowRun};
+
+/// Polling interval for tracked runs in seconds
+pub const RUN_TRACK_INTERVAL: u64 = 15;
+
+/// Consecutive failed polls after which a run is no longer tracked
+const MAX_POLL_ERRORS: u32 = 3;
+
+/// Polls still showing the previous attempt after which a re-run is no longer
+/// tracked (about 5 minutes)
+const MAX_STALE_POLLS: u32 = 20;
+
+/// A workflow run being followed until it completes
+#[derive(Debug, Clone)]
+pub struct TrackedRun {
+    pub project_path: String,
+    pub run_id: u64,
+    /// After a re-run: the attempt GitHub must report before the run's state
+    /// is current (until then it still shows the previous, completed attempt)
+    min_attempt: Option<u32>,
+    errors: u32,
+    stale_polls: u32,
+    /// A poll for this run is running
+    in_flight: bool,
+}
+
+/// Tracked workflow runs by run ID
+pub type TrackedRuns = Arc<Mutex<HashMap<u64, TrackedRun>>>;
+
+/// `workflow-run:update` event payload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WorkflowRunEvent<'a> {
+    project_path: &'a str,
+    run: &'a WorkflowRun,
+}
+
+/// Start following a run (a re-run of a tracked run just keeps it tracked)
+pub fn track(runs: &TrackedRuns, project_path: String, run_id: u64, min_attempt: Option<u32>) {
+    let mut guard = runs.lock().unwrap();
+    let in_flight = guard.get(&run_id).is_some_and(|r| r.in_flight);
+    guard.insert(
+        run_id,
+        TrackedRun {
+            project_path,
+            run_id,
+            min_attempt,
+            errors: 0,
+            stale_polls: 0,
+            in_flight,
+        },
+    );
+}
+
+/// Poll the tracked runs if the tracking interval has passed since `last_poll`.
+/// Called from the polling loop, including while it waits between iterations.
+pub fn poll_tracked_runs_if_due(app: &AppHandle, runs: &TrackedRuns, last_poll: &AtomicU64) {
+    let now = super::now_unix_secs();
+    if now.saturating_sub(last_poll.load(Ordering::Relaxed)) < RUN_TRACK_INTERVAL {
+        return;
+    }
+    last_poll.store(now, Ordering::Relaxed);
+    poll_tracked_runs(app, runs);
+}
+
+/// Start a poll for every tracked run that isn't already being polled
+fn poll_tracked_runs(app: &AppHandle, runs: &TrackedRuns) {
+    let due: Vec<TrackedRun> = runs
+        .lock()
+        .unwrap()
+        .values_mut()
+        .filter(|r| !r.in_flight)
+        .map(|r| {
+            r.in_flight = true;
+            r.clone()
+        })
+        .collect();
+    if due.is_empty() {
+        return;
+    }
+    let gh = resolve_gh_binary(app);
+
+    for entry in due {
+        let app = app.clone();
+        let runs = Arc::clone(runs);
+        let gh = gh.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            let result = fetch_workflow_run(&gh, &entry.project_path, entry.run_id);
+            poll_finished(&app, &runs, &entry, result);
+        });
+    }
+}
+
+/// Emit a polled run's state and update or drop its tracking entry
+fn poll_finished(
+    app: &AppHandle,
+    runs: &TrackedRuns,
+    entry: &TrackedRun,
+    result: Result<WorkflowRun, String>,
+) {
+    let mut guard = runs.lock().unwrap();
+    let Some(tracked) = guard.get_mut(&entry.run_id) else {
+        return;
+    };
+    tracked.in_flight = false;
+
+    let run = match result {
+        Ok(run) => run,
+        Err(e) => {
+            log::warn!("Failed to poll workflow run {}: {e}", entry.run_id);
+            tracked.errors += 1;
+            if tracked.errors >= MAX_POLL_ERRORS {
+                guard.remove(&entry.run_id);
+            }
+            return;
+        }
+    };
+    tracked.errors = 0;
+
+    if tracked
+        .min_attempt
+        .is_some_and(|attempt| run.attempt < attempt)
+    {
+        log::trace!(
+            "Tracked run {} still reports attempt {}, waiting for the re-run",
+            run.database_id,
+            run.attempt
+        );
+        tracked.stale_polls += 1;
+        if tracked.stale_polls >= MAX_STALE_POLLS {
+            log::warn!("Re-run of workflow run {} never started", entry.run_id);
+            guard.remove(&entry.run_id);
+        }
+        return;
+    }
+    tracked.min_attempt = None;
+
+    log::trace!(
+        "Tracked run {}: attempt={}, status={}, conclusion={:?}",
+        run.database_id,
+        run.attempt,
+        run.status,
+        run.conclusion
+    );
+    if run.status == "completed" {
+        guard.remove(&entry.run_id);
+    }
+    drop(guard);
+
+    let event = WorkflowRunEvent {
+        project_path: &entry.project_path,
+        run: &run,
+    };
+    if let Err(e) = app.emit_all("workflow-run:update", &event) {
+        log::error!("Failed to emit workflow-run:update event: {e}");
+    }
+}
@@ -8,6 +8,7 @@ use crate::http_server::EmitExt;
 use crate::projects::github_issues::{
     get_github_contexts_dir, get_session_issue_refs, get_session_pr_refs,
 };
+use crate::projects::jira_issues::get_session_jira_refs;
 use crate::projects::linear_issues::get_session_linear_refs;
 use crate::projects::storage::load_projects_data;
 
@@ -355,6 +356,21 @@ fn build_claude_args(
         }
     }
 
+    // Permission policy (project settings + jean.json): allow/deny become CLI flags,
+    // ask rules go into the settings JSON
+    let policy_rules = super::policy::load_effective_policy(app, worktree_id)
+        .map(|policy| policy.to_claude_rules())
+        .unwrap_or_default();
+    if !policy_rules.ask.is_empty() {
+        let obj = settings_json.get_or_insert_with(|| serde_json::json!({}));
+        if let Some(map) = obj.as_object_mut() {
+            map.insert(
+                "permissions".to_string(),
+                serde_json::json!({ "ask": policy_rules.ask }),
+            );
+        }
+    }
+
     // Emit --settings if we have any settings to pass
     if let Some(settings) = &settings_json {
         args.push("--settings".to_string());
@@ -369,6 +385,15 @@ fn build_claude_args(
         }
     }
 
+    for tool in &policy_rules.allowed {
+        args.push("--allowedTools".to_string());
+        args.push(tool.clone());
+    }
+    for tool in &policy_rules.disallowed {
+        args.push("--disallowedTools".to_string());
+        args.push(tool.clone());
+    }
+
     // Allow embedded CLI binaries without approval via --allowedTools
     // Claude wraps paths with spaces in quotes, so the actual command is:
     // "/Users/.../Application Support/.../gh-cli/gh" --version
@@ -586,6 +611,37 @@ fn build_claude_args(
         }
     }
 
+    // Check for Jira issue context files (shared storage)
+    let mut jira_keys = get_session_jira_refs(app, session_id).unwrap_or_default();
+    if let Ok(wt_keys) = get_session_jira_refs(app, worktree_id) {
+        for key in wt_keys {
+            if !jira_keys.contains(&key) {
+                jira_keys.push(key);
+            }
+        }
+    }
+    if !jira_keys.is_empty() {
+        if let Ok(contexts_dir) = get_github_contexts_dir(app) {
+            for key in jira_keys {
+                // key format: "{project_name}-{issue_key}" where issue_key is "ENG-123"
+                // context file format: "{project_name}-jira-{issue_key_lower}.md"
+                // Jira project keys can't contain dashes, so like Linear identifiers
+                // rsplitn(3, '-') separates the number, project key, and project name.
+                let parts: Vec<&str> = key.rsplitn(3, '-').collect();
+                if parts.len() == 3 {
+                    let project_name_part = parts[2];
+                    let key_lower = format!("{}-{}", parts[1].to_lowercase(), parts[0]);
+                    let file_path =
+                        contexts_dir.join(format!("{project_name_part}-jira-{key_lower}.md"));
+                    if file_path.exists() {
+                        log::trace!("Adding Jira issue context file: {:?}", file_path);
+                        all_context_paths.push(file_path);
+                    }
+                }
+            }
+        }
+    }
+
     // Check for attached saved context files
     if let Ok(app_data_dir) = app.path().app_data_dir() {
         let saved_contexts_dir = app_data_dir.join("session-context");
@@ -645,6 +701,13 @@ fn build_claude_args(
                     s.contains("git-context") && s.contains("-linear-")
                 })
                 .count();
+            let jira_count = all_context_paths
+                .iter()
+                .filter(|p| {
+                    let s = p.to_string_lossy();
+                    s.contains("git-context") && s.contains("-jira-")
+                })
+                .count();
             let saved_context_count = all_context_paths
                 .iter()
                 .filter(|p| {
@@ -673,7 +736,12 @@ fn build_claude_args(
                 combined_content
                     .push_str("You should be aware of this when working on this task.\n\n");
 
-                if issue_count > 0 || pr_count > 0 || linear_count > 0 || saved_context_count > 0 {
+                if issue_count > 0
+                    || pr_count > 0
+                    || linear_count > 0
+                    || jira_count > 0
+                    || saved_context_count > 0
+                {
                     combined_content.push_str("**Summary:**\n");
                     if issue_count > 0 {
                         combined_content.push_str(&format!("- {} GitHub Issue(s)\n", issue_count));
@@ -685,6 +753,9 @@ fn build_claude_args(
                     if linear_count > 0 {
                         combined_content.push_str(&format!("- {} Linear Issue(s)\n", linear_count));
                     }
+                    if jira_count > 0 {
+                        combined_content.push_str(&format!("- {} Jira Issue(s)\n", jira_count));
+                    }
                     if saved_context_count > 0 {
                         combined_content
                             .push_str(&format!("- {} Saved Context(s)\n", saved_context_count));
@@ -745,9 +816,66 @@ fn build_claude_args(
         env_vars.push(("JEAN_CLAUDE_SESSION_ID".to_string(), claude_sid.to_string()));
     }
 
+    // Ports allocated to the worktree, so dev servers the agent starts don't collide
+    if let Some(ports) = crate::projects::ports::ensure_ports(app, worktree_id) {
+        env_vars.extend(ports.env_vars());
+    }
+
     (args, env_vars)
 }
 
+/// Wrapper command for a detached Claude run: `exec` into the worktree's
+/// container when jean.json declares one, else bubblewrap when the project
+/// enables the sandbox, else nothing.
+fn run_command_prefix(
+    app: &tauri::AppHandle,
+    session_id: &str,
+    worktree_id: &str,
+    working_dir: &std::path::Path,
+    env_keys: &[&str],
+) -> Result<Vec<String>, String> {
+    let workdir = working_dir.to_string_lossy();
+    if let Some(container) = crate::projects::container::ensure_container(app, &workdir)? {
+        if container.runs_agent {
+            log::debug!(
+                "Running Claude CLI in container {} for session: {session_id}",
+                container.name
+            );
+            return Ok(container.agent_command_prefix(&workdir, session_id, env_keys));
+        }
+    }
+
+    if let Some(spec) = super::sandbox::resolve_for_worktree(
+        app,
+        worktree_id,
+        session_id,
+        &super::types::Backend::Claude,
+    )? {
+        log::debug!("Running Claude CLI in sandbox for session: {session_id}");
+        let bwrap = super::sandbox::find_bwrap()?;
+        return Ok(sandbox_command_prefix(&bwrap, spec, working_dir));
+    }
+
+    Ok(Vec::new())
+}
+
+/// bubblewrap prefix for a detached Claude run. The run is started via
+/// `sh -c "… &"` and must keep going after that shell (and Jean) exits.
+fn sandbox_command_prefix(
+    bwrap: &std::path::Path,
+    spec: super::sandbox::SandboxSpec,
+    working_dir: &std::path::Path,
+) -> Vec<String> {
+    let spec = super::sandbox::SandboxSpec {
+        detached: true,
+        ..spec
+    };
+    let mut prefix = vec![bwrap.to_string_lossy().to_string()];
+    prefix.extend(spec.bwrap_args(working_dir));
+    prefix.push("--".to_string());
+    prefix
+}
+
 /// Execute Claude CLI in detached mode.
 ///
 /// Spawns Claude CLI as a fully detached process that survives Jean quitting.
@@ -836,6 +964,22 @@ pub fn execute_claude_detached(
         .map(|(k, v)| (k.as_str(), v.as_str()))
         .collect();
 
+    // Container (jean.json) or sandbox (project setting) wrapper
+    let env_keys: Vec<&str> = env_vars.iter().map(|(k, _)| k.as_str()).collect();
+    let command_prefix = run_command_prefix(app, session_id, worktree_id, working_dir, &env_keys)
+        .map_err(|e| {
+        log::error!("{e}");
+        let _ = app.emit_all(
+            "chat:error",
+            &ErrorEvent {
+                session_id: session_id.to_string(),
+                worktree_id: worktree_id.to_string(),
+                error: e.clone(),
+            },
+        );
+        e
+    })?;
+
     // Spawn detached process
     let pid = spawn_detached_claude(
         &cli_path,
@@ -844,6 +988,7 @@ pub fn execute_claude_detached(
         output_file,
         working_dir,
         &env_refs,
+        &command_prefix,
     )
     .map_err(|e| {
         let error_msg = format!("Failed to start Claude CLI: {e}");
@@ -1290,9 +1435,16 @@ pub fn tail_claude_output(
                                         tool_use_id: d.get("tool_use_id")?.as_str()?.to_string(),
                                         tool_input: tool_input.clone(),
                                         rpc_id: None,
+                                        permission_id: None,
                                     })
                                 })
                                 .collect();
+                            let denial_events = super::policy::filter_claude_denials(
+                                app,
+                                session_id,
+                                worktree_id,
+                                denial_events,
+                            );
 
                             if !denial_events.is_empty() {
                                 log::trace!(
@@ -1478,3 +1630,24 @@ pub fn tail_claude_output(
         usage,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn test_sandbox_prefix_survives_detaching() {
+        let spec = super::super::sandbox::SandboxSpec {
+            writable_paths: vec![PathBuf::from("/wt")],
+            read_only_paths: Vec::new(),
+            block_network: false,
+            detached: false,
+        };
+        let prefix = sandbox_command_prefix(Path::new("/usr/bin/bwrap"), spec, Path::new("/wt"));
+
+        assert_eq!(prefix.first().map(String::as_str), Some("/usr/bin/bwrap"));
+        assert_eq!(prefix.last().map(String::as_str), Some("--"));
+        assert!(!prefix.contains(&"--die-with-parent".to_string()));
+    }
+}
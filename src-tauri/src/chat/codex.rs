@@ -8,7 +8,11 @@
 //! directly since they don't need streaming.
 
 use super::claude::CancelledEvent;
-use super::types::{ContentBlock, PermissionDenial, PermissionDeniedEvent, ToolCall, UsageData};
+use super::policy::ToolRequest;
+use super::types::{
+    ContentBlock, PermissionDenial, PermissionDeniedEvent, PermissionPolicy, PolicyAction,
+    ToolCall, UsageData,
+};
 use crate::http_server::EmitExt;
 
 use std::collections::HashMap;
@@ -240,6 +244,31 @@ pub fn build_turn_start_params(
     params
 }
 
+/// Replace the turn's sandbox policy with the project sandbox: writable roots
+/// from the spec (plus `add_dirs`). Build mode never gets network access; yolo
+/// keeps it unless the project blocks it.
+fn apply_sandbox_policy(
+    params: &mut serde_json::Value,
+    spec: &super::sandbox::SandboxSpec,
+    add_dirs: &[String],
+    is_build_mode: bool,
+) {
+    let mut writable_roots = spec.writable_roots();
+    for dir in add_dirs {
+        if !writable_roots.contains(dir) {
+            writable_roots.push(dir.clone());
+        }
+    }
+    params["sandboxPolicy"] = serde_json::json!({
+        "type": "workspaceWrite",
+        "writableRoots": writable_roots,
+        "readOnlyAccess": { "type": "fullAccess" },
+        "networkAccess": !is_build_mode && !spec.block_network,
+        "excludeTmpdirEnvVar": false,
+        "excludeSlashTmp": false,
+    });
+}
+
 // =============================================================================
 // Execution via app-server
 // =============================================================================
@@ -271,6 +300,13 @@ pub fn execute_codex_via_server(
     let is_plan_mode = execution_mode.unwrap_or("plan") == "plan";
     let is_build_mode = execution_mode.unwrap_or("plan") == "build";
 
+    // Permission policy: yolo normally never asks for approval, so when the policy
+    // has deny/ask rules, request approvals and let the policy decide each one.
+    let policy = super::policy::load_policy_with_worktree_path(app, worktree_id);
+    let force_approvals = !is_plan_mode
+        && !is_build_mode
+        && policy.as_ref().is_some_and(|(p, _)| p.has_restrictions());
+
     log::debug!(
         "Codex server turn: session={session_id}, model={model:?}, mode={execution_mode:?}, effort={reasoning_effort:?}, resume={}",
         existing_thread_id.is_some()
@@ -284,7 +320,7 @@ pub fn execute_codex_via_server(
     // (ensure_running incremented it, but no session is registered yet)
     let thread_id_result = if let Some(tid) = existing_thread_id {
         // Resume existing thread
-        let resume_params = build_thread_start_params(
+        let mut resume_params = build_thread_start_params(
             working_dir,
             model,
             execution_mode,
@@ -293,6 +329,9 @@ pub fn execute_codex_via_server(
             multi_agent_enabled,
             max_agent_threads,
         );
+        if force_approvals {
+            resume_params["approvalPolicy"] = serde_json::json!("untrusted");
+        }
         let mut full_params =
             serde_json::json!({ "threadId": tid, "persistExtendedHistory": true });
         // Copy overridable fields
@@ -320,6 +359,7 @@ pub fn execute_codex_via_server(
                     instructions_file,
                     multi_agent_enabled,
                     max_agent_threads,
+                    force_approvals,
                 )
             }
         }
@@ -332,6 +372,7 @@ pub fn execute_codex_via_server(
             instructions_file,
             multi_agent_enabled,
             max_agent_threads,
+            force_approvals,
         )
     };
 
@@ -345,7 +386,7 @@ pub fn execute_codex_via_server(
     };
 
     // Build turn params
-    let turn_params = build_turn_start_params(
+    let mut turn_params = build_turn_start_params(
         &thread_id,
         prompt,
         working_dir,
@@ -354,6 +395,19 @@ pub fn execute_codex_via_server(
         add_dirs,
     );
 
+    // Project sandbox: the app-server is shared, so apply Codex's own sandbox
+    // per turn (also in yolo mode). Plan mode is already read-only.
+    if !is_plan_mode {
+        if let Some(spec) = super::sandbox::resolve_for_worktree(
+            app,
+            worktree_id,
+            session_id,
+            &super::types::Backend::Codex,
+        )? {
+            apply_sandbox_policy(&mut turn_params, &spec, add_dirs, is_build_mode);
+        }
+    }
+
     // Set up event channel for this session
     let (event_tx, event_rx) = std::sync::mpsc::channel();
     let ctx = codex_server::SessionContext {
@@ -385,6 +439,7 @@ pub fn execute_codex_via_server(
         output_file,
         is_plan_mode,
         is_build_mode,
+        policy.as_ref(),
         &event_rx,
     );
     super::decrement_tailer_count();
@@ -403,6 +458,7 @@ pub fn execute_codex_via_server(
 }
 
 /// Start a new Codex thread via app-server.
+#[allow(clippy::too_many_arguments)]
 fn start_new_thread(
     working_dir: &std::path::Path,
     model: Option<&str>,
@@ -411,10 +467,11 @@ fn start_new_thread(
     instructions_file: Option<&std::path::Path>,
     multi_agent_enabled: bool,
     max_agent_threads: Option<u32>,
+    force_approvals: bool,
 ) -> Result<String, String> {
     use super::codex_server;
 
-    let params = build_thread_start_params(
+    let mut params = build_thread_start_params(
         working_dir,
         model,
         execution_mode,
@@ -423,6 +480,9 @@ fn start_new_thread(
         multi_agent_enabled,
         max_agent_threads,
     );
+    if force_approvals {
+        params["approvalPolicy"] = serde_json::json!("untrusted");
+    }
 
     let result = codex_server::send_request("thread/start", params)?;
     let thread_id = result
@@ -446,6 +506,7 @@ fn process_turn_events(
     output_file: &std::path::Path,
     is_plan_mode: bool,
     is_build_mode: bool,
+    policy: Option<&(PermissionPolicy, String)>,
     event_rx: &std::sync::mpsc::Receiver<super::codex_server::ServerEvent>,
 ) -> CodexResponse {
     use super::codex_server::ServerEvent;
@@ -560,7 +621,10 @@ fn process_turn_events(
                     id,
                     &method,
                     &params,
-                    is_build_mode,
+                    &tool_calls,
+                    policy,
+                    is_plan_mode,
+                    !is_plan_mode && !is_build_mode,
                 );
             }
             ServerEvent::ServerDied => {
@@ -956,6 +1020,15 @@ fn normalize_item_types(item: &serde_json::Value) -> serde_json::Value {
 }
 
 /// Handle an approval request from the app-server.
+///
+/// The project's permission policy is consulted first; requests no rule matches
+/// fall back to the execution mode default: in build mode commands and file
+/// changes are surfaced to the user (with the proposed patch), plan mode accepts
+/// file changes (the read-only sandbox prevents writes), and yolo mode only gets
+/// approval requests because of the policy, so unmatched ones are accepted.
+/// File changes without paths are always surfaced when the policy restricts
+/// anything, unless a rule denies them.
+#[allow(clippy::too_many_arguments)]
 fn handle_approval_request(
     app: &tauri::AppHandle,
     session_id: &str,
@@ -963,65 +1036,149 @@ fn handle_approval_request(
     rpc_id: u64,
     method: &str,
     params: &serde_json::Value,
-    is_build_mode: bool,
+    tool_calls: &[ToolCall],
+    policy: Option<&(PermissionPolicy, String)>,
+    is_plan_mode: bool,
+    is_yolo_mode: bool,
 ) {
+    let item_id = params
+        .get("itemId")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+
+    let respond = |decision: &str| {
+        if let Err(e) =
+            super::codex_server::send_response(rpc_id, serde_json::json!({ "decision": decision }))
+        {
+            log::error!("Failed to send approval decision '{decision}' (rpc_id={rpc_id}): {e}");
+        }
+    };
+    let surface = |tool_name: &str, tool_input: serde_json::Value| {
+        let denial = PermissionDenial {
+            tool_name: tool_name.to_string(),
+            tool_use_id: item_id.clone(),
+            tool_input,
+            rpc_id: Some(rpc_id),
+            permission_id: None,
+        };
+        let _ = app.emit_all(
+            "chat:permission_denied",
+            &PermissionDeniedEvent {
+                session_id: session_id.to_string(),
+                worktree_id: worktree_id.to_string(),
+                denials: vec![denial],
+            },
+        );
+        // Response will come from approve_codex_command Tauri command
+    };
+
     match method {
         "item/fileChange/requestApproval" => {
-            // Auto-accept file changes in build mode
-            if is_build_mode {
-                log::trace!("Auto-accepting file change (rpc_id={rpc_id})");
-                if let Err(e) = super::codex_server::send_response(
-                    rpc_id,
-                    serde_json::json!({"decision": "accept"}),
-                ) {
-                    log::error!("Failed to auto-accept file change: {e}");
-                }
-            } else {
-                // In non-build modes, also auto-accept (read-only sandbox prevents actual changes)
-                let _ = super::codex_server::send_response(
-                    rpc_id,
-                    serde_json::json!({"decision": "accept"}),
+            // The file change item (with its paths) was emitted before the approval request
+            let changes = tool_calls
+                .iter()
+                .find(|tc| tc.id == item_id)
+                .map(|tc| tc.input.clone())
+                .unwrap_or(serde_json::Value::Null);
+            let mut unchecked_paths = false;
+            let decision = policy.and_then(|(policy, worktree_path)| {
+                let paths: Vec<String> = changes
+                    .as_array()
+                    .map(|arr| {
+                        arr.iter()
+                            .filter_map(|c| c.get("path").and_then(|p| p.as_str()))
+                            .map(String::from)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                // Path rules can't be checked without paths, so the user decides
+                unchecked_paths = paths.is_empty() && policy.has_restrictions();
+                let request = ToolRequest {
+                    tool: "Edit",
+                    command: None,
+                    paths: super::policy::relative_paths(
+                        std::path::Path::new(worktree_path),
+                        &paths,
+                    ),
+                };
+                let decision = policy.evaluate(&request);
+                super::policy::record_decision(
+                    app,
+                    session_id,
+                    worktree_id,
+                    "codex",
+                    &request,
+                    decision,
                 );
+                decision
+            });
+
+            match decision.map(|d| d.action) {
+                Some(PolicyAction::Deny) => {
+                    log::trace!("Declining file change per policy (rpc_id={rpc_id})");
+                    respond("decline");
+                }
+                Some(PolicyAction::Allow) if !unchecked_paths => respond("accept"),
+                None if !unchecked_paths && (is_plan_mode || is_yolo_mode) => {
+                    log::trace!("Auto-accepting file change (rpc_id={rpc_id})");
+                    respond("accept");
+                }
+                _ => {
+                    log::trace!("File change approval requested (rpc_id={rpc_id})");
+                    surface(
+                        "FileChange",
+                        serde_json::json!({
+                            "changes": changes,
+                            "reason": params.get("reason"),
+                            "grant_root": params.get("grantRoot"),
+                        }),
+                    );
+                }
             }
         }
         "item/commandExecution/requestApproval" => {
-            // Emit permission denied event for the frontend
             let command = params
                 .get("command")
                 .and_then(|v| v.as_str())
                 .unwrap_or("")
                 .to_string();
-            let item_id = params
-                .get("itemId")
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
 
             log::trace!("Command approval requested (rpc_id={rpc_id}): {command}");
 
-            let denial = PermissionDenial {
-                tool_name: "Bash".to_string(),
-                tool_use_id: item_id,
-                tool_input: serde_json::json!({ "command": command }),
-                rpc_id: Some(rpc_id),
-            };
-            let _ = app.emit_all(
-                "chat:permission_denied",
-                &PermissionDeniedEvent {
-                    session_id: session_id.to_string(),
-                    worktree_id: worktree_id.to_string(),
-                    denials: vec![denial],
-                },
-            );
-            // Response will come from approve_codex_command Tauri command
+            let decision = policy.and_then(|(policy, _)| {
+                let request = ToolRequest {
+                    tool: "Bash",
+                    command: Some(&command),
+                    paths: Vec::new(),
+                };
+                let decision = policy.evaluate(&request);
+                super::policy::record_decision(
+                    app,
+                    session_id,
+                    worktree_id,
+                    "codex",
+                    &request,
+                    decision,
+                );
+                decision
+            });
+
+            match decision.map(|d| d.action) {
+                Some(PolicyAction::Allow) => respond("accept"),
+                Some(PolicyAction::Deny) => respond("decline"),
+                None if is_yolo_mode => respond("accept"),
+                Some(PolicyAction::Ask) | None => {
+                    // Emit permission denied event for the frontend
+                    surface("Bash", serde_json::json!({ "command": command }));
+                }
+            }
         }
         _ => {
-            log::debug!("Unknown approval request method: {method}");
-            // Auto-accept unknown approvals to avoid blocking
-            let _ = super::codex_server::send_response(
-                rpc_id,
-                serde_json::json!({"decision": "accept"}),
-            );
+            // The policy can't judge an approval it doesn't understand, so
+            // always leave it to the user (even in yolo mode)
+            log::warn!("Unknown approval request method (rpc_id={rpc_id}): {method}");
+            surface(method, params.clone());
         }
     }
 }
@@ -2167,6 +2324,27 @@ mod tests {
     use super::*;
     use crate::chat::types::{RunEntry, RunStatus};
 
+    #[test]
+    fn sandbox_policy_uses_spec_roots_and_network_setting() {
+        let spec = crate::chat::sandbox::SandboxSpec {
+            writable_paths: vec![std::path::PathBuf::from("/wt")],
+            read_only_paths: Vec::new(),
+            block_network: false,
+            detached: false,
+        };
+        let mut params = serde_json::json!({});
+        apply_sandbox_policy(&mut params, &spec, &["/data/runs/s1".to_string()], false);
+        assert_eq!(params["sandboxPolicy"]["type"], "workspaceWrite");
+        assert_eq!(
+            params["sandboxPolicy"]["writableRoots"],
+            serde_json::json!(["/wt", "/data/runs/s1"])
+        );
+        assert_eq!(params["sandboxPolicy"]["networkAccess"], true);
+
+        apply_sandbox_policy(&mut params, &spec, &[], true);
+        assert_eq!(params["sandboxPolicy"]["networkAccess"], false);
+    }
+
     #[test]
     fn gpt_5_4_fast_enables_fast_service_tier() {
         let params = build_thread_start_params(
@@ -2239,6 +2417,7 @@ mod tests {
             claude_session_id: None,
             pid: None,
             usage: None,
+            permission_audit: Vec::new(),
         };
 
         let message = parse_codex_run_to_message(&lines, &run).expect("message");
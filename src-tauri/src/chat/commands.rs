@@ -503,6 +503,7 @@ pub async fn update_session_state(
     review_results: Option<Option<serde_json::Value>>,
     enabled_mcp_servers: Option<Option<Vec<String>>>,
     selected_execution_mode: Option<Option<String>>,
+    review_threads: Option<std::collections::HashMap<String, super::types::ReviewThreadProgress>>,
 ) -> Result<(), String> {
     log::trace!("Updating session state for: {session_id}");
 
@@ -556,6 +557,9 @@ pub async fn update_session_state(
             if let Some(v) = selected_execution_mode {
                 session.selected_execution_mode = v;
             }
+            if let Some(v) = review_threads {
+                session.review_threads = v;
+            }
             Ok(())
         } else {
             log::trace!("Session already removed, skipping update: {session_id}");
@@ -942,6 +946,7 @@ pub async fn restore_session_with_base(
         pr_url: None,
         issue_number: None,
         linear_issue_identifier: None,
+        jira_issue_key: None,
         cached_pr_status: None,
         cached_check_status: None,
         cached_behind_count: None,
@@ -959,6 +964,9 @@ pub async fn restore_session_with_base(
         label: None,
         archived_at: None,
         last_opened_at: None,
+        ports: None,
+        parent_worktree_id: None,
+        stack_base_commit: None,
     };
 
     projects_data.add_worktree(new_worktree.clone());
@@ -1287,6 +1295,7 @@ pub async fn send_chat_message(
     parallel_execution_prompt: Option<String>,
     ai_language: Option<String>,
     allowed_tools: Option<Vec<String>>,
+    command_allowed_tools: Option<Vec<String>>,
     mcp_config: Option<String>,
     chrome_enabled: Option<bool>,
     custom_profile_name: Option<String>,
@@ -1300,6 +1309,13 @@ pub async fn send_chat_message(
         return Err("Message cannot be empty".to_string());
     }
 
+    // Queued runs of this session reuse the tools the user approved. A command's
+    // own `allowed-tools` only apply to this run.
+    if let Some(tools) = &allowed_tools {
+        super::queue::remember_allowed_tools(&session_id, tools);
+    }
+    let allowed_tools = super::queue::with_command_tools(allowed_tools, command_allowed_tools);
+
     if worktree_path.is_empty() {
         return Err("Worktree path cannot be empty".to_string());
     }
@@ -2656,6 +2672,9 @@ pub async fn send_chat_message(
         log::info!("[SendChat] EXIT session={session_id} reason=cancelled_with_content");
     } else {
         log::info!("[SendChat] EXIT session={session_id} reason=success");
+        // Backend owns queue execution: start the next queued message (if any)
+        // so queues keep draining without a connected client.
+        super::queue::schedule_queue_processing(&app, &session_id, &worktree_id);
     }
     Ok(assistant_msg)
 }
@@ -5308,19 +5327,66 @@ fn parse_codex_mcp_list_json(output: &str) -> std::collections::HashMap<String,
     std::collections::HashMap::new()
 }
 
-/// Approve or decline a Codex command execution approval request.
+/// Approve or decline a Codex command execution or file change approval request.
 ///
-/// Called by the frontend when the user clicks "Approve" or "Cancel" in the
-/// PermissionApproval UI during a Codex build-mode session.
+/// Called by the frontend when the user clicks "Approve", "Approve for session" or
+/// "Cancel" in the PermissionApproval UI during a Codex build-mode session.
+/// `decision` is one of `accept`, `acceptForSession`, `decline` or `cancel`.
 #[tauri::command]
 pub fn approve_codex_command(
     _session_id: String,
     rpc_id: u64,
     decision: String,
 ) -> Result<(), String> {
+    if !matches!(
+        decision.as_str(),
+        "accept" | "acceptForSession" | "decline" | "cancel"
+    ) {
+        return Err(format!("Invalid approval decision: {decision}"));
+    }
     super::codex_server::send_response(rpc_id, serde_json::json!({"decision": decision}))
 }
 
+/// Answer a pending OpenCode permission request surfaced via `chat:permission_denied`.
+///
+/// `decision` uses the same vocabulary as `approve_codex_command`
+/// (`accept`, `acceptForSession`, `decline`).
+#[tauri::command]
+pub async fn approve_opencode_permission(
+    _session_id: String,
+    permission_id: String,
+    decision: String,
+) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        super::opencode::respond_to_permission(&permission_id, &decision)
+    })
+    .await
+    .map_err(|e| format!("Failed to answer OpenCode permission: {e}"))?
+}
+
+/// Get the permission policy decisions recorded for each run of a session
+#[tauri::command]
+pub async fn get_permission_audit(
+    app: AppHandle,
+    session_id: String,
+) -> Result<Vec<super::types::RunPermissionAudit>, String> {
+    let Some(metadata) = load_metadata(&app, &session_id)? else {
+        return Ok(Vec::new());
+    };
+
+    Ok(metadata
+        .runs
+        .into_iter()
+        .filter(|run| !run.permission_audit.is_empty())
+        .map(|run| super::types::RunPermissionAudit {
+            run_id: run.run_id,
+            user_message_id: run.user_message_id,
+            started_at: run.started_at,
+            entries: run.permission_audit,
+        })
+        .collect())
+}
+
 // =============================================================================
 // Queue management commands (atomic operations for cross-client sync)
 // =============================================================================
@@ -5426,6 +5492,19 @@ pub async fn clear_message_queue(
     Ok(())
 }
 
+/// Start processing a session's queue on the backend if the session is idle.
+/// Clients call this when they observe queued messages on an idle session
+/// (e.g. after a restart); the backend then runs them and clients just observe.
+#[tauri::command]
+pub async fn process_message_queue(
+    app: AppHandle,
+    worktree_id: String,
+    session_id: String,
+) -> Result<(), String> {
+    super::queue::schedule_queue_processing(&app, &session_id, &worktree_id);
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -21,6 +21,9 @@ use crate::platform::silent_command;
 /// Uses `nohup` and shell backgrounding to fully detach the process.
 /// The process reads input from a file and writes output to the NDJSON file.
 ///
+/// `command_prefix` is a wrapper the CLI runs under (sandbox or container
+/// `exec`), empty to run it directly.
+///
 /// Returns the PID of the detached Claude CLI process.
 #[cfg(unix)]
 #[allow(clippy::too_many_arguments)]
@@ -31,6 +34,7 @@ pub fn spawn_detached_claude(
     output_file: &Path,
     working_dir: &Path,
     env_vars: &[(&str, &str)],
+    command_prefix: &[String],
 ) -> Result<u32, String> {
     // Build the shell command:
     // cat input.jsonl | nohup /path/to/claude [args] >> output.jsonl 2>&1 & echo $!
@@ -46,8 +50,16 @@ pub fn spawn_detached_claude(
     // - echo $!: Print the PID of the background process
 
     // Escape ALL paths for safe shell usage (paths may contain spaces like "Application Support")
-    let cli_path_escaped =
+    let mut cli_path_escaped =
         shell_escape(cli_path.to_str().ok_or("CLI path contains invalid UTF-8")?);
+    if !command_prefix.is_empty() {
+        let prefix = command_prefix
+            .iter()
+            .map(|arg| shell_escape(arg))
+            .collect::<Vec<_>>()
+            .join(" ");
+        cli_path_escaped = format!("{prefix} {cli_path_escaped}");
+    }
     let input_path_escaped = shell_escape(
         input_file
             .to_str()
@@ -178,11 +190,16 @@ pub fn spawn_detached_claude(
     output_file: &Path,
     working_dir: &Path,
     env_vars: &[(&str, &str)],
+    command_prefix: &[String],
 ) -> Result<u32, String> {
     use std::fs::OpenOptions;
     use std::io::Write;
     use std::os::windows::process::CommandExt;
 
+    if !command_prefix.is_empty() {
+        return Err("Sandboxed and containerized runs are not supported on Windows".to_string());
+    }
+
     const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
     const CREATE_NO_WINDOW: u32 = 0x08000000;
 
@@ -5,9 +5,12 @@ mod commands;
 pub mod detached;
 mod naming;
 pub(crate) mod opencode;
+pub(crate) mod policy;
 pub(crate) mod provider_status;
+mod queue;
 pub mod registry;
 pub mod run_log;
+pub(crate) mod sandbox;
 pub mod storage;
 pub mod tail;
 pub mod types;
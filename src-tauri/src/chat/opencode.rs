@@ -1,12 +1,17 @@
 //! OpenCode HTTP execution engine (opencode serve).
 
-use super::types::{ContentBlock, ToolCall, UsageData};
+use super::policy::ToolRequest;
+use super::types::{
+    ContentBlock, PermissionDenial, PermissionDeniedEvent, PermissionPolicy, PolicyAction,
+    ToolCall, UsageData,
+};
 use crate::http_server::EmitExt;
 use base64::{engine::general_purpose::STANDARD, Engine};
+use once_cell::sync::Lazy;
 use regex::Regex;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tauri::AppHandle;
 
@@ -311,6 +316,288 @@ fn agent_for_execution_mode(execution_mode: Option<&str>) -> &'static str {
     }
 }
 
+/// Map an OpenCode permission kind ("bash", "edit", "webfetch") to the policy's
+/// Claude-style tool name
+fn policy_tool_name(kind: &str) -> String {
+    match kind {
+        "bash" => "Bash".to_string(),
+        "edit" => "Edit".to_string(),
+        "write" => "Write".to_string(),
+        "read" => "Read".to_string(),
+        "webfetch" => "WebFetch".to_string(),
+        other => {
+            let mut chars = other.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        }
+    }
+}
+
+/// OpenCode tools to disable for a message: deny rules that name a plain tool
+/// without command/path conditions
+fn disabled_tools_for_policy(
+    policy: &PermissionPolicy,
+) -> serde_json::Map<String, serde_json::Value> {
+    policy
+        .rules
+        .iter()
+        .filter(|r| r.action == PolicyAction::Deny && r.command.is_none() && r.path.is_none())
+        .filter_map(|r| r.tool.as_deref())
+        .filter(|t| t.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+        .map(|t| (t.to_lowercase(), serde_json::Value::Bool(false)))
+        .collect()
+}
+
+/// OpenCode permission kind covering a Claude-style policy tool name
+fn opencode_permission_kind(tool: &str) -> String {
+    match tool {
+        "Edit" | "MultiEdit" | "Write" | "NotebookEdit" => "edit".to_string(),
+        "LS" => "list".to_string(),
+        other => other.to_lowercase(),
+    }
+}
+
+/// OpenCode permission kinds the policy has to check call by call (ask rules
+/// and deny rules that can't be expressed by disabling a tool)
+fn runtime_checked_kinds(policy: &PermissionPolicy) -> Vec<String> {
+    let mut kinds: Vec<String> = Vec::new();
+    for tool in policy.runtime_checked_tools() {
+        let kind = opencode_permission_kind(&tool);
+        if !kinds.contains(&kind) {
+            kinds.push(kind);
+        }
+    }
+    kinds
+}
+
+/// Session permission rules that make OpenCode ask before every call of the
+/// given kinds, so each one reaches `handle_permission_event`. OpenCode only
+/// takes them when the session is created.
+fn ask_permission_rules(kinds: &[String]) -> Vec<serde_json::Value> {
+    kinds
+        .iter()
+        .map(|kind| serde_json::json!({ "permission": kind, "pattern": "*", "action": "ask" }))
+        .collect()
+}
+
+/// State the SSE listener needs to answer `permission.*` events
+struct PermissionContext {
+    base_url: String,
+    working_dir: String,
+    policy: Option<(PermissionPolicy, String)>,
+    /// Agent the message runs with ("build" or "plan")
+    agent: &'static str,
+    /// Kinds forced to "ask" by `ask_permission_rules`
+    forced_kinds: Vec<String>,
+}
+
+/// A permission request surfaced to the user, waiting for `approve_opencode_permission`
+#[derive(Debug, Clone)]
+struct PendingPermission {
+    base_url: String,
+    working_dir: String,
+    opencode_session_id: String,
+}
+
+/// OpenCode permission requests waiting for the user, keyed by permission ID
+static PENDING_PERMISSIONS: Lazy<Mutex<HashMap<String, PendingPermission>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// POST the answer for a permission request ("once", "always" or "reject")
+fn send_permission_reply(
+    pending: &PendingPermission,
+    permission_id: &str,
+    response: &str,
+) -> Result<(), String> {
+    let url = format!(
+        "{}/session/{}/permissions/{permission_id}",
+        pending.base_url, pending.opencode_session_id
+    );
+    let resp = reqwest::blocking::Client::new()
+        .post(&url)
+        .query(&[("directory", pending.working_dir.as_str())])
+        .json(&serde_json::json!({ "response": response }))
+        .send()
+        .map_err(|e| format!("Failed to answer OpenCode permission: {e}"))?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().unwrap_or_default();
+        return Err(format!(
+            "OpenCode permission reply failed: status={status}, body={body}"
+        ));
+    }
+    log::debug!("OpenCode: answered permission {permission_id} with '{response}'");
+    Ok(())
+}
+
+/// Answer a permission request the user approved or declined in the UI.
+/// `decision` is `accept`, `acceptForSession` or `decline` (same as Codex approvals).
+pub fn respond_to_permission(permission_id: &str, decision: &str) -> Result<(), String> {
+    let response = match decision {
+        "accept" => "once",
+        "acceptForSession" => "always",
+        "decline" | "cancel" => "reject",
+        other => return Err(format!("Invalid approval decision: {other}")),
+    };
+    let pending = PENDING_PERMISSIONS
+        .lock()
+        .unwrap()
+        .remove(permission_id)
+        .ok_or_else(|| format!("No pending OpenCode permission: {permission_id}"))?;
+    send_permission_reply(&pending, permission_id, response)
+}
+
+/// Drop pending permissions of an OpenCode session (run finished or was cancelled)
+fn clear_pending_permissions(opencode_session_id: &str) {
+    PENDING_PERMISSIONS
+        .lock()
+        .unwrap()
+        .retain(|_, p| p.opencode_session_id != opencode_session_id);
+}
+
+/// Handle an OpenCode `permission.updated` / `permission.asked` event.
+///
+/// The project's permission policy answers the request when a rule allows or
+/// denies it; otherwise the request is surfaced to the user as a
+/// `chat:permission_denied` event and answered via `approve_opencode_permission`.
+/// Requests that only exist because the policy forced their kind to "ask" get
+/// the agent's default when no rule matches: allowed for build, and surfaced
+/// (or rejected, for edits) for plan.
+fn handle_permission_event(
+    app: &AppHandle,
+    properties: &serde_json::Value,
+    opencode_session_id: &str,
+    session_id: &str,
+    worktree_id: &str,
+    ctx: &PermissionContext,
+) {
+    let perm_session_id = properties
+        .get("sessionID")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    if perm_session_id != opencode_session_id {
+        return;
+    }
+    let Some(permission_id) = properties.get("id").and_then(|v| v.as_str()) else {
+        return;
+    };
+
+    // Newer servers send `permission` + `patterns`, older ones `type` + `pattern`
+    let kind = properties
+        .get("permission")
+        .or_else(|| properties.get("type"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    let patterns: Vec<String> = match properties
+        .get("patterns")
+        .or_else(|| properties.get("pattern"))
+    {
+        Some(serde_json::Value::String(p)) => vec![p.clone()],
+        Some(serde_json::Value::Array(arr)) => arr
+            .iter()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect(),
+        _ => Vec::new(),
+    };
+    let metadata = properties.get("metadata").cloned().unwrap_or_default();
+
+    let tool = policy_tool_name(kind);
+    let command = metadata
+        .get("command")
+        .and_then(|v| v.as_str())
+        .map(String::from)
+        .or_else(|| (kind == "bash").then(|| patterns.join(" ")));
+    let mut paths: Vec<String> = ["filepath", "filePath", "path"]
+        .iter()
+        .filter_map(|key| metadata.get(*key).and_then(|v| v.as_str()))
+        .map(String::from)
+        .collect();
+    if paths.is_empty() && kind != "bash" {
+        paths = patterns;
+    }
+
+    let pending = PendingPermission {
+        base_url: ctx.base_url.clone(),
+        working_dir: ctx.working_dir.clone(),
+        opencode_session_id: opencode_session_id.to_string(),
+    };
+
+    let decision = ctx.policy.as_ref().and_then(|(policy, worktree_path)| {
+        let request = ToolRequest {
+            tool: &tool,
+            command: command.as_deref(),
+            paths: super::policy::relative_paths(std::path::Path::new(worktree_path), &paths),
+        };
+        let decision = policy.evaluate(&request);
+        super::policy::record_decision(
+            app,
+            session_id,
+            worktree_id,
+            "opencode",
+            &request,
+            decision,
+        );
+        decision
+    });
+
+    let forced = ctx.forced_kinds.iter().any(|k| k == kind);
+    let response = match decision.map(|d| d.action) {
+        Some(PolicyAction::Allow) => "once",
+        Some(PolicyAction::Deny) => "reject",
+        None if forced && ctx.agent == "build" => "once",
+        None if forced && kind == "edit" => "reject",
+        Some(PolicyAction::Ask) | None => {
+            log::info!("OpenCode SSE: permission {permission_id} ({kind}) needs approval");
+            PENDING_PERMISSIONS
+                .lock()
+                .unwrap()
+                .insert(permission_id.to_string(), pending);
+
+            let mut tool_input = serde_json::json!({
+                "title": properties.get("title"),
+                "metadata": metadata,
+            });
+            if let Some(command) = &command {
+                tool_input["command"] = serde_json::json!(command);
+            }
+            if let Some(path) = paths.first() {
+                tool_input["file_path"] = serde_json::json!(path);
+            }
+            let tool_use_id = properties
+                .get("callID")
+                .or_else(|| properties.get("tool").and_then(|t| t.get("callID")))
+                .and_then(|v| v.as_str())
+                .unwrap_or(permission_id)
+                .to_string();
+            let _ = app.emit_all(
+                "chat:permission_denied",
+                &PermissionDeniedEvent {
+                    session_id: session_id.to_string(),
+                    worktree_id: worktree_id.to_string(),
+                    denials: vec![PermissionDenial {
+                        tool_name: tool,
+                        tool_use_id,
+                        tool_input,
+                        rpc_id: None,
+                        permission_id: Some(permission_id.to_string()),
+                    }],
+                },
+            );
+            return;
+        }
+    };
+
+    // Answer off the async SSE listener thread
+    let permission_id = permission_id.to_string();
+    std::thread::spawn(move || {
+        if let Err(e) = send_permission_reply(&pending, &permission_id, response) {
+            log::warn!("{e}");
+        }
+    });
+}
+
 fn variant_for_effort(reasoning_effort: Option<&str>) -> Option<&'static str> {
     match reasoning_effort {
         Some("xhigh") => Some("max"),
@@ -449,6 +736,7 @@ fn spawn_sse_listener(
     session_id: String,
     worktree_id: String,
     working_dir: String,
+    agent: &'static str,
     done_flag: Arc<AtomicBool>,
     cancelled: Arc<AtomicBool>,
     sse_ready_tx: std::sync::mpsc::SyncSender<bool>,
@@ -478,6 +766,7 @@ fn spawn_sse_listener(
                 session_id,
                 worktree_id,
                 working_dir,
+                agent,
                 done_flag,
                 cancelled,
                 sse_active_clone,
@@ -498,6 +787,7 @@ async fn sse_listener_loop(
     session_id: String,
     worktree_id: String,
     working_dir: String,
+    agent: &'static str,
     done_flag: Arc<AtomicBool>,
     cancelled: Arc<AtomicBool>,
     sse_active: Arc<AtomicBool>,
@@ -556,6 +846,18 @@ async fn sse_listener_loop(
 
     let _ = sse_ready_tx.send(true);
 
+    let policy = super::policy::load_policy_with_worktree_path(&app, &worktree_id);
+    let permissions = PermissionContext {
+        base_url: base_url.clone(),
+        working_dir: working_dir.clone(),
+        forced_kinds: policy
+            .as_ref()
+            .map(|(policy, _)| runtime_checked_kinds(policy))
+            .unwrap_or_default(),
+        policy,
+        agent,
+    };
+
     // Read SSE stream chunk by chunk
     let mut response = response;
     let mut buffer = String::new();
@@ -656,6 +958,7 @@ async fn sse_listener_loop(
                                 &session_id,
                                 &worktree_id,
                                 &mut tracked_parts,
+                                &permissions,
                             ) {
                                 if emitted {
                                     total_events_emitted += 1;
@@ -707,6 +1010,7 @@ fn process_sse_event(
     session_id: &str,
     worktree_id: &str,
     tracked_parts: &mut HashMap<String, TrackedPartState>,
+    permissions: &PermissionContext,
 ) -> Option<bool> {
     let json: serde_json::Value = match serde_json::from_str(data) {
         Ok(v) => v,
@@ -1071,6 +1375,30 @@ fn process_sse_event(
             Some(false)
         }
 
+        "permission.replied" => {
+            // Answered elsewhere (policy, another client, OpenCode TUI)
+            if let Some(id) = properties
+                .get("permissionID")
+                .or_else(|| properties.get("requestID"))
+                .and_then(|v| v.as_str())
+            {
+                PENDING_PERMISSIONS.lock().unwrap().remove(id);
+            }
+            Some(false)
+        }
+
+        "permission.updated" | "permission.asked" => {
+            handle_permission_event(
+                app,
+                &properties,
+                opencode_session_id,
+                session_id,
+                worktree_id,
+                permissions,
+            );
+            Some(false)
+        }
+
         "session.updated" => {
             log::info!(
                 "OpenCode SSE: session.updated id={:?}",
@@ -1116,6 +1444,15 @@ pub fn execute_opencode_http(
         });
     }
 
+    // The shared server is sandboxed on its own; this only refuses runs the
+    // project sandbox can't be enforced for (see chat::sandbox)
+    super::sandbox::resolve_for_worktree(
+        app,
+        worktree_id,
+        session_id,
+        &super::types::Backend::Opencode,
+    )?;
+
     let base_url = crate::opencode_server::acquire(app)?;
 
     // RAII guard: decrements the server usage count when this function exits.
@@ -1135,14 +1472,22 @@ pub fn execute_opencode_http(
         .map_err(|e| format!("Failed to build OpenCode HTTP client: {e}"))?;
 
     let query = [("directory", working_dir.to_string_lossy().to_string())];
+    let policy = super::policy::load_effective_policy(app, worktree_id);
 
     let opencode_session_id = if let Some(existing) = existing_opencode_session_id {
         existing.to_string()
     } else {
         let create_url = format!("{base_url}/session");
-        let create_payload = serde_json::json!({
+        let mut create_payload = serde_json::json!({
             "title": format!("Jean {session_id}"),
         });
+        let checked_kinds = policy
+            .as_ref()
+            .map(runtime_checked_kinds)
+            .unwrap_or_default();
+        if !checked_kinds.is_empty() {
+            create_payload["permission"] = ask_permission_rules(&checked_kinds).into();
+        }
         let create_resp = client
             .post(&create_url)
             .query(&query)
@@ -1216,6 +1561,7 @@ pub fn execute_opencode_http(
     // --- SSE streaming: spawn a background listener before sending POST ---
     let done_flag = Arc::new(AtomicBool::new(false));
     let (sse_ready_tx, sse_ready_rx) = std::sync::mpsc::sync_channel::<bool>(1);
+    let agent = agent_for_execution_mode(execution_mode);
 
     let sse_active = spawn_sse_listener(
         app.clone(),
@@ -1224,6 +1570,7 @@ pub fn execute_opencode_http(
         session_id.to_string(),
         worktree_id.to_string(),
         working_dir.to_string_lossy().to_string(),
+        agent,
         done_flag.clone(),
         cancelled.clone(),
         sse_ready_tx,
@@ -1243,7 +1590,7 @@ pub fn execute_opencode_http(
     let msg_url = format!("{base_url}/session/{opencode_session_id}/message");
 
     let mut payload = serde_json::json!({
-        "agent": agent_for_execution_mode(execution_mode),
+        "agent": agent,
         "model": {
             "providerID": selected_model.0,
             "modelID": selected_model.1,
@@ -1257,6 +1604,12 @@ pub fn execute_opencode_http(
     if let Some(system) = system_prompt.map(str::trim).filter(|s| !s.is_empty()) {
         payload["system"] = serde_json::Value::String(system.to_string());
     }
+    if let Some(policy) = &policy {
+        let disabled = disabled_tools_for_policy(policy);
+        if !disabled.is_empty() {
+            payload["tools"] = serde_json::Value::Object(disabled);
+        }
+    }
 
     // Retry once on connection-level errors (server temporarily unreachable).
     let response = match client.post(&msg_url).query(&query).json(&payload).send() {
@@ -1274,6 +1627,9 @@ pub fn execute_opencode_http(
         Err(e) => return Err(format!("Failed to send OpenCode message: {e}")),
     };
 
+    // The message has finished, so none of its permission requests can still be pending
+    clear_pending_permissions(&opencode_session_id);
+
     if !response.status().is_success() {
         done_flag.store(true, Ordering::Relaxed);
         let status = response.status();
@@ -0,0 +1,677 @@
+//! Tool permission policy engine.
+//!
+//! A project can define allow/deny/ask rules (by tool name, command regex and
+//! path glob) in its settings and in the project root's `jean.json` under
+//! `permissions`. Worktree copies of `jean.json` are ignored so an agent can't
+//! rewrite its own policy. The same rules are enforced for every backend:
+//!
+//! - Claude: translated into `--allowedTools` / `--disallowedTools` and the
+//!   `permissions.ask` settings key before the CLI starts.
+//! - Codex: evaluated when the app-server sends an approval request.
+//! - OpenCode: plain tool deny rules disable the tool; the tools of other deny
+//!   and ask rules are set to "ask" on new sessions and evaluated when the
+//!   server emits a `permission.*` event.
+//!
+//! Tool names use Claude's vocabulary ("Bash", "Edit", "Write", "Read", ...);
+//! other backends map their own tool kinds onto it. Every decision taken at
+//! runtime is appended to the running run's `permission_audit`.
+
+use std::path::Path;
+
+use globset::{GlobBuilder, GlobMatcher};
+use regex::Regex;
+use tauri::AppHandle;
+
+use super::types::{
+    PermissionAuditEntry, PermissionDecisionEvent, PermissionDenial, PermissionPolicy,
+    PolicyAction, PolicyRule,
+};
+use crate::http_server::EmitExt;
+use crate::projects::storage::load_projects_data;
+
+/// Tools a rule with a `path` but no `tool` expands to for Claude
+const PATH_TOOLS: [&str; 3] = ["Read", "Edit", "Write"];
+
+/// Claude tools a tool glob (e.g. `{Edit,Write}`) is expanded against
+const CLAUDE_TOOLS: [&str; 13] = [
+    "Bash",
+    "Read",
+    "Edit",
+    "MultiEdit",
+    "Write",
+    "NotebookEdit",
+    "Glob",
+    "Grep",
+    "LS",
+    "WebFetch",
+    "WebSearch",
+    "Task",
+    "TodoWrite",
+];
+
+/// A tool call to evaluate against the policy
+#[derive(Debug, Default)]
+pub struct ToolRequest<'a> {
+    pub tool: &'a str,
+    pub command: Option<&'a str>,
+    /// File paths touched by the call, relative to the worktree where possible
+    pub paths: Vec<String>,
+}
+
+/// Result of evaluating a tool call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PolicyDecision {
+    pub action: PolicyAction,
+    pub rule_index: usize,
+}
+
+/// Claude CLI permission arguments derived from a policy
+#[derive(Debug, Default, PartialEq)]
+pub struct ClaudePermissionRules {
+    pub allowed: Vec<String>,
+    pub disallowed: Vec<String>,
+    pub ask: Vec<String>,
+}
+
+impl PermissionPolicy {
+    /// Evaluate a tool call. Deny beats ask beats allow when several rules match.
+    /// Returns None when no rule matches (the execution mode's default applies).
+    pub fn evaluate(&self, request: &ToolRequest) -> Option<PolicyDecision> {
+        let mut best: Option<PolicyDecision> = None;
+        for (index, rule) in self.rules.iter().enumerate() {
+            if !rule_matches(rule, request) {
+                continue;
+            }
+            let candidate = PolicyDecision {
+                action: rule.action,
+                rule_index: index,
+            };
+            best = match best {
+                Some(current) if precedence(current.action) >= precedence(rule.action) => {
+                    Some(current)
+                }
+                _ => Some(candidate),
+            };
+        }
+        best
+    }
+
+    /// Whether any rule can restrict tool use (deny or ask)
+    pub fn has_restrictions(&self) -> bool {
+        self.rules
+            .iter()
+            .any(|r| matches!(r.action, PolicyAction::Deny | PolicyAction::Ask))
+    }
+
+    /// Check that every rule has a matcher and every regex and glob compiles
+    pub fn validate(&self) -> Result<(), String> {
+        for (index, rule) in self.rules.iter().enumerate() {
+            if let Some(error) = rule_error(rule) {
+                return Err(format!("Rule {}: {error}", index + 1));
+            }
+        }
+        Ok(())
+    }
+
+    /// Claude tool names whose calls have to be checked one by one at runtime:
+    /// those of ask rules, and of deny rules a backend can't enforce by simply
+    /// disabling a named tool (conditional, globbed or invalid rules).
+    pub fn runtime_checked_tools(&self) -> Vec<String> {
+        let mut tools: Vec<String> = Vec::new();
+        for rule in &self.rules {
+            let invalid = rule_error(rule).is_some();
+            let checked = match rule.action {
+                PolicyAction::Allow => false,
+                PolicyAction::Ask => true,
+                PolicyAction::Deny => {
+                    invalid
+                        || rule.command.is_some()
+                        || rule.path.is_some()
+                        || rule
+                            .tool
+                            .as_deref()
+                            .is_some_and(|t| t.contains(['*', '?', '[', '{']))
+                }
+            };
+            if !checked {
+                continue;
+            }
+            let rule_tools = if invalid {
+                CLAUDE_TOOLS.iter().map(|t| t.to_string()).collect()
+            } else {
+                rule_tools(rule)
+            };
+            for tool in rule_tools {
+                if !tools.contains(&tool) {
+                    tools.push(tool);
+                }
+            }
+        }
+        tools
+    }
+
+    /// Translate the policy into Claude CLI permission rules.
+    ///
+    /// Claude only understands prefix/wildcard Bash patterns, so command regexes
+    /// are translated when they are plain literals (optionally anchored with `^`
+    /// and `$`). Deny and ask rules that can't be expressed fail closed: they
+    /// apply to the whole tool. Inexpressible allow rules are skipped. Invalid
+    /// deny and ask rules apply to every tool, invalid allow rules are skipped.
+    pub fn to_claude_rules(&self) -> ClaudePermissionRules {
+        let mut result = ClaudePermissionRules::default();
+        for (index, rule) in self.rules.iter().enumerate() {
+            let specifiers = if rule_error(rule).is_some() {
+                if rule.action == PolicyAction::Allow {
+                    continue;
+                }
+                Some(CLAUDE_TOOLS.iter().map(|t| t.to_string()).collect())
+            } else {
+                claude_specifiers(rule)
+            };
+            let specifiers = match specifiers {
+                Some(specifiers) => specifiers,
+                None if rule.action == PolicyAction::Allow => {
+                    log::warn!(
+                        "Permission rule {} can't be expressed as a Claude permission, skipping",
+                        index + 1
+                    );
+                    continue;
+                }
+                None => {
+                    let tools = rule_tools(rule);
+                    log::warn!(
+                        "Permission rule {} can't be expressed as a Claude permission, applying it to {}",
+                        index + 1,
+                        tools.join(", ")
+                    );
+                    tools
+                }
+            };
+            let target = match rule.action {
+                PolicyAction::Allow => &mut result.allowed,
+                PolicyAction::Deny => &mut result.disallowed,
+                PolicyAction::Ask => &mut result.ask,
+            };
+            target.extend(specifiers);
+        }
+        result
+    }
+}
+
+fn precedence(action: PolicyAction) -> u8 {
+    match action {
+        PolicyAction::Allow => 0,
+        PolicyAction::Ask => 1,
+        PolicyAction::Deny => 2,
+    }
+}
+
+/// Tool names are matched case-insensitively ("bash" from OpenCode matches "Bash")
+fn tool_glob(pattern: &str) -> Result<GlobMatcher, String> {
+    GlobBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .map(|g| g.compile_matcher())
+        .map_err(|e| e.to_string())
+}
+
+/// Path globs follow gitignore-like semantics: `*` stays within a directory, `**` crosses them
+fn path_glob(pattern: &str) -> Result<GlobMatcher, String> {
+    GlobBuilder::new(pattern)
+        .literal_separator(true)
+        .build()
+        .map(|g| g.compile_matcher())
+        .map_err(|e| e.to_string())
+}
+
+/// Why a rule can't be evaluated: it has no matcher or a pattern doesn't compile
+fn rule_error(rule: &PolicyRule) -> Option<String> {
+    if rule.tool.is_none() && rule.command.is_none() && rule.path.is_none() {
+        return Some("needs a tool, command or path to match".to_string());
+    }
+    if let Some(Err(e)) = rule.tool.as_deref().map(tool_glob) {
+        return Some(format!("invalid tool pattern: {e}"));
+    }
+    if let Some(Err(e)) = rule.command.as_deref().map(Regex::new) {
+        return Some(format!("invalid command regex: {e}"));
+    }
+    if let Some(Err(e)) = rule.path.as_deref().map(path_glob) {
+        return Some(format!("invalid path glob: {e}"));
+    }
+    None
+}
+
+fn rule_matches(rule: &PolicyRule, request: &ToolRequest) -> bool {
+    // Invalid rules are reported when the policy is loaded and fail closed:
+    // a deny or ask rule matches every call, an allow rule none
+    if rule_error(rule).is_some() {
+        return rule.action != PolicyAction::Allow;
+    }
+
+    if let Some(tool) = &rule.tool {
+        if !tool_glob(tool).is_ok_and(|m| m.is_match(request.tool)) {
+            return false;
+        }
+    }
+
+    if let Some(pattern) = &rule.command {
+        let Some(command) = request.command else {
+            return false;
+        };
+        if !Regex::new(pattern).is_ok_and(|re| re.is_match(command)) {
+            return false;
+        }
+    }
+
+    if let Some(pattern) = &rule.path {
+        let Ok(matcher) = path_glob(pattern) else {
+            return false;
+        };
+        if !request.paths.iter().any(|p| matcher.is_match(p)) {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Claude tool names a rule's tool pattern covers (a rule with only a command
+/// targets Bash, one with only a path targets the file tools)
+fn rule_tools(rule: &PolicyRule) -> Vec<String> {
+    match rule.tool.as_deref() {
+        Some(pattern) if pattern.contains(['*', '?', '[', '{']) => match tool_glob(pattern) {
+            Ok(matcher) => CLAUDE_TOOLS
+                .iter()
+                .filter(|t| matcher.is_match(t))
+                .map(|t| t.to_string())
+                .collect(),
+            Err(_) => vec![],
+        },
+        Some(tool) => vec![tool.to_string()],
+        None if rule.command.is_some() => vec!["Bash".to_string()],
+        None => PATH_TOOLS.iter().map(|t| t.to_string()).collect(),
+    }
+}
+
+/// Claude permission specifiers (e.g. `Bash(git push:*)`, `Edit(src/**)`) for a rule
+fn claude_specifiers(rule: &PolicyRule) -> Option<Vec<String>> {
+    match (&rule.command, &rule.path) {
+        (Some(_), Some(_)) => None,
+        (Some(command), None) => {
+            let tool = rule.tool.as_deref().unwrap_or("Bash");
+            if !tool.eq_ignore_ascii_case("Bash") {
+                return None;
+            }
+            Some(vec![format!("Bash({})", claude_command_pattern(command)?)])
+        }
+        (None, Some(path)) => Some(
+            rule_tools(rule)
+                .into_iter()
+                .map(|t| format!("{t}({path})"))
+                .collect(),
+        ),
+        (None, None) => Some(rule_tools(rule)),
+    }
+}
+
+/// Convert a literal command regex into a Claude Bash pattern:
+/// `^git push$` → `git push`, `^git push` → `git push:*`, `rm -rf` → `*rm -rf*`.
+fn claude_command_pattern(regex: &str) -> Option<String> {
+    let (anchored_start, rest) = match regex.strip_prefix('^') {
+        Some(rest) => (true, rest),
+        None => (false, regex),
+    };
+    let (anchored_end, body) = match rest.strip_suffix('$') {
+        Some(body) if !body.ends_with('\\') => (true, body),
+        _ => (false, rest.strip_suffix(".*").unwrap_or(rest)),
+    };
+
+    let mut literal = String::new();
+    let mut chars = body.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => literal.push(chars.next()?),
+            '.' | '*' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}' | '|' | '^' | '$' => {
+                return None
+            }
+            _ => literal.push(c),
+        }
+    }
+    if literal.is_empty() {
+        return None;
+    }
+
+    Some(match (anchored_start, anchored_end) {
+        (true, true) => literal,
+        (true, false) => format!("{literal}:*"),
+        (false, true) => format!("*{literal}"),
+        (false, false) => format!("*{literal}*"),
+    })
+}
+
+/// Make file paths relative to the worktree so they match worktree-relative globs
+pub fn relative_paths(working_dir: &Path, paths: &[String]) -> Vec<String> {
+    paths
+        .iter()
+        .map(|p| {
+            Path::new(p)
+                .strip_prefix(working_dir)
+                .map(|rel| rel.to_string_lossy().to_string())
+                .unwrap_or_else(|_| p.clone())
+        })
+        .collect()
+}
+
+/// Load the effective policy for a worktree: project settings rules first,
+/// followed by the rules from the project root's `jean.json`.
+/// Returns None when neither defines any rules.
+pub fn load_effective_policy(app: &AppHandle, worktree_id: &str) -> Option<PermissionPolicy> {
+    load_policy_with_worktree_path(app, worktree_id).map(|(policy, _)| policy)
+}
+
+/// Same as `load_effective_policy`, also returning the worktree path
+/// (needed to make tool paths relative before matching)
+pub fn load_policy_with_worktree_path(
+    app: &AppHandle,
+    worktree_id: &str,
+) -> Option<(PermissionPolicy, String)> {
+    let data = load_projects_data(app).ok()?;
+    let worktree = data.find_worktree(worktree_id)?;
+
+    let project = data.find_project(&worktree.project_id)?;
+
+    let mut rules = project
+        .permission_policy
+        .as_ref()
+        .map(|p| p.rules.clone())
+        .unwrap_or_default();
+
+    // Never the worktree's copy: the agent can edit it
+    if let Some(jean_policy) =
+        crate::projects::git::read_jean_config(&project.path).and_then(|c| c.permissions)
+    {
+        rules.extend(jean_policy.rules);
+    }
+
+    for (index, rule) in rules.iter().enumerate() {
+        if let Some(error) = rule_error(rule) {
+            let effect = match rule.action {
+                PolicyAction::Allow => "ignoring it",
+                PolicyAction::Deny | PolicyAction::Ask => "applying it to every tool call",
+            };
+            log::warn!(
+                "Permission rule {} of project {} is invalid ({error}), {effect}",
+                index + 1,
+                project.name
+            );
+        }
+    }
+
+    if rules.is_empty() {
+        None
+    } else {
+        Some((PermissionPolicy { rules }, worktree.path.clone()))
+    }
+}
+
+/// Record policy decisions for Claude's permission denials and drop the ones the
+/// policy denies outright, so they aren't offered for approval in the UI.
+pub fn filter_claude_denials(
+    app: &AppHandle,
+    session_id: &str,
+    worktree_id: &str,
+    denials: Vec<PermissionDenial>,
+) -> Vec<PermissionDenial> {
+    let Some((policy, worktree_path)) = load_policy_with_worktree_path(app, worktree_id) else {
+        return denials;
+    };
+
+    denials
+        .into_iter()
+        .filter(|denial| {
+            let input = &denial.tool_input;
+            let paths: Vec<String> = ["file_path", "notebook_path", "path"]
+                .iter()
+                .filter_map(|key| input.get(*key).and_then(|v| v.as_str()))
+                .map(String::from)
+                .collect();
+            let request = ToolRequest {
+                tool: &denial.tool_name,
+                command: input.get("command").and_then(|v| v.as_str()),
+                paths: relative_paths(Path::new(&worktree_path), &paths),
+            };
+            let decision = policy.evaluate(&request);
+            record_decision(app, session_id, worktree_id, "claude", &request, decision);
+            !matches!(decision, Some(d) if d.action == PolicyAction::Deny)
+        })
+        .collect()
+}
+
+/// Append a decision to the running run's audit trail and notify the frontend
+pub fn record_decision(
+    app: &AppHandle,
+    session_id: &str,
+    worktree_id: &str,
+    backend: &str,
+    request: &ToolRequest,
+    decision: Option<PolicyDecision>,
+) {
+    let target = match (request.command, request.paths.is_empty()) {
+        (Some(command), _) => Some(command.to_string()),
+        (None, false) => Some(request.paths.join(", ")),
+        (None, true) => None,
+    };
+    let entry = PermissionAuditEntry {
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        backend: backend.to_string(),
+        tool_name: request.tool.to_string(),
+        target,
+        action: decision.map(|d| d.action),
+        rule_index: decision.map(|d| d.rule_index),
+    };
+
+    log::debug!(
+        "[Policy] session={session_id} {backend} {} → {:?}",
+        entry.tool_name,
+        entry.action
+    );
+
+    if let Err(e) = super::run_log::append_permission_audit(app, session_id, entry.clone()) {
+        log::warn!("Failed to record permission decision for session {session_id}: {e}");
+    }
+
+    let _ = app.emit_all(
+        "chat:permission_decision",
+        &PermissionDecisionEvent {
+            session_id: session_id.to_string(),
+            worktree_id: worktree_id.to_string(),
+            entry,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(
+        action: PolicyAction,
+        tool: Option<&str>,
+        command: Option<&str>,
+        path: Option<&str>,
+    ) -> PolicyRule {
+        PolicyRule {
+            action,
+            tool: tool.map(String::from),
+            command: command.map(String::from),
+            path: path.map(String::from),
+        }
+    }
+
+    fn bash(command: &str) -> ToolRequest<'_> {
+        ToolRequest {
+            tool: "Bash",
+            command: Some(command),
+            paths: vec![],
+        }
+    }
+
+    #[test]
+    fn test_deny_beats_allow() {
+        let policy = PermissionPolicy {
+            rules: vec![
+                rule(PolicyAction::Allow, Some("Bash"), None, None),
+                rule(PolicyAction::Deny, None, Some("^git push"), None),
+            ],
+        };
+        let decision = policy.evaluate(&bash("git push --force")).unwrap();
+        assert_eq!(decision.action, PolicyAction::Deny);
+        assert_eq!(decision.rule_index, 1);
+
+        let decision = policy.evaluate(&bash("git status")).unwrap();
+        assert_eq!(decision.action, PolicyAction::Allow);
+    }
+
+    #[test]
+    fn test_path_and_tool_globs() {
+        let policy = PermissionPolicy {
+            rules: vec![rule(
+                PolicyAction::Ask,
+                Some("{Edit,Write}"),
+                None,
+                Some("migrations/**"),
+            )],
+        };
+        let request = ToolRequest {
+            tool: "edit",
+            command: None,
+            paths: vec!["migrations/001_init.sql".to_string()],
+        };
+        assert_eq!(policy.evaluate(&request).unwrap().action, PolicyAction::Ask);
+
+        let request = ToolRequest {
+            tool: "Edit",
+            command: None,
+            paths: vec!["src/migrations.rs".to_string()],
+        };
+        assert!(policy.evaluate(&request).is_none());
+        assert!(policy.evaluate(&bash("ls migrations")).is_none());
+    }
+
+    #[test]
+    fn test_to_claude_rules() {
+        let policy = PermissionPolicy {
+            rules: vec![
+                rule(PolicyAction::Allow, Some("WebFetch"), None, None),
+                rule(PolicyAction::Deny, None, Some("^git push"), None),
+                rule(PolicyAction::Deny, None, Some(r"rm -rf"), None),
+                rule(PolicyAction::Ask, None, None, Some(".env*")),
+                rule(PolicyAction::Ask, None, Some("^npm (install|i)"), None),
+            ],
+        };
+        let rules = policy.to_claude_rules();
+        assert_eq!(rules.allowed, vec!["WebFetch"]);
+        assert_eq!(rules.disallowed, vec!["Bash(git push:*)", "Bash(*rm -rf*)"]);
+        // The regex ask rule can't be expressed, so Claude asks for every Bash call
+        assert_eq!(
+            rules.ask,
+            vec!["Read(.env*)", "Edit(.env*)", "Write(.env*)", "Bash"]
+        );
+    }
+
+    #[test]
+    fn test_inexpressible_deny_rules_fail_closed() {
+        let policy = PermissionPolicy {
+            rules: vec![
+                rule(PolicyAction::Deny, None, Some("^npm (install|i)"), None),
+                rule(
+                    PolicyAction::Deny,
+                    Some("Bash"),
+                    Some("^cat"),
+                    Some("secrets/**"),
+                ),
+                rule(PolicyAction::Deny, Some("{Edit,Write}"), None, None),
+                rule(PolicyAction::Allow, None, Some("^(a|b)"), None),
+            ],
+        };
+        let rules = policy.to_claude_rules();
+        assert_eq!(rules.disallowed, vec!["Bash", "Bash", "Edit", "Write"]);
+        assert!(rules.allowed.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_rules_fail_closed() {
+        let deny = PermissionPolicy {
+            rules: vec![rule(PolicyAction::Deny, Some("Bash"), Some("("), None)],
+        };
+        let decision = deny.evaluate(&bash("ls")).unwrap();
+        assert_eq!(decision.action, PolicyAction::Deny);
+        assert_eq!(deny.to_claude_rules().disallowed.len(), CLAUDE_TOOLS.len());
+        assert_eq!(deny.runtime_checked_tools().len(), CLAUDE_TOOLS.len());
+
+        let allow = PermissionPolicy {
+            rules: vec![rule(PolicyAction::Allow, Some("{Bash"), None, None)],
+        };
+        assert!(allow.evaluate(&bash("ls")).is_none());
+        assert_eq!(allow.to_claude_rules(), ClaudePermissionRules::default());
+    }
+
+    #[test]
+    fn test_runtime_checked_tools() {
+        let policy = PermissionPolicy {
+            rules: vec![
+                rule(PolicyAction::Deny, Some("WebFetch"), None, None),
+                rule(PolicyAction::Deny, None, Some("^git push"), None),
+                rule(PolicyAction::Ask, None, None, Some(".env*")),
+                rule(PolicyAction::Deny, Some("Web*"), None, None),
+                rule(PolicyAction::Allow, Some("Task"), None, None),
+            ],
+        };
+        assert_eq!(
+            policy.runtime_checked_tools(),
+            vec!["Bash", "Read", "Edit", "Write", "WebFetch", "WebSearch"]
+        );
+    }
+
+    #[test]
+    fn test_claude_command_pattern() {
+        assert_eq!(
+            claude_command_pattern("^cargo test$").as_deref(),
+            Some("cargo test")
+        );
+        assert_eq!(
+            claude_command_pattern(r"^make\.sh.*").as_deref(),
+            Some("make.sh:*")
+        );
+        assert_eq!(claude_command_pattern("^(a|b)"), None);
+    }
+
+    #[test]
+    fn test_validate_reports_rule_number() {
+        let policy = PermissionPolicy {
+            rules: vec![
+                rule(PolicyAction::Allow, Some("Bash"), None, None),
+                rule(PolicyAction::Deny, None, Some("(unclosed"), None),
+            ],
+        };
+        let err = policy.validate().unwrap_err();
+        assert!(err.starts_with("Rule 2:"), "{err}");
+
+        let policy = PermissionPolicy {
+            rules: vec![rule(PolicyAction::Deny, None, None, None)],
+        };
+        assert!(policy.validate().is_err());
+    }
+
+    #[test]
+    fn test_relative_paths() {
+        let paths = vec!["/w/src/main.rs".to_string(), "/other/file".to_string()];
+        assert_eq!(
+            relative_paths(Path::new("/w"), &paths),
+            vec!["src/main.rs".to_string(), "/other/file".to_string()]
+        );
+    }
+}
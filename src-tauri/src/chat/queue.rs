@@ -0,0 +1,464 @@
+//! Server-side processing of queued chat messages.
+//!
+//! Queued messages are persisted on session metadata by `enqueue_message`. When a
+//! run finishes, the backend dequeues the next message and starts it with the
+//! settings captured at queue time, so queues keep draining without any client
+//! connected (headless mode, closed laptop lid, etc.). Clients only observe the
+//! regular `chat:*` and `queue:updated` events.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use tauri::AppHandle;
+
+use super::registry::is_session_actively_managed;
+use super::storage::{load_metadata, with_existing_metadata_mut};
+use super::types::{EffortLevel, ThinkingLevel};
+use crate::http_server::EmitExt;
+use crate::projects::storage::load_projects_data;
+
+/// Sessions whose queue is currently being drained by a backend worker.
+/// Prevents two workers (run-completion hook + explicit kick) from dequeuing
+/// concurrently and racing each other into `send_chat_message`.
+static DRAINING_SESSIONS: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Tools allowed per session by client sends (git tools plus everything the user
+/// approved from permission prompts). Queued runs reuse them so they don't stop
+/// again on tools the user already approved for that session.
+static SESSION_ALLOWED_TOOLS: Lazy<Mutex<HashMap<String, Vec<String>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Git commands auto-approved alongside session approvals (`GIT_ALLOWED_TOOLS` in the frontend)
+const GIT_ALLOWED_TOOLS: &[&str] = &["Bash(git:*)"];
+
+/// Image attached to a queued message
+#[derive(Debug, Clone, Deserialize)]
+struct QueuedImage {
+    path: String,
+}
+
+/// File or directory mentioned in a queued message
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QueuedFile {
+    relative_path: String,
+    #[serde(default)]
+    is_directory: bool,
+}
+
+/// Skill or text file attached to a queued message
+#[derive(Debug, Clone, Deserialize)]
+struct QueuedPath {
+    path: String,
+}
+
+/// Typed view of a queued message as stored by the frontend (`QueuedMessage` in types/chat.ts)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedMessage {
+    pub id: String,
+    pub message: String,
+    #[serde(default)]
+    pending_images: Vec<QueuedImage>,
+    #[serde(default)]
+    pending_files: Vec<QueuedFile>,
+    #[serde(default)]
+    pending_skills: Vec<QueuedPath>,
+    #[serde(default)]
+    pending_text_files: Vec<QueuedPath>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub execution_mode: Option<String>,
+    #[serde(default)]
+    pub thinking_level: Option<ThinkingLevel>,
+    #[serde(default)]
+    pub effort_level: Option<EffortLevel>,
+    #[serde(default)]
+    pub mcp_config: Option<String>,
+    #[serde(default)]
+    pub command_allowed_tools: Option<Vec<String>>,
+    #[serde(default)]
+    pub backend: Option<String>,
+}
+
+impl QueuedMessage {
+    /// Build the full message text with attachment references, mirroring what the
+    /// chat input sends for a direct (non-queued) message.
+    pub fn message_with_refs(&self) -> String {
+        let mut refs: Vec<String> = Vec::new();
+
+        if !self.pending_files.is_empty() {
+            refs.push(
+                self.pending_files
+                    .iter()
+                    .map(|f| {
+                        if f.is_directory {
+                            format!(
+                                "[Directory: {} - Use Glob and Read tools to explore this directory]",
+                                f.relative_path
+                            )
+                        } else {
+                            format!(
+                                "[File: {} - Use the Read tool to view this file]",
+                                f.relative_path
+                            )
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            );
+        }
+
+        if !self.pending_skills.is_empty() {
+            refs.push(
+                self.pending_skills
+                    .iter()
+                    .map(|s| {
+                        format!(
+                            "[Skill: {} - Read and use this skill to guide your response]",
+                            s.path
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            );
+        }
+
+        if !self.pending_images.is_empty() {
+            refs.push(
+                self.pending_images
+                    .iter()
+                    .map(|img| {
+                        format!(
+                            "[Image attached: {} - Use the Read tool to view this image]",
+                            img.path
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            );
+        }
+
+        if !self.pending_text_files.is_empty() {
+            refs.push(
+                self.pending_text_files
+                    .iter()
+                    .map(|tf| {
+                        format!(
+                            "[Text file attached: {} - Use the Read tool to view this file]",
+                            tf.path
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            );
+        }
+
+        let mut message = self.message.clone();
+        for block in refs {
+            if message.is_empty() {
+                message = block;
+            } else {
+                message = format!("{message}\n\n{block}");
+            }
+        }
+        message
+    }
+}
+
+/// Remember the tools a client allowed for a session's run
+pub fn remember_allowed_tools(session_id: &str, tools: &[String]) {
+    if tools.is_empty() {
+        return;
+    }
+    let mut allowed = SESSION_ALLOWED_TOOLS.lock().unwrap();
+    let entry = allowed.entry(session_id.to_string()).or_default();
+    for tool in tools {
+        if !entry.contains(tool) {
+            entry.push(tool.clone());
+        }
+    }
+}
+
+/// Add a command's frontmatter `allowed-tools` to the tools allowed for a single run
+pub fn with_command_tools(
+    allowed: Option<Vec<String>>,
+    command: Option<Vec<String>>,
+) -> Option<Vec<String>> {
+    let Some(command) = command.filter(|c| !c.is_empty()) else {
+        return allowed;
+    };
+    let mut tools = allowed.unwrap_or_default();
+    for tool in command {
+        if !tools.contains(&tool) {
+            tools.push(tool);
+        }
+    }
+    Some(tools)
+}
+
+/// Allowed tools for a queued run: git tools plus session approvals, when there are
+/// any (as the frontend queue processor did)
+fn merge_allowed_tools(approved: &[String]) -> Option<Vec<String>> {
+    let mut tools: Vec<String> = Vec::new();
+    if !approved.is_empty() {
+        tools.extend(GIT_ALLOWED_TOOLS.iter().map(|t| t.to_string()));
+        tools.extend(approved.iter().cloned());
+    }
+
+    let mut seen = HashSet::new();
+    tools.retain(|t| seen.insert(t.clone()));
+    (!tools.is_empty()).then_some(tools)
+}
+
+/// Whether a session can start its next queued message right now
+fn is_session_idle(app: &AppHandle, session_id: &str) -> bool {
+    if is_session_actively_managed(session_id) {
+        return false;
+    }
+    match load_metadata(app, session_id) {
+        Ok(Some(metadata)) => !metadata.waiting_for_input && metadata.archived_at.is_none(),
+        _ => false,
+    }
+}
+
+/// Atomically pop the first queued message and broadcast the new queue state.
+/// Messages that can't be parsed are dropped (and logged) so they can't wedge the queue.
+fn dequeue_next(app: &AppHandle, session_id: &str) -> Result<Option<QueuedMessage>, String> {
+    let (dequeued, queue) = with_existing_metadata_mut(app, session_id, |metadata| {
+        let dequeued = if metadata.queued_messages.is_empty() {
+            None
+        } else {
+            Some(metadata.queued_messages.remove(0))
+        };
+        (dequeued, metadata.queued_messages.clone())
+    })?;
+
+    if dequeued.is_none() {
+        return Ok(None);
+    }
+
+    app.emit_all(
+        "queue:updated",
+        &serde_json::json!({ "sessionId": session_id, "queue": queue }),
+    )
+    .ok();
+
+    match serde_json::from_value::<QueuedMessage>(dequeued.unwrap_or_default()) {
+        Ok(msg) => Ok(Some(msg)),
+        Err(e) => {
+            log::error!(
+                "[Queue] Dropping unparseable queued message for session {session_id}: {e}"
+            );
+            Err(format!("Invalid queued message: {e}"))
+        }
+    }
+}
+
+/// Start draining a session's queue in the background if it isn't already being drained.
+///
+/// Safe to call from anywhere (run completion, explicit client kick): if the session is
+/// busy, waiting for input, or has an empty queue, the worker exits without doing anything.
+pub fn schedule_queue_processing(app: &AppHandle, session_id: &str, worktree_id: &str) {
+    {
+        let mut draining = DRAINING_SESSIONS.lock().unwrap();
+        if !draining.insert(session_id.to_string()) {
+            log::trace!("[Queue] session={session_id} already draining");
+            return;
+        }
+    }
+
+    let app = app.clone();
+    let session_id = session_id.to_string();
+    let worktree_id = worktree_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        drain_queue(&app, &session_id, &worktree_id).await;
+        DRAINING_SESSIONS.lock().unwrap().remove(&session_id);
+    });
+}
+
+/// Run queued messages one after another until the queue is empty or the
+/// session stops being idle (cancelled run, waiting for input, error).
+async fn drain_queue(app: &AppHandle, session_id: &str, worktree_id: &str) {
+    let worktree_path = match load_projects_data(app)
+        .ok()
+        .and_then(|data| data.find_worktree(worktree_id).map(|w| w.path.clone()))
+    {
+        Some(path) => path,
+        None => {
+            log::warn!("[Queue] Cannot resolve worktree {worktree_id} for session {session_id}");
+            return;
+        }
+    };
+
+    let prefs = crate::load_preferences_sync(app).unwrap_or_default();
+    let parallel_execution_prompt = if prefs.parallel_execution_prompt_enabled {
+        Some(
+            prefs
+                .magic_prompts
+                .parallel_execution
+                .clone()
+                .unwrap_or_else(crate::default_parallel_execution_prompt),
+        )
+    } else {
+        None
+    };
+    let ai_language = Some(prefs.ai_language.clone()).filter(|l| !l.trim().is_empty());
+
+    loop {
+        if !is_session_idle(app, session_id) {
+            log::trace!("[Queue] session={session_id} not idle, stopping drain");
+            return;
+        }
+
+        let msg = match dequeue_next(app, session_id) {
+            Ok(Some(msg)) => msg,
+            Ok(None) => return,
+            Err(_) => continue,
+        };
+
+        log::info!(
+            "[Queue] session={session_id} processing queued message {}",
+            msg.id
+        );
+
+        let approved = SESSION_ALLOWED_TOOLS
+            .lock()
+            .unwrap()
+            .get(session_id)
+            .cloned()
+            .unwrap_or_default();
+        // The command's tools go separately so they aren't remembered for the session
+        let allowed_tools = merge_allowed_tools(&approved);
+        // `__anthropic__` (and profiles deleted since queueing) mean the default CLI settings
+        let custom_profile_name = msg
+            .provider
+            .clone()
+            .filter(|p| prefs.custom_cli_profiles.iter().any(|c| &c.name == p));
+
+        let result = super::send_chat_message(
+            app.clone(),
+            session_id.to_string(),
+            worktree_id.to_string(),
+            worktree_path.clone(),
+            msg.message_with_refs(),
+            msg.model.clone(),
+            msg.execution_mode.clone(),
+            msg.thinking_level.clone(),
+            msg.effort_level.clone(),
+            parallel_execution_prompt.clone(),
+            ai_language.clone(),
+            allowed_tools,
+            msg.command_allowed_tools.clone(),
+            msg.mcp_config.clone(),
+            Some(prefs.chrome_enabled),
+            custom_profile_name,
+            msg.backend.clone(),
+        )
+        .await;
+
+        match result {
+            Ok(response) if response.cancelled => {
+                log::info!("[Queue] session={session_id} run cancelled, pausing queue");
+                return;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                log::warn!("[Queue] session={session_id} queued run failed: {e}");
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_frontend_queued_message() {
+        let value = serde_json::json!({
+            "id": "q1",
+            "message": "Fix the bug",
+            "pendingImages": [{ "id": "i1", "path": "/tmp/a.png", "filename": "a.png" }],
+            "pendingFiles": [
+                { "id": "f1", "relativePath": "src/main.rs", "extension": "rs", "isDirectory": false },
+                { "id": "f2", "relativePath": "src", "extension": "", "isDirectory": true }
+            ],
+            "pendingSkills": [],
+            "pendingTextFiles": [],
+            "model": "opus",
+            "provider": null,
+            "executionMode": "build",
+            "thinkingLevel": "think",
+            "effortLevel": "high",
+            "queuedAt": 1700000000
+        });
+
+        let msg: QueuedMessage = serde_json::from_value(value).unwrap();
+        assert_eq!(msg.model.as_deref(), Some("opus"));
+        assert_eq!(msg.execution_mode.as_deref(), Some("build"));
+        assert_eq!(msg.thinking_level, Some(ThinkingLevel::Think));
+        assert_eq!(msg.effort_level, Some(EffortLevel::High));
+        assert!(msg.provider.is_none());
+
+        let full = msg.message_with_refs();
+        assert_eq!(
+            full,
+            "Fix the bug\n\n\
+             [File: src/main.rs - Use the Read tool to view this file]\n\
+             [Directory: src - Use Glob and Read tools to explore this directory]\n\n\
+             [Image attached: /tmp/a.png - Use the Read tool to view this image]"
+        );
+    }
+
+    #[test]
+    fn test_merge_allowed_tools() {
+        let approved = vec!["Bash(bun test)".to_string(), "Bash(git:*)".to_string()];
+
+        assert_eq!(
+            merge_allowed_tools(&approved),
+            Some(vec![
+                "Bash(git:*)".to_string(),
+                "Bash(bun test)".to_string()
+            ])
+        );
+        assert_eq!(merge_allowed_tools(&[]), None);
+    }
+
+    #[test]
+    fn test_with_command_tools() {
+        let allowed = Some(vec!["Bash(git:*)".to_string(), "Read".to_string()]);
+        let command = Some(vec!["Read".to_string(), "Bash(bun test)".to_string()]);
+
+        assert_eq!(
+            with_command_tools(allowed.clone(), command),
+            Some(vec![
+                "Bash(git:*)".to_string(),
+                "Read".to_string(),
+                "Bash(bun test)".to_string(),
+            ])
+        );
+        assert_eq!(with_command_tools(allowed.clone(), Some(vec![])), allowed);
+        assert_eq!(with_command_tools(None, None), None);
+    }
+
+    #[test]
+    fn test_message_with_refs_empty_text() {
+        let value = serde_json::json!({
+            "id": "q2",
+            "message": "",
+            "pendingTextFiles": [{ "id": "t1", "path": "/tmp/paste.txt", "size": 10 }]
+        });
+        let msg: QueuedMessage = serde_json::from_value(value).unwrap();
+        assert_eq!(
+            msg.message_with_refs(),
+            "[Text file attached: /tmp/paste.txt - Use the Read tool to view this file]"
+        );
+    }
+}
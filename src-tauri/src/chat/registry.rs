@@ -212,6 +212,21 @@ pub fn is_session_actively_managed(session_id: &str) -> bool {
         || lock_recover(&CODEX_TURN_REGISTRY, "CODEX_TURN_REGISTRY").contains_key(session_id)
 }
 
+/// Stop a Claude run inside the worktree's container, if it has one (background thread)
+fn stop_container_agent(app: &AppHandle, session_id: &str, worktree_id: &str) {
+    let Some(worktree_path) = crate::projects::storage::load_projects_data(app)
+        .ok()
+        .and_then(|data| data.find_worktree(worktree_id).map(|w| w.path.clone()))
+    else {
+        return;
+    };
+    let app = app.clone();
+    let session_id = session_id.to_string();
+    std::thread::spawn(move || {
+        crate::projects::container::kill_agent(&app, &worktree_path, &session_id);
+    });
+}
+
 /// Cancel a running Claude process for a session by sending SIGKILL to the process group
 /// Returns true if a process was found and signal sent, false otherwise
 ///
@@ -267,6 +282,9 @@ pub fn cancel_process(
             log::trace!("Direct kill of pid={pid} succeeded");
         }
 
+        // Runs inside a worktree container outlive the host-side `exec` client
+        stop_container_agent(app, session_id, worktree_id);
+
         // Update manifest SYNCHRONOUSLY before emitting event
         // This ensures any frontend refetch sees "Cancelled" status, not "Running"
         if let Err(e) = run_log::mark_running_run_cancelled(app, session_id) {
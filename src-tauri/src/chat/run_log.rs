@@ -11,10 +11,12 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
 use super::storage::{
-    get_session_dir, list_all_session_ids, load_metadata, save_metadata, with_metadata_mut,
+    get_session_dir, list_all_session_ids, load_metadata, save_metadata,
+    with_existing_metadata_mut, with_metadata_mut,
 };
 use super::types::{
-    Backend, ChatMessage, ContentBlock, MessageRole, RunEntry, RunStatus, ToolCall, UsageData,
+    Backend, ChatMessage, ContentBlock, MessageRole, PermissionAuditEntry, RunEntry, RunStatus,
+    ToolCall, UsageData,
 };
 
 // ============================================================================
@@ -329,6 +331,7 @@ pub fn start_run(
         claude_session_id: None,
         pid: None,   // Set later via set_pid() after spawning detached process
         usage: None, // Set on completion via complete()
+        permission_audit: Vec::new(),
     };
 
     with_metadata_mut(
@@ -797,6 +800,25 @@ pub fn load_session_messages(
     Ok(messages)
 }
 
+/// Append a permission policy decision to the session's running run.
+/// Decisions made while no run is active are dropped.
+pub fn append_permission_audit(
+    app: &tauri::AppHandle,
+    session_id: &str,
+    entry: PermissionAuditEntry,
+) -> Result<(), String> {
+    with_existing_metadata_mut(app, session_id, |metadata| {
+        if let Some(run) = metadata
+            .runs
+            .iter_mut()
+            .rev()
+            .find(|r| r.status == RunStatus::Running)
+        {
+            run.permission_audit.push(entry);
+        }
+    })
+}
+
 /// Mark any running run for this session as cancelled (called by cancel_process)
 /// This is called synchronously when the user cancels, before emitting chat:cancelled event.
 /// This ensures the metadata is updated immediately, not after tail_claude_output times out.
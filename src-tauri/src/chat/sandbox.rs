@@ -0,0 +1,524 @@
+//! Sandboxed execution of agent runs (Linux).
+//!
+//! A project can enable `sandbox` in its settings to restrict what agent
+//! processes may write to: the worktree, the repository's git dir, Jean's
+//! per-session app-data folders and the backend's own state dirs. Everything
+//! else on the filesystem stays readable but becomes read-only, and so do the
+//! git dir's `hooks/` and `config`: Jean runs git unsandboxed, so an agent
+//! must not be able to install hooks or point `core.hooksPath` /
+//! `core.fsmonitor` at its own scripts. For the same reason a worktree's
+//! `.git` pointer file and its per-worktree gitdir's `commondir`, `gitdir`
+//! and `config.worktree` stay read-only, so git can't be redirected to a git
+//! dir the agent controls.
+//!
+//! - Claude: the detached CLI is started under bubblewrap (`bwrap`).
+//! - Codex: the app-server is shared, so each turn gets Codex's own
+//!   (landlock-based) `workspaceWrite` sandbox policy with the same roots.
+//! - OpenCode: the server is shared, so it is started under bubblewrap as a
+//!   whole when any project enables the sandbox (takes effect on the next
+//!   server start).
+//!
+//! `block_network` is enforced by Codex's sandbox, which cuts network access
+//! for the commands it runs while the agent itself keeps talking to its API
+//! (build mode never has network access). Claude and OpenCode need the network
+//! for their model API and can't restrict only their commands, so runs on those
+//! backends are refused while it is set.
+
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, Manager};
+
+use super::types::Backend;
+use crate::projects::storage::{get_project_worktrees_dir, load_projects_data};
+
+/// App-data subdirectories agents read from and write to during a run
+const APP_DATA_SUBDIRS: [&str; 5] = [
+    "pasted-images",
+    "pasted-texts",
+    "session-context",
+    "git-context",
+    "combined-contexts",
+];
+
+/// Resolved sandbox for a single run
+#[derive(Debug, Clone, PartialEq)]
+pub struct SandboxSpec {
+    /// Paths bind-mounted writable (all other paths are read-only)
+    pub writable_paths: Vec<PathBuf>,
+    /// Paths inside writable paths that stay read-only (git hooks, config and
+    /// worktree pointers)
+    pub read_only_paths: Vec<PathBuf>,
+    /// Cut network access (Codex only, see module docs)
+    pub block_network: bool,
+    /// The sandboxed process outlives Jean (detached runs), so it must not
+    /// be killed when its parent exits
+    pub detached: bool,
+}
+
+impl SandboxSpec {
+    /// Build the bubblewrap arguments that precede the sandboxed command.
+    pub fn bwrap_args(&self, working_dir: &Path) -> Vec<String> {
+        let mut args: Vec<String> = [
+            "--ro-bind",
+            "/",
+            "/",
+            "--dev",
+            "/dev",
+            "--proc",
+            "/proc",
+            "--tmpfs",
+            "/tmp",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        for path in &self.writable_paths {
+            let path = path.to_string_lossy().to_string();
+            args.push("--bind".to_string());
+            args.push(path.clone());
+            args.push(path);
+        }
+
+        // After the writable binds, so they take precedence
+        for path in &self.read_only_paths {
+            let path = path.to_string_lossy().to_string();
+            args.push("--ro-bind".to_string());
+            args.push(path.clone());
+            args.push(path);
+        }
+
+        if !self.detached {
+            args.push("--die-with-parent".to_string());
+        }
+        args.push("--new-session".to_string());
+        args.push("--chdir".to_string());
+        args.push(working_dir.to_string_lossy().to_string());
+        args
+    }
+
+    /// Writable paths as strings (for Codex `writableRoots`).
+    ///
+    /// Codex can't exclude subpaths, so a writable path that contains
+    /// read-only paths is replaced by its other subdirectories, recursively
+    /// (files next to a read-only path, e.g. the git dir's `config`, stay
+    /// read-only). Codex itself keeps a writable root's top-level `.git`
+    /// read-only, so a worktree's pointer file doesn't need this.
+    pub fn writable_roots(&self) -> Vec<String> {
+        let mut roots = Vec::new();
+        for path in &self.writable_paths {
+            self.push_writable_root(path, &mut roots);
+        }
+        roots
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect()
+    }
+
+    fn push_writable_root(&self, path: &Path, roots: &mut Vec<PathBuf>) {
+        let protects_descendant = self
+            .read_only_paths
+            .iter()
+            .filter(|p| p.file_name() != Some(std::ffi::OsStr::new(".git")))
+            .any(|p| p != path && p.starts_with(path));
+        if !protects_descendant {
+            roots.push(path.to_path_buf());
+            return;
+        }
+        let Ok(entries) = std::fs::read_dir(path) else {
+            return;
+        };
+        let mut children: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_dir() && !self.read_only_paths.contains(p))
+            .collect();
+        children.sort();
+        for child in children {
+            self.push_writable_root(&child, roots);
+        }
+    }
+}
+
+/// Locate the bubblewrap binary. Sandboxing via bwrap is Linux-only.
+pub fn find_bwrap() -> Result<PathBuf, String> {
+    if !cfg!(target_os = "linux") {
+        return Err("Sandboxed runs are only supported on Linux".to_string());
+    }
+    which::which("bwrap").map_err(|_| {
+        "Sandbox is enabled for this project but bubblewrap (bwrap) is not installed. \
+         Install it or disable the sandbox in project settings."
+            .to_string()
+    })
+}
+
+/// Resolve the sandbox for a run in the given worktree.
+///
+/// Returns `None` when the project doesn't enable the sandbox, and an error
+/// when it blocks the network for a backend that can't enforce that.
+pub fn resolve_for_worktree(
+    app: &AppHandle,
+    worktree_id: &str,
+    session_id: &str,
+    backend: &Backend,
+) -> Result<Option<SandboxSpec>, String> {
+    let Ok(data) = load_projects_data(app) else {
+        return Ok(None);
+    };
+    let Some(worktree) = data.find_worktree(worktree_id) else {
+        return Ok(None);
+    };
+    let Some(config) = data
+        .find_project(&worktree.project_id)
+        .and_then(|p| p.sandbox.as_ref())
+        .filter(|c| c.enabled)
+    else {
+        return Ok(None);
+    };
+    check_network_support(config.block_network, backend)?;
+
+    let mut candidates = vec![PathBuf::from(&worktree.path)];
+    let mut read_only = protected_worktree_paths(Path::new(&worktree.path));
+    if let Some(git_dir) = git_common_dir(Path::new(&worktree.path)) {
+        read_only.extend(protected_git_paths(&git_dir));
+        candidates.push(git_dir);
+    }
+    if let Ok(app_data_dir) = app.path().app_data_dir() {
+        for subdir in APP_DATA_SUBDIRS {
+            candidates.push(app_data_dir.join(subdir));
+        }
+        candidates.push(app_data_dir.join("runs").join(session_id));
+    }
+    candidates.extend(backend_state_paths(backend));
+    candidates.extend(config.extra_writable_paths.iter().map(|p| expand_home(p)));
+
+    Ok(Some(SandboxSpec {
+        writable_paths: existing_unique(candidates),
+        read_only_paths: existing_unique(read_only),
+        block_network: config.block_network,
+        detached: false,
+    }))
+}
+
+/// Only Codex can cut network access for the agent's commands without
+/// cutting off its model API.
+fn check_network_support(block_network: bool, backend: &Backend) -> Result<(), String> {
+    if block_network && !matches!(backend, Backend::Codex) {
+        return Err(
+            "This project's sandbox blocks network access, which is only supported for Codex. \
+             Switch the session to Codex or turn off \"Block network\" in project settings."
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+/// Resolve the sandbox for a server process shared by all projects.
+///
+/// The server is sandboxed when any project enables the sandbox. Every
+/// project's repository and worktrees stay writable so unsandboxed projects
+/// keep working.
+pub fn resolve_for_shared_server(app: &AppHandle, backend: &Backend) -> Option<SandboxSpec> {
+    let data = load_projects_data(app).ok()?;
+    let sandboxed = data
+        .projects
+        .iter()
+        .filter_map(|p| p.sandbox.as_ref())
+        .any(|c| c.enabled);
+    if !sandboxed {
+        return None;
+    }
+
+    let mut candidates = Vec::new();
+    let mut read_only = Vec::new();
+    for project in data.projects.iter().filter(|p| !p.path.is_empty()) {
+        candidates.push(PathBuf::from(&project.path));
+        if let Some(git_dir) = git_common_dir(Path::new(&project.path)) {
+            read_only.extend(protected_git_paths(&git_dir));
+            candidates.push(git_dir);
+        }
+        if let Ok(dir) = get_project_worktrees_dir(&project.name, project.worktrees_dir.as_deref())
+        {
+            candidates.push(dir);
+        }
+        if let Some(config) = project.sandbox.as_ref() {
+            candidates.extend(config.extra_writable_paths.iter().map(|p| expand_home(p)));
+        }
+    }
+    for worktree in &data.worktrees {
+        read_only.extend(protected_worktree_paths(Path::new(&worktree.path)));
+    }
+    if let Ok(app_data_dir) = app.path().app_data_dir() {
+        for subdir in APP_DATA_SUBDIRS {
+            candidates.push(app_data_dir.join(subdir));
+        }
+        candidates.push(app_data_dir.join("runs"));
+    }
+    candidates.extend(backend_state_paths(backend));
+
+    Some(SandboxSpec {
+        writable_paths: existing_unique(candidates),
+        read_only_paths: existing_unique(read_only),
+        block_network: false,
+        detached: false,
+    })
+}
+
+/// State directories each CLI needs to write (auth, sessions, caches)
+fn backend_state_paths(backend: &Backend) -> Vec<PathBuf> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+    match backend {
+        Backend::Claude => vec![home.join(".claude"), home.join(".claude.json")],
+        Backend::Codex => vec![home.join(".codex")],
+        Backend::Opencode => vec![
+            home.join(".local/share/opencode"),
+            home.join(".local/state/opencode"),
+            home.join(".cache/opencode"),
+            home.join(".config/opencode"),
+        ],
+    }
+}
+
+/// Shared git dir of a worktree (commits write objects and refs there)
+fn git_common_dir(worktree_path: &Path) -> Option<PathBuf> {
+    let output = crate::platform::silent_command("git")
+        .args(["rev-parse", "--path-format=absolute", "--git-common-dir"])
+        .current_dir(worktree_path)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let dir = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!dir.is_empty()).then(|| PathBuf::from(dir))
+}
+
+/// Parts of a git dir that must stay read-only: hooks and config run or are
+/// read by Jean's own (unsandboxed) git commands. A missing `hooks/` is
+/// created so the agent can't create it either.
+fn protected_git_paths(git_dir: &Path) -> Vec<PathBuf> {
+    let hooks = git_dir.join("hooks");
+    if let Err(e) = std::fs::create_dir_all(&hooks) {
+        log::warn!("Failed to create {}: {e}", hooks.display());
+    }
+    vec![hooks, git_dir.join("config")]
+}
+
+/// Files that tell git where a linked worktree's repository is: the
+/// worktree's `.git` pointer file, and `commondir`, `gitdir` and
+/// `config.worktree` in the per-worktree gitdir it points to. A worktree
+/// whose `.git` is a directory (the main checkout) has none.
+fn protected_worktree_paths(worktree_path: &Path) -> Vec<PathBuf> {
+    let pointer = worktree_path.join(".git");
+    if !pointer.is_file() {
+        return Vec::new();
+    }
+    let mut paths = vec![pointer.clone()];
+    let content = std::fs::read_to_string(&pointer).unwrap_or_default();
+    if let Some(dir) = content.lines().find_map(|l| l.strip_prefix("gitdir:")) {
+        // Relative paths are relative to the worktree
+        let git_dir = worktree_path.join(dir.trim());
+        for name in ["commondir", "gitdir", "config.worktree"] {
+            paths.push(git_dir.join(name));
+        }
+    }
+    paths
+}
+
+/// Expand a leading `~/` to the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ if path == "~" => dirs::home_dir().unwrap_or_else(|| PathBuf::from(path)),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Keep only paths that exist (bwrap fails on missing bind sources), without duplicates
+fn existing_unique(candidates: Vec<PathBuf>) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for path in candidates {
+        if path.exists() && !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bwrap_args_binds_writable_paths_and_chdir() {
+        let spec = SandboxSpec {
+            writable_paths: vec![PathBuf::from("/work/tree"), PathBuf::from("/data/runs/s1")],
+            read_only_paths: Vec::new(),
+            block_network: false,
+            detached: false,
+        };
+        let args = spec.bwrap_args(Path::new("/work/tree"));
+
+        assert_eq!(&args[..3], &["--ro-bind", "/", "/"]);
+        let joined = args.join(" ");
+        assert!(joined.contains("--bind /work/tree /work/tree"));
+        assert!(joined.contains("--bind /data/runs/s1 /data/runs/s1"));
+        assert!(joined.ends_with("--chdir /work/tree"));
+        assert!(!args.contains(&"--unshare-net".to_string()));
+        assert!(args.contains(&"--die-with-parent".to_string()));
+    }
+
+    #[test]
+    fn test_detached_runs_survive_parent() {
+        let spec = SandboxSpec {
+            writable_paths: vec![PathBuf::from("/w")],
+            read_only_paths: Vec::new(),
+            block_network: false,
+            detached: true,
+        };
+        let args = spec.bwrap_args(Path::new("/w"));
+        assert!(!args.contains(&"--die-with-parent".to_string()));
+        assert!(args.contains(&"--new-session".to_string()));
+    }
+
+    #[test]
+    fn test_writable_binds_come_after_read_only_root() {
+        let spec = SandboxSpec {
+            writable_paths: vec![PathBuf::from("/w")],
+            read_only_paths: Vec::new(),
+            block_network: true,
+            detached: false,
+        };
+        let args = spec.bwrap_args(Path::new("/w"));
+        let ro = args.iter().position(|a| a == "--ro-bind").unwrap();
+        let rw = args.iter().position(|a| a == "--bind").unwrap();
+        assert!(ro < rw, "later binds must override the read-only root");
+    }
+
+    #[test]
+    fn test_git_hooks_and_config_rebound_read_only() {
+        let spec = SandboxSpec {
+            writable_paths: vec![PathBuf::from("/w"), PathBuf::from("/repo/.git")],
+            read_only_paths: vec![
+                PathBuf::from("/repo/.git/hooks"),
+                PathBuf::from("/repo/.git/config"),
+            ],
+            block_network: false,
+            detached: false,
+        };
+        let args = spec.bwrap_args(Path::new("/w"));
+        let joined = args.join(" ");
+        let rw = joined.find("--bind /repo/.git /repo/.git").unwrap();
+        let hooks = joined
+            .find("--ro-bind /repo/.git/hooks /repo/.git/hooks")
+            .unwrap();
+        let config = joined
+            .find("--ro-bind /repo/.git/config /repo/.git/config")
+            .unwrap();
+        assert!(rw < hooks && rw < config);
+    }
+
+    #[test]
+    fn test_writable_roots_skip_protected_git_paths() {
+        let git_dir = std::env::temp_dir().join(format!("jean-sandbox-{}", std::process::id()));
+        for dir in ["hooks", "objects", "refs"] {
+            std::fs::create_dir_all(git_dir.join(dir)).unwrap();
+        }
+        std::fs::write(git_dir.join("config"), "").unwrap();
+
+        let spec = SandboxSpec {
+            writable_paths: vec![PathBuf::from("/w"), git_dir.clone()],
+            read_only_paths: protected_git_paths(&git_dir),
+            block_network: false,
+            detached: false,
+        };
+        let roots = spec.writable_roots();
+        let _ = std::fs::remove_dir_all(&git_dir);
+
+        let expected: Vec<String> = [
+            PathBuf::from("/w"),
+            git_dir.join("objects"),
+            git_dir.join("refs"),
+        ]
+        .iter()
+        .map(|p| p.to_string_lossy().to_string())
+        .collect();
+        assert_eq!(roots, expected);
+    }
+
+    #[test]
+    fn test_worktree_pointers_stay_read_only() {
+        let root = std::env::temp_dir().join(format!("jean-sandbox-wt-{}", std::process::id()));
+        let git_dir = root.join("repo/.git/worktrees/feature");
+        let worktree = root.join("feature");
+        std::fs::create_dir_all(&git_dir).unwrap();
+        std::fs::create_dir_all(git_dir.join("logs")).unwrap();
+        std::fs::create_dir_all(&worktree).unwrap();
+        std::fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", git_dir.display()),
+        )
+        .unwrap();
+        std::fs::write(git_dir.join("commondir"), "../..\n").unwrap();
+        std::fs::write(git_dir.join("gitdir"), "").unwrap();
+        std::fs::create_dir_all(root.join("main/.git")).unwrap();
+
+        let protected = existing_unique(protected_worktree_paths(&worktree));
+        let main_checkout = protected_worktree_paths(&root.join("main"));
+        let spec = SandboxSpec {
+            writable_paths: vec![worktree.clone(), root.join("repo/.git")],
+            read_only_paths: protected.clone(),
+            block_network: false,
+            detached: false,
+        };
+        let args = spec.bwrap_args(&worktree).join(" ");
+        let roots = spec.writable_roots();
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(
+            protected,
+            vec![
+                worktree.join(".git"),
+                git_dir.join("commondir"),
+                git_dir.join("gitdir"),
+            ]
+        );
+        assert!(main_checkout.is_empty());
+        let pointer = worktree.join(".git").to_string_lossy().to_string();
+        assert!(args.contains(&format!("--ro-bind {pointer} {pointer}")));
+        // Codex: the worktree stays a root, the per-worktree gitdir only
+        // through its subdirectories
+        assert!(roots.contains(&worktree.to_string_lossy().to_string()));
+        assert!(roots.contains(&git_dir.join("logs").to_string_lossy().to_string()));
+        assert!(!roots.contains(&git_dir.to_string_lossy().to_string()));
+    }
+
+    #[test]
+    fn test_block_network_only_for_codex() {
+        assert!(check_network_support(true, &Backend::Codex).is_ok());
+        assert!(check_network_support(true, &Backend::Claude).is_err());
+        assert!(check_network_support(true, &Backend::Opencode).is_err());
+        assert!(check_network_support(false, &Backend::Claude).is_ok());
+    }
+
+    #[test]
+    fn test_expand_home() {
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(expand_home("~/.cargo"), home.join(".cargo"));
+        assert_eq!(expand_home("~"), home);
+        assert_eq!(expand_home("/opt/cache"), PathBuf::from("/opt/cache"));
+    }
+
+    #[test]
+    fn test_existing_unique_drops_missing_and_duplicates() {
+        let tmp = std::env::temp_dir();
+        let paths = existing_unique(vec![
+            tmp.clone(),
+            PathBuf::from("/definitely/not/here"),
+            tmp.clone(),
+        ]);
+        assert_eq!(paths, vec![tmp]);
+    }
+}
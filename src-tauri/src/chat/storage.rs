@@ -677,6 +677,7 @@ pub fn load_sessions(
                 answered_questions: vec![],
                 submitted_answers: std::collections::HashMap::new(),
                 fixed_findings: vec![],
+                review_threads: std::collections::HashMap::new(),
                 review_results: None,
                 pending_permission_denials: vec![],
                 denied_message_context: None,
@@ -758,6 +759,7 @@ where
                 answered_questions: vec![],
                 submitted_answers: std::collections::HashMap::new(),
                 fixed_findings: vec![],
+                review_threads: std::collections::HashMap::new(),
                 review_results: None,
                 pending_permission_denials: vec![],
                 denied_message_context: None,
@@ -197,6 +197,9 @@ pub struct PermissionDenial {
     /// JSON-RPC request ID (Codex only — used to respond to approval requests)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rpc_id: Option<u64>,
+    /// Permission request ID (OpenCode only — used to answer the pending request)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permission_id: Option<String>,
 }
 
 /// Payload for permission denied events sent to frontend
@@ -207,6 +210,77 @@ pub struct PermissionDeniedEvent {
     pub denials: Vec<PermissionDenial>,
 }
 
+/// Decision a permission policy rule makes for a matching tool call
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyAction {
+    /// Run the tool without asking
+    Allow,
+    /// Refuse the tool call
+    Deny,
+    /// Surface the tool call to the user for approval
+    Ask,
+}
+
+/// A single permission rule. All specified matchers must match for the rule to apply.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PolicyRule {
+    pub action: PolicyAction,
+    /// Tool name or glob (e.g., "Bash", "Edit", "mcp__*"). None = any tool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    /// Regex matched against shell commands (e.g., "^git push")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Glob matched against file paths, relative to the worktree (e.g., "migrations/**")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+/// Tool permission policy for a project (from project settings and/or jean.json)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct PermissionPolicy {
+    #[serde(default)]
+    pub rules: Vec<PolicyRule>,
+}
+
+/// A permission decision recorded on a run for auditing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionAuditEntry {
+    /// Unix timestamp of the decision
+    pub timestamp: u64,
+    /// Backend that requested the permission (claude, codex, opencode)
+    pub backend: String,
+    /// Tool name (e.g., "Bash", "Edit")
+    pub tool_name: String,
+    /// Command or file paths the decision applied to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// Decision taken (None = no rule matched, execution mode default applied)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<PolicyAction>,
+    /// Index of the matching rule in the effective policy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_index: Option<usize>,
+}
+
+/// Permission decisions of a single run (returned by get_permission_audit)
+#[derive(Debug, Clone, Serialize)]
+pub struct RunPermissionAudit {
+    pub run_id: String,
+    pub user_message_id: String,
+    pub started_at: u64,
+    pub entries: Vec<PermissionAuditEntry>,
+}
+
+/// Payload for permission decision events sent to frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct PermissionDecisionEvent {
+    pub session_id: String,
+    pub worktree_id: String,
+    pub entry: PermissionAuditEntry,
+}
+
 /// Context for a denied message that can be re-sent after permission approval
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeniedMessageContext {
@@ -218,6 +292,35 @@ pub struct DeniedMessageContext {
     pub thinking_level: String,
 }
 
+/// Progress of addressing a PR review thread from a session
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewThreadStatus {
+    /// Sent to the session, waiting for the run to finish
+    InProgress,
+    /// Changes pushed and a reply posted on the thread
+    Replied,
+    /// Replied and resolved on GitHub
+    Resolved,
+    /// The run didn't report on the thread, or replying failed
+    Failed,
+}
+
+/// Per-thread state of an "address review" run, keyed by thread ID on the session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewThreadProgress {
+    pub status: ReviewThreadStatus,
+    /// Commit the reply points at
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_sha: Option<String>,
+    /// What changed, as reported by the run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub updated_at: u64,
+}
+
 /// A content block in a message - text, tool use, or thinking
 /// Used to preserve the order of content in Claude's response
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -413,6 +516,9 @@ pub struct Session {
     /// Finding keys that have been marked as fixed
     #[serde(default)]
     pub fixed_findings: Vec<String>,
+    /// PR review threads being addressed from this session: thread ID -> progress
+    #[serde(default)]
+    pub review_threads: HashMap<String, ReviewThreadProgress>,
     /// AI code review results for this session
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub review_results: Option<serde_json::Value>,
@@ -504,6 +610,7 @@ impl Session {
             answered_questions: vec![],
             submitted_answers: HashMap::new(),
             fixed_findings: vec![],
+            review_threads: HashMap::new(),
             review_results: None,
             pending_permission_denials: vec![],
             denied_message_context: None,
@@ -684,6 +791,7 @@ impl SessionMetadata {
             answered_questions: self.answered_questions.clone(),
             submitted_answers: self.submitted_answers.clone(),
             fixed_findings: self.fixed_findings.clone(),
+            review_threads: self.review_threads.clone(),
             review_results: self.review_results.clone(),
             pending_permission_denials: self.pending_permission_denials.clone(),
             denied_message_context: self.denied_message_context.clone(),
@@ -720,6 +828,7 @@ impl SessionMetadata {
         self.answered_questions = session.answered_questions.clone();
         self.submitted_answers = session.submitted_answers.clone();
         self.fixed_findings = session.fixed_findings.clone();
+        self.review_threads = session.review_threads.clone();
         self.review_results = session.review_results.clone();
         self.pending_permission_denials = session.pending_permission_denials.clone();
         self.denied_message_context = session.denied_message_context.clone();
@@ -946,6 +1055,9 @@ pub struct RunEntry {
     /// Token usage for this run (captured from Claude CLI result)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub usage: Option<UsageData>,
+    /// Permission policy decisions made during this run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub permission_audit: Vec<PermissionAuditEntry>,
 }
 
 /// Session metadata - single source of truth for session data and run history
@@ -1005,6 +1117,9 @@ pub struct SessionMetadata {
     /// Finding keys that have been marked as fixed
     #[serde(default)]
     pub fixed_findings: Vec<String>,
+    /// PR review threads being addressed from this session: thread ID -> progress
+    #[serde(default)]
+    pub review_threads: HashMap<String, ReviewThreadProgress>,
     /// AI code review results for this session
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub review_results: Option<serde_json::Value>,
@@ -1134,6 +1249,7 @@ impl SessionMetadata {
             answered_questions: vec![],
             submitted_answers: HashMap::new(),
             fixed_findings: vec![],
+            review_threads: HashMap::new(),
             review_results: None,
             pending_permission_denials: vec![],
             denied_message_context: None,
@@ -1509,6 +1625,7 @@ mod tests {
             claude_session_id: None,
             pid: Some(12345),
             usage: None,
+            permission_audit: Vec::new(),
         });
 
         assert!(metadata.find_run("run-1").is_some());
@@ -1545,6 +1662,7 @@ mod tests {
             claude_session_id: None,
             pid: None,
             usage: None,
+            permission_audit: Vec::new(),
         });
 
         assert!(metadata.latest_claude_session_id().is_none());
@@ -1567,6 +1685,7 @@ mod tests {
             claude_session_id: Some("claude-sess-abc".to_string()),
             pid: None,
             usage: None,
+            permission_audit: Vec::new(),
         });
 
         assert_eq!(metadata.latest_claude_session_id(), Some("claude-sess-abc"));
@@ -82,7 +82,9 @@ pub async fn dispatch_command(
             let security_context = field_opt(&args, "securityContext", "security_context")?;
             let advisory_context = field_opt(&args, "advisoryContext", "advisory_context")?;
             let linear_context = field_opt(&args, "linearContext", "linear_context")?;
+            let jira_context = field_opt(&args, "jiraContext", "jira_context")?;
             let custom_name = field_opt(&args, "customName", "custom_name")?;
+            let parent_worktree_id = field_opt(&args, "parentWorktreeId", "parent_worktree_id")?;
             let result = crate::projects::create_worktree(
                 app.clone(),
                 project_id,
@@ -92,7 +94,9 @@ pub async fn dispatch_command(
                 security_context,
                 advisory_context,
                 linear_context,
+                jira_context,
                 custom_name,
+                parent_worktree_id,
             )
             .await?;
             // No cache invalidation here — worktree creation uses event-based sync
@@ -128,6 +132,16 @@ pub async fn dispatch_command(
                 None,
                 None,
                 None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
             )
             .await?;
             to_value(result)
@@ -201,6 +215,43 @@ pub async fn dispatch_command(
             let result = crate::projects::get_commit_diff(worktree_path, commit_sha).await?;
             to_value(result)
         }
+        "get_rebase_plan" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let base_branch: Option<String> = field_opt(&args, "baseBranch", "base_branch")?;
+            let result =
+                crate::projects::get_rebase_plan(app.clone(), worktree_id, base_branch).await?;
+            to_value(result)
+        }
+        "apply_rebase_plan" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let base: String = from_field(&args, "base")?;
+            let steps: Vec<crate::projects::git_log::RebaseStep> = from_field(&args, "steps")?;
+            let result =
+                crate::projects::apply_rebase_plan(app.clone(), worktree_id, base, steps).await?;
+            emit_cache_invalidation(app, &["projects"]);
+            to_value(result)
+        }
+        "generate_squash_message" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let shas: Vec<String> = from_field(&args, "shas")?;
+            let custom_prompt: Option<String> = field_opt(&args, "customPrompt", "custom_prompt")?;
+            let model: Option<String> = from_field_opt(&args, "model")?;
+            let custom_profile_name: Option<String> =
+                field_opt(&args, "customProfileName", "custom_profile_name")?;
+            let reasoning_effort: Option<String> =
+                field_opt(&args, "reasoningEffort", "reasoning_effort")?;
+            let result = crate::projects::generate_squash_message(
+                app.clone(),
+                worktree_id,
+                shas,
+                custom_prompt,
+                model,
+                custom_profile_name,
+                reasoning_effort,
+            )
+            .await?;
+            to_value(result)
+        }
         "get_repo_branches" => {
             let repo_path: String = field(&args, "repoPath", "repo_path")?;
             let result = crate::projects::get_repo_branches(repo_path).await?;
@@ -293,6 +344,7 @@ pub async fn dispatch_command(
                 field_opt(&args, "customProfileName", "custom_profile_name")?;
             let reasoning_effort: Option<String> =
                 field_opt(&args, "reasoningEffort", "reasoning_effort")?;
+            let stage_all: Option<bool> = field_opt(&args, "stageAll", "stage_all")?;
             let result = crate::projects::create_commit_with_ai(
                 app.clone(),
                 worktree_path,
@@ -303,10 +355,38 @@ pub async fn dispatch_command(
                 model,
                 custom_profile_name,
                 reasoning_effort,
+                stage_all,
+            )
+            .await?;
+            to_value(result)
+        }
+        "propose_commit_split" => {
+            let worktree_path: String = field(&args, "worktreePath", "worktree_path")?;
+            let custom_prompt: Option<String> = field_opt(&args, "customPrompt", "custom_prompt")?;
+            let model: Option<String> = from_field_opt(&args, "model")?;
+            let custom_profile_name: Option<String> =
+                field_opt(&args, "customProfileName", "custom_profile_name")?;
+            let reasoning_effort: Option<String> =
+                field_opt(&args, "reasoningEffort", "reasoning_effort")?;
+            let result = crate::projects::propose_commit_split(
+                app.clone(),
+                worktree_path,
+                custom_prompt,
+                model,
+                custom_profile_name,
+                reasoning_effort,
             )
             .await?;
             to_value(result)
         }
+        "apply_commit_split" => {
+            let worktree_path: String = field(&args, "worktreePath", "worktree_path")?;
+            let plan: crate::projects::commit_split::CommitSplitPlan = from_field(&args, "plan")?;
+            let result =
+                crate::projects::apply_commit_split(app.clone(), worktree_path, plan).await?;
+            emit_cache_invalidation(app, &["projects"]);
+            to_value(result)
+        }
         "revert_last_local_commit" => {
             let worktree_path: String = field(&args, "worktreePath", "worktree_path")?;
             let result =
@@ -335,6 +415,54 @@ pub async fn dispatch_command(
             .await?;
             to_value(result)
         }
+        "publish_review_to_pr" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let review: crate::projects::ReviewResponse = from_field(&args, "review")?;
+            let submit: Option<bool> = from_field_opt(&args, "submit")?;
+            let result =
+                crate::projects::publish_review_to_pr(app.clone(), worktree_id, review, submit)
+                    .await?;
+            to_value(result)
+        }
+        "get_pr_review_threads" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let include_resolved: Option<bool> =
+                field_opt(&args, "includeResolved", "include_resolved")?;
+            let result =
+                crate::projects::get_pr_review_threads(app.clone(), worktree_id, include_resolved)
+                    .await?;
+            to_value(result)
+        }
+        "start_addressing_review" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let session_id: String = field(&args, "sessionId", "session_id")?;
+            let thread_ids: Option<Vec<String>> = field_opt(&args, "threadIds", "thread_ids")?;
+            let result = crate::projects::start_addressing_review(
+                app.clone(),
+                worktree_id,
+                session_id,
+                thread_ids,
+            )
+            .await?;
+            to_value(result)
+        }
+        "finish_addressing_review" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let session_id: String = field(&args, "sessionId", "session_id")?;
+            let commit_message: Option<String> =
+                field_opt(&args, "commitMessage", "commit_message")?;
+            let resolve: Option<bool> = from_field_opt(&args, "resolve")?;
+            let result = crate::projects::finish_addressing_review(
+                app.clone(),
+                worktree_id,
+                session_id,
+                commit_message,
+                resolve,
+            )
+            .await?;
+            emit_cache_invalidation(app, &["sessions"]);
+            to_value(result)
+        }
         "cancel_review_with_ai" => {
             let review_run_id: String = field(&args, "reviewRunId", "review_run_id")?;
             let result = crate::projects::cancel_review_with_ai(review_run_id).await?;
@@ -393,6 +521,12 @@ pub async fn dispatch_command(
         // =====================================================================
         // GitHub Issues & PRs
         // =====================================================================
+        "get_project_forge" => {
+            let project_path: String = field(&args, "projectPath", "project_path")?;
+            let result =
+                crate::projects::forge::get_project_forge(app.clone(), project_path).await?;
+            to_value(result)
+        }
         "list_github_issues" => {
             let project_path: String = field(&args, "projectPath", "project_path")?;
             let state: Option<String> = from_field_opt(&args, "state")?;
@@ -799,6 +933,8 @@ pub async fn dispatch_command(
             let ai_language: Option<String> = field_opt(&args, "aiLanguage", "ai_language")?;
             let allowed_tools: Option<Vec<String>> =
                 field_opt(&args, "allowedTools", "allowed_tools")?;
+            let command_allowed_tools: Option<Vec<String>> =
+                field_opt(&args, "commandAllowedTools", "command_allowed_tools")?;
             let mut effort_level: Option<crate::chat::types::EffortLevel> =
                 field_opt(&args, "effortLevel", "effort_level")?;
             let thinking_level: Option<crate::chat::types::ThinkingLevel> = match thinking_level_raw
@@ -860,6 +996,7 @@ pub async fn dispatch_command(
                 parallel_execution_prompt,
                 ai_language,
                 allowed_tools,
+                command_allowed_tools,
                 mcp_config,
                 chrome_enabled,
                 custom_profile_name,
@@ -1085,6 +1222,30 @@ pub async fn dispatch_command(
             let result = crate::background_tasks::commands::get_remote_poll_interval(state)?;
             to_value(result)
         }
+        "start_ci_fix_watcher" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let session_id: String = field(&args, "sessionId", "session_id")?;
+            let max_attempts: Option<u32> = field_opt(&args, "maxAttempts", "max_attempts")?;
+            let state = app.state::<crate::background_tasks::BackgroundTaskManager>();
+            let result = crate::background_tasks::commands::start_ci_fix_watcher(
+                state,
+                worktree_id,
+                session_id,
+                max_attempts,
+            )?;
+            to_value(result)
+        }
+        "stop_ci_fix_watcher" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let state = app.state::<crate::background_tasks::BackgroundTaskManager>();
+            crate::background_tasks::commands::stop_ci_fix_watcher(state, worktree_id)?;
+            Ok(Value::Null)
+        }
+        "get_ci_fix_watchers" => {
+            let state = app.state::<crate::background_tasks::BackgroundTaskManager>();
+            let result = crate::background_tasks::commands::get_ci_fix_watchers(state)?;
+            to_value(result)
+        }
 
         // =====================================================================
         // Terminal
@@ -1172,6 +1333,7 @@ pub async fn dispatch_command(
             let security_context = field_opt(&args, "securityContext", "security_context")?;
             let advisory_context = field_opt(&args, "advisoryContext", "advisory_context")?;
             let linear_context = field_opt(&args, "linearContext", "linear_context")?;
+            let jira_context = field_opt(&args, "jiraContext", "jira_context")?;
             let result = crate::projects::create_worktree_from_existing_branch(
                 app.clone(),
                 project_id,
@@ -1181,6 +1343,7 @@ pub async fn dispatch_command(
                 security_context,
                 advisory_context,
                 linear_context,
+                jira_context,
             )
             .await?;
             to_value(result)
@@ -1272,7 +1435,7 @@ pub async fn dispatch_command(
         }
         "get_github_remotes" => {
             let repo_path: String = field(&args, "repoPath", "repo_path")?;
-            let result = crate::projects::get_github_remotes(repo_path).await?;
+            let result = crate::projects::get_github_remotes(app.clone(), repo_path).await?;
             to_value(result)
         }
         "get_github_branch_url" => {
@@ -1304,6 +1467,28 @@ pub async fn dispatch_command(
                 crate::projects::rebase_worktree(app.clone(), worktree_id, commit_message).await?;
             to_value(result)
         }
+        "create_child_worktree" => {
+            let parent_worktree_id: String =
+                field(&args, "parentWorktreeId", "parent_worktree_id")?;
+            let custom_name = field_opt(&args, "customName", "custom_name")?;
+            let result = crate::projects::create_child_worktree(
+                app.clone(),
+                parent_worktree_id,
+                custom_name,
+            )
+            .await?;
+            // Same event-based sync as create_worktree, no cache invalidation
+            to_value(result)
+        }
+        "restack_worktree" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let commit_message: Option<String> =
+                field_opt(&args, "commitMessage", "commit_message")?;
+            let result =
+                crate::projects::restack_worktree(app.clone(), worktree_id, commit_message).await?;
+            emit_cache_invalidation(app, &["projects"]);
+            to_value(result)
+        }
 
         // =====================================================================
         // Git Operations (additional)
@@ -1327,6 +1512,72 @@ pub async fn dispatch_command(
             let result = crate::projects::fetch_and_merge_base(app.clone(), worktree_id).await?;
             to_value(result)
         }
+        "get_conflict_state" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let result = crate::projects::get_conflict_state(app.clone(), worktree_id).await?;
+            to_value(result)
+        }
+        "resolve_conflict_hunk" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let path: String = field(&args, "path", "path")?;
+            let hunk_index: usize = field(&args, "hunkIndex", "hunk_index")?;
+            let resolution: crate::projects::conflicts::HunkResolution =
+                from_field(&args, "resolution")?;
+            let result = crate::projects::resolve_conflict_hunk(
+                app.clone(),
+                worktree_id,
+                path,
+                hunk_index,
+                resolution,
+            )
+            .await?;
+            to_value(result)
+        }
+        "mark_conflict_resolved" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let path: String = field(&args, "path", "path")?;
+            let side: Option<crate::projects::conflicts::ConflictSide> =
+                from_field_opt(&args, "side")?;
+            let result =
+                crate::projects::mark_conflict_resolved(app.clone(), worktree_id, path, side)
+                    .await?;
+            to_value(result)
+        }
+        "continue_conflict_operation" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let result =
+                crate::projects::continue_conflict_operation(app.clone(), worktree_id).await?;
+            emit_cache_invalidation(app, &["projects"]);
+            to_value(result)
+        }
+        "abort_conflict_operation" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let result =
+                crate::projects::abort_conflict_operation(app.clone(), worktree_id).await?;
+            emit_cache_invalidation(app, &["projects"]);
+            to_value(result)
+        }
+        "suggest_conflict_resolution" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let path: String = field(&args, "path", "path")?;
+            let hunk_index: usize = field(&args, "hunkIndex", "hunk_index")?;
+            let model: Option<String> = field_opt(&args, "model", "model")?;
+            let custom_profile_name: Option<String> =
+                field_opt(&args, "customProfileName", "custom_profile_name")?;
+            let reasoning_effort: Option<String> =
+                field_opt(&args, "reasoningEffort", "reasoning_effort")?;
+            let result = crate::projects::suggest_conflict_resolution(
+                app.clone(),
+                worktree_id,
+                path,
+                hunk_index,
+                model,
+                custom_profile_name,
+                reasoning_effort,
+            )
+            .await?;
+            to_value(result)
+        }
 
         // =====================================================================
         // Skills & Search
@@ -1443,6 +1694,45 @@ pub async fn dispatch_command(
             // NATIVE ONLY: Terminals don't work in browser mode
             Ok(Value::Null)
         }
+        "get_run_scripts" => {
+            // NATIVE ONLY: Terminals don't work in browser mode
+            Ok(Value::Array(vec![]))
+        }
+
+        // =====================================================================
+        // Services
+        // =====================================================================
+        "start_service" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let name: String = from_field(&args, "name")?;
+            let result = crate::services::start_service(app.clone(), worktree_id, name).await?;
+            to_value(result)
+        }
+        "stop_service" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let name: String = from_field(&args, "name")?;
+            crate::services::stop_service(app.clone(), worktree_id, name).await?;
+            Ok(Value::Null)
+        }
+        "restart_service" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let name: String = from_field(&args, "name")?;
+            let result = crate::services::restart_service(app.clone(), worktree_id, name).await?;
+            to_value(result)
+        }
+        "list_services" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let result = crate::services::list_services(app.clone(), worktree_id).await?;
+            to_value(result)
+        }
+        "get_service_logs" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let name: String = from_field(&args, "name")?;
+            let lines: Option<usize> = from_field_opt(&args, "lines")?;
+            let result =
+                crate::services::get_service_logs(app.clone(), worktree_id, name, lines).await?;
+            to_value(result)
+        }
 
         // =====================================================================
         // Session Management (additional)
@@ -1494,6 +1784,9 @@ pub async fn dispatch_command(
                 field_opt(&args, "enabledMcpServers", "enabled_mcp_servers")?;
             let selected_execution_mode: Option<Option<String>> =
                 field_opt(&args, "selectedExecutionMode", "selected_execution_mode")?;
+            let review_threads: Option<
+                std::collections::HashMap<String, crate::chat::types::ReviewThreadProgress>,
+            > = field_opt(&args, "reviewThreads", "review_threads")?;
             crate::chat::update_session_state(
                 app.clone(),
                 worktree_id,
@@ -1514,6 +1807,7 @@ pub async fn dispatch_command(
                 review_results,
                 enabled_mcp_servers,
                 selected_execution_mode,
+                review_threads,
             )
             .await?;
             emit_cache_invalidation(app, &["sessions"]);
@@ -1806,6 +2100,18 @@ pub async fn dispatch_command(
             crate::chat::approve_codex_command(session_id, rpc_id, decision)?;
             Ok(Value::Null)
         }
+        "approve_opencode_permission" => {
+            let session_id: String = field(&args, "sessionId", "session_id")?;
+            let permission_id: String = field(&args, "permissionId", "permission_id")?;
+            let decision: String = from_field(&args, "decision")?;
+            crate::chat::approve_opencode_permission(session_id, permission_id, decision).await?;
+            Ok(Value::Null)
+        }
+        "get_permission_audit" => {
+            let session_id: String = field(&args, "sessionId", "session_id")?;
+            let result = crate::chat::get_permission_audit(app.clone(), session_id).await?;
+            to_value(result)
+        }
 
         // =====================================================================
         // Queue management (cross-client sync)
@@ -1857,6 +2163,12 @@ pub async fn dispatch_command(
                 .await?;
             Ok(Value::Null)
         }
+        "process_message_queue" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let session_id: String = field(&args, "sessionId", "session_id")?;
+            crate::chat::process_message_queue(app.clone(), worktree_id, session_id).await?;
+            Ok(Value::Null)
+        }
 
         // =====================================================================
         // Chat (additional)
@@ -1993,6 +2305,30 @@ pub async fn dispatch_command(
             crate::projects::revert_file(worktree_path, file_path, file_status).await?;
             Ok(Value::Null)
         }
+        "stage_changes" => {
+            let worktree_path: String = field(&args, "worktreePath", "worktree_path")?;
+            let file_path: String = field(&args, "filePath", "file_path")?;
+            let hunks: Option<Vec<crate::projects::staging::HunkSelection>> =
+                from_field_opt(&args, "hunks")?;
+            crate::projects::stage_changes(worktree_path, file_path, hunks).await?;
+            Ok(Value::Null)
+        }
+        "unstage_changes" => {
+            let worktree_path: String = field(&args, "worktreePath", "worktree_path")?;
+            let file_path: String = field(&args, "filePath", "file_path")?;
+            let hunks: Option<Vec<crate::projects::staging::HunkSelection>> =
+                from_field_opt(&args, "hunks")?;
+            crate::projects::unstage_changes(worktree_path, file_path, hunks).await?;
+            Ok(Value::Null)
+        }
+        "discard_changes" => {
+            let worktree_path: String = field(&args, "worktreePath", "worktree_path")?;
+            let file_path: String = field(&args, "filePath", "file_path")?;
+            let hunks: Option<Vec<crate::projects::staging::HunkSelection>> =
+                from_field_opt(&args, "hunks")?;
+            crate::projects::discard_changes(worktree_path, file_path, hunks).await?;
+            Ok(Value::Null)
+        }
         "set_worktree_last_opened" => {
             let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
             crate::projects::set_worktree_last_opened(app.clone(), worktree_id).await?;
@@ -2029,6 +2365,45 @@ pub async fn dispatch_command(
             .await?;
             to_value(result)
         }
+        "get_pr_details" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let result = crate::projects::get_pr_details(app.clone(), worktree_id).await?;
+            to_value(result)
+        }
+        "set_pr_draft" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let draft: bool = from_field(&args, "draft")?;
+            let result = crate::projects::set_pr_draft(app.clone(), worktree_id, draft).await?;
+            to_value(result)
+        }
+        "edit_pr_metadata" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let changes: crate::projects::PrMetadataChanges = from_field(&args, "changes")?;
+            let result =
+                crate::projects::edit_pr_metadata(app.clone(), worktree_id, changes).await?;
+            to_value(result)
+        }
+        "set_pr_auto_merge" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let enabled: bool = from_field(&args, "enabled")?;
+            let method: Option<String> = from_field_opt(&args, "method")?;
+            let result =
+                crate::projects::set_pr_auto_merge(app.clone(), worktree_id, enabled, method)
+                    .await?;
+            to_value(result)
+        }
+        "close_pr" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let comment: Option<String> = from_field_opt(&args, "comment")?;
+            let result = crate::projects::close_pr(app.clone(), worktree_id, comment).await?;
+            emit_cache_invalidation(app, &["projects"]);
+            to_value(result)
+        }
+        "apply_pr_defaults" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let result = crate::projects::apply_pr_defaults(app.clone(), worktree_id).await?;
+            to_value(result)
+        }
         "update_pr_description" => {
             let worktree_path: String = field(&args, "worktreePath", "worktree_path")?;
             let pr_number: u32 = field(&args, "prNumber", "pr_number")?;
@@ -2046,7 +2421,7 @@ pub async fn dispatch_command(
         }
         "get_jean_config" => {
             let project_path: String = field(&args, "projectPath", "project_path")?;
-            let result = crate::projects::get_jean_config(project_path).await;
+            let result = crate::projects::get_jean_config(project_path).await?;
             to_value(result)
         }
         "save_jean_config" => {
@@ -2055,6 +2430,19 @@ pub async fn dispatch_command(
             crate::projects::save_jean_config(project_path, config).await?;
             Ok(Value::Null)
         }
+        "rerun_setup_script" => {
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let result = crate::projects::rerun_setup_script(app.clone(), worktree_id).await?;
+            emit_cache_invalidation(app, &["projects"]);
+            to_value(result)
+        }
+        "cancel_jean_script" => {
+            let worktree_path: String = field(&args, "worktreePath", "worktree_path")?;
+            let kind: Option<String> = field_opt(&args, "kind", "kind")?;
+            let result =
+                crate::projects::cancel_jean_script(app.clone(), worktree_path, kind).await?;
+            to_value(result)
+        }
         "list_github_releases" => {
             let project_path: String = field(&args, "projectPath", "project_path")?;
             let result = crate::projects::list_github_releases(app.clone(), project_path).await?;
@@ -2121,6 +2509,90 @@ pub async fn dispatch_command(
                 crate::projects::list_workflow_runs(app.clone(), project_path, branch).await?;
             to_value(result)
         }
+        "list_workflows" => {
+            let project_path: String = field(&args, "projectPath", "project_path")?;
+            let result = crate::projects::list_workflows(app.clone(), project_path).await?;
+            to_value(result)
+        }
+        "dispatch_workflow" => {
+            let project_path: String = field(&args, "projectPath", "project_path")?;
+            let workflow: String = from_field(&args, "workflow")?;
+            let branch: Option<String> = from_field_opt(&args, "branch")?;
+            let inputs: Option<std::collections::HashMap<String, String>> =
+                from_field_opt(&args, "inputs")?;
+            let result = crate::projects::dispatch_workflow(
+                app.clone(),
+                project_path,
+                workflow,
+                branch,
+                inputs,
+            )
+            .await?;
+            to_value(result)
+        }
+        "rerun_workflow_run" => {
+            let project_path: String = field(&args, "projectPath", "project_path")?;
+            let run_id: u64 = field(&args, "runId", "run_id")?;
+            let failed_only: Option<bool> = field_opt(&args, "failedOnly", "failed_only")?;
+            let result =
+                crate::projects::rerun_workflow_run(app.clone(), project_path, run_id, failed_only)
+                    .await?;
+            to_value(result)
+        }
+        "cancel_workflow_run" => {
+            let project_path: String = field(&args, "projectPath", "project_path")?;
+            let run_id: u64 = field(&args, "runId", "run_id")?;
+            crate::projects::cancel_workflow_run(app.clone(), project_path, run_id).await?;
+            Ok(Value::Null)
+        }
+        "get_workflow_run_details" => {
+            let project_path: String = field(&args, "projectPath", "project_path")?;
+            let run_id: u64 = field(&args, "runId", "run_id")?;
+            let result =
+                crate::projects::get_workflow_run_details(app.clone(), project_path, run_id)
+                    .await?;
+            to_value(result)
+        }
+        "get_workflow_job_log" => {
+            let project_path: String = field(&args, "projectPath", "project_path")?;
+            let job_id: u64 = field(&args, "jobId", "job_id")?;
+            let result =
+                crate::projects::get_workflow_job_log(app.clone(), project_path, job_id).await?;
+            to_value(result)
+        }
+        "search_workflow_job_log" => {
+            let project_path: String = field(&args, "projectPath", "project_path")?;
+            let job_id: u64 = field(&args, "jobId", "job_id")?;
+            let pattern: String = from_field(&args, "pattern")?;
+            let context_lines: Option<usize> = field_opt(&args, "contextLines", "context_lines")?;
+            let ignore_case: Option<bool> = field_opt(&args, "ignoreCase", "ignore_case")?;
+            let result = crate::projects::search_workflow_job_log(
+                app.clone(),
+                project_path,
+                job_id,
+                pattern,
+                context_lines,
+                ignore_case,
+            )
+            .await?;
+            to_value(result)
+        }
+        "get_workflow_failure_snippets" => {
+            let project_path: String = field(&args, "projectPath", "project_path")?;
+            let run_id: u64 = field(&args, "runId", "run_id")?;
+            let result =
+                crate::projects::get_workflow_failure_snippets(app.clone(), project_path, run_id)
+                    .await?;
+            to_value(result)
+        }
+        "get_workflow_failure_context" => {
+            let project_path: String = field(&args, "projectPath", "project_path")?;
+            let run_id: u64 = field(&args, "runId", "run_id")?;
+            let result =
+                crate::projects::get_workflow_failure_context(app.clone(), project_path, run_id)
+                    .await?;
+            to_value(result)
+        }
 
         // =====================================================================
         // Linear Issues
@@ -2210,6 +2682,79 @@ pub async fn dispatch_command(
             Ok(Value::Null)
         }
 
+        // Jira Issues
+        "list_jira_issues" => {
+            let project_id: String = field(&args, "projectId", "project_id")?;
+            let result = crate::projects::list_jira_issues(app.clone(), project_id).await?;
+            to_value(result)
+        }
+        "search_jira_issues" => {
+            let project_id: String = field(&args, "projectId", "project_id")?;
+            let query: String = from_field(&args, "query")?;
+            let result =
+                crate::projects::search_jira_issues(app.clone(), project_id, query).await?;
+            to_value(result)
+        }
+        "get_jira_issue" => {
+            let project_id: String = field(&args, "projectId", "project_id")?;
+            let issue_key: String = field(&args, "issueKey", "issue_key")?;
+            let result =
+                crate::projects::get_jira_issue(app.clone(), project_id, issue_key).await?;
+            to_value(result)
+        }
+        "load_jira_issue_context" => {
+            let session_id: String = field(&args, "sessionId", "session_id")?;
+            let project_id: String = field(&args, "projectId", "project_id")?;
+            let issue_key: String = field(&args, "issueKey", "issue_key")?;
+            let result = crate::projects::load_jira_issue_context(
+                app.clone(),
+                session_id,
+                project_id,
+                issue_key,
+            )
+            .await?;
+            to_value(result)
+        }
+        "list_loaded_jira_issue_contexts" => {
+            let session_id: String = field(&args, "sessionId", "session_id")?;
+            let worktree_id: Option<String> = field_opt(&args, "worktreeId", "worktree_id")?;
+            let project_id: String = field(&args, "projectId", "project_id")?;
+            let result = crate::projects::list_loaded_jira_issue_contexts(
+                app.clone(),
+                session_id,
+                worktree_id,
+                project_id,
+            )
+            .await?;
+            to_value(result)
+        }
+        "get_jira_issue_context_contents" => {
+            let session_id: String = field(&args, "sessionId", "session_id")?;
+            let worktree_id: Option<String> = field_opt(&args, "worktreeId", "worktree_id")?;
+            let project_id: String = field(&args, "projectId", "project_id")?;
+            let result = crate::projects::get_jira_issue_context_contents(
+                app.clone(),
+                session_id,
+                worktree_id,
+                project_id,
+            )
+            .await?;
+            to_value(result)
+        }
+        "remove_jira_issue_context" => {
+            let session_id: String = field(&args, "sessionId", "session_id")?;
+            let project_id: String = field(&args, "projectId", "project_id")?;
+            let issue_key: String = field(&args, "issueKey", "issue_key")?;
+            crate::projects::remove_jira_issue_context(
+                app.clone(),
+                session_id,
+                project_id,
+                issue_key,
+            )
+            .await?;
+            Ok(Value::Null)
+        }
+
         // =====================================================================
         // CLI Profiles
         // =====================================================================
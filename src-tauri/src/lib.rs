@@ -18,6 +18,7 @@ mod opencode_cli;
 mod opencode_server;
 mod platform;
 mod projects;
+mod services;
 mod terminal;
 
 // Validation functions
@@ -664,8 +665,11 @@ fn default_investigate_workflow_run_prompt() -> String {
 - Branch: {branch}
 - Run URL: {runUrl}
 
+**Failure context:**
+{failureContext}
+
 **Instructions:**
-1. Use the GitHub CLI to fetch the workflow run logs: `gh run view {runId} --log-failed`
+1. Start from the failed jobs, steps and log excerpts above. If you need more, fetch the full logs with `gh run view {runId} --log-failed`
 2. Read the error output carefully to identify the failure cause
 3. Explore the relevant code in the codebase to understand the context
 4. Determine if this is a code issue, configuration issue, or flaky test
@@ -2516,9 +2520,21 @@ pub fn run() {
             projects::merge_github_pr,
             projects::generate_pr_update_content,
             projects::update_pr_description,
+            projects::get_pr_details,
+            projects::set_pr_draft,
+            projects::edit_pr_metadata,
+            projects::set_pr_auto_merge,
+            projects::close_pr,
+            projects::apply_pr_defaults,
             projects::create_commit_with_ai,
+            projects::propose_commit_split,
+            projects::apply_commit_split,
             projects::revert_last_local_commit,
             projects::run_review_with_ai,
+            projects::publish_review_to_pr,
+            projects::get_pr_review_threads,
+            projects::start_addressing_review,
+            projects::finish_addressing_review,
             projects::cancel_review_with_ai,
             projects::list_github_releases,
             projects::generate_release_notes,
@@ -2535,6 +2551,8 @@ pub fn run() {
             projects::update_project_settings,
             projects::get_jean_config,
             projects::save_jean_config,
+            projects::rerun_setup_script,
+            projects::cancel_jean_script,
             projects::get_pr_prompt,
             projects::get_review_prompt,
             projects::save_worktree_pr,
@@ -2542,12 +2560,20 @@ pub fn run() {
             projects::clear_worktree_pr,
             projects::update_worktree_cached_status,
             projects::rebase_worktree,
+            projects::create_child_worktree,
+            projects::restack_worktree,
             projects::has_uncommitted_changes,
             projects::get_git_diff,
             projects::get_commit_history,
             projects::get_commit_diff,
+            projects::get_rebase_plan,
+            projects::apply_rebase_plan,
+            projects::generate_squash_message,
             projects::get_repo_branches,
             projects::revert_file,
+            projects::stage_changes,
+            projects::unstage_changes,
+            projects::discard_changes,
             projects::git_pull,
             projects::git_stash,
             projects::git_stash_pop,
@@ -2555,6 +2581,12 @@ pub fn run() {
             projects::merge_worktree_to_base,
             projects::get_merge_conflicts,
             projects::fetch_and_merge_base,
+            projects::get_conflict_state,
+            projects::resolve_conflict_hunk,
+            projects::mark_conflict_resolved,
+            projects::continue_conflict_operation,
+            projects::abort_conflict_operation,
+            projects::suggest_conflict_resolution,
             projects::reorder_projects,
             projects::reorder_worktrees,
             projects::fetch_worktrees_status,
@@ -2563,6 +2595,7 @@ pub fn run() {
             projects::list_claude_commands,
             projects::resolve_claude_command,
             // GitHub issues commands
+            projects::forge::get_project_forge,
             projects::list_github_issues,
             projects::search_github_issues,
             projects::get_github_issue,
@@ -2580,6 +2613,14 @@ pub fn run() {
             projects::list_loaded_linear_issue_contexts,
             projects::get_linear_issue_context_contents,
             projects::remove_linear_issue_context,
+            // Jira issues commands
+            projects::list_jira_issues,
+            projects::search_jira_issues,
+            projects::get_jira_issue,
+            projects::load_jira_issue_context,
+            projects::list_loaded_jira_issue_contexts,
+            projects::get_jira_issue_context_contents,
+            projects::remove_jira_issue_context,
             // GitHub PR commands
             projects::list_github_prs,
             projects::search_github_prs,
@@ -2607,6 +2648,15 @@ pub fn run() {
             projects::get_advisory_context_content,
             // GitHub Actions commands
             projects::list_workflow_runs,
+            projects::get_workflow_run_details,
+            projects::get_workflow_job_log,
+            projects::search_workflow_job_log,
+            projects::get_workflow_failure_snippets,
+            projects::get_workflow_failure_context,
+            projects::list_workflows,
+            projects::dispatch_workflow,
+            projects::rerun_workflow_run,
+            projects::cancel_workflow_run,
             // Saved context commands
             projects::attach_saved_context,
             projects::remove_saved_context,
@@ -2630,7 +2680,14 @@ pub fn run() {
             terminal::get_active_terminals,
             terminal::has_active_terminal,
             terminal::get_run_script,
+            terminal::get_run_scripts,
             terminal::kill_all_terminals,
+            // Service supervisor commands
+            services::start_service,
+            services::stop_service,
+            services::restart_service,
+            services::list_services,
+            services::get_service_logs,
             // Chat commands - Session management
             chat::get_sessions,
             chat::list_all_sessions,
@@ -2665,11 +2722,14 @@ pub fn run() {
             chat::save_cancelled_message,
             chat::mark_plan_approved,
             chat::approve_codex_command,
+            chat::approve_opencode_permission,
+            chat::get_permission_audit,
             // Chat commands - Queue management (cross-client sync)
             chat::enqueue_message,
             chat::dequeue_message,
             chat::remove_queued_message,
             chat::clear_message_queue,
+            chat::process_message_queue,
             // Chat commands - Image handling
             chat::read_clipboard_image,
             chat::save_pasted_image,
@@ -2737,6 +2797,9 @@ pub fn run() {
             background_tasks::commands::set_remote_poll_interval,
             background_tasks::commands::get_remote_poll_interval,
             background_tasks::commands::trigger_immediate_remote_poll,
+            background_tasks::commands::start_ci_fix_watcher,
+            background_tasks::commands::stop_ci_fix_watcher,
+            background_tasks::commands::get_ci_fix_watchers,
             // HTTP server commands
             start_http_server,
             stop_http_server,
@@ -2760,6 +2823,7 @@ pub fn run() {
                     Err(e) => eprintln!("[OPENCODE CLEANUP] Failed during Exit: {e}"),
                 }
                 chat::codex_server::shutdown_server();
+                services::cleanup_all_services();
             }
             tauri::RunEvent::ExitRequested { api, .. } => {
                 // In headless mode, prevent exit when window closes
@@ -2780,6 +2844,7 @@ pub fn run() {
                     }
                 }
                 chat::codex_server::shutdown_server();
+                services::cleanup_all_services();
             }
             tauri::RunEvent::WindowEvent { label, event, .. } => {
                 if let tauri::WindowEvent::CloseRequested { .. } = event {
@@ -2800,6 +2865,7 @@ pub fn run() {
                         }
                     }
                     chat::codex_server::shutdown_server();
+                    services::cleanup_all_services();
                 }
                 if let tauri::WindowEvent::Destroyed = event {
                     eprintln!("[TERMINAL CLEANUP] Window {label} destroyed");
@@ -199,7 +199,23 @@ pub fn ensure_running(app: &AppHandle) -> Result<String, String> {
         ));
     }
 
-    let mut cmd = silent_command(&cli_path);
+    // Project sandbox: the server is shared, so it runs under bubblewrap as a
+    // whole when any project enables the sandbox.
+    let sandbox = crate::chat::sandbox::resolve_for_shared_server(
+        app,
+        &crate::chat::types::Backend::Opencode,
+    );
+    let mut cmd = match &sandbox {
+        Some(spec) => {
+            let bwrap = crate::chat::sandbox::find_bwrap()?;
+            let cwd = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+            log::info!("Starting OpenCode server in sandbox");
+            let mut cmd = silent_command(bwrap);
+            cmd.args(spec.bwrap_args(&cwd)).arg("--").arg(&cli_path);
+            cmd
+        }
+        None => silent_command(&cli_path),
+    };
     cmd.arg("serve")
         .arg("--hostname")
         .arg(&hostname)
@@ -229,3 +229,20 @@ pub fn terminate_process(pid: u32) -> Result<(), String> {
     // Windows doesn't have SIGTERM, use TerminateProcess
     kill_process(pid)
 }
+
+/// Send SIGTERM to a process group (a process started with `process_group(0)`)
+/// On Windows, this falls back to a tree kill
+#[cfg(unix)]
+pub fn terminate_process_tree(pid: u32) -> Result<(), String> {
+    let result = unsafe { libc::kill(-(pid as i32), libc::SIGTERM) };
+    if result == 0 {
+        Ok(())
+    } else {
+        terminate_process(pid)
+    }
+}
+
+#[cfg(windows)]
+pub fn terminate_process_tree(pid: u32) -> Result<(), String> {
+    kill_process_tree(pid)
+}
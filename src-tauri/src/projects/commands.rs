@@ -2,7 +2,7 @@ use ignore::WalkBuilder;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::Write;
 use std::path::Path;
 use std::process::Stdio;
@@ -16,6 +16,7 @@ use uuid::Uuid;
 
 use rand::Rng;
 
+use super::forge::{self, ForgeKind};
 use super::git;
 use super::git::get_repo_identifier;
 use super::github_issues::{
@@ -23,23 +24,33 @@ use super::github_issues::{
     format_advisory_context_markdown, format_issue_context_markdown, format_pr_context_markdown,
     format_security_context_markdown, generate_branch_name_from_advisory,
     generate_branch_name_from_issue, generate_branch_name_from_security_alert,
-    get_github_contexts_dir, get_github_pr, get_pr_diff, get_session_context_content,
+    get_github_contexts_dir, get_github_pr, get_session_context_content,
     get_session_context_numbers, AdvisoryContext, IssueContext, PullRequestContext,
     SecurityAlertContext,
 };
+use super::jira_issues::{
+    generate_branch_name_from_jira_issue, jira_context_to_detail, normalize_jira_context,
+    session_jira_keys, write_jira_issue_context, JiraIssueContext,
+};
 use super::linear_issues::{
     add_linear_reference, format_linear_issue_context_markdown,
     generate_branch_name_from_linear_issue, get_session_linear_identifiers,
     linear_context_to_detail, LinearIssueContext,
 };
 use super::names::generate_unique_workspace_name;
-use super::storage::{get_project_worktrees_dir, load_projects_data, save_projects_data};
+use super::ports;
+use super::stacks;
+use super::storage::{
+    get_project_worktrees_dir, load_projects_data, save_projects_data, with_projects_data_mut,
+};
 use super::types::{
-    JeanConfig, MergeType, Project, SessionType, Worktree, WorktreeArchivedEvent,
-    WorktreeBranchExistsEvent, WorktreeCreateErrorEvent, WorktreeCreatedEvent,
-    WorktreeCreatingEvent, WorktreeDeleteErrorEvent, WorktreeDeletedEvent, WorktreeDeletingEvent,
-    WorktreePathExistsEvent, WorktreePermanentlyDeletedEvent, WorktreeUnarchivedEvent,
+    JeanConfig, JeanHook, MergeType, PrDefaults, Project, SandboxConfig, SessionType, Worktree,
+    WorktreeArchivedEvent, WorktreeBranchExistsEvent, WorktreeCreateErrorEvent,
+    WorktreeCreatedEvent, WorktreeCreatingEvent, WorktreeDeleteErrorEvent, WorktreeDeletedEvent,
+    WorktreeDeletingEvent, WorktreePathExistsEvent, WorktreePermanentlyDeletedEvent,
+    WorktreeUnarchivedEvent,
 };
+use super::worktree_copy;
 use crate::claude_cli::resolve_cli_binary;
 use crate::codex_cli::resolve_cli_binary as resolve_codex_cli_binary;
 use crate::gh_cli::config::resolve_gh_binary;
@@ -218,6 +229,16 @@ pub async fn add_project(
         worktrees_dir: None,
         linear_api_key: None,
         linear_team_id: None,
+        permission_policy: None,
+        sandbox: None,
+        pr_defaults: None,
+        forge: None,
+        forge_token: None,
+        forge_url: None,
+        jira_url: None,
+        jira_email: None,
+        jira_api_token: None,
+        jira_jql: None,
     };
 
     data.add_project(project.clone());
@@ -377,6 +398,16 @@ pub async fn init_project(
         worktrees_dir: None,
         linear_api_key: None,
         linear_team_id: None,
+        permission_policy: None,
+        sandbox: None,
+        pr_defaults: None,
+        forge: None,
+        forge_token: None,
+        forge_url: None,
+        jira_url: None,
+        jira_email: None,
+        jira_api_token: None,
+        jira_jql: None,
     };
 
     data.add_project(project.clone());
@@ -430,6 +461,16 @@ pub async fn clone_project(
         worktrees_dir: None,
         linear_api_key: None,
         linear_team_id: None,
+        permission_policy: None,
+        sandbox: None,
+        pr_defaults: None,
+        forge: None,
+        forge_token: None,
+        forge_url: None,
+        jira_url: None,
+        jira_email: None,
+        jira_api_token: None,
+        jira_jql: None,
     };
 
     data.add_project(project.clone());
@@ -557,10 +598,13 @@ pub async fn create_worktree(
     security_context: Option<SecurityAlertContext>,
     advisory_context: Option<AdvisoryContext>,
     linear_context: Option<LinearIssueContext>,
+    jira_context: Option<JiraIssueContext>,
     custom_name: Option<String>,
+    parent_worktree_id: Option<String>,
 ) -> Result<Worktree, String> {
     log::trace!("Creating worktree for project: {project_id}");
 
+    let jira_context = jira_context.map(normalize_jira_context).transpose()?;
     let data = load_projects_data(&app)?;
 
     let project = data
@@ -568,12 +612,21 @@ pub async fn create_worktree(
         .ok_or_else(|| format!("Project not found: {project_id}"))?
         .clone();
 
-    // Use provided base branch or project's default branch, with validation
-    let preferred_base = base_branch.unwrap_or_else(|| project.default_branch.clone());
+    // Stacked worktrees branch off their parent's branch
+    let parent_branch = parent_worktree_id
+        .as_deref()
+        .map(|id| stacks::resolve_parent(&data, &project_id, id).map(|p| p.branch.clone()))
+        .transpose()?;
+
+    // Use the parent's branch, provided base branch or project's default branch, with validation
+    let preferred_base = parent_branch
+        .clone()
+        .or(base_branch)
+        .unwrap_or_else(|| project.default_branch.clone());
     let base = git::get_valid_base_branch(&project.path, &preferred_base)?;
 
     // Resolve auto-pull preference now (async), but defer the actual pull to background thread
-    let should_auto_pull = if pr_context.is_none() {
+    let should_auto_pull = if pr_context.is_none() && parent_branch.is_none() {
         crate::load_preferences(app.clone())
             .await
             .map(|prefs| prefs.auto_pull_base_branch)
@@ -644,6 +697,20 @@ pub async fn create_worktree(
         } else {
             linear_branch
         }
+    } else if let Some(ref ctx) = jira_context {
+        let jira_branch = generate_branch_name_from_jira_issue(&ctx.key, &ctx.summary);
+        if data.worktree_name_exists(&project_id, &jira_branch) {
+            let mut counter = 2;
+            loop {
+                let candidate = format!("{jira_branch}-{counter}");
+                if !data.worktree_name_exists(&project_id, &candidate) {
+                    break candidate;
+                }
+                counter += 1;
+            }
+        } else {
+            jira_branch
+        }
     } else if let Some(ref ctx) = issue_context {
         let issue_branch = generate_branch_name_from_issue(ctx.number, &ctx.title);
         // Check if this branch name already exists, if so, add a suffix
@@ -712,6 +779,7 @@ pub async fn create_worktree(
         pr_url: None,
         issue_number: issue_context.as_ref().map(|ctx| ctx.number),
         linear_issue_identifier: linear_context.as_ref().map(|ctx| ctx.identifier.clone()),
+        jira_issue_key: jira_context.as_ref().map(|ctx| ctx.key.clone()),
         cached_pr_status: None,
         cached_check_status: None,
         cached_behind_count: None,
@@ -729,6 +797,9 @@ pub async fn create_worktree(
         archived_at: None,
         label: None,
         last_opened_at: None,
+        ports: None,
+        parent_worktree_id: parent_worktree_id.clone(),
+        stack_base_commit: None,
     };
 
     // Clone values for the background thread
@@ -745,6 +816,8 @@ pub async fn create_worktree(
     let security_context_clone = security_context.clone();
     let advisory_context_clone = advisory_context.clone();
     let linear_context_clone = linear_context.clone();
+    let jira_context_clone = jira_context.clone();
+    let parent_worktree_id_clone = parent_worktree_id.clone();
 
     // Spawn background thread for git operations
     thread::spawn(move || {
@@ -901,6 +974,11 @@ pub async fn create_worktree(
 
             log::trace!("Background: Git worktree created successfully");
 
+            // Remember which parent commit a stacked branch starts from, for restacking
+            let stack_base_commit = parent_worktree_id_clone
+                .as_ref()
+                .and_then(|_| git::rev_parse(&worktree_path_clone, "HEAD").ok());
+
             // For PR context, run gh pr checkout to get the actual PR branch
             let final_branch = if let Some(ref ctx) = pr_context_clone {
                 log::trace!(
@@ -925,19 +1003,25 @@ pub async fn create_worktree(
                 // Clean up stale branch from a previous checkout of this PR
                 git::cleanup_stale_branch(&project_path, &local_branch_name);
 
-                let checkout_result = if branch_collision {
-                    // Bypass gh pr checkout which internally fetches into the conflicting ref.
-                    // Manually fetch the PR into the alt branch name and switch to it.
+                let forge_kind = forge::kind_for_repo(&app_clone, &project_path);
+                let checkout_result = if branch_collision || forge_kind != ForgeKind::Github {
+                    // Bypass gh pr checkout which internally fetches into the conflicting ref
+                    // (and only knows GitHub). Manually fetch the PR into the local branch
+                    // name and switch to it.
                     log::trace!(
-                    "Background: Branch collision, manual fetch PR #{} into {local_branch_name}",
-                    ctx.number
-                );
-                    git::fetch_pr_to_branch(&project_path, ctx.number, &local_branch_name).and_then(
-                        |_| {
-                            git::checkout_branch(&worktree_path_clone, &local_branch_name)?;
-                            Ok(local_branch_name)
-                        },
+                        "Background: Manual fetch PR #{} into {local_branch_name}",
+                        ctx.number
+                    );
+                    git::fetch_pr_to_branch(
+                        &project_path,
+                        ctx.number,
+                        forge_kind,
+                        &local_branch_name,
                     )
+                    .and_then(|_| {
+                        git::checkout_branch(&worktree_path_clone, &local_branch_name)?;
+                        Ok(local_branch_name)
+                    })
                 } else {
                     git::gh_pr_checkout(
                         &worktree_path_clone,
@@ -1037,7 +1121,9 @@ pub async fn create_worktree(
                             let gh = resolve_gh_binary(&app_clone);
                             let ctx_with_diff = if ctx.diff.is_none() {
                                 log::debug!("Background: Fetching diff for PR #{}", ctx.number);
-                                let diff = get_pr_diff(&project_path, ctx.number, &gh).ok();
+                                let diff =
+                                    forge::get_pr_diff(&app_clone, &project_path, ctx.number, &gh)
+                                        .ok();
                                 PullRequestContext {
                                     number: ctx.number,
                                     title: ctx.title.clone(),
@@ -1196,16 +1282,47 @@ pub async fn create_worktree(
                 }
             }
 
+            // Write Jira issue context file if provided
+            if let Some(ctx) = &jira_context_clone {
+                log::trace!(
+                    "Background: Writing Jira issue context file for {}",
+                    ctx.key
+                );
+                if let Err(e) = write_jira_issue_context(
+                    &app_clone,
+                    &project_name,
+                    &jira_context_to_detail(ctx),
+                    &worktree_id_clone,
+                ) {
+                    log::warn!("Background: Failed to write Jira issue context: {e}");
+                }
+            }
+
+            // Bring untracked files and caches over from the project root
+            let copy_summary =
+                copy_untracked_into_worktree(&app_clone, &project_path, &worktree_path_clone);
+
+            // Allocate ports and render env templates before setup so it can use them
+            let ports = ports::prepare_new_worktree(
+                &app_clone,
+                &worktree_id_clone,
+                &worktree_path_clone,
+                &project_path,
+                &final_branch,
+            );
+
             // Check for jean.json and run setup script
             let (setup_output, setup_script, setup_success) =
                 if let Some(config) = git::read_jean_config(&project_path) {
                     if let Some(script) = config.scripts.setup {
                         log::trace!("Background: Found jean.json with setup script, executing...");
                         match git::run_setup_script(
+                            &app_clone,
                             &worktree_path_clone,
                             &project_path,
                             &final_branch,
                             &script,
+                            ports.as_ref(),
                         ) {
                             Ok(output) => (Some(output), Some(script), Some(true)),
                             Err(e) => {
@@ -1219,6 +1336,7 @@ pub async fn create_worktree(
                 } else {
                     (None, None, None)
                 };
+            let setup_output = prepend_copy_summary(copy_summary, setup_output);
 
             // Save to storage
             if let Ok(mut data) = load_projects_data(&app_clone) {
@@ -1249,6 +1367,7 @@ pub async fn create_worktree(
                     linear_issue_identifier: linear_context_clone
                         .as_ref()
                         .map(|ctx| ctx.identifier.clone()),
+                    jira_issue_key: jira_context_clone.as_ref().map(|ctx| ctx.key.clone()),
                     cached_pr_status: None,
                     cached_check_status: None,
                     cached_behind_count: None,
@@ -1266,6 +1385,9 @@ pub async fn create_worktree(
                     archived_at: None,
                     label: None,
                     last_opened_at: None,
+                    ports,
+                    parent_worktree_id: parent_worktree_id_clone,
+                    stack_base_commit,
                 };
 
                 data.add_worktree(worktree.clone());
@@ -1336,9 +1458,11 @@ pub async fn create_worktree_from_existing_branch(
     security_context: Option<SecurityAlertContext>,
     advisory_context: Option<AdvisoryContext>,
     linear_context: Option<LinearIssueContext>,
+    jira_context: Option<JiraIssueContext>,
 ) -> Result<Worktree, String> {
     log::trace!("Creating worktree from existing branch {branch_name} for project: {project_id}");
 
+    let jira_context = jira_context.map(normalize_jira_context).transpose()?;
     let data = load_projects_data(&app)?;
 
     let project = data
@@ -1392,6 +1516,7 @@ pub async fn create_worktree_from_existing_branch(
         pr_url: None,
         issue_number: issue_context.as_ref().map(|ctx| ctx.number),
         linear_issue_identifier: linear_context.as_ref().map(|ctx| ctx.identifier.clone()),
+        jira_issue_key: jira_context.as_ref().map(|ctx| ctx.key.clone()),
         cached_pr_status: None,
         cached_check_status: None,
         cached_behind_count: None,
@@ -1409,6 +1534,9 @@ pub async fn create_worktree_from_existing_branch(
         archived_at: None,
         label: None,
         last_opened_at: None,
+        ports: None,
+        parent_worktree_id: None,
+        stack_base_commit: None,
     };
 
     // Clone values for the background thread
@@ -1425,6 +1553,7 @@ pub async fn create_worktree_from_existing_branch(
     let security_context_clone = security_context.clone();
     let advisory_context_clone = advisory_context.clone();
     let linear_context_clone = linear_context.clone();
+    let jira_context_clone = jira_context.clone();
 
     // Spawn background thread for git operations
     thread::spawn(move || {
@@ -1555,7 +1684,9 @@ pub async fn create_worktree_from_existing_branch(
                             let gh = resolve_gh_binary(&app_clone);
                             let ctx_with_diff = if ctx.diff.is_none() {
                                 log::debug!("Background: Fetching diff for PR #{}", ctx.number);
-                                let diff = get_pr_diff(&project_path, ctx.number, &gh).ok();
+                                let diff =
+                                    forge::get_pr_diff(&app_clone, &project_path, ctx.number, &gh)
+                                        .ok();
                                 PullRequestContext {
                                     number: ctx.number,
                                     title: ctx.title.clone(),
@@ -1711,16 +1842,47 @@ pub async fn create_worktree_from_existing_branch(
                 }
             }
 
+            // Write Jira issue context file if provided
+            if let Some(ctx) = &jira_context_clone {
+                log::trace!(
+                    "Background: Writing Jira issue context file for {}",
+                    ctx.key
+                );
+                if let Err(e) = write_jira_issue_context(
+                    &app_clone,
+                    &project_name,
+                    &jira_context_to_detail(ctx),
+                    &worktree_id_clone,
+                ) {
+                    log::warn!("Background: Failed to write Jira issue context: {e}");
+                }
+            }
+
+            // Bring untracked files and caches over from the project root
+            let copy_summary =
+                copy_untracked_into_worktree(&app_clone, &project_path, &worktree_path_clone);
+
+            // Allocate ports and render env templates before setup so it can use them
+            let ports = ports::prepare_new_worktree(
+                &app_clone,
+                &worktree_id_clone,
+                &worktree_path_clone,
+                &project_path,
+                &name_clone,
+            );
+
             // Check for jean.json and run setup script
             let (setup_output, setup_script, setup_success) =
                 if let Some(config) = git::read_jean_config(&project_path) {
                     if let Some(script) = config.scripts.setup {
                         log::trace!("Background: Found jean.json with setup script, executing...");
                         match git::run_setup_script(
+                            &app_clone,
                             &worktree_path_clone,
                             &project_path,
                             &name_clone,
                             &script,
+                            ports.as_ref(),
                         ) {
                             Ok(output) => (Some(output), Some(script), Some(true)),
                             Err(e) => {
@@ -1734,6 +1896,7 @@ pub async fn create_worktree_from_existing_branch(
                 } else {
                     (None, None, None)
                 };
+            let setup_output = prepend_copy_summary(copy_summary, setup_output);
 
             // Save to storage
             if let Ok(mut data) = load_projects_data(&app_clone) {
@@ -1764,6 +1927,7 @@ pub async fn create_worktree_from_existing_branch(
                     linear_issue_identifier: linear_context_clone
                         .as_ref()
                         .map(|ctx| ctx.identifier.clone()),
+                    jira_issue_key: jira_context_clone.as_ref().map(|ctx| ctx.key.clone()),
                     cached_pr_status: None,
                     cached_check_status: None,
                     cached_behind_count: None,
@@ -1781,6 +1945,9 @@ pub async fn create_worktree_from_existing_branch(
                     archived_at: None,
                     label: None,
                     last_opened_at: None,
+                    ports,
+                    parent_worktree_id: None,
+                    stack_base_commit: None,
                 };
 
                 data.add_worktree(worktree.clone());
@@ -1985,6 +2152,7 @@ pub async fn checkout_pr(
         pr_url: None,
         issue_number: None,
         linear_issue_identifier: None,
+        jira_issue_key: None,
         cached_pr_status: None,
         cached_check_status: None,
         cached_behind_count: None,
@@ -2002,6 +2170,9 @@ pub async fn checkout_pr(
         archived_at: None,
         label: None,
         last_opened_at: None,
+        ports: None,
+        parent_worktree_id: None,
+        stack_base_commit: None,
     };
 
     // Clone values for background thread
@@ -2093,13 +2264,21 @@ pub async fn checkout_pr(
             // If branch name collides (e.g. PR head is "main" and "main" is checked out),
             // bypass `gh pr checkout` which internally fetches into the conflicting ref.
             // Instead, manually fetch the PR into the alt branch name and switch to it.
-            let actual_branch = if branch_collision {
-                log::trace!("Background: Branch collision detected, using manual fetch for PR #{pr_number} into {local_branch_name}");
-                match git::fetch_pr_to_branch(&project_path, pr_number, &local_branch_name)
-                    .and_then(|_| {
-                        git::checkout_branch(&worktree_path_clone, &local_branch_name)?;
-                        Ok(local_branch_name.clone())
-                    }) {
+            let forge_kind = forge::kind_for_repo(&app_clone, &project_path);
+            let actual_branch = if branch_collision || forge_kind != ForgeKind::Github {
+                log::trace!(
+                    "Background: Using manual fetch for PR #{pr_number} into {local_branch_name}"
+                );
+                match git::fetch_pr_to_branch(
+                    &project_path,
+                    pr_number,
+                    forge_kind,
+                    &local_branch_name,
+                )
+                .and_then(|_| {
+                    git::checkout_branch(&worktree_path_clone, &local_branch_name)?;
+                    Ok(local_branch_name.clone())
+                }) {
                     Ok(branch) => {
                         log::trace!(
                             "Background: Manual PR fetch+checkout succeeded, branch: {branch}"
@@ -2169,16 +2348,31 @@ pub async fn checkout_pr(
                 "Background: Git worktree ready with PR #{pr_number} on branch {actual_branch}"
             );
 
+            // Bring untracked files and caches over from the project root
+            let copy_summary =
+                copy_untracked_into_worktree(&app_clone, &project_path, &worktree_path_clone);
+
+            // Allocate ports and render env templates before setup so it can use them
+            let ports = ports::prepare_new_worktree(
+                &app_clone,
+                &worktree_id_clone,
+                &worktree_path_clone,
+                &project_path,
+                &actual_branch,
+            );
+
             // Check for jean.json and run setup script
             let (setup_output, setup_script, setup_success) =
                 if let Some(config) = git::read_jean_config(&worktree_path_clone) {
                     if let Some(script) = config.scripts.setup {
                         log::trace!("Background: Found jean.json with setup script, executing...");
                         match git::run_setup_script(
+                            &app_clone,
                             &worktree_path_clone,
                             &project_path,
                             &actual_branch,
                             &script,
+                            ports.as_ref(),
                         ) {
                             Ok(output) => (Some(output), Some(script), Some(true)),
                             Err(e) => {
@@ -2192,6 +2386,28 @@ pub async fn checkout_pr(
                 } else {
                     (None, None, None)
                 };
+            let setup_output = prepend_copy_summary(copy_summary, setup_output);
+
+            // jean.json post_checkout_pr hook runs after setup; its output is
+            // appended to the setup output
+            let (setup_output, setup_success) = match git::run_hook(
+                &app_clone,
+                JeanHook::PostCheckoutPr,
+                &worktree_path_clone,
+                &project_path,
+                &actual_branch,
+                ports.as_ref(),
+            ) {
+                Ok(None) => (setup_output, setup_success),
+                Ok(Some(output)) => (
+                    Some(join_script_output(setup_output, output)),
+                    setup_success.or(Some(true)),
+                ),
+                Err(e) => {
+                    log::warn!("Background: post_checkout_pr hook failed (continuing): {e}");
+                    (Some(join_script_output(setup_output, e)), Some(false))
+                }
+            };
 
             // Write PR context file to shared git-context directory
             if let Ok(repo_id) = get_repo_identifier(&project_path) {
@@ -2228,7 +2444,8 @@ pub async fn checkout_pr(
                                     submitted_at: r.submitted_at,
                                 })
                                 .collect(),
-                            diff: get_pr_diff(
+                            diff: forge::get_pr_diff(
+                                &app_clone,
                                 &project_path,
                                 pr_number,
                                 &resolve_gh_binary(&app_clone),
@@ -2287,6 +2504,7 @@ pub async fn checkout_pr(
                     pr_url: None,
                     issue_number: None,
                     linear_issue_identifier: None,
+                    jira_issue_key: None,
                     cached_pr_status: None,
                     cached_check_status: None,
                     cached_behind_count: None,
@@ -2304,6 +2522,9 @@ pub async fn checkout_pr(
                     archived_at: None,
                     label: None,
                     last_opened_at: None,
+                    ports,
+                    parent_worktree_id: None,
+                    stack_base_commit: None,
                 };
 
                 data.add_worktree(worktree.clone());
@@ -2376,6 +2597,7 @@ pub async fn delete_worktree(app: AppHandle, worktree_id: String) -> Result<(),
 
     // Cancel any running Claude processes for this worktree FIRST
     crate::chat::registry::cancel_processes_for_worktree(&app, &worktree_id);
+    crate::services::stop_worktree_services(&app, &worktree_id);
 
     let data = load_projects_data(&app)?;
 
@@ -2438,8 +2660,14 @@ pub async fn delete_worktree(app: AppHandle, worktree_id: String) -> Result<(),
         let mut teardown_output: Option<String> = None;
         if let Some(ref script) = teardown_script {
             log::trace!("Background: Running teardown script for {worktree_name}");
-            match git::run_teardown_script(&worktree_path, &project_path, &worktree_branch, script)
-            {
+            match git::run_teardown_script(
+                &app_clone,
+                &worktree_path,
+                &project_path,
+                &worktree_branch,
+                script,
+                worktree_for_restore.ports.as_ref(),
+            ) {
                 Ok(output) => {
                     if !output.is_empty() {
                         teardown_output = Some(output);
@@ -2478,6 +2706,8 @@ pub async fn delete_worktree(app: AppHandle, worktree_id: String) -> Result<(),
             }
         }
 
+        super::container::remove_container(&app_clone, &worktree_path);
+
         log::trace!("Background: Removing git worktree at {worktree_path}");
 
         // Remove the git worktree (this can be slow for large repos)
@@ -2566,6 +2796,7 @@ pub async fn create_base_session(app: AppHandle, project_id: String) -> Result<W
         pr_url: None,
         issue_number: None,
         linear_issue_identifier: None,
+        jira_issue_key: None,
         cached_pr_status: None,
         cached_check_status: None,
         cached_behind_count: None,
@@ -2583,6 +2814,9 @@ pub async fn create_base_session(app: AppHandle, project_id: String) -> Result<W
         archived_at: None,
         label: None,
         last_opened_at: None,
+        ports: None,
+        parent_worktree_id: None,
+        stack_base_commit: None,
     };
 
     data.add_worktree(session.clone());
@@ -2773,6 +3007,7 @@ pub async fn archive_worktree(app: AppHandle, worktree_id: String) -> Result<(),
 
     // Cancel any running Claude processes for this worktree
     crate::chat::registry::cancel_processes_for_worktree(&app, &worktree_id);
+    crate::services::stop_worktree_services(&app, &worktree_id);
 
     let mut data = load_projects_data(&app)?;
 
@@ -2793,6 +3028,27 @@ pub async fn archive_worktree(app: AppHandle, worktree_id: String) -> Result<(),
     }
 
     let project_id = worktree.project_id.clone();
+    let worktree_path = worktree.path.clone();
+    let branch = worktree.branch.clone();
+    let ports = worktree.ports;
+
+    // jean.json pre_archive hook; failure blocks archiving
+    let project_path = data
+        .find_project(&project_id)
+        .map(|p| p.path.clone())
+        .unwrap_or_default();
+    git::run_hook(
+        &app,
+        JeanHook::PreArchive,
+        &worktree_path,
+        &project_path,
+        &branch,
+        ports.as_ref(),
+    )?;
+
+    let worktree = data
+        .find_worktree_mut(&worktree_id)
+        .ok_or_else(|| format!("Worktree not found: {worktree_id}"))?;
 
     // Set archived timestamp
     worktree.archived_at = Some(now());
@@ -2800,6 +3056,10 @@ pub async fn archive_worktree(app: AppHandle, worktree_id: String) -> Result<(),
     // Save the updated data
     save_projects_data(&app, &data)?;
 
+    // Tear down the worktree's container (recreated on demand after unarchive)
+    let app_clone = app.clone();
+    thread::spawn(move || super::container::remove_container(&app_clone, &worktree_path));
+
     // Emit archived event
     let event = WorktreeArchivedEvent {
         id: worktree_id.clone(),
@@ -2957,6 +3217,7 @@ pub async fn import_worktree(
         pr_url: None,
         issue_number: None,
         linear_issue_identifier: None,
+        jira_issue_key: None,
         cached_pr_status: None,
         cached_check_status: None,
         cached_behind_count: None,
@@ -2974,6 +3235,9 @@ pub async fn import_worktree(
         archived_at: None,
         label: None,
         last_opened_at: None,
+        ports: None,
+        parent_worktree_id: None,
+        stack_base_commit: None,
     };
 
     data.add_worktree(worktree.clone());
@@ -3569,9 +3833,13 @@ pub async fn get_git_remotes(repo_path: String) -> Result<Vec<git::GitRemote>, S
 
 /// Get all GitHub remotes for a repository
 #[tauri::command]
-pub async fn get_github_remotes(repo_path: String) -> Result<Vec<git::GitHubRemote>, String> {
+pub async fn get_github_remotes(
+    app: AppHandle,
+    repo_path: String,
+) -> Result<Vec<git::GitHubRemote>, String> {
     log::trace!("Getting GitHub remotes for: {repo_path}");
-    git::get_github_remotes(&repo_path)
+    let project = forge::project_for_path(&app, &repo_path);
+    git::get_github_remotes(&repo_path, project.as_ref())
 }
 
 /// Get the GitHub URL for a branch (for frontend to open)
@@ -3781,6 +4049,20 @@ pub async fn commit_changes(
         .find_worktree(&worktree_id)
         .ok_or_else(|| format!("Worktree not found: {worktree_id}"))?;
 
+    // jean.json pre_commit hook; failure blocks the commit
+    let project_path = data
+        .find_project(&worktree.project_id)
+        .map(|p| p.path.as_str())
+        .unwrap_or_default();
+    git::run_hook(
+        &app,
+        JeanHook::PreCommit,
+        &worktree.path,
+        project_path,
+        &worktree.branch,
+        worktree.ports.as_ref(),
+    )?;
+
     let result = git::commit_changes(&worktree.path, &message, stage_all.unwrap_or(false))?;
 
     log::trace!(
@@ -3988,6 +4270,16 @@ pub async fn update_project_settings(
     worktrees_dir: Option<String>,
     linear_api_key: Option<String>,
     linear_team_id: Option<String>,
+    permission_policy: Option<crate::chat::types::PermissionPolicy>,
+    sandbox: Option<SandboxConfig>,
+    pr_defaults: Option<PrDefaults>,
+    forge: Option<String>,
+    forge_token: Option<String>,
+    forge_url: Option<String>,
+    jira_url: Option<String>,
+    jira_email: Option<String>,
+    jira_api_token: Option<String>,
+    jira_jql: Option<String>,
 ) -> Result<Project, String> {
     log::trace!("Updating settings for project: {project_id}");
 
@@ -4058,6 +4350,101 @@ pub async fn update_project_settings(
         };
     }
 
+    if let Some(policy) = permission_policy {
+        policy.validate()?;
+        log::trace!("Updating permission policy ({} rules)", policy.rules.len());
+        project.permission_policy = if policy.rules.is_empty() {
+            None
+        } else {
+            Some(policy)
+        };
+    }
+
+    if let Some(mut sandbox) = sandbox {
+        sandbox.extra_writable_paths = sandbox
+            .extra_writable_paths
+            .into_iter()
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect();
+        if let Some(p) = sandbox
+            .extra_writable_paths
+            .iter()
+            .find(|p| !p.starts_with('/') && !p.starts_with('~'))
+        {
+            return Err(format!("Sandbox writable path must be absolute: {p}"));
+        }
+        log::trace!("Updating sandbox settings: enabled={}", sandbox.enabled);
+        project.sandbox = if sandbox == SandboxConfig::default() {
+            None
+        } else {
+            Some(sandbox)
+        };
+    }
+
+    if let Some(defaults) = pr_defaults {
+        log::trace!("Updating PR defaults: {defaults:?}");
+        project.pr_defaults = super::pr_lifecycle::normalize_pr_defaults(defaults)?;
+    }
+
+    if let Some(kind) = forge {
+        log::trace!("Updating forge: {kind:?}");
+        project.forge = if kind == "__none__" {
+            None
+        } else {
+            Some(
+                serde_json::from_value(serde_json::Value::String(kind.clone()))
+                    .map_err(|_| format!("Unknown forge: {kind}"))?,
+            )
+        };
+    }
+
+    if let Some(token) = forge_token {
+        let token = token.trim().to_string();
+        log::trace!("Updating forge token ({} chars)", token.len());
+        project.forge_token = if token.is_empty() { None } else { Some(token) };
+    }
+
+    if let Some(url) = forge_url {
+        let url = url.trim().trim_end_matches('/').to_string();
+        if !url.is_empty() && !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(format!(
+                "Forge URL must start with http:// or https://: {url}"
+            ));
+        }
+        log::trace!("Updating forge URL: {url:?}");
+        project.forge_url = if url.is_empty() { None } else { Some(url) };
+    }
+
+    if let Some(url) = jira_url {
+        let url = url.trim().trim_end_matches('/').to_string();
+        if !url.is_empty() && !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(format!(
+                "Jira URL must start with http:// or https://: {url}"
+            ));
+        }
+        log::trace!("Updating Jira URL: {url:?}");
+        project.jira_url = if url.is_empty() { None } else { Some(url) };
+    }
+
+    if let Some(email) = jira_email {
+        let email = email.trim().to_string();
+        log::trace!("Updating Jira email: {email:?}");
+        project.jira_email = if email.is_empty() { None } else { Some(email) };
+    }
+
+    if let Some(token) = jira_api_token {
+        let token = token.trim().to_string();
+        log::trace!("Updating Jira API token ({} chars)", token.len());
+        project.jira_api_token = if token.is_empty() { None } else { Some(token) };
+    }
+
+    if let Some(jql) = jira_jql {
+        let jql = jql.trim().to_string();
+        log::trace!("Updating Jira JQL filter: {jql:?}");
+        project.jira_jql = if jql.is_empty() { None } else { Some(jql) };
+    }
+
     let updated_project = project.clone();
     save_projects_data(&app, &data)?;
 
@@ -4137,7 +4524,7 @@ pub async fn get_pr_prompt(app: AppHandle, worktree_path: String) -> Result<Stri
         .find_project(&worktree.project_id)
         .ok_or_else(|| format!("Project not found: {}", worktree.project_id))?;
 
-    let target_branch = &project.default_branch;
+    let target_branch = &stacks::pr_target_branch(&data, worktree, project);
     let context = git::generate_pr_context(&worktree_path, target_branch)?;
 
     let mut prompt = format!(
@@ -4229,7 +4616,7 @@ pub async fn get_review_prompt(
         .find_project(&worktree.project_id)
         .ok_or_else(|| format!("Project not found: {}", worktree.project_id))?;
 
-    let target_branch = &project.default_branch;
+    let target_branch = &stacks::pr_target_branch(&data, worktree, project);
     let current_branch = git::get_current_branch(&worktree_path)?;
 
     // Get the full git diff (origin/target...HEAD)
@@ -4604,6 +4991,9 @@ pub async fn update_worktree_cached_status(
             worktree.name = b.clone();
         }
     }
+    // A parent PR that just merged hands its stacked children down the stack
+    let just_merged = pr_status.as_deref() == Some("merged")
+        && worktree.cached_pr_status.as_deref() != Some("merged");
     if pr_status.is_some() {
         worktree.cached_pr_status = pr_status;
     }
@@ -4649,6 +5039,11 @@ pub async fn update_worktree_cached_status(
 
     save_projects_data(&app, &data)?;
 
+    if just_merged {
+        let app_clone = app.clone();
+        thread::spawn(move || stacks::retarget_children(&app_clone, &worktree_id));
+    }
+
     Ok(())
 }
 
@@ -4656,6 +5051,8 @@ pub async fn update_worktree_cached_status(
 ///
 /// `diff_type` can be:
 /// - "uncommitted": Working directory changes vs HEAD
+/// - "staged": Index vs HEAD
+/// - "unstaged": Working directory changes vs index (including untracked files)
 /// - "branch": All changes in current branch vs base branch
 #[tauri::command]
 pub async fn get_git_diff(
@@ -4692,6 +5089,65 @@ pub async fn get_commit_diff(
     super::git_log::get_commit_diff(&worktree_path, &commit_sha)
 }
 
+/// Get the commits a rebase plan can rewrite, relative to `base_branch` or
+/// (by default) the worktree's PR target branch
+#[tauri::command]
+pub async fn get_rebase_plan(
+    app: AppHandle,
+    worktree_id: String,
+    base_branch: Option<String>,
+) -> Result<super::git_log::RebasePlan, String> {
+    log::trace!("Getting rebase plan for worktree {worktree_id} (base={base_branch:?})");
+    let data = load_projects_data(&app)?;
+    let worktree = data
+        .find_worktree(&worktree_id)
+        .ok_or_else(|| format!("Worktree not found: {worktree_id}"))?;
+    let project = data
+        .find_project(&worktree.project_id)
+        .ok_or_else(|| format!("Project not found: {}", worktree.project_id))?;
+    let target = base_branch.unwrap_or_else(|| stacks::pr_target_branch(&data, worktree, project));
+    let worktree_path = worktree.path.clone();
+
+    tokio::task::spawn_blocking(move || {
+        // Prefer the remote-tracking branch so the plan matches what the PR compares against
+        let remote_ref = format!("origin/{target}");
+        let base_ref = if super::git::rev_parse(&worktree_path, &remote_ref).is_ok() {
+            remote_ref
+        } else {
+            target
+        };
+        super::git_log::get_rebase_plan(&worktree_path, &base_ref)
+    })
+    .await
+    .map_err(|e| format!("Rebase plan task failed: {e}"))?
+}
+
+/// Apply a rebase plan (pick/reword/squash/fixup/drop, in the given order)
+/// onto `base`
+#[tauri::command]
+pub async fn apply_rebase_plan(
+    app: AppHandle,
+    worktree_id: String,
+    base: String,
+    steps: Vec<super::git_log::RebaseStep>,
+) -> Result<super::git_log::RebasePlanResult, String> {
+    log::trace!(
+        "Applying rebase plan with {} step(s) onto {base} in worktree {worktree_id}",
+        steps.len()
+    );
+    let data = load_projects_data(&app)?;
+    let worktree_path = data
+        .find_worktree(&worktree_id)
+        .map(|w| w.path.clone())
+        .ok_or_else(|| format!("Worktree not found: {worktree_id}"))?;
+
+    tokio::task::spawn_blocking(move || {
+        super::git_log::apply_rebase_plan(&worktree_path, &base, &steps)
+    })
+    .await
+    .map_err(|e| format!("Rebase plan task failed: {e}"))?
+}
+
 /// Get local branches for a repository by path
 #[tauri::command]
 pub async fn get_repo_branches(repo_path: String) -> Result<Vec<String>, String> {
@@ -4870,6 +5326,9 @@ pub struct CreatePrResponse {
     pub title: String,
     /// Whether this PR already existed (was linked, not newly created)
     pub existing: bool,
+    /// Project PR defaults (reviewers, labels, ...) that failed to apply
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub defaults_errors: Vec<String>,
 }
 
 /// Extract structured output from Claude CLI stream-json response
@@ -5199,7 +5658,7 @@ pub async fn create_pr_with_ai_content(
         .find_project(&worktree.project_id)
         .ok_or_else(|| format!("Project not found: {}", worktree.project_id))?;
 
-    let target_branch = &project.default_branch;
+    let target_branch = &stacks::pr_target_branch(&data, worktree, project);
     let current_branch = git::get_current_branch(&worktree_path)?;
 
     // Check if we're on the target branch (can't create PR to same branch)
@@ -5325,6 +5784,7 @@ pub async fn create_pr_with_ai_content(
                         pr_url,
                         title,
                         existing: true,
+                        defaults_errors: Vec::new(),
                     });
                 }
             }
@@ -5410,7 +5870,9 @@ pub async fn create_pr_with_ai_content(
         }
     }
 
-    // Append unconditional issue/PR/Linear references to the body
+    let jira_keys = session_jira_keys(&app, effective_session_id, worktree, project_name);
+
+    // Append unconditional issue/PR/Linear/Jira references to the body
     let mut refs: Vec<String> = Vec::new();
     for num in &issue_nums {
         refs.push(format!("Fixes #{num}"));
@@ -5421,25 +5883,70 @@ pub async fn create_pr_with_ai_content(
     for identifier in &linear_identifiers {
         refs.push(format!("Addresses {identifier}"));
     }
+    for key in &jira_keys {
+        refs.push(format!("Addresses {key}"));
+    }
     if !refs.is_empty() {
         pr_content.body = format!("{}\n\n---\n\n{}", pr_content.body, refs.join("\n"));
     }
 
     log::trace!("Generated PR title: {}", pr_content.title);
 
+    let pr_defaults = project.pr_defaults.clone().unwrap_or_default();
+
+    // Other forges open a merge request through their API
+    if let Some(forge) = forge::for_repo(&app, &project.path)? {
+        log::trace!("Creating merge request on {:?}", forge.kind());
+        let request = forge::NewMergeRequest {
+            source_branch: current_branch.clone(),
+            target_branch: target_branch.clone(),
+            title: pr_content.title.clone(),
+            body: pr_content.body.clone(),
+            draft: pr_defaults.draft,
+        };
+        let forge_name = forge.kind().display_name();
+        let created = forge::run(forge, move |f| f.create_merge_request(&request)).await?;
+
+        if created.existing {
+            if let Ok(mut data) = load_projects_data(&app) {
+                if let Some(wt) = data.worktrees.iter_mut().find(|w| w.path == worktree_path) {
+                    wt.pr_number = Some(created.number);
+                    wt.pr_url = Some(created.url.clone());
+                    let _ = save_projects_data(&app, &data);
+                }
+            }
+        }
+
+        return Ok(CreatePrResponse {
+            pr_number: created.number,
+            pr_url: created.url,
+            title: pr_content.title,
+            existing: created.existing,
+            defaults_errors: if created.existing {
+                Vec::new()
+            } else {
+                super::pr_lifecycle::unsupported_defaults(&pr_defaults, forge_name)
+            },
+        });
+    }
+
     // Create the PR using gh CLI
     log::trace!("Creating PR with gh CLI");
-    let output = silent_command(&gh)
-        .args([
-            "pr",
-            "create",
-            "--base",
-            target_branch,
-            "--title",
-            &pr_content.title,
-            "--body",
-            &pr_content.body,
-        ])
+    let mut create_cmd = silent_command(&gh);
+    create_cmd.args([
+        "pr",
+        "create",
+        "--base",
+        target_branch,
+        "--title",
+        &pr_content.title,
+        "--body",
+        &pr_content.body,
+    ]);
+    if pr_defaults.draft {
+        create_cmd.arg("--draft");
+    }
+    let output = create_cmd
         .current_dir(&worktree_path)
         .output()
         .map_err(|e| format!("Failed to run gh pr create: {e}"))?;
@@ -5479,6 +5986,7 @@ pub async fn create_pr_with_ai_content(
                                 pr_url,
                                 title,
                                 existing: true,
+                                defaults_errors: Vec::new(),
                             });
                         }
                     }
@@ -5495,11 +6003,17 @@ pub async fn create_pr_with_ai_content(
 
     log::trace!("Successfully created PR #{pr_number}: {pr_url}");
 
+    // Reviewers, labels etc. are applied after creation so that e.g. an
+    // unknown label doesn't fail the whole `gh pr create`
+    let defaults_errors =
+        super::pr_lifecycle::apply_defaults_to_pr(&gh, &worktree_path, pr_number, &pr_defaults);
+
     Ok(CreatePrResponse {
         pr_number,
         pr_url,
         title: pr_content.title,
         existing: false,
+        defaults_errors,
     })
 }
 
@@ -5620,7 +6134,7 @@ pub async fn generate_pr_update_content(
         .find_project(&worktree.project_id)
         .ok_or_else(|| format!("Project not found: {}", worktree.project_id))?;
 
-    let target_branch = &project.default_branch;
+    let target_branch = &stacks::pr_target_branch(&data, worktree, project);
     let current_branch = git::get_current_branch(&worktree_path)?;
 
     // Gather issue/PR context for this session AND worktree (same logic as create_pr_with_ai_content)
@@ -5700,7 +6214,9 @@ pub async fn generate_pr_update_content(
         }
     }
 
-    // Append unconditional issue/PR/Linear references to the body
+    let jira_keys = session_jira_keys(&app, effective_session_id, worktree, project_name);
+
+    // Append unconditional issue/PR/Linear/Jira references to the body
     let mut refs: Vec<String> = Vec::new();
     for num in &issue_nums {
         refs.push(format!("Fixes #{num}"));
@@ -5711,6 +6227,9 @@ pub async fn generate_pr_update_content(
     for identifier in &linear_identifiers {
         refs.push(format!("Addresses {identifier}"));
     }
+    for key in &jira_keys {
+        refs.push(format!("Addresses {key}"));
+    }
     if !refs.is_empty() {
         pr_content.body = format!("{}\n\n---\n\n{}", pr_content.body, refs.join("\n"));
     }
@@ -5824,6 +6343,17 @@ fn get_git_status(repo_path: &str) -> Result<String, String> {
     Ok(String::from_utf8_lossy(&output.stdout).to_string())
 }
 
+/// Get the status of staged files only (e.g. "M\tsrc/main.rs")
+fn get_staged_status(repo_path: &str) -> Result<String, String> {
+    let output = silent_command("git")
+        .args(["diff", "--cached", "--name-status"])
+        .current_dir(repo_path)
+        .output()
+        .map_err(|e| format!("Failed to get staged status: {e}"))?;
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
 /// Get compact diff stat summary (e.g. "src/main.rs | 5 ++--")
 fn get_staged_diff_stat(repo_path: &str) -> Result<String, String> {
     let output = silent_command("git")
@@ -5836,9 +6366,9 @@ fn get_staged_diff_stat(repo_path: &str) -> Result<String, String> {
 }
 
 /// Max lines of hunk content to keep per file
-const DIFF_MAX_LINES_PER_FILE: usize = 50;
+pub(crate) const DIFF_MAX_LINES_PER_FILE: usize = 50;
 /// Global char budget for the truncated diff
-const DIFF_MAX_CHARS: usize = 15_000;
+pub(crate) const DIFF_MAX_CHARS: usize = 15_000;
 
 /// Get staged diff with smart per-file truncation.
 ///
@@ -5921,7 +6451,7 @@ fn get_staged_diff(repo_path: &str) -> Result<String, String> {
 }
 
 /// Get recent commit messages for style reference
-fn get_recent_commits(repo_path: &str, count: u32) -> Result<String, String> {
+pub(crate) fn get_recent_commits(repo_path: &str, count: u32) -> Result<String, String> {
     let output = silent_command("git")
         .args(["log", "--oneline", &format!("-{count}")])
         .current_dir(repo_path)
@@ -5950,7 +6480,7 @@ fn stage_all_changes(repo_path: &str) -> Result<(), String> {
 }
 
 /// Create a git commit with the given message
-fn create_git_commit(repo_path: &str, message: &str) -> Result<String, String> {
+pub(crate) fn create_git_commit(repo_path: &str, message: &str) -> Result<String, String> {
     let output = silent_command("git")
         .args(["commit", "-m", message])
         .current_dir(repo_path)
@@ -6023,45 +6553,67 @@ fn generate_commit_message(
     magic_backend: Option<&str>,
     reasoning_effort: Option<&str>,
 ) -> Result<CommitMessageResponse, String> {
+    let json_content = run_structured_prompt(
+        app,
+        prompt,
+        COMMIT_MESSAGE_SCHEMA,
+        model,
+        custom_profile_name,
+        working_dir,
+        worktree_id,
+        magic_backend,
+        reasoning_effort,
+    )?;
+    log::trace!("Extracted commit message JSON: {json_content}");
+
+    serde_json::from_str::<CommitMessageResponse>(&json_content)
+        .map_err(|e| format!("Failed to parse commit message response: {e}"))
+}
+
+/// Run a one-shot prompt constrained by a JSON schema on the magic prompt
+/// backend (Claude CLI, Codex or OpenCode) and return the JSON output
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn run_structured_prompt(
+    app: &AppHandle,
+    prompt: &str,
+    schema: &str,
+    model: Option<&str>,
+    custom_profile_name: Option<&str>,
+    working_dir: Option<&std::path::Path>,
+    worktree_id: Option<&str>,
+    magic_backend: Option<&str>,
+    reasoning_effort: Option<&str>,
+) -> Result<String, String> {
     let model_str = model.unwrap_or("sonnet");
 
     // Per-operation backend > project/global default_backend
     let backend = crate::chat::resolve_magic_prompt_backend(app, magic_backend, worktree_id);
 
     if backend == crate::chat::types::Backend::Opencode {
-        log::trace!("Generating commit message with OpenCode");
-        let json_str = crate::chat::opencode::execute_one_shot_opencode(
+        log::trace!("Running structured prompt with OpenCode");
+        return crate::chat::opencode::execute_one_shot_opencode(
             app,
             prompt,
             model_str,
-            Some(COMMIT_MESSAGE_SCHEMA),
+            Some(schema),
             working_dir,
             reasoning_effort,
-        )?;
-        return serde_json::from_str(&json_str).map_err(|e| {
-            log::error!("Failed to parse OpenCode commit message JSON: {e}, content: {json_str}");
-            format!("Failed to parse commit message: {e}")
-        });
+        );
     }
 
     if backend == crate::chat::types::Backend::Codex {
-        log::trace!("Generating commit message with Codex CLI (output-schema)");
-        let json_str = crate::chat::codex::execute_one_shot_codex(
+        log::trace!("Running structured prompt with Codex CLI (output-schema)");
+        return crate::chat::codex::execute_one_shot_codex(
             app,
             prompt,
             model_str,
-            COMMIT_MESSAGE_SCHEMA,
+            schema,
             working_dir,
             reasoning_effort,
-        )?;
-        return serde_json::from_str(&json_str).map_err(|e| {
-            log::error!("Failed to parse Codex commit message JSON: {e}, content: {json_str}");
-            format!("Failed to parse commit message: {e}")
-        });
+        );
     }
 
-    log::trace!("Generating commit message with Claude CLI (JSON schema)");
-
+    log::trace!("Running structured prompt with Claude CLI (JSON schema)");
     let cli_path = resolve_cli_binary(app);
     if !cli_path.exists() {
         return Err("Claude CLI not installed".to_string());
@@ -6084,7 +6636,7 @@ fn generate_commit_message(
         "--max-turns",
         "1",
         "--json-schema",
-        COMMIT_MESSAGE_SCHEMA,
+        schema,
     ]);
 
     cmd.stdin(Stdio::piped())
@@ -6123,13 +6675,43 @@ fn generate_commit_message(
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    log::trace!("Claude CLI commit generation stdout: {stdout}");
+    log::trace!("Claude CLI structured prompt stdout: {stdout}");
 
-    let json_content = extract_structured_output(&stdout)?;
-    log::trace!("Extracted commit message JSON: {json_content}");
+    extract_structured_output(&stdout)
+}
 
-    serde_json::from_str::<CommitMessageResponse>(&json_content)
-        .map_err(|e| format!("Failed to parse commit message response: {e}"))
+/// ID of the worktree at a path, if Jean knows it
+pub(crate) fn worktree_id_for_path(app: &AppHandle, worktree_path: &str) -> Option<String> {
+    load_projects_data(app).ok().and_then(|d| {
+        d.worktrees
+            .iter()
+            .find(|w| w.path == worktree_path)
+            .map(|w| w.id.clone())
+    })
+}
+
+/// Run the jean.json pre_commit hook for a worktree identified by path
+pub(crate) fn run_pre_commit_hook_for_path(
+    app: &AppHandle,
+    worktree_path: &str,
+) -> Result<(), String> {
+    let data = load_projects_data(app)?;
+    let Some(worktree) = data.worktrees.iter().find(|w| w.path == worktree_path) else {
+        return Ok(());
+    };
+    let project_path = data
+        .find_project(&worktree.project_id)
+        .map(|p| p.path.as_str())
+        .unwrap_or_default();
+    git::run_hook(
+        app,
+        JeanHook::PreCommit,
+        worktree_path,
+        project_path,
+        &worktree.branch,
+        worktree.ports.as_ref(),
+    )
+    .map(|_| ())
 }
 
 /// Create a commit with AI-generated message
@@ -6145,8 +6727,11 @@ pub async fn create_commit_with_ai(
     model: Option<String>,
     custom_profile_name: Option<String>,
     reasoning_effort: Option<String>,
+    stage_all: Option<bool>,
 ) -> Result<CreateCommitResponse, String> {
-    log::trace!("Creating commit for: {worktree_path}");
+    // Defaults to staging everything; with `false` only what is already staged is committed
+    let stage_all = stage_all.unwrap_or(true);
+    log::trace!("Creating commit for: {worktree_path} (stage_all={stage_all})");
 
     // 1. Check for uncommitted changes
     let status = get_git_status(&worktree_path)?;
@@ -6170,14 +6755,25 @@ pub async fn create_commit_with_ai(
         return Err("No changes to commit".to_string());
     }
 
+    // jean.json pre_commit hook; failure blocks the commit
+    run_pre_commit_hook_for_path(&app, &worktree_path)?;
+
     // 2. Stage all changes
-    stage_all_changes(&worktree_path)?;
+    if stage_all {
+        stage_all_changes(&worktree_path)?;
+    }
 
     // 3. Get staged diff
     let diff = get_staged_diff(&worktree_path)?;
     if diff.trim().is_empty() {
         return Err("No staged changes to commit".to_string());
     }
+    // Describe only the staged subset when the rest stays uncommitted
+    let status = if stage_all {
+        status
+    } else {
+        get_staged_status(&worktree_path)?
+    };
 
     // 4. Get context for commit message generation
     let diff_stat = get_staged_diff_stat(&worktree_path)?;
@@ -6203,12 +6799,7 @@ pub async fn create_commit_with_ai(
         .and_then(|p| std::fs::read_to_string(p).ok())
         .and_then(|c| serde_json::from_str::<crate::AppPreferences>(&c).ok())
         .and_then(|p| p.magic_prompt_backends.commit_message_backend);
-    let worktree_id = load_projects_data(&app).ok().and_then(|d| {
-        d.worktrees
-            .iter()
-            .find(|w| w.path == worktree_path)
-            .map(|w| w.id.clone())
-    });
+    let worktree_id = worktree_id_for_path(&app, &worktree_path);
     let response = generate_commit_message(
         &app,
         &prompt,
@@ -6249,6 +6840,85 @@ pub async fn create_commit_with_ai(
     })
 }
 
+/// Generate a combined message for commits being squashed together, using the
+/// commit message magic prompt with the commits' diffs in place of staged changes
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub async fn generate_squash_message(
+    app: AppHandle,
+    worktree_id: String,
+    shas: Vec<String>,
+    custom_prompt: Option<String>,
+    model: Option<String>,
+    custom_profile_name: Option<String>,
+    reasoning_effort: Option<String>,
+) -> Result<String, String> {
+    log::trace!(
+        "Generating squash message for {} commit(s) in worktree {worktree_id}",
+        shas.len()
+    );
+    if shas.is_empty() {
+        return Err("No commits to squash".to_string());
+    }
+    let worktree_path = load_projects_data(&app)?
+        .find_worktree(&worktree_id)
+        .map(|w| w.path.clone())
+        .ok_or_else(|| format!("Worktree not found: {worktree_id}"))?;
+
+    let run_git = |args: &[&str]| super::git_log::run_git(&worktree_path, args);
+
+    let mut diff_stat = String::new();
+    let mut diff = String::new();
+    let mut messages = String::new();
+    for sha in &shas {
+        let sha = super::git_log::resolve_commit(&worktree_path, sha)?;
+        diff_stat.push_str(&run_git(&["show", "--stat", "--format=%h %s", &sha])?);
+        diff.push_str(&run_git(&["show", "--format=", &sha])?);
+        messages.push_str(&run_git(&["log", "-1", "--format=--- %h%n%B", &sha])?);
+    }
+    let diff = truncate_diff_at_file_boundaries(&diff, DIFF_MAX_CHARS);
+
+    let prompt_template = custom_prompt
+        .as_ref()
+        .filter(|p| !p.trim().is_empty())
+        .map(|s| s.as_str())
+        .unwrap_or(COMMIT_MESSAGE_PROMPT);
+
+    // The squashed commits' own messages stand in for the status and style reference
+    let prompt = prompt_template
+        .replace("{diff_stat}", &diff_stat)
+        .replace(
+            "{status}",
+            &format!("Squashing {} commits into one", shas.len()),
+        )
+        .replace("{diff}", &diff)
+        .replace("{recent_commits}", &messages)
+        .replace("{remote_info}", "");
+
+    let commit_magic_backend = crate::get_preferences_path(&app)
+        .ok()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str::<crate::AppPreferences>(&c).ok())
+        .and_then(|p| p.magic_prompt_backends.commit_message_backend);
+
+    let response = tokio::task::spawn_blocking(move || {
+        generate_commit_message(
+            &app,
+            &prompt,
+            model.as_deref(),
+            custom_profile_name.as_deref(),
+            Some(std::path::Path::new(&worktree_path)),
+            Some(worktree_id.as_str()),
+            commit_magic_backend.as_deref(),
+            reasoning_effort.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| format!("Squash message task failed: {e}"))??;
+
+    Ok(response.message)
+}
+
 // =============================================================================
 // AI-Powered Code Review
 // =============================================================================
@@ -6629,7 +7299,7 @@ pub async fn run_review_with_ai(
         .find_project(&worktree.project_id)
         .ok_or_else(|| format!("Project not found: {}", worktree.project_id))?;
 
-    let target_branch = &project.default_branch;
+    let target_branch = &stacks::pr_target_branch(&data, worktree, project);
     let current_branch = git::get_current_branch(&worktree_path)?;
 
     // Get branch diff (non-fatal — may fail if origin ref doesn't exist)
@@ -7300,8 +7970,22 @@ pub async fn merge_worktree_to_base(
         git::MergeResult::Success { commit_hash } => {
             log::trace!("Merge successful, cleaning up worktree");
 
+            // jean.json post_merge hook runs in the project root; the merge is
+            // already done, so a failure is only logged
+            if let Err(e) = git::run_hook(
+                &app,
+                JeanHook::PostMerge,
+                &project.path,
+                &project.path,
+                &project.default_branch,
+                None,
+            ) {
+                log::warn!("post_merge hook failed: {e}");
+            }
+
             // Cancel any running Claude processes for this worktree
             crate::chat::registry::cancel_processes_for_worktree(&app, &worktree_id);
+            crate::services::stop_worktree_services(&app, &worktree_id);
 
             // Emit deleting event
             let deleting_event = WorktreeDeletingEvent {
@@ -7948,6 +8632,16 @@ pub async fn create_folder(
         worktrees_dir: None,
         linear_api_key: None,
         linear_team_id: None,
+        permission_policy: None,
+        sandbox: None,
+        pr_defaults: None,
+        forge: None,
+        forge_token: None,
+        forge_url: None,
+        jira_url: None,
+        jira_email: None,
+        jira_api_token: None,
+        jira_jql: None,
     };
 
     data.add_project(folder.clone());
@@ -8791,10 +9485,45 @@ pub async fn get_app_data_dir(app: AppHandle) -> Result<String, String> {
     Ok(app_data_dir.to_string_lossy().to_string())
 }
 
+/// Copy jean.json `copy` entries from the project root into a new worktree.
+///
+/// Returns a summary for the worktree's setup output.
+fn copy_untracked_into_worktree(
+    app: &AppHandle,
+    project_path: &str,
+    worktree_path: &str,
+) -> Option<String> {
+    let config =
+        git::read_jean_config(worktree_path).or_else(|| git::read_jean_config(project_path))?;
+    if config.copy.is_empty() {
+        return None;
+    }
+    let copied = worktree_copy::copy_into_worktree(app, project_path, worktree_path, &config.copy);
+    worktree_copy::summarize(&copied)
+}
+
+/// Put the copy summary in front of the setup script's output
+fn prepend_copy_summary(summary: Option<String>, setup_output: Option<String>) -> Option<String> {
+    match (summary, setup_output) {
+        (Some(summary), Some(output)) => Some(join_script_output(Some(summary), output)),
+        (summary, output) => summary.or(output),
+    }
+}
+
+/// Append a hook's output to earlier script output
+fn join_script_output(existing: Option<String>, output: String) -> String {
+    match existing {
+        Some(existing) if !existing.is_empty() => format!("{existing}\n{output}"),
+        _ => output,
+    }
+}
+
 /// Get full jean.json config for a project
+///
+/// Fails with line-accurate messages when jean.json is invalid.
 #[tauri::command]
-pub async fn get_jean_config(project_path: String) -> Option<JeanConfig> {
-    git::read_jean_config(&project_path)
+pub async fn get_jean_config(project_path: String) -> Result<Option<JeanConfig>, String> {
+    super::jean_config::load(&project_path)
 }
 
 /// Save jean.json config to disk
@@ -8803,12 +9532,118 @@ pub async fn save_jean_config(project_path: String, config: JeanConfig) -> Resul
     let config_path = Path::new(&project_path).join("jean.json");
     let json = serde_json::to_string_pretty(&config)
         .map_err(|e| format!("Failed to serialize config: {e}"))?;
+    let errors = super::jean_config::validate(&json, &config);
+    if !errors.is_empty() {
+        return Err(errors.join("\n"));
+    }
     std::fs::write(&config_path, format!("{json}\n"))
         .map_err(|e| format!("Failed to write jean.json: {e}"))?;
     log::trace!("Saved jean.json to {}", config_path.display());
     Ok(())
 }
 
+/// Re-run the jean.json setup script on an existing worktree.
+///
+/// Output streams as `script:output` events; the result replaces the
+/// worktree's persisted setup output.
+#[tauri::command]
+pub async fn rerun_setup_script(app: AppHandle, worktree_id: String) -> Result<Worktree, String> {
+    log::trace!("Re-running setup script for worktree: {worktree_id}");
+
+    // Check and claim under the projects lock so two concurrent re-runs can't
+    // both pass the running check before either script has started
+    let (worktree, project_path, script, _claim) = with_projects_data_mut(&app, |data| {
+        let worktree = data
+            .find_worktree(&worktree_id)
+            .ok_or_else(|| format!("Worktree not found: {worktree_id}"))?
+            .clone();
+        let project_path = data
+            .find_project(&worktree.project_id)
+            .ok_or_else(|| format!("Project not found: {}", worktree.project_id))?
+            .path
+            .clone();
+
+        let script = git::read_jean_config(&worktree.path)
+            .or_else(|| git::read_jean_config(&project_path))
+            .and_then(|config| config.scripts.setup)
+            .ok_or_else(|| "No setup script in jean.json".to_string())?;
+        let claim = SetupRerunClaim::acquire(&worktree_id)
+            .filter(|_| !super::script_runner::is_running(&worktree.path, "setup"))
+            .ok_or_else(|| "The setup script is already running in this worktree".to_string())?;
+        Ok((worktree, project_path, script, claim))
+    })?;
+
+    let app_clone = app.clone();
+    let worktree_clone = worktree.clone();
+    let script_clone = script.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        git::run_setup_script(
+            &app_clone,
+            &worktree_clone.path,
+            &project_path,
+            &worktree_clone.branch,
+            &script_clone,
+            worktree_clone.ports.as_ref(),
+        )
+    })
+    .await
+    .map_err(|e| format!("Setup script task failed: {e}"))?;
+
+    let (setup_output, setup_success) = match result {
+        Ok(output) => (output, true),
+        Err(e) => {
+            log::warn!("Setup script re-run failed: {e}");
+            (e, false)
+        }
+    };
+
+    with_projects_data_mut(&app, |data| {
+        let worktree = data
+            .find_worktree_mut(&worktree_id)
+            .ok_or_else(|| format!("Worktree not found: {worktree_id}"))?;
+        worktree.setup_output = Some(setup_output);
+        worktree.setup_script = Some(script);
+        worktree.setup_success = Some(setup_success);
+        Ok(worktree.clone())
+    })
+}
+
+/// Worktrees with a setup script re-run in flight
+static SETUP_RERUNS: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Claim on a worktree's setup re-run, released when dropped
+struct SetupRerunClaim(String);
+
+impl SetupRerunClaim {
+    /// None when a re-run is already claimed for this worktree
+    fn acquire(worktree_id: &str) -> Option<Self> {
+        SETUP_RERUNS
+            .lock()
+            .unwrap()
+            .insert(worktree_id.to_string())
+            .then(|| Self(worktree_id.to_string()))
+    }
+}
+
+impl Drop for SetupRerunClaim {
+    fn drop(&mut self) {
+        SETUP_RERUNS.lock().unwrap().remove(&self.0);
+    }
+}
+
+/// Cancel a jean.json script (setup, teardown or hook) running in a worktree,
+/// or every running one when `kind` is None.
+///
+/// Returns false when no matching script is running.
+#[tauri::command]
+pub async fn cancel_jean_script(
+    app: AppHandle,
+    worktree_path: String,
+    kind: Option<String>,
+) -> Result<bool, String> {
+    Ok(!super::script_runner::cancel(&app, &worktree_path, kind.as_deref()).is_empty())
+}
+
 /// Response from reverting the last local commit
 #[derive(Debug, Clone, Serialize)]
 pub struct RevertCommitResponse {
@@ -0,0 +1,438 @@
+//! Split a worktree's uncommitted changes into several logical commits.
+//!
+//! The AI proposes a grouping of the changed hunks (plus files without hunks,
+//! such as binaries) into commits with messages. The user can move hunks
+//! between commits and edit messages before the plan is applied: the index is
+//! reset to HEAD and each commit's hunks are staged with partial staging and
+//! committed in order.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use super::commands::{
+    create_git_commit, get_recent_commits, run_pre_commit_hook_for_path, run_structured_prompt,
+    worktree_id_for_path, DIFF_MAX_CHARS, DIFF_MAX_LINES_PER_FILE,
+};
+use super::git_status::{parse_unified_diff, DiffFile};
+use super::staging;
+use crate::platform::silent_command;
+
+/// A unit of change that can be assigned to a commit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitHunk {
+    /// Stable id used in the plan: `path#index`, or `path` for a whole file
+    pub id: String,
+    pub path: String,
+    /// Index into the file's hunks; `None` for files without hunks (binary,
+    /// mode-only changes), which are committed whole
+    pub hunk_index: Option<usize>,
+    /// Hunk header (`@@ -1,5 +1,7 @@`), or the file status for whole files.
+    /// Checked when applying so a changed diff isn't committed by mistake.
+    pub header: String,
+    pub additions: u32,
+    pub deletions: u32,
+}
+
+/// One proposed commit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitCommit {
+    pub message: String,
+    /// Ids of the `SplitHunk`s in this commit
+    #[serde(alias = "hunk_ids")] // as named in the AI response schema
+    pub hunk_ids: Vec<String>,
+}
+
+/// A proposed (or user-adjusted) split of the uncommitted changes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitSplitPlan {
+    /// Commits in the order they will be created
+    pub commits: Vec<SplitCommit>,
+    /// Every hunk of the uncommitted changes, including ones not in any
+    /// commit (those stay uncommitted)
+    pub hunks: Vec<SplitHunk>,
+}
+
+/// Structured response from the split proposal
+#[derive(Debug, Deserialize)]
+struct SplitResponse {
+    commits: Vec<SplitCommit>,
+}
+
+/// JSON schema for commit split proposals
+const COMMIT_SPLIT_SCHEMA: &str = r#"{"type":"object","properties":{"commits":{"type":"array","items":{"type":"object","properties":{"message":{"type":"string","description":"Conventional commit message for this commit"},"hunk_ids":{"type":"array","items":{"type":"string"},"description":"Ids of the hunks that belong in this commit"}},"required":["message","hunk_ids"],"additionalProperties":false},"description":"Commits in the order they should be created"}},"required":["commits"],"additionalProperties":false}"#;
+
+/// Prompt template for commit split proposals
+const COMMIT_SPLIT_PROMPT: &str = r#"Split these uncommitted changes into a small number of logical, self-contained commits.
+
+Each change below is a hunk with an id. Assign every hunk id to exactly one commit. Group changes that belong together (a feature and its tests, a refactor, a fix), order the commits so each builds on the previous ones, and write a conventional commit message for each. Prefer fewer commits over splitting related changes apart.
+
+Changes:
+{hunks}
+
+Recent commits (style reference):
+{recent_commits}"#;
+
+/// Build the hunk list from the worktree's changes against HEAD
+fn collect_hunks(worktree_path: &str) -> Result<(Vec<SplitHunk>, Vec<DiffFile>), String> {
+    let output = silent_command("git")
+        // Renames would turn into a deletion and an untracked file once the
+        // index is reset, so list them that way from the start
+        .args(["diff", "HEAD", "--unified=3", "--no-renames"])
+        .current_dir(worktree_path)
+        .output()
+        .map_err(|e| format!("Failed to run git diff: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Git diff failed: {}", stderr.trim()));
+    }
+    let (mut files, _) = parse_unified_diff(&String::from_utf8_lossy(&output.stdout));
+
+    let untracked = silent_command("git")
+        .args(["ls-files", "--others", "--exclude-standard"])
+        .current_dir(worktree_path)
+        .output()
+        .map_err(|e| format!("Failed to list untracked files: {e}"))?;
+    for path in String::from_utf8_lossy(&untracked.stdout).lines() {
+        let raw = staging::unstaged_file_diff(worktree_path, path)?;
+        let (untracked_files, _) = parse_unified_diff(&raw);
+        files.extend(untracked_files.into_iter().map(|mut f| {
+            f.path = path.to_string();
+            f
+        }));
+    }
+
+    Ok((split_hunks(&files), files))
+}
+
+fn split_hunks(files: &[DiffFile]) -> Vec<SplitHunk> {
+    let mut hunks = Vec::new();
+    for file in files {
+        if file.hunks.is_empty() {
+            hunks.push(SplitHunk {
+                id: file.path.clone(),
+                path: file.path.clone(),
+                hunk_index: None,
+                header: file.status.clone(),
+                additions: file.additions,
+                deletions: file.deletions,
+            });
+            continue;
+        }
+        for (index, hunk) in file.hunks.iter().enumerate() {
+            let count = |kind: &str| hunk.lines.iter().filter(|l| l.line_type == kind).count();
+            hunks.push(SplitHunk {
+                id: format!("{}#{index}", file.path),
+                path: file.path.clone(),
+                hunk_index: Some(index),
+                header: hunk.header.clone(),
+                additions: count("addition") as u32,
+                deletions: count("deletion") as u32,
+            });
+        }
+    }
+    hunks
+}
+
+/// Render the hunks for the prompt, truncating long hunks and listing only
+/// headers once the character budget is used up
+fn format_hunks(files: &[DiffFile]) -> String {
+    let mut out = String::new();
+    for file in files {
+        if file.hunks.is_empty() {
+            let kind = if file.is_binary {
+                "binary"
+            } else {
+                "no text changes"
+            };
+            out.push_str(&format!(
+                "<hunk id=\"{}\">{} file, {kind}</hunk>\n",
+                file.path, file.status
+            ));
+            continue;
+        }
+        for (index, hunk) in file.hunks.iter().enumerate() {
+            out.push_str(&format!(
+                "<hunk id=\"{}#{index}\">\n{}\n",
+                file.path, hunk.header
+            ));
+            if out.len() < DIFF_MAX_CHARS {
+                for line in hunk.lines.iter().take(DIFF_MAX_LINES_PER_FILE) {
+                    let prefix = match line.line_type.as_str() {
+                        "addition" => '+',
+                        "deletion" => '-',
+                        _ => ' ',
+                    };
+                    out.push(prefix);
+                    out.push_str(&line.content);
+                    out.push('\n');
+                }
+                if hunk.lines.len() > DIFF_MAX_LINES_PER_FILE {
+                    out.push_str(&format!(
+                        "... ({} more lines)\n",
+                        hunk.lines.len() - DIFF_MAX_LINES_PER_FILE
+                    ));
+                }
+            }
+            out.push_str("</hunk>\n");
+        }
+    }
+    out
+}
+
+/// Keep only known hunk ids, each in the first commit that claims it, and
+/// drop commits left empty. Hunks the AI left out stay unassigned (and so
+/// uncommitted) for the user to place.
+fn normalize_commits(commits: Vec<SplitCommit>, hunks: &[SplitHunk]) -> Vec<SplitCommit> {
+    let known: HashSet<&str> = hunks.iter().map(|h| h.id.as_str()).collect();
+    let mut seen: HashSet<String> = HashSet::new();
+
+    let commits: Vec<SplitCommit> = commits
+        .into_iter()
+        .map(|c| SplitCommit {
+            message: c.message.trim().to_string(),
+            hunk_ids: c
+                .hunk_ids
+                .into_iter()
+                .filter(|id| known.contains(id.as_str()) && seen.insert(id.clone()))
+                .collect(),
+        })
+        .filter(|c| !c.hunk_ids.is_empty())
+        .collect();
+
+    let unassigned = hunks.len() - seen.len();
+    if unassigned > 0 {
+        log::debug!("Commit split proposal left {unassigned} hunk(s) unassigned");
+    }
+    commits
+}
+
+/// Ask the AI to group the worktree's uncommitted hunks into commits
+#[tauri::command]
+pub async fn propose_commit_split(
+    app: AppHandle,
+    worktree_path: String,
+    custom_prompt: Option<String>,
+    model: Option<String>,
+    custom_profile_name: Option<String>,
+    reasoning_effort: Option<String>,
+) -> Result<CommitSplitPlan, String> {
+    log::trace!("Proposing commit split for {worktree_path}");
+
+    let (hunks, files) = collect_hunks(&worktree_path)?;
+    if hunks.is_empty() {
+        return Err("No changes to commit".to_string());
+    }
+    let recent_commits = get_recent_commits(&worktree_path, 5)?;
+
+    let prompt_template = custom_prompt
+        .as_ref()
+        .filter(|p| !p.trim().is_empty())
+        .map(|s| s.as_str())
+        .unwrap_or(COMMIT_SPLIT_PROMPT);
+    let prompt = prompt_template
+        .replace("{hunks}", &format_hunks(&files))
+        .replace("{recent_commits}", &recent_commits);
+
+    let backend = crate::get_preferences_path(&app)
+        .ok()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str::<crate::AppPreferences>(&c).ok())
+        .and_then(|p| p.magic_prompt_backends.commit_message_backend);
+    let worktree_id = worktree_id_for_path(&app, &worktree_path);
+
+    let response = tokio::task::spawn_blocking({
+        let worktree_path = worktree_path.clone();
+        move || {
+            let json = run_structured_prompt(
+                &app,
+                &prompt,
+                COMMIT_SPLIT_SCHEMA,
+                model.as_deref(),
+                custom_profile_name.as_deref(),
+                Some(Path::new(&worktree_path)),
+                worktree_id.as_deref(),
+                backend.as_deref(),
+                reasoning_effort.as_deref(),
+            )?;
+            serde_json::from_str::<SplitResponse>(&json)
+                .map_err(|e| format!("Failed to parse commit split response: {e}"))
+        }
+    })
+    .await
+    .map_err(|e| format!("Commit split task failed: {e}"))??;
+
+    Ok(CommitSplitPlan {
+        commits: normalize_commits(response.commits, &hunks),
+        hunks,
+    })
+}
+
+/// Create the commits of a split plan in order.
+///
+/// Anything already staged is unstaged first so each commit contains only
+/// its own hunks. Hunks not assigned to a commit stay uncommitted. Returns
+/// the created commit hashes.
+#[tauri::command]
+pub async fn apply_commit_split(
+    app: AppHandle,
+    worktree_path: String,
+    plan: CommitSplitPlan,
+) -> Result<Vec<String>, String> {
+    log::trace!(
+        "Applying commit split with {} commit(s) in {worktree_path}",
+        plan.commits.len()
+    );
+    if let Some(empty) = plan
+        .commits
+        .iter()
+        .position(|c| c.message.trim().is_empty())
+    {
+        return Err(format!("Commit {} needs a message", empty + 1));
+    }
+
+    // jean.json pre_commit hook; failure blocks the commits
+    run_pre_commit_hook_for_path(&app, &worktree_path)?;
+
+    tokio::task::spawn_blocking(move || apply_plan(&worktree_path, &plan))
+        .await
+        .map_err(|e| format!("Commit split task failed: {e}"))?
+}
+
+fn apply_plan(worktree_path: &str, plan: &CommitSplitPlan) -> Result<Vec<String>, String> {
+    // The plan must still describe the current changes
+    let (current, _) = collect_hunks(worktree_path)?;
+    let current: HashMap<&str, &SplitHunk> = current.iter().map(|h| (h.id.as_str(), h)).collect();
+    let planned: HashMap<&str, &SplitHunk> =
+        plan.hunks.iter().map(|h| (h.id.as_str(), h)).collect();
+    for id in plan.commits.iter().flat_map(|c| &c.hunk_ids) {
+        let matches = match (planned.get(id.as_str()), current.get(id.as_str())) {
+            (Some(p), Some(c)) => p.header == c.header && p.hunk_index == c.hunk_index,
+            _ => false,
+        };
+        if !matches {
+            return Err(format!(
+                "The changes in {id} are no longer what was planned. Propose the split again."
+            ));
+        }
+    }
+
+    let output = silent_command("git")
+        .args(["reset", "-q"])
+        .current_dir(worktree_path)
+        .output()
+        .map_err(|e| format!("Failed to unstage changes: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to unstage changes: {}", stderr.trim()));
+    }
+
+    // Stage from the current hunks: the client's copies were only compared by
+    // header and index, so their paths can't be trusted
+    let assigned = |ids: &[String]| -> Vec<&SplitHunk> {
+        ids.iter()
+            .filter_map(|id| current.get(id.as_str()).copied())
+            .collect()
+    };
+
+    // Capture each file's diff once: hunk indices refer to these diffs, and
+    // later hunks still apply after earlier ones are committed
+    let mut diffs: HashMap<&str, String> = HashMap::new();
+    for hunk in plan.commits.iter().flat_map(|c| assigned(&c.hunk_ids)) {
+        if hunk.hunk_index.is_some() && !diffs.contains_key(hunk.path.as_str()) {
+            diffs.insert(
+                &hunk.path,
+                staging::unstaged_file_diff(worktree_path, &hunk.path)?,
+            );
+        }
+    }
+
+    let mut hashes = Vec::new();
+    for (n, commit) in plan.commits.iter().enumerate() {
+        let fail = |e: String| {
+            format!(
+                "Commit {} of {} failed after creating {}: {e}",
+                n + 1,
+                plan.commits.len(),
+                hashes.len()
+            )
+        };
+
+        // Group the commit's hunks by file, keeping plan order
+        let mut by_file: Vec<(&str, Vec<usize>)> = Vec::new();
+        for hunk in assigned(&commit.hunk_ids) {
+            match hunk.hunk_index {
+                None => staging::stage_file(worktree_path, &hunk.path).map_err(fail)?,
+                Some(index) => match by_file.iter_mut().find(|(p, _)| *p == hunk.path) {
+                    Some((_, indices)) => indices.push(index),
+                    None => by_file.push((&hunk.path, vec![index])),
+                },
+            }
+        }
+        for (path, indices) in &by_file {
+            let raw = diffs.get(path).map(String::as_str).unwrap_or_default();
+            staging::stage_hunks(worktree_path, path, raw, indices).map_err(fail)?;
+        }
+
+        let hash = create_git_commit(worktree_path, commit.message.trim()).map_err(fail)?;
+        log::trace!("Created split commit {}: {hash}", n + 1);
+        hashes.push(hash);
+    }
+
+    Ok(hashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hunk(id: &str) -> SplitHunk {
+        SplitHunk {
+            id: id.to_string(),
+            path: id.split('#').next().unwrap().to_string(),
+            hunk_index: id.split('#').nth(1).map(|i| i.parse().unwrap()),
+            header: String::new(),
+            additions: 0,
+            deletions: 0,
+        }
+    }
+
+    fn commit(message: &str, ids: &[&str]) -> SplitCommit {
+        SplitCommit {
+            message: message.to_string(),
+            hunk_ids: ids.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_commits() {
+        let hunks = vec![
+            hunk("a.rs#0"),
+            hunk("a.rs#1"),
+            hunk("b.rs#0"),
+            hunk("logo.png"),
+        ];
+        let commits = normalize_commits(
+            vec![
+                commit("feat: a", &["a.rs#0", "nope#3", "a.rs#0"]),
+                commit("chore: dup", &["a.rs#0"]),
+                commit(" fix: b \n", &["b.rs#0"]),
+            ],
+            &hunks,
+        );
+
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].hunk_ids, vec!["a.rs#0"]);
+        assert_eq!(commits[1].message, "fix: b");
+        // Unassigned hunks stay out of every commit
+        assert_eq!(commits[1].hunk_ids, vec!["b.rs#0"]);
+
+        let commits = normalize_commits(Vec::new(), &hunks);
+        assert!(commits.is_empty());
+    }
+}
@@ -0,0 +1,814 @@
+//! Structured merge/rebase conflicts.
+//!
+//! Instead of one `git diff` blob, each unmerged file is returned with its
+//! base/ours/theirs versions (from the index stages via `git checkout-index`)
+//! and its conflict hunks parsed from the markers in the working file. Hunks
+//! can be resolved one at a time, files marked resolved, and the merge,
+//! rebase, cherry-pick or revert continued or aborted.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use super::storage::load_projects_data;
+use crate::platform::silent_command;
+
+/// Git operation that stopped on conflicts
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictOperation {
+    Merge,
+    Rebase,
+    CherryPick,
+    Revert,
+}
+
+impl ConflictOperation {
+    pub(crate) fn command(self) -> &'static str {
+        match self {
+            ConflictOperation::Merge => "merge",
+            ConflictOperation::Rebase => "rebase",
+            ConflictOperation::CherryPick => "cherry-pick",
+            ConflictOperation::Revert => "revert",
+        }
+    }
+}
+
+/// How a file is unmerged (from the `git status` XY code)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictStatus {
+    /// UU
+    BothModified,
+    /// AA
+    BothAdded,
+    /// DU
+    DeletedByUs,
+    /// UD
+    DeletedByThem,
+    /// AU
+    AddedByUs,
+    /// UA
+    AddedByThem,
+    /// DD
+    BothDeleted,
+}
+
+impl ConflictStatus {
+    fn from_code(code: &str) -> Option<Self> {
+        Some(match code {
+            "UU" => ConflictStatus::BothModified,
+            "AA" => ConflictStatus::BothAdded,
+            "DU" => ConflictStatus::DeletedByUs,
+            "UD" => ConflictStatus::DeletedByThem,
+            "AU" => ConflictStatus::AddedByUs,
+            "UA" => ConflictStatus::AddedByThem,
+            "DD" => ConflictStatus::BothDeleted,
+            _ => return None,
+        })
+    }
+}
+
+/// One conflict region (`<<<<<<<` .. `>>>>>>>`) in a working file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConflictHunk {
+    /// Position among the file's remaining hunks
+    pub index: usize,
+    /// 1-based line of the `<<<<<<<` marker
+    pub start_line: usize,
+    /// 1-based line of the `>>>>>>>` marker
+    pub end_line: usize,
+    pub ours: String,
+    /// Common ancestor version, when known (diff3 markers or the index stages)
+    pub base: Option<String>,
+    pub theirs: String,
+}
+
+/// An unmerged file with its three versions and remaining conflict hunks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileConflict {
+    pub path: String,
+    pub status: ConflictStatus,
+    /// Binary files have no contents or hunks; resolve them by taking a side
+    pub binary: bool,
+    /// Label after `<<<<<<<` (e.g. "HEAD")
+    pub ours_label: Option<String>,
+    /// Label after `>>>>>>>` (e.g. the merged branch or replayed commit)
+    pub theirs_label: Option<String>,
+    /// Stage 1 (common ancestor)
+    pub base: Option<String>,
+    /// Stage 2 (current branch; the upstream during a rebase)
+    pub ours: Option<String>,
+    /// Stage 3 (incoming changes; the replayed commit during a rebase)
+    pub theirs: Option<String>,
+    /// Conflict hunks still in the working file
+    pub hunks: Vec<ConflictHunk>,
+}
+
+/// Conflicts in a worktree and the operation that produced them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictState {
+    /// `None` when no merge/rebase/cherry-pick/revert is in progress
+    pub operation: Option<ConflictOperation>,
+    pub files: Vec<FileConflict>,
+}
+
+/// Resolution for a single conflict hunk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum HunkResolution {
+    Ours,
+    Theirs,
+    /// Ours followed by theirs
+    Both,
+    Base,
+    /// Hand-edited or AI-suggested content
+    Manual {
+        content: String,
+    },
+}
+
+/// Side to take for a whole file
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictSide {
+    Ours,
+    Theirs,
+}
+
+/// AI-suggested content for a conflict hunk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictSuggestion {
+    pub content: String,
+    pub explanation: Option<String>,
+}
+
+/// A hunk located in the working file, with the byte range it spans
+#[derive(Debug, Clone)]
+struct LocatedHunk {
+    hunk: ConflictHunk,
+    start: usize,
+    end: usize,
+    ours_label: Option<String>,
+    theirs_label: Option<String>,
+}
+
+/// Match a 7-character conflict marker line, returning its label
+fn marker(line: &str, ch: char) -> Option<&str> {
+    let line = line.trim_end_matches(['\n', '\r']);
+    let prefix: String = std::iter::repeat_n(ch, 7).collect();
+    let rest = line.strip_prefix(prefix.as_str())?;
+    if rest.is_empty() {
+        Some("")
+    } else {
+        rest.strip_prefix(' ').map(str::trim)
+    }
+}
+
+/// Parse the conflict hunks in a working file (merge, diff3 and zdiff3 styles)
+fn locate_hunks(content: &str) -> Vec<LocatedHunk> {
+    enum Section {
+        Outside,
+        Ours,
+        Base,
+        Theirs,
+    }
+
+    let mut hunks = Vec::new();
+    let mut section = Section::Outside;
+    let mut offset = 0;
+    let mut current: Option<LocatedHunk> = None;
+
+    for (i, line) in content.split_inclusive('\n').enumerate() {
+        let line_no = i + 1;
+        match section {
+            Section::Outside => {
+                if let Some(label) = marker(line, '<') {
+                    current = Some(LocatedHunk {
+                        hunk: ConflictHunk {
+                            index: hunks.len(),
+                            start_line: line_no,
+                            end_line: line_no,
+                            ours: String::new(),
+                            base: None,
+                            theirs: String::new(),
+                        },
+                        start: offset,
+                        end: offset,
+                        ours_label: (!label.is_empty()).then(|| label.to_string()),
+                        theirs_label: None,
+                    });
+                    section = Section::Ours;
+                }
+            }
+            Section::Ours | Section::Base => {
+                let Some(hunk) = current.as_mut() else {
+                    break;
+                };
+                if marker(line, '|').is_some() {
+                    hunk.hunk.base = Some(String::new());
+                    section = Section::Base;
+                } else if marker(line, '=') == Some("") {
+                    section = Section::Theirs;
+                } else if matches!(section, Section::Base) {
+                    if let Some(base) = hunk.hunk.base.as_mut() {
+                        base.push_str(line);
+                    }
+                } else {
+                    hunk.hunk.ours.push_str(line);
+                }
+            }
+            Section::Theirs => {
+                let Some(mut hunk) = current.take() else {
+                    break;
+                };
+                if let Some(label) = marker(line, '>') {
+                    hunk.hunk.end_line = line_no;
+                    hunk.end = offset + line.len();
+                    hunk.theirs_label = (!label.is_empty()).then(|| label.to_string());
+                    hunks.push(hunk);
+                    section = Section::Outside;
+                } else {
+                    hunk.hunk.theirs.push_str(line);
+                    current = Some(hunk);
+                }
+            }
+        }
+        offset += line.len();
+    }
+
+    hunks
+}
+
+/// Replace hunk `index` in a working file with its resolution
+fn apply_resolution(
+    file: &str,
+    index: usize,
+    resolution: &HunkResolution,
+) -> Result<String, String> {
+    let hunks = locate_hunks(file);
+    let located = hunks
+        .get(index)
+        .ok_or_else(|| format!("Conflict hunk {index} not found"))?;
+    let hunk = &located.hunk;
+
+    let replacement = match resolution {
+        HunkResolution::Ours => hunk.ours.clone(),
+        HunkResolution::Theirs => hunk.theirs.clone(),
+        HunkResolution::Both => format!("{}{}", hunk.ours, hunk.theirs),
+        HunkResolution::Base => hunk
+            .base
+            .clone()
+            .ok_or_else(|| "The base version of this hunk is unknown".to_string())?,
+        HunkResolution::Manual { content } => {
+            // Hunks are whole lines; keep the following line on its own line
+            if content.is_empty() || content.ends_with('\n') {
+                content.clone()
+            } else {
+                format!("{content}\n")
+            }
+        }
+    };
+
+    Ok(format!(
+        "{}{}{}",
+        &file[..located.start],
+        replacement,
+        &file[located.end..]
+    ))
+}
+
+/// Locate the `.git` path for a worktree-relative git file (e.g. `MERGE_HEAD`)
+pub(crate) fn git_path(worktree_path: &str, name: &str) -> Option<PathBuf> {
+    let output = silent_command("git")
+        .args(["rev-parse", "--git-path", name])
+        .current_dir(worktree_path)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let path = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    Some(if path.is_absolute() {
+        path
+    } else {
+        Path::new(worktree_path).join(path)
+    })
+}
+
+/// Detect the merge/rebase/cherry-pick/revert in progress, if any
+pub fn detect_operation(worktree_path: &str) -> Option<ConflictOperation> {
+    let exists = |name: &str| git_path(worktree_path, name).is_some_and(|p| p.exists());
+    if exists("rebase-merge") || exists("rebase-apply") {
+        Some(ConflictOperation::Rebase)
+    } else if exists("CHERRY_PICK_HEAD") {
+        Some(ConflictOperation::CherryPick)
+    } else if exists("REVERT_HEAD") {
+        Some(ConflictOperation::Revert)
+    } else if exists("MERGE_HEAD") {
+        Some(ConflictOperation::Merge)
+    } else {
+        None
+    }
+}
+
+/// Unmerged paths and how they conflict
+fn unmerged_paths(worktree_path: &str) -> Result<Vec<(String, ConflictStatus)>, String> {
+    let output = silent_command("git")
+        .args(["status", "--porcelain=v1", "-z", "--untracked-files=no"])
+        .current_dir(worktree_path)
+        .output()
+        .map_err(|e| format!("Failed to run git status: {e}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to get git status: {stderr}"));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter_map(|entry| {
+            let code = entry.get(..2)?;
+            let path = entry.get(3..)?;
+            ConflictStatus::from_code(code).map(|status| (path.to_string(), status))
+        })
+        .collect())
+}
+
+/// Read the base/ours/theirs index stages of an unmerged file.
+///
+/// `git checkout-index --temp` writes each stage to a temporary file in the
+/// worktree; they are read and removed right away. A stage is `None` when the
+/// file doesn't exist on that side.
+fn read_stages(worktree_path: &str, path: &str) -> Result<[Option<Vec<u8>>; 3], String> {
+    let output = silent_command("git")
+        .args(["checkout-index", "--stage=all", "--temp", "--", path])
+        .current_dir(worktree_path)
+        .output()
+        .map_err(|e| format!("Failed to run git checkout-index: {e}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to read conflict stages: {stderr}"));
+    }
+
+    // Output: "<stage1> <stage2> <stage3>\t<path>", "." for a missing stage
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let temps = stdout.split('\t').next().unwrap_or_default();
+    let mut stages: [Option<Vec<u8>>; 3] = [None, None, None];
+    for (slot, temp) in stages.iter_mut().zip(temps.split(' ')) {
+        if temp == "." || temp.is_empty() {
+            continue;
+        }
+        let temp_path = Path::new(worktree_path).join(temp);
+        *slot = std::fs::read(&temp_path).ok();
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    Ok(stages)
+}
+
+/// Regenerate diff3-style hunks from the index stages to recover base content
+/// when the working file was written without it (the default `merge` style)
+fn diff3_hunks(worktree_path: &str, stages: &[Option<String>; 3]) -> Option<Vec<ConflictHunk>> {
+    let [Some(base), Some(ours), Some(theirs)] = stages else {
+        return None;
+    };
+    let dir = std::env::temp_dir().join(format!("jean-conflict-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).ok()?;
+    let write = |name: &str, content: &str| -> Option<PathBuf> {
+        let path = dir.join(name);
+        std::fs::write(&path, content).ok()?;
+        Some(path)
+    };
+    let output = (|| {
+        let (ours, base, theirs) = (
+            write("ours", ours)?,
+            write("base", base)?,
+            write("theirs", theirs)?,
+        );
+        silent_command("git")
+            .arg("merge-file")
+            .args(["-p", "--diff3"])
+            .arg(&ours)
+            .arg(&base)
+            .arg(&theirs)
+            .current_dir(worktree_path)
+            .output()
+            .ok()
+    })();
+    let _ = std::fs::remove_dir_all(&dir);
+    let output = output?;
+    // Exit code is the number of conflicts; negative (signal/None) means failure
+    output.status.code().filter(|c| *c >= 0)?;
+
+    let merged = String::from_utf8_lossy(&output.stdout);
+    Some(locate_hunks(&merged).into_iter().map(|h| h.hunk).collect())
+}
+
+fn decode(bytes: Option<Vec<u8>>) -> (Option<String>, bool) {
+    match bytes {
+        Some(bytes) if bytes.contains(&0) => (None, true),
+        Some(bytes) => (Some(String::from_utf8_lossy(&bytes).into_owned()), false),
+        None => (None, false),
+    }
+}
+
+/// Build the structured conflict for one unmerged file
+fn file_conflict(
+    worktree_path: &str,
+    path: &str,
+    status: ConflictStatus,
+) -> Result<FileConflict, String> {
+    let [base, ours, theirs] = read_stages(worktree_path, path)?;
+    let (base, base_binary) = decode(base);
+    let (ours, ours_binary) = decode(ours);
+    let (theirs, theirs_binary) = decode(theirs);
+    let binary = base_binary || ours_binary || theirs_binary;
+
+    let working = if binary {
+        None
+    } else {
+        std::fs::read_to_string(Path::new(worktree_path).join(path)).ok()
+    };
+    let located = working.as_deref().map(locate_hunks).unwrap_or_default();
+    let ours_label = located.iter().find_map(|h| h.ours_label.clone());
+    let theirs_label = located.iter().find_map(|h| h.theirs_label.clone());
+    let mut hunks: Vec<ConflictHunk> = located.into_iter().map(|h| h.hunk).collect();
+
+    if hunks.iter().any(|h| h.base.is_none()) {
+        let stages = [base.clone(), ours.clone(), theirs.clone()];
+        if let Some(regenerated) = diff3_hunks(worktree_path, &stages) {
+            // Only trust the regenerated hunks when they line up one-to-one
+            let matches = regenerated.len() == hunks.len()
+                && regenerated
+                    .iter()
+                    .zip(&hunks)
+                    .all(|(r, h)| r.ours == h.ours && r.theirs == h.theirs);
+            if matches {
+                for (hunk, regenerated) in hunks.iter_mut().zip(regenerated) {
+                    hunk.base = hunk.base.take().or(regenerated.base);
+                }
+            }
+        }
+    }
+
+    Ok(FileConflict {
+        path: path.to_string(),
+        status,
+        binary,
+        ours_label,
+        theirs_label,
+        base,
+        ours,
+        theirs,
+        hunks,
+    })
+}
+
+/// Collect the conflict state of a worktree
+pub fn conflict_state(worktree_path: &str) -> Result<ConflictState, String> {
+    let files = unmerged_paths(worktree_path)?
+        .into_iter()
+        .map(|(path, status)| file_conflict(worktree_path, &path, status))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ConflictState {
+        operation: detect_operation(worktree_path),
+        files,
+    })
+}
+
+fn worktree_path(app: &AppHandle, worktree_id: &str) -> Result<String, String> {
+    let data = load_projects_data(app)?;
+    data.find_worktree(worktree_id)
+        .map(|w| w.path.clone())
+        .ok_or_else(|| format!("Worktree not found: {worktree_id}"))
+}
+
+/// Reject paths that would escape the worktree
+fn checked_path(worktree_path: &str, path: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(path);
+    if relative.is_absolute()
+        || relative
+            .components()
+            .any(|c| matches!(c, std::path::Component::ParentDir))
+    {
+        return Err(format!("Invalid conflict path: {path}"));
+    }
+    Ok(Path::new(worktree_path).join(relative))
+}
+
+fn run_git(worktree_path: &str, args: &[&str]) -> Result<String, String> {
+    let output = silent_command("git")
+        .args(args)
+        .current_dir(worktree_path)
+        // Accept default messages instead of opening an editor
+        .env("GIT_EDITOR", "true")
+        .output()
+        .map_err(|e| format!("Failed to run git {}: {e}", args[0]))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        return Err(format!("git {} failed: {}{}", args[0], stdout, stderr)
+            .trim()
+            .to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Get structured per-file conflicts for a worktree
+#[tauri::command]
+pub async fn get_conflict_state(
+    app: AppHandle,
+    worktree_id: String,
+) -> Result<ConflictState, String> {
+    log::trace!("Getting conflict state for worktree: {worktree_id}");
+    let path = worktree_path(&app, &worktree_id)?;
+    conflict_state(&path)
+}
+
+/// Resolve one conflict hunk in a file and return the file's updated conflict
+#[tauri::command]
+pub async fn resolve_conflict_hunk(
+    app: AppHandle,
+    worktree_id: String,
+    path: String,
+    hunk_index: usize,
+    resolution: HunkResolution,
+) -> Result<FileConflict, String> {
+    log::trace!("Resolving hunk {hunk_index} of {path} in worktree: {worktree_id}");
+    let wt_path = worktree_path(&app, &worktree_id)?;
+    let file_path = checked_path(&wt_path, &path)?;
+
+    let content =
+        std::fs::read_to_string(&file_path).map_err(|e| format!("Failed to read {path}: {e}"))?;
+    let resolved = apply_resolution(&content, hunk_index, &resolution)?;
+    std::fs::write(&file_path, resolved).map_err(|e| format!("Failed to write {path}: {e}"))?;
+
+    let status = unmerged_paths(&wt_path)?
+        .into_iter()
+        .find(|(p, _)| *p == path)
+        .map(|(_, status)| status)
+        .unwrap_or(ConflictStatus::BothModified);
+    file_conflict(&wt_path, &path, status)
+}
+
+/// Mark a conflicted file as resolved.
+///
+/// With a `side`, the whole file is taken from that side (deleting it when the
+/// side deleted it). Without one, the working file is staged as is, which
+/// fails while it still contains conflict markers.
+#[tauri::command]
+pub async fn mark_conflict_resolved(
+    app: AppHandle,
+    worktree_id: String,
+    path: String,
+    side: Option<ConflictSide>,
+) -> Result<ConflictState, String> {
+    log::trace!("Marking {path} resolved in worktree: {worktree_id} (side: {side:?})");
+    let wt_path = worktree_path(&app, &worktree_id)?;
+    let file_path = checked_path(&wt_path, &path)?;
+
+    match side {
+        Some(side) => {
+            let [_, ours, theirs] = read_stages(&wt_path, &path)?;
+            let (flag, exists) = match side {
+                ConflictSide::Ours => ("--ours", ours.is_some()),
+                ConflictSide::Theirs => ("--theirs", theirs.is_some()),
+            };
+            if exists {
+                run_git(&wt_path, &["checkout", flag, "--", &path])?;
+                run_git(&wt_path, &["add", "--", &path])?;
+            } else {
+                run_git(&wt_path, &["rm", "--quiet", "--", &path])?;
+            }
+        }
+        None if file_path.exists() => {
+            if let Ok(content) = std::fs::read_to_string(&file_path) {
+                let remaining = locate_hunks(&content).len();
+                if remaining > 0 {
+                    return Err(format!(
+                        "{path} still has {remaining} unresolved conflict(s)"
+                    ));
+                }
+            }
+            run_git(&wt_path, &["add", "--", &path])?;
+        }
+        None => {
+            run_git(&wt_path, &["rm", "--quiet", "--cached", "--", &path])?;
+        }
+    }
+
+    conflict_state(&wt_path)
+}
+
+/// Continue the merge, rebase, cherry-pick or revert once all files are resolved.
+///
+/// A rebase can stop again on the next commit; the returned state then holds
+/// the new conflicts.
+#[tauri::command]
+pub async fn continue_conflict_operation(
+    app: AppHandle,
+    worktree_id: String,
+) -> Result<ConflictState, String> {
+    log::trace!("Continuing conflicted operation in worktree: {worktree_id}");
+    let wt_path = worktree_path(&app, &worktree_id)?;
+
+    let operation =
+        detect_operation(&wt_path).ok_or_else(|| "No merge or rebase in progress".to_string())?;
+    let unresolved = unmerged_paths(&wt_path)?.len();
+    if unresolved > 0 {
+        return Err(format!(
+            "{unresolved} file(s) still have conflicts. Mark them resolved first."
+        ));
+    }
+
+    let result = tokio::task::spawn_blocking({
+        let wt_path = wt_path.clone();
+        move || match operation {
+            ConflictOperation::Merge => run_git(&wt_path, &["commit", "--no-edit"]),
+            other => run_git(&wt_path, &[other.command(), "--continue"]),
+        }
+    })
+    .await
+    .map_err(|e| format!("Continue task failed: {e}"))?;
+
+    super::git_log::cleanup_rebase_plan(&wt_path);
+    let state = conflict_state(&wt_path)?;
+    match result {
+        Ok(_) => Ok(state),
+        // Stopped on the next set of conflicts
+        Err(_) if !state.files.is_empty() => Ok(state),
+        Err(e) => Err(e),
+    }
+}
+
+/// Abort the merge, rebase, cherry-pick or revert in progress
+#[tauri::command]
+pub async fn abort_conflict_operation(
+    app: AppHandle,
+    worktree_id: String,
+) -> Result<ConflictState, String> {
+    log::trace!("Aborting conflicted operation in worktree: {worktree_id}");
+    let wt_path = worktree_path(&app, &worktree_id)?;
+
+    let operation =
+        detect_operation(&wt_path).ok_or_else(|| "No merge or rebase in progress".to_string())?;
+    run_git(&wt_path, &[operation.command(), "--abort"])?;
+    super::git_log::cleanup_rebase_plan(&wt_path);
+    conflict_state(&wt_path)
+}
+
+/// JSON schema for AI conflict hunk suggestions
+const CONFLICT_SUGGESTION_SCHEMA: &str = r#"{"type":"object","properties":{"content":{"type":"string","description":"The resolved code that replaces the whole conflict region, without conflict markers"},"explanation":{"type":"string","description":"One or two sentences on how both sides were combined"}},"required":["content","explanation"],"additionalProperties":false}"#;
+
+/// Lines of surrounding file content given to the AI for context
+const SUGGESTION_CONTEXT_LINES: usize = 20;
+
+/// Ask the AI for a resolution of one conflict hunk.
+///
+/// The suggestion is not applied; pass its content back to
+/// `resolve_conflict_hunk` as a manual resolution to accept it.
+#[tauri::command]
+pub async fn suggest_conflict_resolution(
+    app: AppHandle,
+    worktree_id: String,
+    path: String,
+    hunk_index: usize,
+    model: Option<String>,
+    custom_profile_name: Option<String>,
+    reasoning_effort: Option<String>,
+) -> Result<ConflictSuggestion, String> {
+    log::trace!("Suggesting resolution for hunk {hunk_index} of {path}");
+    let wt_path = worktree_path(&app, &worktree_id)?;
+    let file_path = checked_path(&wt_path, &path)?;
+    let content =
+        std::fs::read_to_string(&file_path).map_err(|e| format!("Failed to read {path}: {e}"))?;
+
+    let status = unmerged_paths(&wt_path)?
+        .into_iter()
+        .find(|(p, _)| *p == path)
+        .map(|(_, status)| status)
+        .unwrap_or(ConflictStatus::BothModified);
+    let conflict = file_conflict(&wt_path, &path, status)?;
+    let hunk = conflict
+        .hunks
+        .get(hunk_index)
+        .ok_or_else(|| format!("Conflict hunk {hunk_index} not found"))?;
+    let prompt = build_suggestion_prompt(&conflict, hunk, &content);
+
+    let backend = crate::get_preferences_path(&app)
+        .ok()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str::<crate::AppPreferences>(&c).ok())
+        .and_then(|p| p.magic_prompt_backends.resolve_conflicts_backend);
+
+    tokio::task::spawn_blocking(move || {
+        let json = super::commands::run_structured_prompt(
+            &app,
+            &prompt,
+            CONFLICT_SUGGESTION_SCHEMA,
+            model.as_deref(),
+            custom_profile_name.as_deref(),
+            Some(Path::new(&wt_path)),
+            Some(&worktree_id),
+            backend.as_deref(),
+            reasoning_effort.as_deref(),
+        )?;
+        serde_json::from_str::<ConflictSuggestion>(&json)
+            .map_err(|e| format!("Failed to parse conflict suggestion: {e}"))
+    })
+    .await
+    .map_err(|e| format!("Suggestion task failed: {e}"))?
+}
+
+fn build_suggestion_prompt(conflict: &FileConflict, hunk: &ConflictHunk, content: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let before_start = hunk.start_line.saturating_sub(1 + SUGGESTION_CONTEXT_LINES);
+    let before = lines[before_start..hunk.start_line.saturating_sub(1)].join("\n");
+    let after_end = (hunk.end_line + SUGGESTION_CONTEXT_LINES).min(lines.len());
+    let after = lines[hunk.end_line.min(lines.len())..after_end].join("\n");
+
+    let ours_label = conflict.ours_label.as_deref().unwrap_or("ours");
+    let theirs_label = conflict.theirs_label.as_deref().unwrap_or("theirs");
+    let base = hunk
+        .base
+        .as_deref()
+        .unwrap_or("(unknown: both sides added this code)");
+
+    format!(
+        "Resolve this git conflict in `{path}`. Combine the intent of both sides; \
+         don't drop changes from either unless they contradict. Return only the code \
+         that replaces the conflict region.\n\n\
+         ## Code before the conflict\n```\n{before}\n```\n\n\
+         ## Ours ({ours_label})\n```\n{ours}```\n\n\
+         ## Base (common ancestor)\n```\n{base}```\n\n\
+         ## Theirs ({theirs_label})\n```\n{theirs}```\n\n\
+         ## Code after the conflict\n```\n{after}\n```\n",
+        path = conflict.path,
+        ours = hunk.ours,
+        theirs = hunk.theirs,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MERGE_STYLE: &str = "a\n<<<<<<< HEAD\nours 1\nours 2\n=======\ntheirs\n>>>>>>> feature\nb\n<<<<<<< HEAD\nx\n=======\ny\n>>>>>>> feature\n";
+
+    #[test]
+    fn test_locate_hunks_merge_style() {
+        let hunks = locate_hunks(MERGE_STYLE);
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunks[0].hunk.ours, "ours 1\nours 2\n");
+        assert_eq!(hunks[0].hunk.theirs, "theirs\n");
+        assert_eq!(hunks[0].hunk.base, None);
+        assert_eq!((hunks[0].hunk.start_line, hunks[0].hunk.end_line), (2, 7));
+        assert_eq!(hunks[0].ours_label.as_deref(), Some("HEAD"));
+        assert_eq!(hunks[0].theirs_label.as_deref(), Some("feature"));
+        assert_eq!(hunks[1].hunk.index, 1);
+        assert_eq!(hunks[1].hunk.start_line, 9);
+    }
+
+    #[test]
+    fn test_locate_hunks_diff3_style_and_crlf() {
+        let content =
+            "<<<<<<< ours\r\nnew\r\n||||||| base\r\nold\r\n=======\r\nother\r\n>>>>>>> theirs\r\n";
+        let hunks = locate_hunks(content);
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].hunk.ours, "new\r\n");
+        assert_eq!(hunks[0].hunk.base.as_deref(), Some("old\r\n"));
+        assert_eq!(hunks[0].hunk.theirs, "other\r\n");
+    }
+
+    #[test]
+    fn test_locate_hunks_ignores_unterminated_and_lookalikes() {
+        assert!(locate_hunks("<<<<<<< HEAD\nours\n=======\ntheirs\n").is_empty());
+        assert!(locate_hunks("<<<<<<<<< not a marker\n========\n").is_empty());
+    }
+
+    #[test]
+    fn test_apply_resolution() {
+        let ours = apply_resolution(MERGE_STYLE, 0, &HunkResolution::Ours).unwrap();
+        assert!(ours.starts_with("a\nours 1\nours 2\nb\n<<<<<<< HEAD\n"));
+        assert_eq!(locate_hunks(&ours).len(), 1);
+
+        let both = apply_resolution(MERGE_STYLE, 1, &HunkResolution::Both).unwrap();
+        assert!(both.ends_with("b\nx\ny\n"));
+
+        let manual = apply_resolution(
+            MERGE_STYLE,
+            0,
+            &HunkResolution::Manual {
+                content: "merged".to_string(),
+            },
+        )
+        .unwrap();
+        assert!(manual.starts_with("a\nmerged\nb\n"));
+
+        assert!(apply_resolution(MERGE_STYLE, 0, &HunkResolution::Base).is_err());
+        assert!(apply_resolution(MERGE_STYLE, 5, &HunkResolution::Ours).is_err());
+    }
+}
@@ -0,0 +1,491 @@
+//! Container-backed worktrees.
+//!
+//! A project can declare a container in `jean.json`:
+//!
+//! ```json
+//! { "container": { "image": "node:22", "run_args": ["-p", "3000:3000"] } }
+//! ```
+//!
+//! or `{ "container": { "dockerfile": "Dockerfile.dev" } }`. Jean then keeps one
+//! long-lived container per worktree (Docker or Podman, local daemon only) with
+//! the worktree and its git dir bind-mounted at the same paths. Setup/teardown
+//! scripts and integrated terminal shells are executed inside it with `exec`,
+//! and so are Claude runs when `agent` is set (Linux hosts only: the host's
+//! CLI is mounted into the container). Containers are found by label, started
+//! lazily on first use and removed when the worktree is archived or deleted.
+//!
+//! The container settings decide what runs on the host (`runtime`), what the
+//! container can reach (`run_args` such as `--privileged` or `-v /:/host`) and
+//! whether the agent runs inside it, so they are only read from the project
+//! root's `jean.json`, never from a worktree's copy the agent can edit. A
+//! `dockerfile` is resolved against the project root too.
+//!
+//! Codex and OpenCode run through servers shared by all worktrees, so they stay
+//! on the host.
+
+use std::path::{Path, PathBuf};
+use std::process::Output;
+
+use sha2::{Digest, Sha256};
+use tauri::AppHandle;
+
+use super::git::read_jean_config;
+use super::storage::load_projects_data;
+use super::types::ContainerConfig;
+use crate::platform::silent_command;
+
+/// Label identifying the worktree a container belongs to (value: worktree path)
+const WORKTREE_LABEL: &str = "dev.jean.worktree";
+
+/// A running container for a worktree
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorktreeContainer {
+    /// Container runtime binary (docker or podman)
+    pub runtime: PathBuf,
+    /// Container name
+    pub name: String,
+    /// Whether Claude sessions should run inside the container
+    pub runs_agent: bool,
+}
+
+impl WorktreeContainer {
+    /// Arguments for `exec` into the container, up to and including the container name.
+    ///
+    /// `env_keys` are passed by name only, so values are taken from the
+    /// environment of the runtime client instead of appearing on the command line.
+    pub fn exec_args(&self, workdir: &str, tty: bool, env_keys: &[&str]) -> Vec<String> {
+        let mut args = vec![
+            self.runtime.to_string_lossy().to_string(),
+            "exec".to_string(),
+            "-i".to_string(),
+        ];
+        if tty {
+            args.push("-t".to_string());
+        }
+        args.push("-w".to_string());
+        args.push(workdir.to_string());
+        for key in env_keys {
+            args.push("-e".to_string());
+            args.push(key.to_string());
+        }
+        args.push(self.name.clone());
+        args
+    }
+
+    /// Command prefix for running an agent CLI in the container.
+    ///
+    /// The CLI's PID inside the container is written to a per-session file so
+    /// `kill_agent` can stop it (killing the `exec` client on the host doesn't).
+    pub fn agent_command_prefix(
+        &self,
+        workdir: &str,
+        session_id: &str,
+        env_keys: &[&str],
+    ) -> Vec<String> {
+        let mut keys = vec!["HOME"];
+        keys.extend_from_slice(env_keys);
+        self.tracked_command_prefix(workdir, &agent_pid_file(session_id), &keys)
+    }
+
+    /// Command prefix that records the command's PID inside the container in
+    /// `pid_file`, so `kill_tracked` can stop it later.
+    pub fn tracked_command_prefix(
+        &self,
+        workdir: &str,
+        pid_file: &str,
+        env_keys: &[&str],
+    ) -> Vec<String> {
+        let mut args = self.exec_args(workdir, false, env_keys);
+        args.push("sh".to_string());
+        args.push("-c".to_string());
+        args.push(format!("echo $$ > {pid_file}; exec \"$@\""));
+        args.push("sh".to_string());
+        args
+    }
+}
+
+/// Container settings for a worktree, from the project root's jean.json (see module docs)
+pub fn config_for_worktree(app: &AppHandle, worktree_path: &str) -> Option<ContainerConfig> {
+    project_root_for(app, worktree_path)
+        .and_then(|root| read_jean_config(&root))
+        .and_then(|c| c.container)
+}
+
+/// Make sure the worktree's container exists and is running.
+///
+/// Returns `None` when the project doesn't declare a container.
+pub fn ensure_container(
+    app: &AppHandle,
+    worktree_path: &str,
+) -> Result<Option<WorktreeContainer>, String> {
+    let Some(project_root) = project_root_for(app, worktree_path) else {
+        return Ok(None);
+    };
+    let Some(config) = read_jean_config(&project_root).and_then(|c| c.container) else {
+        return Ok(None);
+    };
+    let runtime = resolve_runtime(config.runtime.as_deref())?;
+    let runs_agent = config.agent.unwrap_or(false);
+    if runs_agent && !cfg!(target_os = "linux") {
+        return Err(
+            "jean.json `container.agent` needs a Linux host (the host's Claude CLI is mounted \
+             into the container)"
+                .to_string(),
+        );
+    }
+
+    if let Some((name, running)) = find_container(&runtime, worktree_path) {
+        if !running {
+            log::debug!("Starting stopped container {name} for {worktree_path}");
+            check_output(
+                silent_command(&runtime).args(["start", &name]).output(),
+                "start container",
+            )?;
+        }
+        return Ok(Some(WorktreeContainer {
+            runtime,
+            name,
+            runs_agent,
+        }));
+    }
+
+    let name = container_name(worktree_path);
+    let image = resolve_image(&runtime, &config, &project_root, worktree_path, &name)?;
+    let mut args: Vec<String> = vec![
+        "run".into(),
+        "-d".into(),
+        "--init".into(),
+        "--name".into(),
+        name.clone(),
+        "--label".into(),
+        format!("{WORKTREE_LABEL}={worktree_path}"),
+        "-w".into(),
+        worktree_path.to_string(),
+    ];
+    for (path, read_only) in bind_mounts(app, worktree_path, runs_agent) {
+        let path = path.to_string_lossy();
+        args.push("-v".into());
+        args.push(if read_only {
+            format!("{path}:{path}:ro")
+        } else {
+            format!("{path}:{path}")
+        });
+    }
+    args.extend(config.run_args);
+    args.extend([image, "sleep".into(), "infinity".into()]);
+
+    log::info!("Creating container {name} for {worktree_path}");
+    check_output(
+        silent_command(&runtime).args(&args).output(),
+        "create container",
+    )?;
+
+    Ok(Some(WorktreeContainer {
+        runtime,
+        name,
+        runs_agent,
+    }))
+}
+
+/// Remove the worktree's container, if any. Errors are logged, not returned,
+/// so archive/delete never fail because of a container.
+pub fn remove_container(app: &AppHandle, worktree_path: &str) {
+    let preferred = config_for_worktree(app, worktree_path).and_then(|c| c.runtime);
+    let runtimes: Vec<PathBuf> = match preferred {
+        Some(r) => which::which(r).into_iter().collect(),
+        None => ["docker", "podman"]
+            .iter()
+            .filter_map(|r| which::which(r).ok())
+            .collect(),
+    };
+
+    for runtime in runtimes {
+        if let Some((name, _)) = find_container(&runtime, worktree_path) {
+            log::info!("Removing container {name} for {worktree_path}");
+            if let Err(e) = check_output(
+                silent_command(&runtime).args(["rm", "-f", &name]).output(),
+                "remove container",
+            ) {
+                log::warn!("{e}");
+            }
+        }
+    }
+}
+
+/// Stop a Claude run inside the worktree's container (see `agent_command_prefix`)
+pub fn kill_agent(app: &AppHandle, worktree_path: &str, session_id: &str) {
+    kill_tracked(app, worktree_path, &agent_pid_file(session_id));
+}
+
+/// Stop a process started with `tracked_command_prefix` inside the worktree's container
+pub fn kill_tracked(app: &AppHandle, worktree_path: &str, pid_file: &str) {
+    let Some(config) = config_for_worktree(app, worktree_path) else {
+        return;
+    };
+    let Ok(runtime) = resolve_runtime(config.runtime.as_deref()) else {
+        return;
+    };
+    let Some((name, true)) = find_container(&runtime, worktree_path) else {
+        return;
+    };
+    let script = format!("[ -f {pid_file} ] && kill -TERM \"$(cat {pid_file})\"; rm -f {pid_file}");
+    let _ = silent_command(&runtime)
+        .args(["exec", &name, "sh", "-c", &script])
+        .output();
+}
+
+/// Project root for a worktree path (the path itself for base sessions)
+fn project_root_for(app: &AppHandle, worktree_path: &str) -> Option<String> {
+    let data = load_projects_data(app).ok()?;
+    if data.projects.iter().any(|p| p.path == worktree_path) {
+        return Some(worktree_path.to_string());
+    }
+    let project_id = data
+        .worktrees
+        .iter()
+        .find(|w| w.path == worktree_path)
+        .map(|w| w.project_id.clone())?;
+    data.find_project(&project_id).map(|p| p.path.clone())
+}
+
+/// Locate the container runtime binary
+fn resolve_runtime(preferred: Option<&str>) -> Result<PathBuf, String> {
+    match preferred {
+        Some(runtime) => {
+            if !matches!(runtime, "docker" | "podman") {
+                return Err(format!(
+                    "Unsupported container runtime in jean.json: {runtime} (use docker or podman)"
+                ));
+            }
+            which::which(runtime)
+                .map_err(|_| format!("Container runtime '{runtime}' is not installed"))
+        }
+        None => which::which("docker")
+            .or_else(|_| which::which("podman"))
+            .map_err(|_| {
+                "jean.json declares a container but neither docker nor podman is installed"
+                    .to_string()
+            }),
+    }
+}
+
+/// Find the container labelled with this worktree path: (name, is_running)
+fn find_container(runtime: &Path, worktree_path: &str) -> Option<(String, bool)> {
+    let output = silent_command(runtime)
+        .args([
+            "ps",
+            "-a",
+            "--filter",
+            &format!("label={WORKTREE_LABEL}={worktree_path}"),
+            "--format",
+            "{{.Names}}\t{{.State}}",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(parse_ps_line)
+}
+
+fn parse_ps_line(line: &str) -> Option<(String, bool)> {
+    let (name, state) = line.trim().split_once('\t')?;
+    if name.is_empty() {
+        return None;
+    }
+    Some((name.to_string(), state.eq_ignore_ascii_case("running")))
+}
+
+/// Image to run: the configured image, or one built from the Dockerfile
+fn resolve_image(
+    runtime: &Path,
+    config: &ContainerConfig,
+    project_root: &str,
+    worktree_path: &str,
+    name: &str,
+) -> Result<String, String> {
+    if let Some(dockerfile) = config.dockerfile.as_deref() {
+        let tag = format!("{name}:latest");
+        let dockerfile_path = Path::new(project_root).join(dockerfile);
+        log::info!(
+            "Building container image {tag} from {}",
+            dockerfile_path.display()
+        );
+        check_output(
+            silent_command(runtime)
+                .arg("build")
+                .arg("-t")
+                .arg(&tag)
+                .arg("-f")
+                .arg(&dockerfile_path)
+                .arg(worktree_path)
+                .output(),
+            "build container image",
+        )?;
+        return Ok(tag);
+    }
+    config
+        .image
+        .clone()
+        .filter(|i| !i.trim().is_empty())
+        .ok_or_else(|| "jean.json `container` needs an `image` or a `dockerfile`".to_string())
+}
+
+/// Paths bind-mounted into the container at the same location: (path, read_only)
+fn bind_mounts(app: &AppHandle, worktree_path: &str, runs_agent: bool) -> Vec<(PathBuf, bool)> {
+    let mut mounts = vec![(PathBuf::from(worktree_path), false)];
+
+    // Worktrees point at the main repository's git dir
+    if let Ok(output) = silent_command("git")
+        .args(["rev-parse", "--path-format=absolute", "--git-common-dir"])
+        .current_dir(worktree_path)
+        .output()
+    {
+        let git_dir = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if output.status.success() && !git_dir.starts_with(worktree_path) {
+            mounts.push((PathBuf::from(git_dir), false));
+        }
+    }
+
+    // The Claude CLI and its state, so sessions can run inside
+    if runs_agent {
+        if let Some(cli_dir) = crate::claude_cli::resolve_cli_binary(app).parent() {
+            mounts.push((cli_dir.to_path_buf(), true));
+        }
+        if let Some(home) = dirs::home_dir() {
+            mounts.push((home.join(".claude"), false));
+            mounts.push((home.join(".claude.json"), false));
+        }
+    }
+
+    mounts.retain(|(path, _)| path.exists());
+    mounts
+}
+
+/// Container name: readable worktree dir name plus a hash of the full path
+fn container_name(worktree_path: &str) -> String {
+    let dir_name: String = Path::new(worktree_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .take(40)
+        .collect();
+    let hash = Sha256::digest(worktree_path.as_bytes());
+    let short: String = hash.iter().take(4).map(|b| format!("{b:02x}")).collect();
+    if dir_name.is_empty() {
+        format!("jean-{short}")
+    } else {
+        format!("jean-{dir_name}-{short}")
+    }
+}
+
+/// PID file of a Claude run inside the container
+fn agent_pid_file(session_id: &str) -> String {
+    format!("/tmp/jean-agent-{}.pid", pid_file_safe(session_id))
+}
+
+/// PID file of a jean.json script (setup, teardown, hook) inside the container
+pub fn script_pid_file(kind: &str) -> String {
+    format!("/tmp/jean-script-{}.pid", pid_file_safe(kind))
+}
+
+/// PID file of a jean.json service inside the container
+pub fn service_pid_file(service_name: &str) -> String {
+    format!("/tmp/jean-service-{}.pid", pid_file_safe(service_name))
+}
+
+fn pid_file_safe(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect()
+}
+
+fn check_output(output: std::io::Result<Output>, action: &str) -> Result<(), String> {
+    let output = output.map_err(|e| format!("Failed to {action}: {e}"))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Failed to {action}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn container() -> WorktreeContainer {
+        WorktreeContainer {
+            runtime: PathBuf::from("/usr/bin/docker"),
+            name: "jean-feature-1234abcd".to_string(),
+            runs_agent: true,
+        }
+    }
+
+    #[test]
+    fn test_container_name_is_sanitized_and_stable() {
+        let a = container_name("/home/me/jean/app/Fuzzy Otter");
+        let b = container_name("/home/me/jean/app/Fuzzy Otter");
+        let c = container_name("/home/me/jean/other/Fuzzy Otter");
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(a.starts_with("jean-fuzzy-otter-"));
+        assert!(a
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_'));
+    }
+
+    #[test]
+    fn test_exec_args_pass_env_by_name() {
+        let args = container().exec_args("/wt", true, &["API_KEY"]);
+        assert_eq!(
+            args,
+            vec![
+                "/usr/bin/docker",
+                "exec",
+                "-i",
+                "-t",
+                "-w",
+                "/wt",
+                "-e",
+                "API_KEY",
+                "jean-feature-1234abcd"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_agent_prefix_records_pid() {
+        let args = container().agent_command_prefix("/wt", "abc-123", &[]);
+        assert!(args.contains(&"HOME".to_string()));
+        let script = &args[args.len() - 2];
+        assert!(script.contains("/tmp/jean-agent-abc-123.pid"));
+        assert!(script.ends_with("exec \"$@\""));
+        assert_eq!(args.last().unwrap(), "sh");
+    }
+
+    #[test]
+    fn test_parse_ps_line() {
+        assert_eq!(
+            parse_ps_line("jean-x-1\trunning"),
+            Some(("jean-x-1".to_string(), true))
+        );
+        assert_eq!(
+            parse_ps_line("jean-x-1\texited"),
+            Some(("jean-x-1".to_string(), false))
+        );
+        assert_eq!(parse_ps_line(""), None);
+    }
+}
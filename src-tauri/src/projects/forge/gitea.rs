@@ -0,0 +1,855 @@
+//! Gitea and Forgejo (including Codeberg) over the REST API v1.
+//!
+//! Gitea has no pipelines API shared by all instances, so CI shows up as the
+//! commit statuses reported on the branch head (by Gitea/Forgejo Actions,
+//! Woodpecker, Drone, ...).
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use super::{
+    encode_component, CreatedMergeRequest, Forge, ForgeKind, ForgeRemote, HttpTransport,
+    NewMergeRequest,
+};
+use crate::projects::github_actions::{WorkflowRun, WorkflowRunsResult};
+use crate::projects::github_issues::{
+    GitHubAuthor, GitHubComment, GitHubIssue, GitHubIssueDetail, GitHubIssueListResult,
+    GitHubLabel, GitHubPullRequest, GitHubPullRequestDetail, GitHubReview,
+};
+use crate::projects::pr_status::{
+    compute_display_status, CheckStatus, MergeStateStatus, MergeableStatus, PrState, PrStatus,
+    ReviewDecision,
+};
+
+/// Diffs larger than this are cut off (same limit as `gh pr diff`)
+const MAX_DIFF_SIZE: usize = 100_000;
+
+/// Title prefixes Gitea treats as work in progress (its default `WORK_IN_PROGRESS_PREFIXES`)
+const WIP_PREFIXES: [&str; 2] = ["WIP:", "[WIP]"];
+
+#[derive(Debug, Deserialize)]
+struct TeaConfig {
+    #[serde(default)]
+    logins: Vec<TeaLogin>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TeaLogin {
+    url: String,
+    token: String,
+}
+
+/// Token from `FORGEJO_TOKEN` / `GITEA_TOKEN` (only for the instance named
+/// by `FORGEJO_HOST` / `GITEA_HOST`), or from the `tea` CLI login for the
+/// instance
+pub fn token_from_environment(base_url: &str) -> Option<String> {
+    if let Some(token) = env_token(base_url, |var| std::env::var(var).ok()) {
+        return Some(token);
+    }
+    let path = dirs::config_dir()?.join("tea").join("config.yml");
+    let config: TeaConfig = serde_yaml::from_str(&std::fs::read_to_string(path).ok()?).ok()?;
+    config
+        .logins
+        .into_iter()
+        .find(|login| login.url.trim_end_matches('/') == base_url)
+        .map(|login| login.token)
+        .filter(|token| !token.is_empty())
+}
+
+/// `FORGEJO_TOKEN` / `GITEA_TOKEN` when its `*_HOST` variable names the
+/// instance at `base_url`. There's no default instance, so without it (or
+/// for any other host) the token is never sent.
+fn env_token(base_url: &str, var: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let instance = normalize_instance(base_url);
+    [
+        ("FORGEJO_TOKEN", "FORGEJO_HOST"),
+        ("GITEA_TOKEN", "GITEA_HOST"),
+    ]
+    .into_iter()
+    .find_map(|(token_var, host_var)| {
+        let token = var(token_var).filter(|t| !t.trim().is_empty())?;
+        let host = var(host_var).map(|h| normalize_instance(&h))?;
+        (host == instance).then(|| token.trim().to_string())
+    })
+}
+
+/// `host[:port][/path]` of an instance URL or bare host name, lowercased
+fn normalize_instance(url: &str) -> String {
+    let url = url.trim();
+    url.strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url)
+        .trim_end_matches('/')
+        .to_lowercase()
+}
+
+// =============================================================================
+// Raw API types
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+struct GtUser {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GtLabel {
+    name: String,
+    #[serde(default)]
+    color: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GtIssue {
+    number: u32,
+    title: String,
+    #[serde(default)]
+    body: String,
+    state: String,
+    #[serde(default)]
+    labels: Vec<GtLabel>,
+    created_at: String,
+    user: GtUser,
+    #[serde(default)]
+    html_url: String,
+    /// Set when the issue is a pull request
+    #[serde(default)]
+    pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GtComment {
+    #[serde(default)]
+    body: String,
+    user: GtUser,
+    created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GtBranchRef {
+    #[serde(rename = "ref")]
+    ref_name: String,
+    #[serde(default)]
+    sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GtPullRequest {
+    number: u32,
+    title: String,
+    #[serde(default)]
+    body: String,
+    state: String,
+    head: GtBranchRef,
+    base: GtBranchRef,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    merged: bool,
+    #[serde(default)]
+    mergeable: bool,
+    created_at: String,
+    user: GtUser,
+    #[serde(default)]
+    labels: Vec<GtLabel>,
+    #[serde(default)]
+    html_url: String,
+    #[serde(default)]
+    requested_reviewers: Option<Vec<GtUser>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GtReview {
+    #[serde(default)]
+    body: String,
+    state: String,
+    user: Option<GtUser>,
+    submitted_at: Option<String>,
+    #[serde(default)]
+    stale: bool,
+    #[serde(default)]
+    dismissed: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GtCommitStatus {
+    id: u64,
+    status: String,
+    #[serde(default)]
+    context: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    target_url: String,
+    created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GtCombinedStatus {
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    statuses: Vec<GtCommitStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GtRepository {
+    default_branch: String,
+}
+
+// =============================================================================
+// Mapping
+// =============================================================================
+
+fn author(user: GtUser) -> GitHubAuthor {
+    GitHubAuthor { login: user.login }
+}
+
+fn labels(labels: Vec<GtLabel>) -> Vec<GitHubLabel> {
+    labels
+        .into_iter()
+        .map(|l| GitHubLabel {
+            name: l.name,
+            color: l.color.trim_start_matches('#').to_string(),
+        })
+        .collect()
+}
+
+fn body(body: String) -> Option<String> {
+    (!body.is_empty()).then_some(body)
+}
+
+/// GitHub-style state filter to Gitea's (merged PRs are closed ones)
+fn state_filter(state: &str) -> &'static str {
+    match state {
+        "closed" | "merged" => "closed",
+        "all" => "all",
+        _ => "open",
+    }
+}
+
+fn is_draft(pr: &GtPullRequest) -> bool {
+    pr.draft || WIP_PREFIXES.iter().any(|p| pr.title.starts_with(p))
+}
+
+fn issue(raw: GtIssue) -> GitHubIssue {
+    GitHubIssue {
+        number: raw.number,
+        title: raw.title,
+        body: body(raw.body),
+        state: raw.state.to_uppercase(),
+        labels: labels(raw.labels),
+        created_at: raw.created_at,
+        author: author(raw.user),
+    }
+}
+
+fn pull_request(raw: GtPullRequest) -> GitHubPullRequest {
+    let is_draft = is_draft(&raw);
+    GitHubPullRequest {
+        number: raw.number,
+        title: raw.title,
+        body: body(raw.body),
+        state: if raw.merged {
+            "MERGED".to_string()
+        } else {
+            raw.state.to_uppercase()
+        },
+        head_ref_name: raw.head.ref_name,
+        base_ref_name: raw.base.ref_name,
+        is_draft,
+        created_at: raw.created_at,
+        author: author(raw.user),
+        labels: labels(raw.labels),
+    }
+}
+
+fn comments(comments: Vec<GtComment>) -> Vec<GitHubComment> {
+    comments
+        .into_iter()
+        .map(|c| GitHubComment {
+            body: c.body,
+            author: author(c.user),
+            created_at: c.created_at,
+        })
+        .collect()
+}
+
+/// Gitea review state to GitHub's
+fn review_state(state: &str) -> &str {
+    match state {
+        "REQUEST_CHANGES" => "CHANGES_REQUESTED",
+        "COMMENT" => "COMMENTED",
+        other => other,
+    }
+}
+
+/// Decision from each reviewer's latest approving or blocking review
+fn review_decision(reviews: &[GtReview], review_requested: bool) -> (Option<ReviewDecision>, u32) {
+    let mut latest: HashMap<&str, &str> = HashMap::new();
+    for review in reviews {
+        if review.stale || review.dismissed {
+            continue;
+        }
+        if let ("APPROVED" | "REQUEST_CHANGES", Some(user)) = (review.state.as_str(), &review.user)
+        {
+            latest.insert(&user.login, &review.state);
+        }
+    }
+    let approved = latest.values().filter(|s| **s == "APPROVED").count() as u32;
+    let decision = if latest.values().any(|s| *s == "REQUEST_CHANGES") {
+        Some(ReviewDecision::ChangesRequested)
+    } else if approved > 0 {
+        Some(ReviewDecision::Approved)
+    } else if review_requested {
+        Some(ReviewDecision::ReviewRequired)
+    } else {
+        None
+    };
+    (decision, approved)
+}
+
+fn check_status(combined: &GtCombinedStatus) -> Option<CheckStatus> {
+    if combined.statuses.is_empty() {
+        return None;
+    }
+    Some(match combined.state.as_str() {
+        "success" | "warning" => CheckStatus::Success,
+        "failure" => CheckStatus::Failure,
+        "error" => CheckStatus::Error,
+        _ => CheckStatus::Pending,
+    })
+}
+
+/// Commit status to GitHub's (status, conclusion) pair
+fn run_status(status: &str) -> (&'static str, Option<&'static str>) {
+    match status {
+        "success" => ("completed", Some("success")),
+        "failure" | "error" => ("completed", Some("failure")),
+        "warning" => ("completed", Some("neutral")),
+        _ => ("in_progress", None),
+    }
+}
+
+/// Truncate a diff at a UTF-8 boundary near `MAX_DIFF_SIZE`
+fn truncate_diff(diff: String, number: u32) -> String {
+    if diff.len() <= MAX_DIFF_SIZE {
+        return diff;
+    }
+    let mut end = MAX_DIFF_SIZE;
+    while !diff.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!(
+        "{}...\n\n[Diff truncated at 100KB - {} bytes total. See pull request #{number} for the full diff.]",
+        &diff[..end],
+        diff.len()
+    )
+}
+
+// =============================================================================
+// Client
+// =============================================================================
+
+pub struct Gitea {
+    /// `{base_url}/api/v1/repos/{owner}/{repo}`
+    repo_api: String,
+    transport: Box<dyn HttpTransport>,
+}
+
+impl Gitea {
+    pub fn new(remote: &ForgeRemote, transport: Box<dyn HttpTransport>) -> Self {
+        Self {
+            repo_api: format!("{}/api/v1/repos/{}", remote.base_url, remote.path),
+            transport,
+        }
+    }
+
+    fn request(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<(String, Option<u32>), String> {
+        let url = format!("{}{path}", self.repo_api);
+        let response = self.transport.send(method, &url, body)?;
+        match response.status {
+            200..=299 => Ok((response.body, response.total)),
+            401 => Err(
+                "Gitea/Forgejo token is invalid or expired. Update it in project settings."
+                    .to_string(),
+            ),
+            403 => Err("Gitea/Forgejo token lacks permission for this repository".to_string()),
+            404 => Err(format!("Not found on Gitea/Forgejo: {path}")),
+            status => Err(format!(
+                "Gitea/Forgejo API error ({status}): {}",
+                response.body.trim()
+            )),
+        }
+    }
+
+    fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T, String> {
+        self.get_with_total(path).map(|(value, _)| value)
+    }
+
+    fn get_with_total<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<(T, Option<u32>), String> {
+        let (body, total) = self.request("GET", path, None)?;
+        let value = serde_json::from_str(&body)
+            .map_err(|e| format!("Failed to parse Gitea/Forgejo response: {e}"))?;
+        Ok((value, total))
+    }
+
+    fn comments(&self, number: u32) -> Result<Vec<GitHubComment>, String> {
+        let raw: Vec<GtComment> = self.get(&format!("/issues/{number}/comments"))?;
+        Ok(comments(raw))
+    }
+
+    fn pull(&self, number: u32) -> Result<GtPullRequest, String> {
+        self.get(&format!("/pulls/{number}")).map_err(|e| {
+            if e.starts_with("Not found") {
+                format!("PR #{number} not found")
+            } else {
+                e
+            }
+        })
+    }
+
+    fn combined_status(&self, git_ref: &str) -> Result<GtCombinedStatus, String> {
+        self.get(&format!("/commits/{}/status", encode_component(git_ref)))
+    }
+}
+
+impl Forge for Gitea {
+    fn kind(&self) -> ForgeKind {
+        ForgeKind::Gitea
+    }
+
+    fn list_issues(&self, state: &str) -> Result<GitHubIssueListResult, String> {
+        let (raw, total): (Vec<GtIssue>, _) = self.get_with_total(&format!(
+            "/issues?state={}&type=issues&limit=100",
+            state_filter(state)
+        ))?;
+        let issues: Vec<GitHubIssue> = raw
+            .into_iter()
+            .filter(|i| i.pull_request.is_none())
+            .map(issue)
+            .collect();
+        let total_count = total.unwrap_or(issues.len() as u32);
+        Ok(GitHubIssueListResult {
+            issues,
+            total_count,
+        })
+    }
+
+    fn search_issues(&self, query: &str) -> Result<Vec<GitHubIssue>, String> {
+        let raw: Vec<GtIssue> = self.get(&format!(
+            "/issues?state=all&type=issues&limit=30&q={}",
+            encode_component(query)
+        ))?;
+        Ok(raw
+            .into_iter()
+            .filter(|i| i.pull_request.is_none())
+            .map(issue)
+            .collect())
+    }
+
+    fn get_issue(&self, number: u32) -> Result<GitHubIssueDetail, String> {
+        let raw: GtIssue = self.get(&format!("/issues/{number}")).map_err(|e| {
+            if e.starts_with("Not found") {
+                format!("Issue #{number} not found")
+            } else {
+                e
+            }
+        })?;
+        let comments = self.comments(number)?;
+        let url = raw.html_url.clone();
+        let issue = issue(raw);
+        Ok(GitHubIssueDetail {
+            number: issue.number,
+            title: issue.title,
+            body: issue.body,
+            state: issue.state,
+            labels: issue.labels,
+            created_at: issue.created_at,
+            author: issue.author,
+            url,
+            comments,
+        })
+    }
+
+    fn list_merge_requests(&self, state: &str) -> Result<Vec<GitHubPullRequest>, String> {
+        let raw: Vec<GtPullRequest> = self.get(&format!(
+            "/pulls?state={}&sort=newest&limit=100",
+            state_filter(state)
+        ))?;
+        Ok(raw
+            .into_iter()
+            .filter(|pr| state != "merged" || pr.merged)
+            .map(pull_request)
+            .collect())
+    }
+
+    fn search_merge_requests(&self, query: &str) -> Result<Vec<GitHubPullRequest>, String> {
+        // The pulls API has no text search; match title and number locally
+        let needle = query.trim().trim_start_matches('#').to_lowercase();
+        let raw: Vec<GtPullRequest> = self.get("/pulls?state=all&sort=newest&limit=100")?;
+        Ok(raw
+            .into_iter()
+            .filter(|pr| {
+                pr.title.to_lowercase().contains(&needle) || pr.number.to_string() == needle
+            })
+            .take(30)
+            .map(pull_request)
+            .collect())
+    }
+
+    fn get_merge_request(&self, number: u32) -> Result<GitHubPullRequestDetail, String> {
+        let raw = self.pull(number)?;
+        let comments = self.comments(number)?;
+        let reviews: Vec<GtReview> = self
+            .get(&format!("/pulls/{number}/reviews"))
+            .unwrap_or_default();
+        let reviews = reviews
+            .into_iter()
+            .filter(|r| r.state != "PENDING")
+            .map(|r| GitHubReview {
+                body: r.body,
+                state: review_state(&r.state).to_string(),
+                author: GitHubAuthor {
+                    login: r.user.map(|u| u.login).unwrap_or_default(),
+                },
+                submitted_at: r.submitted_at,
+            })
+            .collect();
+        let url = raw.html_url.clone();
+        let pr = pull_request(raw);
+        Ok(GitHubPullRequestDetail {
+            number: pr.number,
+            title: pr.title,
+            body: pr.body,
+            state: pr.state,
+            head_ref_name: pr.head_ref_name,
+            base_ref_name: pr.base_ref_name,
+            is_draft: pr.is_draft,
+            created_at: pr.created_at,
+            author: pr.author,
+            url,
+            labels: pr.labels,
+            comments,
+            reviews,
+        })
+    }
+
+    fn get_merge_request_diff(&self, number: u32) -> Result<String, String> {
+        let (diff, _) = self.request("GET", &format!("/pulls/{number}.diff"), None)?;
+        Ok(truncate_diff(diff, number))
+    }
+
+    fn create_merge_request(
+        &self,
+        request: &NewMergeRequest,
+    ) -> Result<CreatedMergeRequest, String> {
+        let title = if request.draft {
+            format!("{} {}", WIP_PREFIXES[0], request.title)
+        } else {
+            request.title.clone()
+        };
+        let body = serde_json::json!({
+            "head": request.source_branch,
+            "base": request.target_branch,
+            "title": title,
+            "body": request.body,
+        });
+        let url = format!("{}/pulls", self.repo_api);
+        let response = self.transport.send("POST", &url, Some(&body))?;
+
+        // 409: an open pull request already exists for head and base
+        if response.status == 409 {
+            let open: Vec<GtPullRequest> = self.get("/pulls?state=open&limit=100")?;
+            let pr = open
+                .into_iter()
+                .find(|pr| pr.head.ref_name == request.source_branch)
+                .ok_or_else(|| "A pull request for this branch already exists".to_string())?;
+            return Ok(CreatedMergeRequest {
+                number: pr.number,
+                url: pr.html_url,
+                existing: true,
+            });
+        }
+        if !(200..=299).contains(&response.status) {
+            return Err(format!(
+                "Failed to create pull request ({}): {}",
+                response.status,
+                response.body.trim()
+            ));
+        }
+        let pr: GtPullRequest = serde_json::from_str(&response.body)
+            .map_err(|e| format!("Failed to parse Gitea/Forgejo response: {e}"))?;
+        Ok(CreatedMergeRequest {
+            number: pr.number,
+            url: pr.html_url,
+            existing: false,
+        })
+    }
+
+    fn merge_request_status(
+        &self,
+        number: u32,
+        url: &str,
+        worktree_id: &str,
+    ) -> Result<PrStatus, String> {
+        let pr = self.pull(number).map_err(|e| {
+            if e.ends_with("not found") {
+                "PR not found - may have been deleted".to_string()
+            } else {
+                e
+            }
+        })?;
+        let reviews: Vec<GtReview> = self
+            .get(&format!("/pulls/{number}/reviews"))
+            .unwrap_or_default();
+        let review_requested = pr
+            .requested_reviewers
+            .as_ref()
+            .is_some_and(|r| !r.is_empty());
+        let (review_decision, approved_review_count) = review_decision(&reviews, review_requested);
+        let check_status = self
+            .combined_status(&pr.head.sha)
+            .ok()
+            .as_ref()
+            .and_then(check_status);
+
+        let pr_state = if pr.merged {
+            PrState::Merged
+        } else if pr.state == "closed" {
+            PrState::Closed
+        } else {
+            PrState::Open
+        };
+        let is_draft = is_draft(&pr);
+        let display_status = compute_display_status(&pr_state, is_draft, &review_decision);
+        let merge_state_status = if is_draft {
+            MergeStateStatus::Draft
+        } else if !pr.mergeable {
+            MergeStateStatus::Dirty
+        } else if matches!(
+            check_status,
+            Some(CheckStatus::Failure | CheckStatus::Error)
+        ) {
+            MergeStateStatus::Unstable
+        } else {
+            MergeStateStatus::Clean
+        };
+        let checked_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        Ok(PrStatus {
+            worktree_id: worktree_id.to_string(),
+            pr_number: number,
+            pr_url: url.to_string(),
+            state: pr_state,
+            is_draft,
+            review_decision,
+            check_status,
+            display_status,
+            mergeable: Some(if pr.mergeable {
+                MergeableStatus::Mergeable
+            } else {
+                MergeableStatus::Conflicting
+            }),
+            merge_state_status: Some(merge_state_status),
+            auto_merge: None,
+            merge_queue: None,
+            required_approving_review_count: None,
+            approved_review_count,
+            missing_required_checks: Vec::new(),
+            head_sha: Some(pr.head.sha).filter(|sha| !sha.is_empty()),
+            checked_at,
+        })
+    }
+
+    fn list_pipelines(&self, branch: Option<&str>) -> Result<WorkflowRunsResult, String> {
+        let branch = match branch {
+            Some(branch) => branch.to_string(),
+            None => self.get::<GtRepository>("")?.default_branch,
+        };
+        let combined = self.combined_status(&branch)?;
+
+        let runs: Vec<WorkflowRun> = combined
+            .statuses
+            .into_iter()
+            .map(|s| {
+                let (status, conclusion) = run_status(&s.status);
+                WorkflowRun {
+                    database_id: s.id,
+                    name: s.context.clone(),
+                    display_title: if s.description.is_empty() {
+                        s.context.clone()
+                    } else {
+                        s.description
+                    },
+                    status: status.to_string(),
+                    conclusion: conclusion.map(str::to_string),
+                    event: "push".to_string(),
+                    head_branch: branch.clone(),
+                    created_at: s.created_at,
+                    url: s.target_url,
+                    workflow_name: s.context,
+                    attempt: 1,
+                }
+            })
+            .collect();
+        // One status per context on the branch head, so each failure counts
+        let failed_count = runs
+            .iter()
+            .filter(|r| r.conclusion.as_deref() == Some("failure"))
+            .count() as u32;
+
+        Ok(WorkflowRunsResult { runs, failed_count })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_transport::{Fixture, Fixtures};
+    use super::*;
+
+    const API: &str = "https://code.example.org/api/v1/repos/team/app";
+
+    fn gitea(responses: Vec<Fixture>) -> (Gitea, &'static Fixtures) {
+        let fixtures = Fixtures::leak(API, 12, responses);
+        let remote = ForgeRemote {
+            kind: ForgeKind::Gitea,
+            host: "code.example.org".to_string(),
+            path: "team/app".to_string(),
+            base_url: "https://code.example.org".to_string(),
+        };
+        (Gitea::new(&remote, Box::new(fixtures)), fixtures)
+    }
+
+    #[test]
+    fn test_env_token_only_for_its_host() {
+        let env = |var: &str| match var {
+            "FORGEJO_TOKEN" => Some("secret".to_string()),
+            "FORGEJO_HOST" => Some("https://code.example.org/".to_string()),
+            _ => None,
+        };
+        assert_eq!(
+            env_token("https://code.example.org", env).as_deref(),
+            Some("secret")
+        );
+        assert_eq!(env_token("https://gitea.attacker.example", env), None);
+        assert_eq!(env_token("https://codeberg.org", env), None);
+
+        let without_host = |var: &str| (var == "GITEA_TOKEN").then(|| "secret".to_string());
+        assert_eq!(env_token("https://code.example.org", without_host), None);
+    }
+
+    const ISSUES: &str = include_str!("fixtures/gitea/issues.json");
+    const ISSUE: &str = include_str!("fixtures/gitea/issue.json");
+    const ISSUE_COMMENTS: &str = include_str!("fixtures/gitea/issue_comments.json");
+    const PULL: &str = include_str!("fixtures/gitea/pull.json");
+    const PULLS: &str = include_str!("fixtures/gitea/pulls.json");
+    const REVIEWS: &str = include_str!("fixtures/gitea/reviews.json");
+    const STATUS: &str = include_str!("fixtures/gitea/combined_status.json");
+    const CONFLICT: &str = include_str!("fixtures/gitea/pull_conflict.json");
+
+    #[test]
+    fn test_list_and_get_issues() {
+        let (gitea, _) = gitea(vec![
+            ("GET", "/issues?", 200, ISSUES),
+            ("GET", "/issues/7/comments", 200, ISSUE_COMMENTS),
+            ("GET", "/issues/7", 200, ISSUE),
+        ]);
+
+        let result = gitea.list_issues("open").unwrap();
+        assert_eq!(result.total_count, 12);
+        // The pull request listed among issues is dropped
+        assert_eq!(result.issues.len(), 1);
+        let first = &result.issues[0];
+        assert_eq!(first.number, 7);
+        assert_eq!(first.state, "OPEN");
+        assert_eq!(first.author.login, "dana");
+        assert_eq!(first.labels[0].color, "ee0701");
+
+        let detail = gitea.get_issue(7).unwrap();
+        assert_eq!(detail.url, "https://code.example.org/team/app/issues/7");
+        assert_eq!(detail.comments.len(), 1);
+        assert_eq!(detail.comments[0].author.login, "erin");
+    }
+
+    #[test]
+    fn test_pull_request_detail_and_status() {
+        let (gitea, _) = gitea(vec![
+            ("GET", "/pulls/8/reviews", 200, REVIEWS),
+            ("GET", "/pulls/8", 200, PULL),
+            ("GET", "/issues/8/comments", 200, "[]"),
+            ("GET", "/commits/a1b2c3d/status", 200, STATUS),
+        ]);
+
+        let detail = gitea.get_merge_request(8).unwrap();
+        assert_eq!(detail.head_ref_name, "fix/export");
+        assert!(detail.is_draft, "WIP: title prefix marks a draft");
+        // Pending reviews are hidden, states use GitHub's names
+        let states: Vec<&str> = detail.reviews.iter().map(|r| r.state.as_str()).collect();
+        assert_eq!(states, ["CHANGES_REQUESTED", "APPROVED", "COMMENTED"]);
+
+        let status = gitea.merge_request_status(8, &detail.url, "wt-1").unwrap();
+        assert_eq!(status.state, PrState::Open);
+        // erin's approval supersedes the earlier change request
+        assert_eq!(status.review_decision, Some(ReviewDecision::Approved));
+        assert_eq!(status.approved_review_count, 1);
+        assert_eq!(status.check_status, Some(CheckStatus::Failure));
+        assert_eq!(status.merge_state_status, Some(MergeStateStatus::Draft));
+        assert_eq!(status.mergeable, Some(MergeableStatus::Mergeable));
+    }
+
+    #[test]
+    fn test_create_pull_request_links_existing_on_conflict() {
+        let (gitea, fixtures) = gitea(vec![
+            ("POST", "/pulls", 409, CONFLICT),
+            ("GET", "/pulls?state=open", 200, PULLS),
+        ]);
+        let request = NewMergeRequest {
+            source_branch: "fix/export".to_string(),
+            target_branch: "main".to_string(),
+            title: "Fix CSV export".to_string(),
+            body: "Body".to_string(),
+            draft: true,
+        };
+        assert_eq!(
+            gitea.create_merge_request(&request),
+            Ok(CreatedMergeRequest {
+                number: 8,
+                url: "https://code.example.org/team/app/pulls/8".to_string(),
+                existing: true,
+            })
+        );
+        let requests = fixtures.requests.lock().unwrap();
+        let body = requests[0].2.as_ref().unwrap();
+        assert_eq!(body["title"], "WIP: Fix CSV export");
+        assert_eq!(body["head"], "fix/export");
+        assert_eq!(body["base"], "main");
+    }
+
+    #[test]
+    fn test_list_pipelines_from_commit_statuses() {
+        let (gitea, fixtures) = gitea(vec![("GET", "/commits/", 200, STATUS)]);
+        let result = gitea.list_pipelines(Some("fix/export")).unwrap();
+        assert!(fixtures.requests.lock().unwrap()[0]
+            .1
+            .ends_with("/commits/fix%2Fexport/status"));
+        assert_eq!(result.runs.len(), 2);
+        assert_eq!(result.failed_count, 1);
+        assert_eq!(result.runs[0].workflow_name, "ci/woodpecker/pr/test");
+        assert_eq!(result.runs[0].conclusion.as_deref(), Some("failure"));
+        assert_eq!(result.runs[1].status, "in_progress");
+        assert_eq!(result.runs[1].display_title, "lint");
+    }
+}
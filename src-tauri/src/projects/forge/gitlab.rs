@@ -0,0 +1,879 @@
+//! GitLab (gitlab.com and self-hosted) over the REST API v4.
+//!
+//! Issues and merge requests are addressed by their project-scoped `iid`,
+//! which is the number shown in the GitLab UI (`#12`, `!34`).
+
+use serde::Deserialize;
+
+use super::{
+    encode_component, CreatedMergeRequest, Forge, ForgeKind, ForgeRemote, HttpTransport,
+    NewMergeRequest,
+};
+use crate::platform::silent_command;
+use crate::projects::github_actions::{WorkflowRun, WorkflowRunsResult};
+use crate::projects::github_issues::{
+    GitHubAuthor, GitHubComment, GitHubIssue, GitHubIssueDetail, GitHubIssueListResult,
+    GitHubLabel, GitHubPullRequest, GitHubPullRequestDetail, GitHubReview,
+};
+use crate::projects::pr_status::{
+    compute_display_status, AutoMergeStatus, CheckStatus, MergeStateStatus, MergeableStatus,
+    PrState, PrStatus, ReviewDecision,
+};
+
+/// Diffs larger than this are cut off (same limit as `gh pr diff`)
+const MAX_DIFF_SIZE: usize = 100_000;
+
+/// Token from `GITLAB_TOKEN` (only for gitlab.com or the `GITLAB_HOST`
+/// instance), or from glab's config for the host
+pub fn token_from_environment(host: &str) -> Option<String> {
+    if let Some(token) = env_token(host, |var| std::env::var(var).ok()) {
+        return Some(token);
+    }
+    let hostname = host.split(':').next().unwrap_or(host);
+    let output = silent_command("glab")
+        .args(["config", "get", "token", "--host", hostname])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!token.is_empty()).then_some(token)
+}
+
+/// `GITLAB_TOKEN` when it's meant for `host`. Like glab, the variable
+/// belongs to gitlab.com unless `GITLAB_HOST` names another instance, so a
+/// repository on some other host never receives it.
+fn env_token(host: &str, var: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let token = var("GITLAB_TOKEN").filter(|t| !t.trim().is_empty())?;
+    let token_host = var("GITLAB_HOST")
+        .map(|h| {
+            let h = h.trim();
+            let h = h
+                .strip_prefix("https://")
+                .or_else(|| h.strip_prefix("http://"))
+                .unwrap_or(h);
+            h.trim_end_matches('/').to_lowercase()
+        })
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| "gitlab.com".to_string());
+    (host.to_lowercase() == token_host).then(|| token.trim().to_string())
+}
+
+// =============================================================================
+// Raw API types
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+struct GlUser {
+    username: String,
+}
+
+/// Labels are plain names unless `with_labels_details=true` is requested
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum GlLabel {
+    Name(String),
+    Detailed {
+        name: String,
+        #[serde(default)]
+        color: String,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct GlIssue {
+    iid: u32,
+    title: String,
+    description: Option<String>,
+    state: String,
+    #[serde(default)]
+    labels: Vec<GlLabel>,
+    created_at: String,
+    author: GlUser,
+    #[serde(default)]
+    web_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlNote {
+    body: String,
+    author: GlUser,
+    created_at: String,
+    #[serde(default)]
+    system: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlPipelineRef {
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlMergeRequest {
+    iid: u32,
+    title: String,
+    description: Option<String>,
+    state: String,
+    source_branch: String,
+    target_branch: String,
+    #[serde(default)]
+    draft: bool,
+    created_at: String,
+    author: GlUser,
+    #[serde(default)]
+    labels: Vec<GlLabel>,
+    #[serde(default)]
+    web_url: String,
+    #[serde(default)]
+    detailed_merge_status: Option<String>,
+    #[serde(default)]
+    has_conflicts: bool,
+    #[serde(default)]
+    merge_when_pipeline_succeeds: bool,
+    #[serde(default)]
+    squash: bool,
+    #[serde(default)]
+    merge_user: Option<GlUser>,
+    #[serde(default)]
+    head_pipeline: Option<GlPipelineRef>,
+    #[serde(default)]
+    sha: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlApprovedBy {
+    user: GlUser,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlApprovals {
+    #[serde(default)]
+    approvals_required: Option<u32>,
+    #[serde(default)]
+    approvals_left: Option<u32>,
+    #[serde(default)]
+    approved_by: Vec<GlApprovedBy>,
+    #[serde(default)]
+    updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlDiff {
+    old_path: String,
+    new_path: String,
+    #[serde(default)]
+    diff: String,
+    #[serde(default)]
+    new_file: bool,
+    #[serde(default)]
+    deleted_file: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlPipeline {
+    id: u64,
+    #[serde(default)]
+    iid: Option<u64>,
+    #[serde(default)]
+    name: Option<String>,
+    status: String,
+    #[serde(rename = "ref")]
+    ref_name: String,
+    #[serde(default)]
+    source: String,
+    created_at: String,
+    #[serde(default)]
+    web_url: String,
+}
+
+// =============================================================================
+// Mapping
+// =============================================================================
+
+fn author(user: GlUser) -> GitHubAuthor {
+    GitHubAuthor {
+        login: user.username,
+    }
+}
+
+fn labels(labels: Vec<GlLabel>) -> Vec<GitHubLabel> {
+    labels
+        .into_iter()
+        .map(|l| match l {
+            GlLabel::Name(name) => GitHubLabel {
+                name,
+                color: String::new(),
+            },
+            GlLabel::Detailed { name, color } => GitHubLabel {
+                name,
+                color: color.trim_start_matches('#').to_string(),
+            },
+        })
+        .collect()
+}
+
+/// GitLab issue/MR state to the uppercase GitHub state
+fn state(gitlab_state: &str) -> String {
+    match gitlab_state {
+        "opened" => "OPEN",
+        "merged" => "MERGED",
+        _ => "CLOSED",
+    }
+    .to_string()
+}
+
+/// GitHub-style state filter to GitLab's
+fn state_filter(state: &str) -> &'static str {
+    match state {
+        "closed" => "closed",
+        "merged" => "merged",
+        "all" => "all",
+        _ => "opened",
+    }
+}
+
+fn issue(raw: GlIssue) -> GitHubIssue {
+    GitHubIssue {
+        number: raw.iid,
+        title: raw.title,
+        body: raw.description,
+        state: state(&raw.state),
+        labels: labels(raw.labels),
+        created_at: raw.created_at,
+        author: author(raw.author),
+    }
+}
+
+fn merge_request(raw: GlMergeRequest) -> GitHubPullRequest {
+    GitHubPullRequest {
+        number: raw.iid,
+        title: raw.title,
+        body: raw.description,
+        state: state(&raw.state),
+        head_ref_name: raw.source_branch,
+        base_ref_name: raw.target_branch,
+        is_draft: raw.draft,
+        created_at: raw.created_at,
+        author: author(raw.author),
+        labels: labels(raw.labels),
+    }
+}
+
+fn comments(notes: Vec<GlNote>) -> Vec<GitHubComment> {
+    notes
+        .into_iter()
+        .filter(|n| !n.system)
+        .map(|n| GitHubComment {
+            body: n.body,
+            author: author(n.author),
+            created_at: n.created_at,
+        })
+        .collect()
+}
+
+/// Pipeline status to GitHub's (status, conclusion) pair
+fn pipeline_status(status: &str) -> (&'static str, Option<&'static str>) {
+    match status {
+        "success" => ("completed", Some("success")),
+        "failed" => ("completed", Some("failure")),
+        "canceled" => ("completed", Some("cancelled")),
+        "skipped" => ("completed", Some("skipped")),
+        "manual" => ("completed", Some("action_required")),
+        "running" => ("in_progress", None),
+        _ => ("queued", None),
+    }
+}
+
+fn check_status(pipeline_status: &str) -> CheckStatus {
+    match pipeline_status {
+        "success" | "skipped" | "manual" => CheckStatus::Success,
+        "failed" => CheckStatus::Failure,
+        "canceled" => CheckStatus::Error,
+        _ => CheckStatus::Pending,
+    }
+}
+
+/// `detailed_merge_status` to the GitHub merge state
+fn merge_state(detailed: &str) -> MergeStateStatus {
+    match detailed {
+        "mergeable" => MergeStateStatus::Clean,
+        "conflict" => MergeStateStatus::Dirty,
+        "need_rebase" => MergeStateStatus::Behind,
+        "draft_status" => MergeStateStatus::Draft,
+        "checking" | "unchecked" | "preparing" | "approvals_syncing" => MergeStateStatus::Unknown,
+        _ => MergeStateStatus::Blocked,
+    }
+}
+
+fn review_decision(approvals: &GlApprovals) -> Option<ReviewDecision> {
+    match approvals.approvals_required {
+        Some(required) if required > 0 => Some(if approvals.approvals_left == Some(0) {
+            ReviewDecision::Approved
+        } else {
+            ReviewDecision::ReviewRequired
+        }),
+        _ if !approvals.approved_by.is_empty() => Some(ReviewDecision::Approved),
+        _ => None,
+    }
+}
+
+fn file_diff(diff: &GlDiff) -> String {
+    let old = if diff.new_file {
+        "/dev/null".to_string()
+    } else {
+        format!("a/{}", diff.old_path)
+    };
+    let new = if diff.deleted_file {
+        "/dev/null".to_string()
+    } else {
+        format!("b/{}", diff.new_path)
+    };
+    let mut out = format!(
+        "diff --git a/{} b/{}\n--- {old}\n+++ {new}\n{}",
+        diff.old_path, diff.new_path, diff.diff
+    );
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+// =============================================================================
+// Client
+// =============================================================================
+
+pub struct GitLab {
+    /// `{base_url}/api/v4/projects/{encoded path}`
+    project_api: String,
+    transport: Box<dyn HttpTransport>,
+}
+
+impl GitLab {
+    pub fn new(remote: &ForgeRemote, transport: Box<dyn HttpTransport>) -> Self {
+        Self {
+            project_api: format!(
+                "{}/api/v4/projects/{}",
+                remote.base_url,
+                encode_component(&remote.path)
+            ),
+            transport,
+        }
+    }
+
+    fn request(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<(String, Option<u32>), String> {
+        let url = format!("{}{path}", self.project_api);
+        let response = self.transport.send(method, &url, body)?;
+        match response.status {
+            200..=299 => Ok((response.body, response.total)),
+            401 => Err(
+                "GitLab token is invalid or expired. Update it in project settings.".to_string(),
+            ),
+            403 => Err("GitLab token lacks permission (needs the 'api' scope)".to_string()),
+            404 => Err(format!("Not found on GitLab: {path}")),
+            status => Err(format!(
+                "GitLab API error ({status}): {}",
+                response.body.trim()
+            )),
+        }
+    }
+
+    fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T, String> {
+        self.get_with_total(path).map(|(value, _)| value)
+    }
+
+    fn get_with_total<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<(T, Option<u32>), String> {
+        let (body, total) = self.request("GET", path, None)?;
+        let value = serde_json::from_str(&body)
+            .map_err(|e| format!("Failed to parse GitLab response: {e}"))?;
+        Ok((value, total))
+    }
+
+    fn notes(&self, kind: &str, iid: u32) -> Result<Vec<GitHubComment>, String> {
+        let notes: Vec<GlNote> = self.get(&format!(
+            "/{kind}/{iid}/notes?sort=asc&order_by=created_at&per_page=100"
+        ))?;
+        Ok(comments(notes))
+    }
+
+    fn approvals(&self, iid: u32) -> Result<GlApprovals, String> {
+        self.get(&format!("/merge_requests/{iid}/approvals"))
+    }
+}
+
+impl Forge for GitLab {
+    fn kind(&self) -> ForgeKind {
+        ForgeKind::Gitlab
+    }
+
+    fn list_issues(&self, state: &str) -> Result<GitHubIssueListResult, String> {
+        let (raw, total): (Vec<GlIssue>, _) = self.get_with_total(&format!(
+            "/issues?state={}&order_by=created_at&sort=desc&per_page=100&with_labels_details=true",
+            state_filter(state)
+        ))?;
+        let issues: Vec<GitHubIssue> = raw.into_iter().map(issue).collect();
+        let total_count = total.unwrap_or(issues.len() as u32);
+        Ok(GitHubIssueListResult {
+            issues,
+            total_count,
+        })
+    }
+
+    fn search_issues(&self, query: &str) -> Result<Vec<GitHubIssue>, String> {
+        let raw: Vec<GlIssue> = self.get(&format!(
+            "/issues?state=all&search={}&per_page=30&with_labels_details=true",
+            encode_component(query)
+        ))?;
+        Ok(raw.into_iter().map(issue).collect())
+    }
+
+    fn get_issue(&self, number: u32) -> Result<GitHubIssueDetail, String> {
+        let raw: GlIssue = self
+            .get(&format!("/issues/{number}?with_labels_details=true"))
+            .map_err(|e| {
+                if e.starts_with("Not found") {
+                    format!("Issue #{number} not found")
+                } else {
+                    e
+                }
+            })?;
+        let comments = self.notes("issues", number)?;
+        let url = raw.web_url.clone();
+        let issue = issue(raw);
+        Ok(GitHubIssueDetail {
+            number: issue.number,
+            title: issue.title,
+            body: issue.body,
+            state: issue.state,
+            labels: issue.labels,
+            created_at: issue.created_at,
+            author: issue.author,
+            url,
+            comments,
+        })
+    }
+
+    fn list_merge_requests(&self, state: &str) -> Result<Vec<GitHubPullRequest>, String> {
+        let raw: Vec<GlMergeRequest> = self.get(&format!(
+            "/merge_requests?state={}&order_by=created_at&sort=desc&per_page=100&with_labels_details=true",
+            state_filter(state)
+        ))?;
+        Ok(raw.into_iter().map(merge_request).collect())
+    }
+
+    fn search_merge_requests(&self, query: &str) -> Result<Vec<GitHubPullRequest>, String> {
+        let raw: Vec<GlMergeRequest> = self.get(&format!(
+            "/merge_requests?state=all&search={}&per_page=30&with_labels_details=true",
+            encode_component(query)
+        ))?;
+        Ok(raw.into_iter().map(merge_request).collect())
+    }
+
+    fn get_merge_request(&self, number: u32) -> Result<GitHubPullRequestDetail, String> {
+        let raw: GlMergeRequest = self
+            .get(&format!(
+                "/merge_requests/{number}?with_labels_details=true"
+            ))
+            .map_err(|e| {
+                if e.starts_with("Not found") {
+                    format!("Merge request !{number} not found")
+                } else {
+                    e
+                }
+            })?;
+        let comments = self.notes("merge_requests", number)?;
+        // Approvals are the closest thing to GitHub reviews
+        let reviews = self
+            .approvals(number)
+            .map(|a| {
+                let submitted_at = a.updated_at.clone();
+                a.approved_by
+                    .into_iter()
+                    .map(|approval| GitHubReview {
+                        body: String::new(),
+                        state: "APPROVED".to_string(),
+                        author: author(approval.user),
+                        submitted_at: submitted_at.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let url = raw.web_url.clone();
+        let mr = merge_request(raw);
+        Ok(GitHubPullRequestDetail {
+            number: mr.number,
+            title: mr.title,
+            body: mr.body,
+            state: mr.state,
+            head_ref_name: mr.head_ref_name,
+            base_ref_name: mr.base_ref_name,
+            is_draft: mr.is_draft,
+            created_at: mr.created_at,
+            author: mr.author,
+            url,
+            labels: mr.labels,
+            comments,
+            reviews,
+        })
+    }
+
+    fn get_merge_request_diff(&self, number: u32) -> Result<String, String> {
+        let diffs: Vec<GlDiff> =
+            self.get(&format!("/merge_requests/{number}/diffs?per_page=100"))?;
+        let mut out = String::new();
+        for diff in &diffs {
+            let file = file_diff(diff);
+            if out.len() + file.len() > MAX_DIFF_SIZE {
+                out.push_str(&format!(
+                    "...\n\n[Diff truncated at 100KB - {} files total. See the merge request for the full diff.]",
+                    diffs.len()
+                ));
+                break;
+            }
+            out.push_str(&file);
+        }
+        Ok(out)
+    }
+
+    fn create_merge_request(
+        &self,
+        request: &NewMergeRequest,
+    ) -> Result<CreatedMergeRequest, String> {
+        let title = if request.draft {
+            format!("Draft: {}", request.title)
+        } else {
+            request.title.clone()
+        };
+        let body = serde_json::json!({
+            "source_branch": request.source_branch,
+            "target_branch": request.target_branch,
+            "title": title,
+            "description": request.body,
+            "remove_source_branch": true,
+        });
+        let url = format!("{}/merge_requests", self.project_api);
+        let response = self.transport.send("POST", &url, Some(&body))?;
+
+        // 409: an open merge request already exists for the source branch
+        if response.status == 409 {
+            let existing: Vec<GlMergeRequest> = self.get(&format!(
+                "/merge_requests?state=opened&source_branch={}",
+                encode_component(&request.source_branch)
+            ))?;
+            let mr = existing
+                .into_iter()
+                .next()
+                .ok_or_else(|| "A merge request for this branch already exists".to_string())?;
+            return Ok(CreatedMergeRequest {
+                number: mr.iid,
+                url: mr.web_url,
+                existing: true,
+            });
+        }
+        if !(200..=299).contains(&response.status) {
+            return Err(format!(
+                "Failed to create merge request ({}): {}",
+                response.status,
+                response.body.trim()
+            ));
+        }
+        let mr: GlMergeRequest = serde_json::from_str(&response.body)
+            .map_err(|e| format!("Failed to parse GitLab response: {e}"))?;
+        Ok(CreatedMergeRequest {
+            number: mr.iid,
+            url: mr.web_url,
+            existing: false,
+        })
+    }
+
+    fn merge_request_status(
+        &self,
+        number: u32,
+        url: &str,
+        worktree_id: &str,
+    ) -> Result<PrStatus, String> {
+        let mr: GlMergeRequest = self
+            .get(&format!("/merge_requests/{number}"))
+            .map_err(|e| {
+                if e.starts_with("Not found") {
+                    "PR not found - may have been deleted".to_string()
+                } else {
+                    e
+                }
+            })?;
+        let approvals = self.approvals(number).ok();
+
+        let pr_state = match mr.state.as_str() {
+            "opened" => PrState::Open,
+            "merged" => PrState::Merged,
+            _ => PrState::Closed,
+        };
+        let review_decision = approvals.as_ref().and_then(review_decision);
+        let display_status = compute_display_status(&pr_state, mr.draft, &review_decision);
+        let mergeable = if mr.has_conflicts {
+            MergeableStatus::Conflicting
+        } else if mr.detailed_merge_status.as_deref() == Some("mergeable") {
+            MergeableStatus::Mergeable
+        } else {
+            MergeableStatus::Unknown
+        };
+        let checked_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        Ok(PrStatus {
+            worktree_id: worktree_id.to_string(),
+            pr_number: number,
+            pr_url: url.to_string(),
+            state: pr_state,
+            is_draft: mr.draft,
+            review_decision,
+            check_status: mr.head_pipeline.as_ref().map(|p| check_status(&p.status)),
+            display_status,
+            mergeable: Some(mergeable),
+            merge_state_status: mr.detailed_merge_status.as_deref().map(merge_state),
+            auto_merge: mr.merge_when_pipeline_succeeds.then(|| AutoMergeStatus {
+                merge_method: if mr.squash { "squash" } else { "merge" }.to_string(),
+                enabled_by: mr.merge_user.map(|u| u.username),
+                enabled_at: None,
+            }),
+            merge_queue: None,
+            required_approving_review_count: approvals
+                .as_ref()
+                .and_then(|a| a.approvals_required)
+                .filter(|n| *n > 0),
+            approved_review_count: approvals.map_or(0, |a| a.approved_by.len() as u32),
+            missing_required_checks: Vec::new(),
+            head_sha: mr.sha,
+            checked_at,
+        })
+    }
+
+    fn list_pipelines(&self, branch: Option<&str>) -> Result<WorkflowRunsResult, String> {
+        let mut path = "/pipelines?per_page=30&order_by=id&sort=desc".to_string();
+        if let Some(branch) = branch {
+            path.push_str(&format!("&ref={}", encode_component(branch)));
+        }
+        let pipelines: Vec<GlPipeline> = self.get(&path)?;
+
+        let runs: Vec<WorkflowRun> = pipelines
+            .into_iter()
+            .map(|p| {
+                let (status, conclusion) = pipeline_status(&p.status);
+                let number = p.iid.unwrap_or(p.id);
+                WorkflowRun {
+                    database_id: p.id,
+                    name: p.name.clone().unwrap_or_else(|| "Pipeline".to_string()),
+                    display_title: p
+                        .name
+                        .unwrap_or_else(|| format!("Pipeline #{number} on {}", p.ref_name)),
+                    status: status.to_string(),
+                    conclusion: conclusion.map(str::to_string),
+                    event: p.source,
+                    head_branch: p.ref_name,
+                    created_at: p.created_at,
+                    url: p.web_url,
+                    workflow_name: "GitLab CI".to_string(),
+                    attempt: 1,
+                }
+            })
+            .collect();
+        // Pipelines run the whole .gitlab-ci.yml, so only the latest one counts
+        let failed_count =
+            runs.first()
+                .is_some_and(|r| r.conclusion.as_deref() == Some("failure")) as u32;
+
+        Ok(WorkflowRunsResult { runs, failed_count })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_transport::{Fixture, Fixtures};
+    use super::*;
+
+    const API: &str = "https://gitlab.example.com/api/v4/projects/group%2Fapp";
+
+    fn gitlab(responses: Vec<Fixture>) -> (GitLab, &'static Fixtures) {
+        let fixtures = Fixtures::leak(API, 57, responses);
+        let remote = ForgeRemote {
+            kind: ForgeKind::Gitlab,
+            host: "gitlab.example.com".to_string(),
+            path: "group/app".to_string(),
+            base_url: "https://gitlab.example.com".to_string(),
+        };
+        (GitLab::new(&remote, Box::new(fixtures)), fixtures)
+    }
+
+    const ISSUES: &str = include_str!("fixtures/gitlab/issues.json");
+    const ISSUE_NOTES: &str = include_str!("fixtures/gitlab/issue_notes.json");
+    const MERGE_REQUEST: &str = include_str!("fixtures/gitlab/merge_request.json");
+    const MERGE_REQUEST_STATUS: &str = include_str!("fixtures/gitlab/merge_request_status.json");
+    const APPROVALS: &str = include_str!("fixtures/gitlab/approvals.json");
+    const DIFFS: &str = include_str!("fixtures/gitlab/merge_request_diffs.json");
+    const PIPELINES: &str = include_str!("fixtures/gitlab/pipelines.json");
+    const OPEN_MERGE_REQUESTS: &str = include_str!("fixtures/gitlab/merge_requests_opened.json");
+    const CONFLICT: &str = include_str!("fixtures/gitlab/merge_request_conflict.json");
+
+    #[test]
+    fn test_env_token_only_for_its_host() {
+        let env = |host: Option<&'static str>| {
+            move |var: &str| match var {
+                "GITLAB_TOKEN" => Some("glpat-secret".to_string()),
+                "GITLAB_HOST" => host.map(str::to_string),
+                _ => None,
+            }
+        };
+        assert_eq!(
+            env_token("gitlab.com", env(None)).as_deref(),
+            Some("glpat-secret")
+        );
+        assert_eq!(env_token("gitlab.attacker.example", env(None)), None);
+        assert_eq!(
+            env_token(
+                "gitlab.corp.example",
+                env(Some("https://gitlab.corp.example/"))
+            )
+            .as_deref(),
+            Some("glpat-secret")
+        );
+        assert_eq!(
+            env_token("gitlab.com", env(Some("gitlab.corp.example"))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_list_and_get_issues() {
+        let (gl, _) = gitlab(vec![
+            ("GET", "/issues?", 200, ISSUES),
+            (
+                "GET",
+                "/issues/12?",
+                200,
+                include_str!("fixtures/gitlab/issue.json"),
+            ),
+            ("GET", "/issues/12/notes", 200, ISSUE_NOTES),
+        ]);
+
+        let result = gl.list_issues("open").unwrap();
+        assert_eq!(result.total_count, 57);
+        assert_eq!(result.issues.len(), 2);
+        let first = &result.issues[0];
+        assert_eq!(first.number, 12);
+        assert_eq!(first.state, "OPEN");
+        assert_eq!(first.author.login, "alice");
+        assert_eq!(first.labels[0].name, "bug");
+        assert_eq!(first.labels[0].color, "d9534f");
+        assert_eq!(result.issues[1].state, "CLOSED");
+
+        let detail = gl.get_issue(12).unwrap();
+        assert_eq!(
+            detail.url,
+            "https://gitlab.example.com/group/app/-/issues/12"
+        );
+        // System notes ("changed the description") are dropped
+        assert_eq!(detail.comments.len(), 1);
+        assert_eq!(detail.comments[0].author.login, "bob");
+    }
+
+    #[test]
+    fn test_merge_request_detail_status_and_diff() {
+        let (gl, _) = gitlab(vec![
+            ("GET", "/merge_requests/34/notes", 200, "[]"),
+            ("GET", "/merge_requests/34/approvals", 200, APPROVALS),
+            ("GET", "/merge_requests/34/diffs", 200, DIFFS),
+            ("GET", "/merge_requests/34?", 200, MERGE_REQUEST),
+            // Requested without label details, so labels are plain names
+            ("GET", "/merge_requests/34", 200, MERGE_REQUEST_STATUS),
+        ]);
+
+        let detail = gl.get_merge_request(34).unwrap();
+        assert_eq!(detail.head_ref_name, "feature/login");
+        assert_eq!(detail.base_ref_name, "main");
+        assert!(detail.is_draft);
+        assert_eq!(detail.reviews.len(), 1);
+        assert_eq!(detail.reviews[0].author.login, "carol");
+        assert_eq!(detail.labels[0].name, "backend");
+        assert_eq!(detail.labels[0].color, "428bca");
+
+        let status = gl.merge_request_status(34, &detail.url, "wt-1").unwrap();
+        assert_eq!(status.state, PrState::Open);
+        assert_eq!(
+            status.display_status,
+            crate::projects::pr_status::PrDisplayStatus::Draft
+        );
+        assert_eq!(status.check_status, Some(CheckStatus::Pending));
+        assert_eq!(status.review_decision, Some(ReviewDecision::ReviewRequired));
+        assert_eq!(status.merge_state_status, Some(MergeStateStatus::Draft));
+        assert_eq!(status.required_approving_review_count, Some(2));
+        assert_eq!(status.approved_review_count, 1);
+        assert_eq!(status.auto_merge.unwrap().merge_method, "squash");
+
+        let diff = gl.get_merge_request_diff(34).unwrap();
+        assert!(diff.starts_with(
+            "diff --git a/src/login.rs b/src/login.rs\n--- a/src/login.rs\n+++ b/src/login.rs\n@@"
+        ));
+        assert!(diff.contains("--- /dev/null\n+++ b/src/session.rs\n"));
+    }
+
+    #[test]
+    fn test_create_merge_request_links_existing_on_conflict() {
+        let (gl, fixtures) = gitlab(vec![
+            ("POST", "/merge_requests", 409, CONFLICT),
+            (
+                "GET",
+                "/merge_requests?state=opened&source_branch=feature%2Flogin",
+                200,
+                OPEN_MERGE_REQUESTS,
+            ),
+        ]);
+        let request = NewMergeRequest {
+            source_branch: "feature/login".to_string(),
+            target_branch: "main".to_string(),
+            title: "Add login".to_string(),
+            body: "Body".to_string(),
+            draft: true,
+        };
+        assert_eq!(
+            gl.create_merge_request(&request),
+            Ok(CreatedMergeRequest {
+                number: 34,
+                url: "https://gitlab.example.com/group/app/-/merge_requests/34".to_string(),
+                existing: true,
+            })
+        );
+        let requests = fixtures.requests.lock().unwrap();
+        let body = requests[0].2.as_ref().unwrap();
+        assert_eq!(body["title"], "Draft: Add login");
+        assert_eq!(body["source_branch"], "feature/login");
+        assert_eq!(body["target_branch"], "main");
+    }
+
+    #[test]
+    fn test_list_pipelines() {
+        let (gl, fixtures) = gitlab(vec![("GET", "/pipelines?", 200, PIPELINES)]);
+        let result = gl.list_pipelines(Some("feature/login")).unwrap();
+        assert!(fixtures.requests.lock().unwrap()[0]
+            .1
+            .ends_with("&ref=feature%2Flogin"));
+        assert_eq!(result.failed_count, 1);
+        assert_eq!(result.runs.len(), 3);
+        let latest = &result.runs[0];
+        assert_eq!(latest.database_id, 9003);
+        assert_eq!(latest.status, "completed");
+        assert_eq!(latest.conclusion.as_deref(), Some("failure"));
+        assert_eq!(latest.display_title, "Pipeline #43 on feature/login");
+        assert_eq!(result.runs[1].status, "in_progress");
+        assert_eq!(result.runs[2].conclusion.as_deref(), Some("success"));
+    }
+}
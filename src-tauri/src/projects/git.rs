@@ -1,9 +1,12 @@
 use crate::platform::silent_command;
+use std::collections::BTreeMap;
 use std::path::Path;
+use tauri::AppHandle;
 
 use serde::{Deserialize, Serialize};
 
-use super::types::{JeanConfig, MergeType};
+use super::forge::ForgeKind;
+use super::types::{JeanConfig, JeanHook, MergeType, PortRange, Project};
 
 /// Repository identifier extracted from GitHub remote URL
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -15,17 +18,33 @@ pub struct RepoIdentifier {
 impl RepoIdentifier {
     /// Create a key string for use in file naming: "{owner}-{repo}"
     pub fn to_key(&self) -> String {
-        format!("{}-{}", self.owner, self.repo)
+        format!("{}-{}", self.owner.replace('/', "-"), self.repo)
     }
 }
 
 /// Extract repository owner and name from a git repository's GitHub remote
 ///
+/// For other forges the owner is the namespace, which may contain nested
+/// groups ("group/subgroup").
+///
 /// Returns an error if:
 /// - The repository has no origin remote
-/// - The remote URL is not a GitHub URL
+/// - The remote URL is not a forge URL
 pub fn get_repo_identifier(repo_path: &str) -> Result<RepoIdentifier, String> {
-    let github_url = get_github_url(repo_path)?;
+    let github_url = match get_github_url(repo_path) {
+        Ok(url) => url,
+        Err(e) => {
+            let (_, path) = super::forge::origin_url(repo_path)
+                .ok()
+                .and_then(|url| super::forge::parse_remote_url(&url))
+                .ok_or(e)?;
+            let (owner, repo) = path.rsplit_once('/').unwrap_or_default();
+            return Ok(RepoIdentifier {
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+            });
+        }
+    };
 
     // Parse owner/repo from URL: https://github.com/owner/repo
     let url_without_prefix = github_url
@@ -53,7 +72,7 @@ pub fn get_repo_identifier(repo_path: &str) -> Result<RepoIdentifier, String> {
 ///
 /// On Windows, PowerShell doesn't have a login mode concept.
 #[cfg(unix)]
-fn get_user_shell() -> (String, bool) {
+pub(crate) fn get_user_shell() -> (String, bool) {
     let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
 
     // Check if shell supports -l (login) flag
@@ -67,7 +86,7 @@ fn get_user_shell() -> (String, bool) {
 }
 
 #[cfg(windows)]
-fn get_user_shell() -> (String, bool) {
+pub(crate) fn get_user_shell() -> (String, bool) {
     // Windows PowerShell doesn't have a login mode concept
     ("powershell.exe".to_string(), false)
 }
@@ -256,12 +275,14 @@ pub struct GitRemote {
     pub name: String,
 }
 
-/// A GitHub remote with its name and resolved HTTPS URL
+/// A forge remote with its name and resolved HTTPS URL
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GitHubRemote {
     pub name: String,
     pub url: String,
+    /// Forge hosting the remote (its web URL layout differs, e.g. for branches)
+    pub forge: ForgeKind,
 }
 
 /// Convert a raw git remote URL to a GitHub HTTPS URL, if possible
@@ -304,8 +325,12 @@ pub fn get_github_url(repo_path: &str) -> Result<String, String> {
     get_github_url_for_remote(repo_path, "origin")
 }
 
-/// Get all GitHub remotes for a repository
-pub fn get_github_remotes(repo_path: &str) -> Result<Vec<GitHubRemote>, String> {
+/// Get all forge remotes (GitHub, GitLab, Gitea/Forgejo) for a repository,
+/// applying the project's `forge`/`forge_url` overrides to self-hosted remotes
+pub fn get_github_remotes(
+    repo_path: &str,
+    project: Option<&Project>,
+) -> Result<Vec<GitHubRemote>, String> {
     let output = silent_command("git")
         .args(["remote"])
         .current_dir(repo_path)
@@ -334,7 +359,21 @@ pub fn get_github_remotes(repo_path: &str) -> Result<Vec<GitHubRemote>, String>
             if url_out.status.success() {
                 let raw = String::from_utf8_lossy(&url_out.stdout).trim().to_string();
                 if let Some(url) = normalize_github_url(&raw) {
-                    result.push(GitHubRemote { name, url });
+                    result.push(GitHubRemote {
+                        name,
+                        url,
+                        forge: ForgeKind::Github,
+                    });
+                } else if let Some(remote) = super::forge::remote_from_url(
+                    &raw,
+                    project.and_then(|p| p.forge),
+                    project.and_then(|p| p.forge_url.as_deref()),
+                ) {
+                    result.push(GitHubRemote {
+                        name,
+                        url: remote.web_url(),
+                        forge: remote.kind,
+                    });
                 }
             }
         }
@@ -1168,13 +1207,15 @@ pub fn create_worktree_from_existing_branch(
 /// - Checking out the actual PR branch
 ///
 /// Fetch a PR ref into a local branch name, bypassing gh cli.
-/// Used when the PR's head branch name collides with a locally checked-out branch.
+/// Used when the PR's head branch name collides with a locally checked-out branch,
+/// and for merge requests on forges other than GitHub.
 pub fn fetch_pr_to_branch(
     repo_path: &str,
     pr_number: u32,
+    forge: ForgeKind,
     local_branch: &str,
 ) -> Result<(), String> {
-    let refspec = format!("pull/{pr_number}/head:{local_branch}");
+    let refspec = format!("{}:{local_branch}", forge.pr_head_ref(pr_number));
     let output = silent_command("git")
         .args(["fetch", "origin", &refspec])
         .current_dir(repo_path)
@@ -1650,7 +1691,8 @@ pub fn generate_pr_context(repo_path: &str, target_branch: &str) -> Result<PrCon
 
 /// Read jean.json configuration from a worktree path
 ///
-/// Returns None if the file doesn't exist or can't be parsed
+/// Returns None if the file doesn't exist, can't be parsed or has a script,
+/// hook or service `cwd` outside the worktree
 pub fn read_jean_config(worktree_path: &str) -> Option<JeanConfig> {
     let config_path = Path::new(worktree_path).join("jean.json");
     if !config_path.exists() {
@@ -1659,8 +1701,16 @@ pub fn read_jean_config(worktree_path: &str) -> Option<JeanConfig> {
     }
 
     match std::fs::read_to_string(&config_path) {
-        Ok(content) => match serde_json::from_str(&content) {
+        Ok(content) => match super::jean_config::parse(&content) {
             Ok(config) => {
+                let errors = super::jean_config::cwd_errors(&config);
+                if !errors.is_empty() {
+                    log::warn!(
+                        "Ignoring jean.json in {worktree_path}: {}",
+                        errors.join("; ")
+                    );
+                    return None;
+                }
                 log::trace!("Successfully parsed jean.json from {worktree_path}");
                 Some(config)
             }
@@ -1678,34 +1728,113 @@ pub fn read_jean_config(worktree_path: &str) -> Option<JeanConfig> {
 
 /// Run a setup script in a worktree directory
 ///
-/// Executes the script using sh -c and captures output.
+/// Executes the script using sh -c, streaming and capturing its output.
 /// Sets environment variables for use in the script:
 /// - JEAN_WORKSPACE_PATH: Path to the newly created worktree
 /// - JEAN_ROOT_PATH: Path to the repository root directory
 /// - JEAN_BRANCH: Current branch name
+/// - JEAN_PORT, JEAN_PORT_1, ...: Ports allocated to the worktree (if any)
 pub fn run_setup_script(
+    app: &AppHandle,
     worktree_path: &str,
     root_path: &str,
     branch: &str,
     script: &str,
+    ports: Option<&PortRange>,
 ) -> Result<String, String> {
-    run_jean_script("setup", worktree_path, root_path, branch, script)
+    let env = jean_env(worktree_path, root_path, ports);
+    run_jean_script(
+        app,
+        "setup",
+        worktree_path,
+        root_path,
+        branch,
+        script,
+        &env,
+        None,
+    )
 }
 
 /// Run a teardown script in a worktree directory before deletion
 ///
-/// Executes the script using sh -c and captures output.
+/// Executes the script using sh -c, streaming and capturing its output.
 /// Sets environment variables for use in the script:
 /// - JEAN_WORKSPACE_PATH: Path to the worktree being deleted
 /// - JEAN_ROOT_PATH: Path to the repository root directory
 /// - JEAN_BRANCH: Branch name of the worktree
+/// - JEAN_PORT, JEAN_PORT_1, ...: Ports allocated to the worktree (if any)
 pub fn run_teardown_script(
+    app: &AppHandle,
     worktree_path: &str,
     root_path: &str,
     branch: &str,
     script: &str,
+    ports: Option<&PortRange>,
 ) -> Result<String, String> {
-    run_jean_script("teardown", worktree_path, root_path, branch, script)
+    let env = jean_env(worktree_path, root_path, ports);
+    run_jean_script(
+        app,
+        "teardown",
+        worktree_path,
+        root_path,
+        branch,
+        script,
+        &env,
+        None,
+    )
+}
+
+/// Run a jean.json lifecycle hook from the project root's jean.json.
+///
+/// Hooks run unsandboxed on the host, so they're never taken from the
+/// worktree's jean.json, which the agent can edit. Returns `Ok(None)` when
+/// the hook isn't configured, the hook's output otherwise.
+pub fn run_hook(
+    app: &AppHandle,
+    hook: JeanHook,
+    worktree_path: &str,
+    root_path: &str,
+    branch: &str,
+    ports: Option<&PortRange>,
+) -> Result<Option<String>, String> {
+    let Some(config) = read_jean_config(root_path) else {
+        return Ok(None);
+    };
+    let Some(entry) = config.hooks.get(hook) else {
+        return Ok(None);
+    };
+    let mut env = super::jean_config::script_env(&config, Some(entry));
+    env.extend(port_env(ports));
+    run_jean_script(
+        app,
+        hook.as_str(),
+        worktree_path,
+        root_path,
+        branch,
+        entry.command(),
+        &env,
+        entry.cwd(),
+    )
+    .map(Some)
+}
+
+/// Top-level jean.json `env` plus the worktree's ports for setup/teardown scripts
+fn jean_env(
+    worktree_path: &str,
+    root_path: &str,
+    ports: Option<&PortRange>,
+) -> BTreeMap<String, String> {
+    let mut env = read_jean_config(worktree_path)
+        .or_else(|| read_jean_config(root_path))
+        .map(|config| config.env)
+        .unwrap_or_default();
+    env.extend(port_env(ports));
+    env
+}
+
+/// `JEAN_PORT`, `JEAN_PORT_1`, ... for a worktree's allocated ports
+fn port_env(ports: Option<&PortRange>) -> Vec<(String, String)> {
+    ports.map(PortRange::env_vars).unwrap_or_default()
 }
 
 /// Validate that environment variables passed to jean.json scripts are safe.
@@ -1737,50 +1866,84 @@ fn validate_script_env(worktree_path: &str, root_path: &str, branch: &str) -> Re
     Ok(())
 }
 
+/// Working directory for a script's `cwd`, which must resolve (following
+/// symlinks) to a directory inside the worktree
+fn script_workdir(worktree_path: &str, cwd: &str) -> Result<String, String> {
+    let worktree = Path::new(worktree_path)
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve worktree {worktree_path}: {e}"))?;
+    let workdir = worktree
+        .join(cwd)
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve script cwd `{cwd}`: {e}"))?;
+    if !workdir.starts_with(&worktree) {
+        return Err(format!("Script cwd `{cwd}` is outside the worktree"));
+    }
+    Ok(workdir.to_string_lossy().to_string())
+}
+
 /// Shared implementation for running jean.json setup/teardown scripts.
 ///
 /// Validates environment variables, then executes the script in the user's
-/// login shell with JEAN_WORKSPACE_PATH, JEAN_ROOT_PATH, and JEAN_BRANCH set.
+/// login shell (or the worktree's container when jean.json declares one) with
+/// JEAN_WORKSPACE_PATH, JEAN_ROOT_PATH, JEAN_BRANCH and the script's `env` set,
+/// in `cwd` (relative to the worktree) when given. Output is streamed as
+/// events and the script is killed after its jean.json timeout (see
+/// `script_runner`).
+#[allow(clippy::too_many_arguments)]
 fn run_jean_script(
+    app: &AppHandle,
     kind: &str,
     worktree_path: &str,
     root_path: &str,
     branch: &str,
     script: &str,
+    env: &BTreeMap<String, String>,
+    cwd: Option<&str>,
 ) -> Result<String, String> {
     log::trace!("Running {kind} script in {worktree_path}: {script}");
 
     validate_script_env(worktree_path, root_path, branch)?;
 
-    let (shell, supports_login) = get_user_shell();
-    log::trace!("Using shell: {shell} (login mode: {supports_login})");
+    let workdir = match cwd {
+        Some(dir) => script_workdir(worktree_path, dir)?,
+        None => worktree_path.to_string(),
+    };
 
-    let mut cmd = silent_command(&shell);
-    if supports_login {
-        cmd.args(["-l", "-i", "-c", script]);
+    let container = super::container::ensure_container(app, worktree_path)?;
+    let container_pid_file = container
+        .as_ref()
+        .map(|_| super::container::script_pid_file(kind));
+    let mut cmd = if let (Some(container), Some(pid_file)) = (&container, &container_pid_file) {
+        log::trace!("Running {kind} script in container {}", container.name);
+        let mut env_keys = vec!["JEAN_WORKSPACE_PATH", "JEAN_ROOT_PATH", "JEAN_BRANCH"];
+        env_keys.extend(env.keys().map(String::as_str));
+        let args = container.tracked_command_prefix(&workdir, pid_file, &env_keys);
+        let mut cmd = silent_command(&args[0]);
+        cmd.args(&args[1..]).args(["sh", "-c", script]);
+        cmd
     } else {
-        cmd.args(["-c", script]);
-    }
+        let (shell, supports_login) = get_user_shell();
+        log::trace!("Using shell: {shell} (login mode: {supports_login})");
 
-    let output = cmd
-        .current_dir(worktree_path)
+        let mut cmd = silent_command(&shell);
+        if supports_login {
+            cmd.args(["-l", "-i", "-c", script]);
+        } else {
+            cmd.args(["-c", script]);
+        }
+        cmd
+    };
+
+    cmd.current_dir(&workdir)
+        .envs(env)
         .env("JEAN_WORKSPACE_PATH", worktree_path)
         .env("JEAN_ROOT_PATH", root_path)
-        .env("JEAN_BRANCH", branch)
-        .output()
-        .map_err(|e| format!("Failed to run {kind} script: {e}"))?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-    if !output.status.success() {
-        let combined = format!("{stdout}{stderr}").trim().to_string();
-        return Err(format!("{kind} script failed:\n{combined}"));
-    }
+        .env("JEAN_BRANCH", branch);
 
-    let combined = format!("{stdout}{stderr}").trim().to_string();
-    log::trace!("{kind} script completed successfully");
-    Ok(combined)
+    let config = read_jean_config(worktree_path).or_else(|| read_jean_config(root_path));
+    let timeout = super::script_runner::timeout_for(config.as_ref(), kind);
+    super::script_runner::run(app, kind, worktree_path, cmd, timeout, container_pid_file)
 }
 
 /// Check if there are uncommitted changes (staged or unstaged)
@@ -1816,42 +1979,84 @@ pub fn rebase_onto_base(
     log::trace!("Starting rebase onto {base_branch} in {repo_path}");
 
     // Step 1: Check for uncommitted changes and commit if needed
-    if has_uncommitted_changes(repo_path) {
-        let message = commit_message.unwrap_or("WIP: Committing changes before rebase");
-        log::trace!("Committing uncommitted changes: {message}");
+    commit_before_rebase(repo_path, commit_message)?;
 
-        // Stage all changes
-        let add_output = silent_command("git")
-            .args(["add", "-A"])
-            .current_dir(repo_path)
-            .output()
-            .map_err(|e| format!("Failed to stage changes: {e}"))?;
+    // Step 2: Fetch from origin
+    fetch_branch(repo_path, base_branch)?;
 
-        if !add_output.status.success() {
-            let stderr = String::from_utf8_lossy(&add_output.stderr);
-            return Err(format!("Failed to stage changes: {stderr}"));
-        }
+    // Step 3: Rebase onto origin/{base_branch}
+    rebase_branch_onto(repo_path, &format!("origin/{base_branch}"), None)?;
 
-        // Commit
-        let commit_output = silent_command("git")
-            .args(["commit", "-m", message])
-            .current_dir(repo_path)
-            .output()
-            .map_err(|e| format!("Failed to commit changes: {e}"))?;
+    // Step 4: Force push with lease
+    force_push_rebased(repo_path)?;
 
-        if !commit_output.status.success() {
-            let stderr = String::from_utf8_lossy(&commit_output.stderr);
-            // Not an error if nothing to commit
-            if !stderr.contains("nothing to commit") {
-                return Err(format!("Failed to commit changes: {stderr}"));
-            }
-        }
+    log::trace!("Rebase completed successfully");
+    Ok("Rebase completed successfully".to_string())
+}
+
+/// Restack a stacked branch onto its (possibly rewritten) parent
+///
+/// Replays only the commits made on top of `old_base` (the parent commit the
+/// branch was last stacked on) onto `onto`, so commits that came from the
+/// parent aren't replayed twice after the parent was rebased or squash-merged.
+/// Falls back to a plain rebase when `old_base` is unknown or no longer in the
+/// branch's history.
+///
+/// Uncommitted changes are committed first, and the result is force pushed
+/// when `push` is set.
+pub fn restack_onto(
+    repo_path: &str,
+    onto: &str,
+    old_base: Option<&str>,
+    commit_message: Option<&str>,
+    push: bool,
+) -> Result<String, String> {
+    log::trace!("Restacking {repo_path} onto {onto} (old base: {old_base:?})");
+
+    commit_before_rebase(repo_path, commit_message)?;
+
+    let upstream = old_base.filter(|sha| is_ancestor(repo_path, sha, "HEAD"));
+    rebase_branch_onto(repo_path, onto, upstream)?;
+
+    if push {
+        force_push_rebased(repo_path)?;
     }
 
-    // Step 2: Fetch from origin
-    log::trace!("Fetching from origin...");
+    log::trace!("Restack completed successfully");
+    Ok(format!("Restacked onto {onto}"))
+}
+
+/// Resolve a ref to its full commit SHA
+pub fn rev_parse(repo_path: &str, rev: &str) -> Result<String, String> {
+    let output = silent_command("git")
+        .args(["rev-parse", "--verify", &format!("{rev}^{{commit}}")])
+        .current_dir(repo_path)
+        .output()
+        .map_err(|e| format!("Failed to run git rev-parse: {e}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to resolve {rev}: {stderr}"));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Check whether `ancestor` is reachable from `descendant`
+pub fn is_ancestor(repo_path: &str, ancestor: &str, descendant: &str) -> bool {
+    silent_command("git")
+        .args(["merge-base", "--is-ancestor", ancestor, descendant])
+        .current_dir(repo_path)
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Fetch a single branch from origin
+pub fn fetch_branch(repo_path: &str, branch: &str) -> Result<(), String> {
+    log::trace!("Fetching {branch} from origin...");
     let fetch_output = silent_command("git")
-        .args(["fetch", "origin", base_branch])
+        .args(["fetch", "origin", branch])
         .current_dir(repo_path)
         .output()
         .map_err(|e| format!("Failed to fetch from origin: {e}"))?;
@@ -1860,11 +2065,60 @@ pub fn rebase_onto_base(
         let stderr = String::from_utf8_lossy(&fetch_output.stderr);
         return Err(format!("Failed to fetch from origin: {stderr}"));
     }
+    Ok(())
+}
+
+/// Commit all uncommitted changes before a rebase (no-op on a clean tree)
+fn commit_before_rebase(repo_path: &str, commit_message: Option<&str>) -> Result<(), String> {
+    if !has_uncommitted_changes(repo_path) {
+        return Ok(());
+    }
 
-    // Step 3: Rebase onto origin/{base_branch}
-    log::trace!("Rebasing onto origin/{base_branch}...");
+    let message = commit_message.unwrap_or("WIP: Committing changes before rebase");
+    log::trace!("Committing uncommitted changes: {message}");
+
+    // Stage all changes
+    let add_output = silent_command("git")
+        .args(["add", "-A"])
+        .current_dir(repo_path)
+        .output()
+        .map_err(|e| format!("Failed to stage changes: {e}"))?;
+
+    if !add_output.status.success() {
+        let stderr = String::from_utf8_lossy(&add_output.stderr);
+        return Err(format!("Failed to stage changes: {stderr}"));
+    }
+
+    // Commit
+    let commit_output = silent_command("git")
+        .args(["commit", "-m", message])
+        .current_dir(repo_path)
+        .output()
+        .map_err(|e| format!("Failed to commit changes: {e}"))?;
+
+    if !commit_output.status.success() {
+        let stderr = String::from_utf8_lossy(&commit_output.stderr);
+        // Not an error if nothing to commit
+        if !stderr.contains("nothing to commit") {
+            return Err(format!("Failed to commit changes: {stderr}"));
+        }
+    }
+    Ok(())
+}
+
+/// Rebase the current branch onto `onto`, aborting on failure.
+///
+/// With an `upstream`, runs `git rebase --onto <onto> <upstream>` so only the
+/// commits after `upstream` are replayed.
+fn rebase_branch_onto(repo_path: &str, onto: &str, upstream: Option<&str>) -> Result<(), String> {
+    log::trace!("Rebasing onto {onto}...");
+    let mut args = vec!["rebase"];
+    match upstream {
+        Some(upstream) => args.extend(["--onto", onto, upstream]),
+        None => args.push(onto),
+    }
     let rebase_output = silent_command("git")
-        .args(["rebase", &format!("origin/{base_branch}")])
+        .args(&args)
         .current_dir(repo_path)
         .output()
         .map_err(|e| format!("Failed to rebase: {e}"))?;
@@ -1880,8 +2134,11 @@ pub fn rebase_onto_base(
             "Rebase failed (conflicts likely). Rebase has been aborted.\n{stderr}"
         ));
     }
+    Ok(())
+}
 
-    // Step 4: Force push with lease
+/// Force push a rebased branch with lease, setting the upstream if it has none
+fn force_push_rebased(repo_path: &str) -> Result<(), String> {
     log::trace!("Force pushing with lease...");
     let push_output = silent_command("git")
         .args(["push", "--force-with-lease"])
@@ -1909,8 +2166,7 @@ pub fn rebase_onto_base(
         }
     }
 
-    log::trace!("Rebase completed successfully");
-    Ok("Rebase completed successfully".to_string())
+    Ok(())
 }
 
 // =============================================================================
@@ -2385,4 +2641,28 @@ mod tests {
         };
         assert_eq!(id.to_key(), "my-org-my-project");
     }
+
+    // ========================================================================
+    // Script cwd tests
+    // ========================================================================
+
+    #[cfg(unix)]
+    #[test]
+    fn test_script_workdir_stays_in_worktree() {
+        let worktree = std::env::temp_dir().join(format!("jean-cwd-{}", std::process::id()));
+        std::fs::create_dir_all(worktree.join("packages/app")).unwrap();
+        std::os::unix::fs::symlink("/", worktree.join("root")).unwrap();
+        let path = worktree.to_string_lossy().to_string();
+
+        let inside = script_workdir(&path, "packages/app");
+        let parent = script_workdir(&path, "..");
+        let absolute = script_workdir(&path, "/");
+        let symlink = script_workdir(&path, "root");
+        let _ = std::fs::remove_dir_all(&worktree);
+
+        assert!(inside.unwrap().ends_with("packages/app"));
+        assert!(parent.is_err());
+        assert!(absolute.is_err());
+        assert!(symlink.is_err());
+    }
 }
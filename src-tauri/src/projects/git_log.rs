@@ -1,6 +1,7 @@
-use crate::platform::silent_command;
-use serde::Serialize;
+use crate::platform::{shell_escape, silent_command};
+use serde::{Deserialize, Serialize};
 
+use super::conflicts::{self, ConflictOperation, ConflictState};
 use super::git_status::{parse_unified_diff, GitDiff};
 
 /// Metadata for a single commit
@@ -195,3 +196,381 @@ pub fn get_commit_diff(repo_path: &str, commit_sha: &str) -> Result<GitDiff, Str
         raw_patch,
     })
 }
+
+// =============================================================================
+// Rebase Plans
+// =============================================================================
+
+/// What to do with a commit in a rebase plan
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RebaseAction {
+    Pick,
+    /// Keep the commit with a new message
+    Reword,
+    /// Fold into the previous commit, combining messages
+    Squash,
+    /// Fold into the previous commit, discarding this message
+    Fixup,
+    Drop,
+}
+
+/// One commit in a rebase plan, in the order it should be applied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebaseStep {
+    pub sha: String,
+    pub action: RebaseAction,
+    /// Full commit message. Required for `reword`; on a `squash` it replaces
+    /// the combined message of the squashed commit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// The commits on a branch that a rebase plan can rewrite
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebasePlan {
+    /// Commit the plan is applied onto (the merge base with the target branch)
+    pub base: String,
+    /// Oldest first, all `pick`, with their current messages
+    pub steps: Vec<RebaseStep>,
+}
+
+/// Outcome of applying a rebase plan
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status")]
+pub enum RebasePlanResult {
+    /// The plan was applied; `head` is the new branch tip
+    Success { head: String },
+    /// The rebase stopped on conflicts (or a failed step) and is still in
+    /// progress; resolve and continue or abort it with the conflict commands
+    Conflict {
+        message: String,
+        state: ConflictState,
+    },
+}
+
+/// Directory (inside the worktree's git dir) holding the todo list and the
+/// commit messages used while a plan is applied
+const REBASE_PLAN_DIR: &str = "jean-rebase-plan";
+
+pub(crate) fn run_git(repo_path: &str, args: &[&str]) -> Result<String, String> {
+    let output = silent_command("git")
+        .args(args)
+        .current_dir(repo_path)
+        .output()
+        .map_err(|e| format!("Failed to run git {}: {e}", args[0]))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git {} failed: {}", args[0], stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Full SHA of a commit named by the client. Anything that looks like an
+/// option (e.g. `--output=<path>`) is rejected before it reaches git.
+pub(crate) fn resolve_commit(repo_path: &str, rev: &str) -> Result<String, String> {
+    if rev.is_empty() || rev.starts_with('-') {
+        return Err(format!("Not a commit: {rev}"));
+    }
+    let commit = format!("{rev}^{{commit}}");
+    run_git(repo_path, &["rev-parse", "--verify", "--quiet", &commit])
+        .map(|sha| sha.trim().to_string())
+        .map_err(|_| format!("Not a commit: {rev}"))
+}
+
+/// Full message of a commit
+fn commit_message(repo_path: &str, sha: &str) -> Result<String, String> {
+    run_git(repo_path, &["log", "-1", "--format=%B", sha]).map(|m| m.trim_end().to_string())
+}
+
+/// List the commits between the merge base with `base_ref` and HEAD as an
+/// all-`pick` plan
+pub fn get_rebase_plan(repo_path: &str, base_ref: &str) -> Result<RebasePlan, String> {
+    let base = run_git(repo_path, &["merge-base", "HEAD", base_ref])?
+        .trim()
+        .to_string();
+    let range = format!("{base}..HEAD");
+
+    if !run_git(repo_path, &["rev-list", "--merges", &range])?
+        .trim()
+        .is_empty()
+    {
+        return Err(
+            "The branch contains merge commits, which can't be rewritten by a rebase plan"
+                .to_string(),
+        );
+    }
+
+    // Records are separated by RS (0x1e), fields by NUL
+    let log = run_git(
+        repo_path,
+        &["log", "--reverse", "--format=%H%x00%B%x1e", &range],
+    )?;
+    let steps = log
+        .split('\x1e')
+        .filter_map(|record| {
+            let (sha, message) = record.trim_start_matches('\n').split_once('\x00')?;
+            Some(RebaseStep {
+                sha: sha.to_string(),
+                action: RebaseAction::Pick,
+                message: Some(message.trim_end().to_string()),
+            })
+        })
+        .collect();
+
+    Ok(RebasePlan { base, steps })
+}
+
+/// Build the rebase todo list for a plan.
+///
+/// Rewording and squashing would normally open an editor, so those commits are
+/// picked/fixed up and then amended by an `exec` line with the final message,
+/// read from a file written next to the todo list. Returns the todo list and
+/// the message files to write (name, content).
+fn build_todo(
+    steps: &[RebaseStep],
+    plan_dir: &str,
+    original_message: impl Fn(&str) -> Result<String, String>,
+) -> Result<(String, Vec<(String, String)>), String> {
+    let mut todo = String::new();
+    let mut messages: Vec<(String, String)> = Vec::new();
+
+    // Message of the commit being built (the last pick/reword and what was folded into it)
+    struct Group {
+        message: String,
+        explicit: Option<String>,
+        amend: bool,
+    }
+    let mut group: Option<Group> = None;
+
+    let mut flush = |group: Option<Group>, todo: &mut String| {
+        let Some(group) = group.filter(|g| g.amend) else {
+            return;
+        };
+        let name = format!("message-{}", messages.len());
+        let path = format!("{plan_dir}/{name}");
+        todo.push_str(&format!(
+            "exec git commit --amend --allow-empty --no-verify --quiet -F {}\n",
+            shell_escape(&path)
+        ));
+        messages.push((name, group.explicit.unwrap_or(group.message)));
+    };
+
+    for step in steps {
+        let sha = step.sha.as_str();
+        match step.action {
+            RebaseAction::Drop => todo.push_str(&format!("drop {sha}\n")),
+            RebaseAction::Pick | RebaseAction::Reword => {
+                flush(group.take(), &mut todo);
+                let reword = step.action == RebaseAction::Reword;
+                let message = if reword {
+                    step.message
+                        .clone()
+                        .filter(|m| !m.trim().is_empty())
+                        .ok_or_else(|| format!("Reword of {sha} needs a message"))?
+                } else {
+                    original_message(sha)?
+                };
+                todo.push_str(&format!("pick {sha}\n"));
+                group = Some(Group {
+                    message,
+                    explicit: None,
+                    amend: reword,
+                });
+            }
+            RebaseAction::Squash | RebaseAction::Fixup => {
+                let current = group.as_mut().ok_or_else(|| {
+                    format!("Can't fold {sha} into an earlier commit: it is the first commit in the plan")
+                })?;
+                todo.push_str(&format!("fixup {sha}\n"));
+                if step.action == RebaseAction::Squash {
+                    current.message = format!("{}\n\n{}", current.message, original_message(sha)?);
+                    current.amend = true;
+                    if let Some(message) = step.message.clone().filter(|m| !m.trim().is_empty()) {
+                        current.explicit = Some(message);
+                    }
+                }
+            }
+        }
+    }
+    flush(group.take(), &mut todo);
+
+    Ok((todo, messages))
+}
+
+/// Apply a rebase plan on top of `base`.
+///
+/// The plan must list every commit in `base..HEAD` exactly once (in any
+/// order). The todo list is handed to `git rebase -i` through
+/// `GIT_SEQUENCE_EDITOR`, so no editor is opened.
+pub fn apply_rebase_plan(
+    repo_path: &str,
+    base: &str,
+    steps: &[RebaseStep],
+) -> Result<RebasePlanResult, String> {
+    if let Some(operation) = conflicts::detect_operation(repo_path) {
+        return Err(format!(
+            "A {} is already in progress. Continue or abort it first.",
+            operation.command()
+        ));
+    }
+    if super::git::has_uncommitted_changes(repo_path) {
+        return Err("Commit or stash your changes before rewriting history".to_string());
+    }
+
+    // Resolve abbreviated SHAs and check the plan covers base..HEAD exactly
+    let mut steps = steps.to_vec();
+    for step in &mut steps {
+        step.sha = super::git::rev_parse(repo_path, &step.sha)?;
+    }
+    let mut expected: Vec<String> = run_git(repo_path, &["rev-list", &format!("{base}..HEAD")])?
+        .lines()
+        .map(str::to_string)
+        .collect();
+    let mut planned: Vec<String> = steps.iter().map(|s| s.sha.clone()).collect();
+    expected.sort();
+    planned.sort();
+    if expected != planned {
+        return Err(
+            "The rebase plan doesn't match the branch's commits. Reload it and try again."
+                .to_string(),
+        );
+    }
+
+    let plan_dir = conflicts::git_path(repo_path, REBASE_PLAN_DIR)
+        .ok_or_else(|| "Failed to locate the git directory".to_string())?;
+    let _ = std::fs::remove_dir_all(&plan_dir);
+    std::fs::create_dir_all(&plan_dir)
+        .map_err(|e| format!("Failed to create rebase plan directory: {e}"))?;
+    // Forward slashes so the paths survive Git for Windows' sh
+    let plan_dir_str = plan_dir.to_string_lossy().replace('\\', "/");
+
+    let (todo, messages) = build_todo(&steps, &plan_dir_str, |sha| commit_message(repo_path, sha))?;
+    for (name, message) in &messages {
+        std::fs::write(plan_dir.join(name), message)
+            .map_err(|e| format!("Failed to write commit message: {e}"))?;
+    }
+    let todo_path = plan_dir.join("todo");
+    std::fs::write(&todo_path, &todo).map_err(|e| format!("Failed to write rebase todo: {e}"))?;
+    let todo_path = todo_path.to_string_lossy().replace('\\', "/");
+
+    log::trace!("Applying rebase plan onto {base}:\n{todo}");
+    let output = silent_command("git")
+        .args(["rebase", "-i", "--no-autosquash", base])
+        .current_dir(repo_path)
+        .env(
+            "GIT_SEQUENCE_EDITOR",
+            format!("cp {}", shell_escape(&todo_path)),
+        )
+        .env("GIT_EDITOR", "true")
+        .output()
+        .map_err(|e| format!("Failed to run git rebase: {e}"))?;
+
+    if output.status.success() {
+        cleanup_rebase_plan(repo_path);
+        let head = super::git::rev_parse(repo_path, "HEAD")?;
+        return Ok(RebasePlanResult::Success { head });
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if conflicts::detect_operation(repo_path) == Some(ConflictOperation::Rebase) {
+        // Message files stay until the rebase is continued or aborted
+        let state = conflicts::conflict_state(repo_path)?;
+        return Ok(RebasePlanResult::Conflict {
+            message: stderr,
+            state,
+        });
+    }
+
+    cleanup_rebase_plan(repo_path);
+    Err(format!("Rebase failed: {stderr}"))
+}
+
+/// Remove the files written for a rebase plan once no rebase is in progress
+pub fn cleanup_rebase_plan(repo_path: &str) {
+    if conflicts::detect_operation(repo_path).is_some() {
+        return;
+    }
+    if let Some(plan_dir) = conflicts::git_path(repo_path, REBASE_PLAN_DIR) {
+        let _ = std::fs::remove_dir_all(plan_dir);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_commit_rejects_options() {
+        let dir = std::env::temp_dir().to_string_lossy().to_string();
+        assert!(resolve_commit(&dir, "--output=/tmp/pwned").is_err());
+        assert!(resolve_commit(&dir, "").is_err());
+    }
+
+    fn step(sha: &str, action: RebaseAction, message: Option<&str>) -> RebaseStep {
+        RebaseStep {
+            sha: sha.to_string(),
+            action,
+            message: message.map(str::to_string),
+        }
+    }
+
+    fn original(sha: &str) -> Result<String, String> {
+        Ok(format!("msg {sha}"))
+    }
+
+    #[test]
+    fn test_build_todo_squash_and_reword() {
+        let steps = vec![
+            step("a", RebaseAction::Pick, None),
+            step("b", RebaseAction::Squash, None),
+            step("c", RebaseAction::Fixup, None),
+            step("d", RebaseAction::Drop, None),
+            step("e", RebaseAction::Reword, Some("new e")),
+            step("f", RebaseAction::Pick, None),
+        ];
+        let (todo, messages) = build_todo(&steps, "/plan", original).unwrap();
+        assert_eq!(
+            todo,
+            "pick a\nfixup b\nfixup c\ndrop d\n\
+             exec git commit --amend --allow-empty --no-verify --quiet -F '/plan/message-0'\n\
+             pick e\n\
+             exec git commit --amend --allow-empty --no-verify --quiet -F '/plan/message-1'\n\
+             pick f\n"
+        );
+        assert_eq!(
+            messages,
+            vec![
+                ("message-0".to_string(), "msg a\n\nmsg b".to_string()),
+                ("message-1".to_string(), "new e".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_todo_explicit_squash_message_and_errors() {
+        let steps = vec![
+            step("a", RebaseAction::Pick, None),
+            step("b", RebaseAction::Squash, Some("combined")),
+        ];
+        let (_, messages) = build_todo(&steps, "/plan", original).unwrap();
+        assert_eq!(messages[0].1, "combined");
+
+        let leading_squash = vec![step("a", RebaseAction::Squash, None)];
+        assert!(build_todo(&leading_squash, "/plan", original).is_err());
+
+        let empty_reword = vec![step("a", RebaseAction::Reword, Some("  "))];
+        assert!(build_todo(&empty_reword, "/plan", original).is_err());
+
+        // Pure pick/fixup needs no amend
+        let fixup_only = vec![
+            step("a", RebaseAction::Pick, None),
+            step("b", RebaseAction::Fixup, None),
+        ];
+        let (todo, messages) = build_todo(&fixup_only, "/plan", original).unwrap();
+        assert_eq!(todo, "pick a\nfixup b\n");
+        assert!(messages.is_empty());
+    }
+}
@@ -477,7 +477,7 @@ pub struct DiffFile {
 /// Complete diff response
 #[derive(Debug, Clone, Serialize)]
 pub struct GitDiff {
-    /// Type of diff: "uncommitted" or "branch"
+    /// Type of diff: "uncommitted", "staged", "unstaged", "branch" or "commit"
     pub diff_type: String,
     /// Base ref (e.g., "origin/main" or "HEAD")
     pub base_ref: String,
@@ -599,7 +599,9 @@ pub fn parse_unified_diff(raw_output: &str) -> (Vec<DiffFile>, String) {
                     lines: Vec::new(),
                 });
             }
-        } else if line.starts_with('+') && !line.starts_with("+++") {
+        } else if line.starts_with('+') && current_hunk.is_some() {
+            // Inside a hunk even "+++"/"---" lines are content; the file
+            // headers come before the first "@@" of each file
             if let Some(ref mut hunk) = current_hunk {
                 hunk.lines.push(DiffLine {
                     line_type: "addition".to_string(),
@@ -612,7 +614,7 @@ pub fn parse_unified_diff(raw_output: &str) -> (Vec<DiffFile>, String) {
                     file.additions += 1;
                 }
             }
-        } else if line.starts_with('-') && !line.starts_with("---") {
+        } else if line.starts_with('-') && current_hunk.is_some() {
             if let Some(ref mut hunk) = current_hunk {
                 hunk.lines.push(DiffLine {
                     line_type: "deletion".to_string(),
@@ -655,7 +657,9 @@ pub fn parse_unified_diff(raw_output: &str) -> (Vec<DiffFile>, String) {
 
 /// Get detailed diff content for a repository
 ///
-/// `diff_type` can be "uncommitted" (working directory vs HEAD) or "branch" (HEAD vs base branch)
+/// `diff_type` can be "uncommitted" (working directory vs HEAD), "staged"
+/// (index vs HEAD), "unstaged" (working directory vs index) or "branch"
+/// (HEAD vs base branch)
 pub fn get_git_diff(
     repo_path: &str,
     diff_type: &str,
@@ -670,6 +674,16 @@ pub fn get_git_diff(
             "working directory".to_string(),
             vec!["diff", "HEAD", "--unified=3"],
         ),
+        "staged" => (
+            "HEAD".to_string(),
+            "index".to_string(),
+            vec!["diff", "--cached", "--unified=3"],
+        ),
+        "unstaged" => (
+            "index".to_string(),
+            "working directory".to_string(),
+            vec!["diff", "--unified=3"],
+        ),
         "branch" => {
             let origin_ref = format!("origin/{base}");
             (
@@ -698,8 +712,8 @@ pub fn get_git_diff(
     // Build raw patch - start with git diff output
     let mut raw_patch = raw_patch_base;
 
-    // For uncommitted diffs, also include untracked (new) files
-    if diff_type == "uncommitted" {
+    // For uncommitted and unstaged diffs, also include untracked (new) files
+    if diff_type == "uncommitted" || diff_type == "unstaged" {
         let untracked_files = get_untracked_files_diff(repo_path);
         files.extend(untracked_files);
 
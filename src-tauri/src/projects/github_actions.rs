@@ -1,5 +1,9 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use tauri::AppHandle;
+use tauri::{AppHandle, Manager};
 
 use crate::gh_cli::config::resolve_gh_binary;
 use crate::platform::silent_command;
@@ -8,6 +12,10 @@ use crate::platform::silent_command;
 // GitHub Actions Types
 // =============================================================================
 
+/// Fields requested from `gh run list` / `gh run view` for a `WorkflowRun`
+const WORKFLOW_RUN_FIELDS: &str =
+    "databaseId,name,displayTitle,status,conclusion,event,headBranch,createdAt,url,workflowName,attempt";
+
 /// A single workflow run from `gh run list`
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -22,6 +30,13 @@ pub struct WorkflowRun {
     pub created_at: String,
     pub url: String,
     pub workflow_name: String,
+    /// Run attempt, incremented by each re-run (1 for forges without re-runs)
+    #[serde(default = "first_attempt")]
+    pub attempt: u32,
+}
+
+fn first_attempt() -> u32 {
+    1
 }
 
 /// Result of listing workflow runs, includes failed count for badge display
@@ -32,6 +47,134 @@ pub struct WorkflowRunsResult {
     pub failed_count: u32,
 }
 
+/// An input of a `workflow_dispatch` trigger
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowInput {
+    pub name: String,
+    pub description: Option<String>,
+    pub required: bool,
+    pub default: Option<String>,
+    /// string, boolean, choice, number or environment
+    pub input_type: String,
+    /// Allowed values of a choice input
+    pub options: Vec<String>,
+}
+
+/// A workflow defined in the repository
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Workflow {
+    pub id: u64,
+    pub name: String,
+    /// Path of the workflow file, e.g. `.github/workflows/ci.yml`
+    pub path: String,
+    /// active, disabled_manually, ...
+    pub state: String,
+    /// Has a `workflow_dispatch` trigger (in the local copy of the file)
+    pub dispatchable: bool,
+    pub inputs: Vec<WorkflowInput>,
+}
+
+/// A step of a workflow job
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowStep {
+    pub number: u32,
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    pub started_at: Option<String>,
+    pub completed_at: Option<String>,
+    pub duration_secs: Option<u64>,
+}
+
+/// A check annotation (error/warning pinned to a file and line)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowAnnotation {
+    pub path: String,
+    pub start_line: Option<u32>,
+    pub end_line: Option<u32>,
+    /// notice, warning or failure
+    pub level: String,
+    pub title: Option<String>,
+    pub message: String,
+}
+
+/// A job of a workflow run
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowJob {
+    pub database_id: u64,
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    pub started_at: Option<String>,
+    pub completed_at: Option<String>,
+    pub duration_secs: Option<u64>,
+    pub url: String,
+    pub steps: Vec<WorkflowStep>,
+    /// Only fetched for jobs that didn't succeed
+    pub annotations: Vec<WorkflowAnnotation>,
+}
+
+/// An artifact uploaded by a workflow run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowArtifact {
+    pub id: u64,
+    pub name: String,
+    pub size_in_bytes: u64,
+    pub expired: bool,
+}
+
+/// A workflow run with its jobs, steps, annotations and artifacts
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowRunDetails {
+    pub database_id: u64,
+    pub name: String,
+    pub workflow_name: String,
+    pub display_title: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    pub event: String,
+    pub head_branch: String,
+    pub head_sha: String,
+    pub attempt: u32,
+    pub created_at: String,
+    pub updated_at: String,
+    pub duration_secs: Option<u64>,
+    pub url: String,
+    pub jobs: Vec<WorkflowJob>,
+    pub artifacts: Vec<WorkflowArtifact>,
+}
+
+/// A job log line matching a search
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogMatch {
+    /// 1-based line number in the job log
+    pub line_number: usize,
+    pub step: Option<String>,
+    pub line: String,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+/// An excerpt of a failed job's log around an error
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailureSnippet {
+    pub job_id: u64,
+    pub job_name: String,
+    pub step: Option<String>,
+    /// 1-based line number of the first line
+    pub start_line: usize,
+    pub lines: Vec<String>,
+}
+
 /// List GitHub Actions workflow runs for a repository
 ///
 /// Uses `gh run list` to fetch recent workflow runs.
@@ -45,14 +188,17 @@ pub async fn list_workflow_runs(
 ) -> Result<WorkflowRunsResult, String> {
     log::trace!("Listing workflow runs for {project_path} with branch: {branch:?}");
 
+    if let Some(forge) = super::forge::for_repo(&app, &project_path)? {
+        return super::forge::run(forge, move |f| f.list_pipelines(branch.as_deref())).await;
+    }
+
     let gh = resolve_gh_binary(&app);
 
     let mut args = vec![
         "run".to_string(),
         "list".to_string(),
         "--json".to_string(),
-        "databaseId,name,displayTitle,status,conclusion,event,headBranch,createdAt,url,workflowName"
-            .to_string(),
+        WORKFLOW_RUN_FIELDS.to_string(),
         "-L".to_string(),
         "30".to_string(),
     ];
@@ -107,6 +253,1025 @@ pub async fn list_workflow_runs(
     Ok(WorkflowRunsResult { runs, failed_count })
 }
 
+/// Failed workflow runs for a commit (used by the CI auto-fix watcher)
+pub fn failed_runs_for_commit(
+    gh: &std::path::Path,
+    repo_path: &str,
+    sha: &str,
+) -> Result<Vec<WorkflowRun>, String> {
+    let output = silent_command(gh)
+        .args([
+            "run",
+            "list",
+            "--commit",
+            sha,
+            "--json",
+            WORKFLOW_RUN_FIELDS,
+            "-L",
+            "30",
+        ])
+        .current_dir(repo_path)
+        .output()
+        .map_err(|e| format!("Failed to run gh run list: {e}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("gh run list failed: {}", stderr.trim()));
+    }
+
+    let runs: Vec<WorkflowRun> = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse gh response: {e}"))?;
+    Ok(runs
+        .into_iter()
+        .filter(|run| {
+            matches!(
+                run.conclusion.as_deref(),
+                Some("failure") | Some("startup_failure") | Some("timed_out")
+            )
+        })
+        .collect())
+}
+
+/// Logs of the failed steps of a workflow run (`gh run view --log-failed`)
+pub fn failed_run_log(
+    gh: &std::path::Path,
+    repo_path: &str,
+    run_id: u64,
+) -> Result<String, String> {
+    let output = silent_command(gh)
+        .args(["run", "view", &run_id.to_string(), "--log-failed"])
+        .current_dir(repo_path)
+        .output()
+        .map_err(|e| format!("Failed to run gh run view: {e}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("gh run view failed: {}", stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// =============================================================================
+// Run Details
+// =============================================================================
+
+/// Raw job from `gh run view --json jobs`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhJob {
+    database_id: u64,
+    name: String,
+    status: String,
+    #[serde(default)]
+    conclusion: String,
+    #[serde(default)]
+    started_at: String,
+    #[serde(default)]
+    completed_at: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    steps: Vec<GhStep>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhStep {
+    number: u32,
+    name: String,
+    status: String,
+    #[serde(default)]
+    conclusion: String,
+    #[serde(default)]
+    started_at: String,
+    #[serde(default)]
+    completed_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhRunView {
+    database_id: u64,
+    name: String,
+    workflow_name: String,
+    display_title: String,
+    status: String,
+    #[serde(default)]
+    conclusion: String,
+    event: String,
+    head_branch: String,
+    head_sha: String,
+    #[serde(default)]
+    attempt: u32,
+    created_at: String,
+    updated_at: String,
+    url: String,
+    #[serde(default)]
+    jobs: Vec<GhJob>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhAnnotation {
+    path: String,
+    start_line: Option<u32>,
+    end_line: Option<u32>,
+    annotation_level: String,
+    title: Option<String>,
+    message: String,
+}
+
+/// gh reports missing values as empty strings
+fn non_empty(value: String) -> Option<String> {
+    Some(value).filter(|v| !v.is_empty())
+}
+
+/// Unix seconds of an RFC 3339 UTC timestamp (`2026-01-02T03:04:05Z`,
+/// fractional seconds allowed). Returns None for gh's zero timestamp.
+fn parse_timestamp(value: &str) -> Option<i64> {
+    let value = value.strip_suffix('Z')?;
+    let (date, time) = value.split_once('T')?;
+    let mut date = date.splitn(3, '-').map(|p| p.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+    let time = time.split('.').next()?;
+    let mut time = time.splitn(3, ':').map(|p| p.parse::<i64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+    if year <= 1 {
+        return None;
+    }
+
+    // Days since the epoch (proleptic Gregorian calendar)
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    Some(days * 86_400 + hour * 3_600 + minute * 60 + second)
+}
+
+/// Seconds between two gh timestamps, if both are set
+fn duration_secs(started_at: &str, completed_at: &str) -> Option<u64> {
+    let started = parse_timestamp(started_at)?;
+    let completed = parse_timestamp(completed_at)?;
+    u64::try_from(completed - started).ok()
+}
+
+/// Run `gh` in the repository and return stdout, mapping common errors
+fn run_gh(gh: &Path, repo_path: &str, args: &[&str]) -> Result<Vec<u8>, String> {
+    let output = silent_command(gh)
+        .args(args)
+        .current_dir(repo_path)
+        .output()
+        .map_err(|e| format!("Failed to run gh {}: {e}", args[0]))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("gh auth login") || stderr.contains("authentication") {
+            return Err("GitHub CLI not authenticated. Run 'gh auth login' first.".to_string());
+        }
+        return Err(format!("gh {} failed: {}", args[0], stderr.trim()));
+    }
+    Ok(output.stdout)
+}
+
+fn fetch_annotations(
+    gh: &Path,
+    repo_path: &str,
+    job_id: u64,
+) -> Result<Vec<WorkflowAnnotation>, String> {
+    // A job's database ID is also its check run ID
+    let endpoint = format!("repos/{{owner}}/{{repo}}/check-runs/{job_id}/annotations");
+    let stdout = run_gh(gh, repo_path, &["api", &endpoint])?;
+    let raw: Vec<GhAnnotation> =
+        serde_json::from_slice(&stdout).map_err(|e| format!("Failed to parse annotations: {e}"))?;
+    Ok(raw
+        .into_iter()
+        .map(|a| WorkflowAnnotation {
+            path: a.path,
+            start_line: a.start_line,
+            end_line: a.end_line,
+            level: a.annotation_level,
+            title: a.title.filter(|t| !t.is_empty()),
+            message: a.message,
+        })
+        .collect())
+}
+
+fn fetch_artifacts(
+    gh: &Path,
+    repo_path: &str,
+    run_id: u64,
+) -> Result<Vec<WorkflowArtifact>, String> {
+    let endpoint = format!("repos/{{owner}}/{{repo}}/actions/runs/{run_id}/artifacts");
+    let stdout = run_gh(gh, repo_path, &["api", &endpoint, "--jq", ".artifacts"])?;
+    serde_json::from_slice(&stdout).map_err(|e| format!("Failed to parse artifacts: {e}"))
+}
+
+fn job_failed(job: &WorkflowJob) -> bool {
+    matches!(
+        job.conclusion.as_deref(),
+        Some("failure") | Some("timed_out") | Some("startup_failure")
+    )
+}
+
+/// Fetch a run with its jobs, steps, annotations and artifacts
+pub fn fetch_run_details(
+    gh: &Path,
+    repo_path: &str,
+    run_id: u64,
+) -> Result<WorkflowRunDetails, String> {
+    let stdout = run_gh(
+        gh,
+        repo_path,
+        &[
+            "run",
+            "view",
+            &run_id.to_string(),
+            "--json",
+            "databaseId,name,workflowName,displayTitle,status,conclusion,event,headBranch,headSha,attempt,createdAt,updatedAt,url,jobs",
+        ],
+    )?;
+    let run: GhRunView =
+        serde_json::from_slice(&stdout).map_err(|e| format!("Failed to parse gh response: {e}"))?;
+
+    let mut jobs: Vec<WorkflowJob> = run
+        .jobs
+        .into_iter()
+        .map(|job| WorkflowJob {
+            database_id: job.database_id,
+            duration_secs: duration_secs(&job.started_at, &job.completed_at),
+            name: job.name,
+            status: job.status,
+            conclusion: non_empty(job.conclusion),
+            started_at: non_empty(job.started_at),
+            completed_at: non_empty(job.completed_at),
+            url: job.url,
+            steps: job
+                .steps
+                .into_iter()
+                .map(|step| WorkflowStep {
+                    number: step.number,
+                    duration_secs: duration_secs(&step.started_at, &step.completed_at),
+                    name: step.name,
+                    status: step.status,
+                    conclusion: non_empty(step.conclusion),
+                    started_at: non_empty(step.started_at),
+                    completed_at: non_empty(step.completed_at),
+                })
+                .collect(),
+            annotations: Vec::new(),
+        })
+        .collect();
+
+    for job in jobs.iter_mut().filter(|j| {
+        !matches!(
+            j.conclusion.as_deref(),
+            None | Some("success") | Some("skipped")
+        )
+    }) {
+        match fetch_annotations(gh, repo_path, job.database_id) {
+            Ok(annotations) => job.annotations = annotations,
+            Err(e) => log::warn!(
+                "Failed to fetch annotations for job {}: {e}",
+                job.database_id
+            ),
+        }
+    }
+
+    let artifacts = fetch_artifacts(gh, repo_path, run_id).unwrap_or_else(|e| {
+        log::warn!("Failed to fetch artifacts for run {run_id}: {e}");
+        Vec::new()
+    });
+
+    Ok(WorkflowRunDetails {
+        database_id: run.database_id,
+        duration_secs: duration_secs(&run.created_at, &run.updated_at),
+        name: run.name,
+        workflow_name: run.workflow_name,
+        display_title: run.display_title,
+        status: run.status,
+        conclusion: non_empty(run.conclusion),
+        event: run.event,
+        head_branch: run.head_branch,
+        head_sha: run.head_sha,
+        attempt: run.attempt,
+        created_at: run.created_at,
+        updated_at: run.updated_at,
+        url: run.url,
+        jobs,
+        artifacts,
+    })
+}
+
+/// Get a workflow run with its jobs, steps (with durations), annotations of
+/// jobs that didn't succeed, and artifacts
+#[tauri::command]
+pub async fn get_workflow_run_details(
+    app: AppHandle,
+    project_path: String,
+    run_id: u64,
+) -> Result<WorkflowRunDetails, String> {
+    log::trace!("Getting details of workflow run {run_id} in {project_path}");
+    let gh = resolve_gh_binary(&app);
+    tokio::task::spawn_blocking(move || fetch_run_details(&gh, &project_path, run_id))
+        .await
+        .map_err(|e| format!("Workflow run task failed: {e}"))?
+}
+
+// =============================================================================
+// Job Logs
+// =============================================================================
+
+/// Cached job logs older than this are removed (logs of a job never change)
+const JOB_LOG_CACHE_MAX_AGE_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Lines of context before an error line in a failure snippet
+const SNIPPET_LINES_BEFORE: usize = 5;
+
+/// Lines of context after an error line in a failure snippet
+const SNIPPET_LINES_AFTER: usize = 15;
+
+/// Snippets kept per failed job
+const MAX_SNIPPETS_PER_JOB: usize = 5;
+
+/// Lines shown from the end of a failed step when no error line is recognized
+const FALLBACK_TAIL_LINES: usize = 30;
+
+/// Lines that usually mark the cause of a failure
+static ERROR_LINE: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
+    Regex::new(
+        r"(?i)(##\[error\]|\berror(\[\w+\])?:|\bfailed\b|\bFAIL\b|panicked at|exception|traceback|npm err!|assertion|exit code [1-9])",
+    )
+    .expect("valid regex")
+});
+
+/// A line of `gh run view --log` output: `<job>\t<step>\t<timestamp> <text>`
+#[derive(Debug, PartialEq)]
+pub struct LogLine<'a> {
+    pub step: Option<&'a str>,
+    pub text: &'a str,
+}
+
+/// Split a log line into its step and text, dropping the job name and timestamp
+pub fn split_log_line(line: &str) -> LogLine<'_> {
+    let mut parts = line.splitn(3, '\t');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(_job), Some(step), Some(rest)) => {
+            let text = match rest.split_once(' ') {
+                Some((ts, text)) if ts.ends_with('Z') && ts.contains('T') => text,
+                _ => rest,
+            };
+            LogLine {
+                step: Some(step),
+                text,
+            }
+        }
+        _ => LogLine {
+            step: None,
+            text: line,
+        },
+    }
+}
+
+fn job_log_cache_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {e}"))?
+        .join("workflow-logs");
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create workflow log cache: {e}"))?;
+    Ok(dir)
+}
+
+/// Remove cached logs that haven't been written for a week
+fn prune_job_log_cache(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let expired = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age.as_secs() > JOB_LOG_CACHE_MAX_AGE_SECS);
+        if expired {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+}
+
+/// Full log of a job, cached on disk once downloaded.
+/// gh only serves logs of finished jobs, so a cached log never goes stale.
+pub fn fetch_job_log(
+    gh: &Path,
+    repo_path: &str,
+    cache_dir: &Path,
+    job_id: u64,
+) -> Result<String, String> {
+    let cache_file = cache_dir.join(format!("{job_id}.log"));
+    if let Ok(log) = std::fs::read_to_string(&cache_file) {
+        log::trace!("Using cached log for job {job_id}");
+        return Ok(log);
+    }
+
+    let stdout = run_gh(
+        gh,
+        repo_path,
+        &["run", "view", "--job", &job_id.to_string(), "--log"],
+    )?;
+    let log = String::from_utf8_lossy(&stdout).to_string();
+    prune_job_log_cache(cache_dir);
+    if let Err(e) = std::fs::write(&cache_file, &log) {
+        log::warn!("Failed to cache log for job {job_id}: {e}");
+    }
+    Ok(log)
+}
+
+/// Search log lines with a regex, returning each match with its context
+fn search_log(log: &str, pattern: &Regex, context: usize) -> Vec<LogMatch> {
+    let lines: Vec<LogLine> = log.lines().map(split_log_line).collect();
+    let text = |range: std::ops::Range<usize>| {
+        lines[range]
+            .iter()
+            .map(|l| l.text.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| pattern.is_match(line.text))
+        .map(|(i, line)| LogMatch {
+            line_number: i + 1,
+            step: line.step.map(String::from),
+            line: line.text.to_string(),
+            before: text(i.saturating_sub(context)..i),
+            after: text(i + 1..(i + 1 + context).min(lines.len())),
+        })
+        .collect()
+}
+
+/// Extract excerpts around error lines of a failed job's log. Only lines of
+/// `failed_steps` are considered when the job reports any; overlapping
+/// excerpts are merged. Falls back to the tail of the log when no line
+/// looks like an error.
+fn extract_failure_snippets(
+    log: &str,
+    job_id: u64,
+    job_name: &str,
+    failed_steps: &[String],
+) -> Vec<FailureSnippet> {
+    let lines: Vec<LogLine> = log.lines().map(split_log_line).collect();
+    let in_scope = |line: &LogLine| {
+        failed_steps.is_empty()
+            || line
+                .step
+                .is_some_and(|s| failed_steps.iter().any(|f| f == s))
+    };
+    let scoped: Vec<usize> = (0..lines.len()).filter(|&i| in_scope(&lines[i])).collect();
+    if scoped.is_empty() {
+        return Vec::new();
+    }
+
+    let snippet = |start: usize, end: usize| FailureSnippet {
+        job_id,
+        job_name: job_name.to_string(),
+        step: lines[start].step.map(String::from),
+        start_line: start + 1,
+        lines: lines[start..end]
+            .iter()
+            .map(|l| l.text.to_string())
+            .collect(),
+    };
+
+    // Windows around error lines, as [start, end) ranges over the whole log
+    let mut windows: Vec<(usize, usize)> = Vec::new();
+    for &i in scoped
+        .iter()
+        .filter(|&&i| ERROR_LINE.is_match(lines[i].text))
+    {
+        let start = i.saturating_sub(SNIPPET_LINES_BEFORE).max(scoped[0]);
+        let end = (i + 1 + SNIPPET_LINES_AFTER).min(scoped[scoped.len() - 1] + 1);
+        match windows.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => windows.push((start, end)),
+        }
+    }
+
+    if windows.is_empty() {
+        let end = scoped[scoped.len() - 1] + 1;
+        let start = scoped[scoped.len().saturating_sub(FALLBACK_TAIL_LINES)];
+        return vec![snippet(start, end)];
+    }
+    // The last errors are usually the summary of what failed
+    let skip = windows.len().saturating_sub(MAX_SNIPPETS_PER_JOB);
+    windows[skip..]
+        .iter()
+        .map(|&(start, end)| snippet(start, end))
+        .collect()
+}
+
+/// Failure snippets of every failed job of a run
+pub fn fetch_failure_snippets(
+    gh: &Path,
+    repo_path: &str,
+    cache_dir: &Path,
+    run: &WorkflowRunDetails,
+) -> Vec<FailureSnippet> {
+    run.jobs
+        .iter()
+        .filter(|job| job_failed(job))
+        .flat_map(|job| {
+            let failed_steps: Vec<String> = job
+                .steps
+                .iter()
+                .filter(|s| s.conclusion.as_deref() == Some("failure"))
+                .map(|s| s.name.clone())
+                .collect();
+            match fetch_job_log(gh, repo_path, cache_dir, job.database_id) {
+                Ok(log) => {
+                    extract_failure_snippets(&log, job.database_id, &job.name, &failed_steps)
+                }
+                Err(e) => {
+                    log::warn!("Failed to fetch log for job {}: {e}", job.database_id);
+                    Vec::new()
+                }
+            }
+        })
+        .collect()
+}
+
+/// Markdown summary of why a run failed: failed jobs and steps, error
+/// annotations and log excerpts. Used as `{failureContext}` in the
+/// investigate-workflow-run prompt.
+fn format_failure_context(run: &WorkflowRunDetails, snippets: &[FailureSnippet]) -> String {
+    let mut out = String::new();
+
+    for job in run.jobs.iter().filter(|j| job_failed(j)) {
+        out.push_str(&format!(
+            "### Job \"{}\" ({})\n",
+            job.name,
+            job.conclusion.as_deref().unwrap_or("unknown")
+        ));
+        for step in job
+            .steps
+            .iter()
+            .filter(|s| s.conclusion.as_deref() == Some("failure"))
+        {
+            out.push_str(&format!("- Failed step {}: {}\n", step.number, step.name));
+        }
+        for a in job.annotations.iter().filter(|a| a.level == "failure") {
+            let location = match a.start_line {
+                Some(line) => format!("{}:{line}", a.path),
+                None => a.path.clone(),
+            };
+            out.push_str(&format!(
+                "- Annotation at {location}: {}\n",
+                a.message.trim()
+            ));
+        }
+        for snippet in snippets.iter().filter(|s| s.job_id == job.database_id) {
+            out.push_str(&format!(
+                "\nLog from line {}{}:\n```\n{}\n```\n",
+                snippet.start_line,
+                snippet
+                    .step
+                    .as_deref()
+                    .map(|s| format!(" (step \"{s}\")"))
+                    .unwrap_or_default(),
+                snippet.lines.join("\n")
+            ));
+        }
+        out.push('\n');
+    }
+
+    if out.is_empty() {
+        return "No failed jobs found in this run.".to_string();
+    }
+    out.trim_end().to_string()
+}
+
+/// Get the full log of a job (cached after the first download)
+#[tauri::command]
+pub async fn get_workflow_job_log(
+    app: AppHandle,
+    project_path: String,
+    job_id: u64,
+) -> Result<String, String> {
+    log::trace!("Getting log of workflow job {job_id}");
+    let gh = resolve_gh_binary(&app);
+    let cache_dir = job_log_cache_dir(&app)?;
+    tokio::task::spawn_blocking(move || fetch_job_log(&gh, &project_path, &cache_dir, job_id))
+        .await
+        .map_err(|e| format!("Job log task failed: {e}"))?
+}
+
+/// Search a job's log with a regex (grep-style), with `context_lines` lines
+/// around each match (default 3)
+#[tauri::command]
+pub async fn search_workflow_job_log(
+    app: AppHandle,
+    project_path: String,
+    job_id: u64,
+    pattern: String,
+    context_lines: Option<usize>,
+    ignore_case: Option<bool>,
+) -> Result<Vec<LogMatch>, String> {
+    log::trace!("Searching log of workflow job {job_id} for {pattern:?}");
+    let regex = regex::RegexBuilder::new(&pattern)
+        .case_insensitive(ignore_case.unwrap_or(false))
+        .build()
+        .map_err(|e| format!("Invalid search pattern: {e}"))?;
+    let gh = resolve_gh_binary(&app);
+    let cache_dir = job_log_cache_dir(&app)?;
+
+    tokio::task::spawn_blocking(move || {
+        let log = fetch_job_log(&gh, &project_path, &cache_dir, job_id)?;
+        Ok(search_log(&log, &regex, context_lines.unwrap_or(3)))
+    })
+    .await
+    .map_err(|e| format!("Job log task failed: {e}"))?
+}
+
+/// Get log excerpts around the errors of each failed job of a run
+#[tauri::command]
+pub async fn get_workflow_failure_snippets(
+    app: AppHandle,
+    project_path: String,
+    run_id: u64,
+) -> Result<Vec<FailureSnippet>, String> {
+    log::trace!("Extracting failure snippets of workflow run {run_id}");
+    let gh = resolve_gh_binary(&app);
+    let cache_dir = job_log_cache_dir(&app)?;
+
+    tokio::task::spawn_blocking(move || {
+        let run = fetch_run_details(&gh, &project_path, run_id)?;
+        Ok(fetch_failure_snippets(&gh, &project_path, &cache_dir, &run))
+    })
+    .await
+    .map_err(|e| format!("Workflow run task failed: {e}"))?
+}
+
+/// Get a markdown summary of why a run failed (failed jobs and steps, error
+/// annotations, log excerpts) for the investigate-workflow-run prompt
+#[tauri::command]
+pub async fn get_workflow_failure_context(
+    app: AppHandle,
+    project_path: String,
+    run_id: u64,
+) -> Result<String, String> {
+    log::trace!("Building failure context of workflow run {run_id}");
+    let gh = resolve_gh_binary(&app);
+    let cache_dir = job_log_cache_dir(&app)?;
+
+    tokio::task::spawn_blocking(move || {
+        let run = fetch_run_details(&gh, &project_path, run_id)?;
+        let snippets = fetch_failure_snippets(&gh, &project_path, &cache_dir, &run);
+        Ok(format_failure_context(&run, &snippets))
+    })
+    .await
+    .map_err(|e| format!("Workflow run task failed: {e}"))?
+}
+
+// =============================================================================
+// Workflow Dispatch, Re-run and Cancel
+// =============================================================================
+
+/// How long to wait for a dispatched run to show up
+const DISPATCH_LOOKUP_ATTEMPTS: u32 = 5;
+
+#[derive(Debug, Deserialize)]
+struct GhWorkflow {
+    id: u64,
+    name: String,
+    path: String,
+    state: String,
+}
+
+/// Parse the `workflow_dispatch` trigger of a workflow file.
+/// Returns None when the workflow can't be dispatched manually.
+fn parse_dispatch_inputs(yaml: &str) -> Option<Vec<WorkflowInput>> {
+    let doc: serde_yaml::Value = serde_yaml::from_str(yaml).ok()?;
+    let on = doc.get("on")?;
+
+    let dispatch = match on {
+        serde_yaml::Value::String(event) => return (event == "workflow_dispatch").then(Vec::new),
+        serde_yaml::Value::Sequence(events) => {
+            return events
+                .iter()
+                .any(|e| e.as_str() == Some("workflow_dispatch"))
+                .then(Vec::new);
+        }
+        serde_yaml::Value::Mapping(events) => events.get("workflow_dispatch")?,
+        _ => return None,
+    };
+
+    let Some(inputs) = dispatch.get("inputs").and_then(|i| i.as_mapping()) else {
+        return Some(Vec::new());
+    };
+    let scalar = |v: &serde_yaml::Value| match v {
+        serde_yaml::Value::String(s) => Some(s.clone()),
+        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    };
+
+    Some(
+        inputs
+            .iter()
+            .filter_map(|(name, spec)| {
+                Some(WorkflowInput {
+                    name: name.as_str()?.to_string(),
+                    description: spec.get("description").and_then(scalar),
+                    required: spec
+                        .get("required")
+                        .and_then(|r| r.as_bool())
+                        .unwrap_or(false),
+                    default: spec.get("default").and_then(scalar),
+                    input_type: spec
+                        .get("type")
+                        .and_then(|t| t.as_str())
+                        .unwrap_or("string")
+                        .to_string(),
+                    options: spec
+                        .get("options")
+                        .and_then(|o| o.as_sequence())
+                        .map(|o| o.iter().filter_map(scalar).collect())
+                        .unwrap_or_default(),
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Normalize gh's empty conclusion of unfinished runs
+fn normalize_run(mut run: WorkflowRun) -> WorkflowRun {
+    run.conclusion = run.conclusion.filter(|c| !c.is_empty());
+    run
+}
+
+/// Fetch a single workflow run
+pub fn fetch_workflow_run(gh: &Path, repo_path: &str, run_id: u64) -> Result<WorkflowRun, String> {
+    let stdout = run_gh(
+        gh,
+        repo_path,
+        &[
+            "run",
+            "view",
+            &run_id.to_string(),
+            "--json",
+            WORKFLOW_RUN_FIELDS,
+        ],
+    )?;
+    serde_json::from_slice(&stdout)
+        .map(normalize_run)
+        .map_err(|e| format!("Failed to parse gh response: {e}"))
+}
+
+/// Find the run created by a dispatch: the newest `workflow_dispatch` run of
+/// the workflow on the branch that isn't older than the dispatch
+fn find_dispatched_run(
+    gh: &Path,
+    repo_path: &str,
+    workflow: &str,
+    branch: &str,
+    dispatched_at: &str,
+) -> Option<WorkflowRun> {
+    for attempt in 0..DISPATCH_LOOKUP_ATTEMPTS {
+        std::thread::sleep(std::time::Duration::from_secs(2 + u64::from(attempt)));
+        let stdout = run_gh(
+            gh,
+            repo_path,
+            &[
+                "run",
+                "list",
+                "--workflow",
+                workflow,
+                "--branch",
+                branch,
+                "--event",
+                "workflow_dispatch",
+                "-L",
+                "1",
+                "--json",
+                WORKFLOW_RUN_FIELDS,
+            ],
+        )
+        .ok()?;
+        let runs: Vec<WorkflowRun> = serde_json::from_slice(&stdout).ok()?;
+        // RFC 3339 UTC timestamps compare correctly as strings
+        if let Some(run) = runs
+            .into_iter()
+            .find(|r| r.created_at.as_str() >= dispatched_at)
+        {
+            return Some(normalize_run(run));
+        }
+    }
+    None
+}
+
+/// Current time as an RFC 3339 UTC timestamp (second precision)
+fn now_rfc3339() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let (days, rem) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+
+    // Civil date from days since the epoch
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60
+    )
+}
+
+/// Follow a run's status in the background poller until it completes
+fn track_run(app: &AppHandle, project_path: &str, run_id: u64, min_attempt: Option<u32>) {
+    if let Some(manager) = app.try_state::<crate::background_tasks::BackgroundTaskManager>() {
+        manager.track_workflow_run(project_path.to_string(), run_id, min_attempt);
+    }
+}
+
+/// List the workflows of a repository, with the `workflow_dispatch` inputs
+/// read from the local workflow files
+#[tauri::command]
+pub async fn list_workflows(app: AppHandle, project_path: String) -> Result<Vec<Workflow>, String> {
+    log::trace!("Listing workflows for {project_path}");
+    let gh = resolve_gh_binary(&app);
+
+    tokio::task::spawn_blocking(move || {
+        let stdout = run_gh(
+            &gh,
+            &project_path,
+            &["workflow", "list", "--all", "--json", "id,name,path,state"],
+        )?;
+        let workflows: Vec<GhWorkflow> = serde_json::from_slice(&stdout)
+            .map_err(|e| format!("Failed to parse gh response: {e}"))?;
+
+        Ok(workflows
+            .into_iter()
+            .map(|w| {
+                let inputs = std::fs::read_to_string(Path::new(&project_path).join(&w.path))
+                    .ok()
+                    .and_then(|yaml| parse_dispatch_inputs(&yaml));
+                Workflow {
+                    id: w.id,
+                    name: w.name,
+                    path: w.path,
+                    state: w.state,
+                    dispatchable: inputs.is_some(),
+                    inputs: inputs.unwrap_or_default(),
+                }
+            })
+            .collect())
+    })
+    .await
+    .map_err(|e| format!("Workflow list task failed: {e}"))?
+}
+
+/// Dispatch a `workflow_dispatch` workflow on a branch (default: the current
+/// branch of `project_path`, usually a worktree). `workflow` is the workflow
+/// ID, name or file name. Returns the created run once GitHub reports it (it
+/// is then tracked by the poller), or None if it didn't show up in time.
+#[tauri::command]
+pub async fn dispatch_workflow(
+    app: AppHandle,
+    project_path: String,
+    workflow: String,
+    branch: Option<String>,
+    inputs: Option<HashMap<String, String>>,
+) -> Result<Option<WorkflowRun>, String> {
+    let branch = match branch.filter(|b| !b.trim().is_empty()) {
+        Some(branch) => branch,
+        None => super::git::get_current_branch(&project_path)?,
+    };
+    log::trace!("Dispatching workflow {workflow} on {branch} in {project_path}");
+    let gh = resolve_gh_binary(&app);
+
+    let run = tokio::task::spawn_blocking({
+        let project_path = project_path.clone();
+        move || -> Result<Option<WorkflowRun>, String> {
+            let dispatched_at = now_rfc3339();
+            // Sorted so the command line is stable
+            let inputs: BTreeMap<String, String> = inputs.unwrap_or_default().into_iter().collect();
+            let mut args = vec![
+                "workflow".to_string(),
+                "run".to_string(),
+                workflow.clone(),
+                "--ref".to_string(),
+                branch.clone(),
+            ];
+            for (name, value) in &inputs {
+                args.push("-f".to_string());
+                args.push(format!("{name}={value}"));
+            }
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            run_gh(&gh, &project_path, &args)?;
+
+            Ok(find_dispatched_run(
+                &gh,
+                &project_path,
+                &workflow,
+                &branch,
+                &dispatched_at,
+            ))
+        }
+    })
+    .await
+    .map_err(|e| format!("Workflow dispatch task failed: {e}"))??;
+
+    if let Some(ref run) = run {
+        track_run(&app, &project_path, run.database_id, None);
+    }
+    Ok(run)
+}
+
+/// Re-run a workflow run: only its failed jobs (default) or all jobs.
+///
+/// GitHub keeps reporting the previous attempt for a moment after `gh run
+/// rerun`, so the run is tracked until its attempt increases, and the returned
+/// run shows the new attempt as queued until GitHub reports it.
+#[tauri::command]
+pub async fn rerun_workflow_run(
+    app: AppHandle,
+    project_path: String,
+    run_id: u64,
+    failed_only: Option<bool>,
+) -> Result<WorkflowRun, String> {
+    let failed_only = failed_only.unwrap_or(true);
+    log::trace!("Re-running workflow run {run_id} (failed only: {failed_only})");
+    let gh = resolve_gh_binary(&app);
+
+    let run = tokio::task::spawn_blocking({
+        let project_path = project_path.clone();
+        move || {
+            let previous = fetch_workflow_run(&gh, &project_path, run_id)?;
+            let id = run_id.to_string();
+            let mut args = vec!["run", "rerun", id.as_str()];
+            if failed_only {
+                args.push("--failed");
+            }
+            run_gh(&gh, &project_path, &args)?;
+            let run = fetch_workflow_run(&gh, &project_path, run_id)
+                .ok()
+                .filter(|run| run.attempt > previous.attempt)
+                .unwrap_or_else(|| queued_attempt(previous));
+            Ok::<_, String>(run)
+        }
+    })
+    .await
+    .map_err(|e| format!("Workflow rerun task failed: {e}"))??;
+
+    track_run(&app, &project_path, run_id, Some(run.attempt));
+    Ok(run)
+}
+
+/// The next attempt of a run that was just re-run, before GitHub reports it
+fn queued_attempt(run: WorkflowRun) -> WorkflowRun {
+    WorkflowRun {
+        status: "queued".to_string(),
+        conclusion: None,
+        attempt: run.attempt + 1,
+        ..run
+    }
+}
+
+/// Cancel a queued or in-progress workflow run
+#[tauri::command]
+pub async fn cancel_workflow_run(
+    app: AppHandle,
+    project_path: String,
+    run_id: u64,
+) -> Result<(), String> {
+    log::trace!("Cancelling workflow run {run_id}");
+    let gh = resolve_gh_binary(&app);
+
+    tokio::task::spawn_blocking({
+        let project_path = project_path.clone();
+        move || run_gh(&gh, &project_path, &["run", "cancel", &run_id.to_string()])
+    })
+    .await
+    .map_err(|e| format!("Workflow cancel task failed: {e}"))??;
+
+    // The poller reports the run once it's cancelled
+    track_run(&app, &project_path, run_id, None);
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,9 +1310,19 @@ mod tests {
             created_at: "2025-01-01T00:00:00Z".into(),
             url: format!("https://example.com/{id}"),
             workflow_name: workflow.into(),
+            attempt: 1,
         }
     }
 
+    #[test]
+    fn test_queued_attempt_after_rerun() {
+        let run = queued_attempt(make_run(7, "CI", Some("failure")));
+        assert_eq!(run.attempt, 2);
+        assert_eq!(run.status, "queued");
+        assert_eq!(run.conclusion, None);
+        assert_eq!(run.database_id, 7);
+    }
+
     fn count_failed(runs: &[WorkflowRun]) -> u32 {
         let mut seen = std::collections::HashSet::new();
         let mut count: u32 = 0;
@@ -215,4 +1390,131 @@ mod tests {
         assert!(json.contains("\"failedCount\":3"));
         assert!(json.contains("\"runs\":[]"));
     }
+
+    #[test]
+    fn test_parse_timestamp_and_duration() {
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(parse_timestamp("2024-03-01T12:30:15Z"), Some(1_709_296_215));
+        assert_eq!(
+            parse_timestamp("2024-03-01T12:30:15.123Z"),
+            Some(1_709_296_215)
+        );
+        assert_eq!(parse_timestamp("0001-01-01T00:00:00Z"), None);
+        assert_eq!(parse_timestamp(""), None);
+        assert_eq!(
+            duration_secs("2024-02-28T23:59:00Z", "2024-02-29T00:01:30Z"),
+            Some(150)
+        );
+        assert_eq!(duration_secs("2024-02-28T23:59:00Z", ""), None);
+    }
+
+    #[test]
+    fn test_split_log_line() {
+        assert_eq!(
+            split_log_line("build\tRun tests\t2026-01-01T00:00:00.0000000Z error: boom"),
+            LogLine {
+                step: Some("Run tests"),
+                text: "error: boom"
+            }
+        );
+        assert_eq!(
+            split_log_line("no tabs here"),
+            LogLine {
+                step: None,
+                text: "no tabs here"
+            }
+        );
+    }
+
+    fn log_lines(step: &str, texts: &[&str]) -> String {
+        texts
+            .iter()
+            .map(|t| format!("build\t{step}\t2026-01-01T00:00:00.0000000Z {t}\n"))
+            .collect()
+    }
+
+    #[test]
+    fn test_search_log() {
+        let log = log_lines("Test", &["a", "b", "ERROR one", "c", "d"]);
+        let regex = regex::RegexBuilder::new("error")
+            .case_insensitive(true)
+            .build()
+            .unwrap();
+        let matches = search_log(&log, &regex, 1);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].line_number, 3);
+        assert_eq!(matches[0].step.as_deref(), Some("Test"));
+        assert_eq!(matches[0].before, vec!["b"]);
+        assert_eq!(matches[0].after, vec!["c"]);
+    }
+
+    #[test]
+    fn test_extract_failure_snippets() {
+        let mut log = log_lines("Checkout", &["error: not in the failed step"]);
+        let filler: Vec<String> = (0..40).map(|i| format!("line {i}")).collect();
+        let mut test_lines: Vec<&str> = filler.iter().map(String::as_str).collect();
+        test_lines[10] = "test foo ... FAILED";
+        test_lines[12] = "thread 'foo' panicked at src/lib.rs:3";
+        log.push_str(&log_lines("Test", &test_lines));
+
+        let snippets = extract_failure_snippets(&log, 1, "build", &["Test".to_string()]);
+        // Both error lines fall into one merged window, the Checkout error is ignored
+        assert_eq!(snippets.len(), 1);
+        assert_eq!(snippets[0].step.as_deref(), Some("Test"));
+        assert_eq!(snippets[0].start_line, 2 + 10 - SNIPPET_LINES_BEFORE);
+        assert_eq!(
+            snippets[0].lines.len(),
+            SNIPPET_LINES_BEFORE + 3 + SNIPPET_LINES_AFTER
+        );
+
+        // No recognizable error: tail of the failed step
+        let quiet = log_lines(
+            "Test",
+            &filler.iter().map(String::as_str).collect::<Vec<_>>(),
+        );
+        let snippets = extract_failure_snippets(&quiet, 1, "build", &["Test".to_string()]);
+        assert_eq!(snippets.len(), 1);
+        assert_eq!(snippets[0].lines.len(), FALLBACK_TAIL_LINES);
+        assert_eq!(
+            snippets[0].lines.last().map(String::as_str),
+            Some("line 39")
+        );
+    }
+
+    #[test]
+    fn test_parse_dispatch_inputs() {
+        let yaml = r#"
+name: Deploy
+on:
+  push:
+    branches: [main]
+  workflow_dispatch:
+    inputs:
+      environment:
+        description: Target environment
+        type: choice
+        required: true
+        options: [staging, production]
+      dry_run:
+        type: boolean
+        default: false
+"#;
+        let inputs = parse_dispatch_inputs(yaml).unwrap();
+        assert_eq!(inputs.len(), 2);
+        assert_eq!(inputs[0].name, "environment");
+        assert!(inputs[0].required);
+        assert_eq!(inputs[0].options, vec!["staging", "production"]);
+        assert_eq!(inputs[1].input_type, "boolean");
+        assert_eq!(inputs[1].default.as_deref(), Some("false"));
+
+        assert_eq!(
+            parse_dispatch_inputs("on: workflow_dispatch")
+                .unwrap()
+                .len(),
+            0
+        );
+        assert!(parse_dispatch_inputs("on: [push, workflow_dispatch]").is_some());
+        assert!(parse_dispatch_inputs("on: [push, pull_request]").is_none());
+        assert!(parse_dispatch_inputs("not: [valid").is_none());
+    }
 }
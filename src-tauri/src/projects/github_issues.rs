@@ -6,6 +6,7 @@ use tauri::{AppHandle, Manager};
 type SessionContextNumbers = (Vec<u32>, Vec<u32>, Vec<u32>);
 type RemovedSessionReferences = (Vec<String>, Vec<String>, Vec<String>, Vec<String>);
 
+use super::forge;
 use super::git::get_repo_identifier;
 use crate::gh_cli::config::resolve_gh_binary;
 use crate::platform::silent_command;
@@ -97,6 +98,11 @@ pub async fn list_github_issues(
 ) -> Result<GitHubIssueListResult, String> {
     log::trace!("Listing GitHub issues for {project_path} with state: {state:?}");
 
+    if let Some(forge) = forge::for_repo(&app, &project_path)? {
+        let state = state.unwrap_or_else(|| "open".to_string());
+        return forge::run(forge, move |f| f.list_issues(&state)).await;
+    }
+
     let gh = resolve_gh_binary(&app);
     let state_arg = state.unwrap_or_else(|| "open".to_string());
 
@@ -189,6 +195,10 @@ pub async fn search_github_issues(
 ) -> Result<Vec<GitHubIssue>, String> {
     log::trace!("Searching GitHub issues for {project_path} with query: {query}");
 
+    if let Some(forge) = forge::for_repo(&app, &project_path)? {
+        return forge::run(forge, move |f| f.search_issues(&query)).await;
+    }
+
     let gh = resolve_gh_binary(&app);
     let output = silent_command(&gh)
         .args([
@@ -241,6 +251,10 @@ pub async fn get_github_issue_by_number(
 ) -> Result<GitHubIssue, String> {
     log::trace!("Getting GitHub issue #{issue_number} by number for {project_path}");
 
+    if let Some(forge) = forge::for_repo(&app, &project_path)? {
+        return forge::run(forge, move |f| f.get_issue_summary(issue_number)).await;
+    }
+
     let gh = resolve_gh_binary(&app);
     let output = silent_command(&gh)
         .args([
@@ -284,6 +298,10 @@ pub async fn get_github_issue(
 ) -> Result<GitHubIssueDetail, String> {
     log::trace!("Getting GitHub issue #{issue_number} for {project_path}");
 
+    if let Some(forge) = forge::for_repo(&app, &project_path)? {
+        return forge::run(forge, move |f| f.get_issue(issue_number)).await;
+    }
+
     let gh = resolve_gh_binary(&app);
     // Run gh issue view
     let output = silent_command(&gh)
@@ -427,6 +445,8 @@ pub struct ContextReferences {
     pub advisories: std::collections::HashMap<String, ContextRef>,
     #[serde(default)]
     pub linear: std::collections::HashMap<String, ContextRef>,
+    #[serde(default)]
+    pub jira: std::collections::HashMap<String, ContextRef>,
 }
 
 /// Get the directory for shared GitHub contexts
@@ -1406,6 +1426,11 @@ pub async fn list_github_prs(
 ) -> Result<Vec<GitHubPullRequest>, String> {
     log::trace!("Listing GitHub PRs for {project_path} with state: {state:?}");
 
+    if let Some(forge) = forge::for_repo(&app, &project_path)? {
+        let state = state.unwrap_or_else(|| "open".to_string());
+        return forge::run(forge, move |f| f.list_merge_requests(&state)).await;
+    }
+
     let gh = resolve_gh_binary(&app);
     let state_arg = state.unwrap_or_else(|| "open".to_string());
 
@@ -1459,6 +1484,10 @@ pub async fn search_github_prs(
 ) -> Result<Vec<GitHubPullRequest>, String> {
     log::trace!("Searching GitHub PRs for {project_path} with query: {query}");
 
+    if let Some(forge) = forge::for_repo(&app, &project_path)? {
+        return forge::run(forge, move |f| f.search_merge_requests(&query)).await;
+    }
+
     let gh = resolve_gh_binary(&app);
     let output = silent_command(&gh)
         .args([
@@ -1511,6 +1540,10 @@ pub async fn get_github_pr_by_number(
 ) -> Result<GitHubPullRequest, String> {
     log::trace!("Getting GitHub PR #{pr_number} by number for {project_path}");
 
+    if let Some(forge) = forge::for_repo(&app, &project_path)? {
+        return forge::run(forge, move |f| f.get_merge_request_summary(pr_number)).await;
+    }
+
     let gh = resolve_gh_binary(&app);
     let output = silent_command(&gh)
         .args([
@@ -1554,6 +1587,10 @@ pub async fn get_github_pr(
 ) -> Result<GitHubPullRequestDetail, String> {
     log::trace!("Getting GitHub PR #{pr_number} for {project_path}");
 
+    if let Some(forge) = forge::for_repo(&app, &project_path)? {
+        return forge::run(forge, move |f| f.get_merge_request(pr_number)).await;
+    }
+
     let gh = resolve_gh_binary(&app);
     // Run gh pr view
     let output = silent_command(&gh)
@@ -1788,8 +1825,15 @@ pub async fn load_pr_context(
     // Fetch PR data from GitHub
     let pr = get_github_pr(app.clone(), project_path.clone(), pr_number).await?;
 
-    // Fetch the diff
-    let diff = get_pr_diff(&project_path, pr_number, &gh).ok();
+    // Fetch the diff (blocking HTTP for non-GitHub forges)
+    let diff_app = app.clone();
+    let diff_path = project_path.clone();
+    let diff = tokio::task::spawn_blocking(move || {
+        forge::get_pr_diff(&diff_app, &diff_path, pr_number, &gh)
+    })
+    .await
+    .ok()
+    .and_then(|r| r.ok());
 
     // Create PR context
     let ctx = PullRequestContext {
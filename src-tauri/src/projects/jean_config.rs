@@ -0,0 +1,544 @@
+//! jean.json parsing and validation.
+//!
+//! Parse errors (syntax, wrong types) come from serde with their exact line and
+//! column. Semantic checks (script names, env var names, working directories,
+//! permission rules) run afterwards and are located by searching for the
+//! offending key in the file. Unknown keys are ignored with a warning, so a
+//! typo or a key from a newer Jean doesn't stop the rest of the file loading.
+
+use std::collections::BTreeMap;
+use std::path::{Component, Path};
+
+use super::types::{JeanConfig, ReadyCheck, ScriptEntry, ScriptSpec};
+use crate::platform::shell_escape;
+
+/// Top-level keys Jean understands
+const KNOWN_KEYS: [&str; 10] = [
+    "$schema",
+    "scripts",
+    "env",
+    "hooks",
+    "permissions",
+    "container",
+    "ports",
+    "services",
+    "timeouts",
+    "copy",
+];
+
+/// Keys of the fixed-shape sections, for unknown-key warnings
+const HOOK_KEYS: [&str; 4] = [
+    "post_checkout_pr",
+    "pre_archive",
+    "post_merge",
+    "pre_commit",
+];
+const SCRIPT_KEYS: [&str; 3] = ["command", "env", "cwd"];
+const SERVICE_KEYS: [&str; 6] = ["command", "env", "cwd", "ready", "restart", "max_restarts"];
+const READY_KEYS: [&str; 3] = ["url", "log", "timeout_secs"];
+const CONTAINER_KEYS: [&str; 5] = ["image", "dockerfile", "runtime", "run_args", "agent"];
+const PORTS_KEYS: [&str; 2] = ["count", "templates"];
+const TIMEOUTS_KEYS: [&str; 3] = ["setup", "teardown", "hooks"];
+const COPY_KEYS: [&str; 2] = ["path", "mode"];
+
+/// Parse jean.json content, reporting serde errors as `jean.json:LINE:COL: message`.
+pub fn parse(content: &str) -> Result<JeanConfig, String> {
+    serde_json::from_str(content).map_err(|e| {
+        let message = e.to_string();
+        let message = message
+            .rfind(" at line ")
+            .map(|idx| message[..idx].to_string())
+            .unwrap_or(message);
+        format!("jean.json:{}:{}: {message}", e.line(), e.column())
+    })
+}
+
+/// Semantic validation of a parsed config. Returns one message per problem.
+pub fn validate(content: &str, config: &JeanConfig) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut report = |path: &[&str], message: String| {
+        let location = find_key_line(content, path)
+            .map(|line| format!("jean.json:{line}"))
+            .unwrap_or_else(|| "jean.json".to_string());
+        errors.push(format!("{location}: {message}"));
+    };
+
+    for (name, value) in [
+        ("setup", &config.scripts.setup),
+        ("teardown", &config.scripts.teardown),
+        ("run", &config.scripts.run),
+    ] {
+        if value.as_deref().is_some_and(|s| s.trim().is_empty()) {
+            report(&["scripts", name], format!("script `{name}` is empty"));
+        }
+    }
+
+    for (name, entry) in &config.scripts.named {
+        if !is_valid_script_name(name) {
+            report(
+                &["scripts", name],
+                format!("invalid script name `{name}` (use letters, digits, `-`, `_`, `:` or `.`)"),
+            );
+        }
+        for message in validate_entry(entry) {
+            report(&["scripts", name], format!("script `{name}`: {message}"));
+        }
+    }
+
+    for hook in [
+        super::types::JeanHook::PostCheckoutPr,
+        super::types::JeanHook::PreArchive,
+        super::types::JeanHook::PostMerge,
+        super::types::JeanHook::PreCommit,
+    ] {
+        if let Some(entry) = config.hooks.get(hook) {
+            for message in validate_entry(entry) {
+                report(
+                    &["hooks", hook.as_str()],
+                    format!("hook `{}`: {message}", hook.as_str()),
+                );
+            }
+        }
+    }
+
+    for (name, service) in &config.services {
+        if !is_valid_script_name(name) {
+            report(
+                &["services", name],
+                format!(
+                    "invalid service name `{name}` (use letters, digits, `-`, `_`, `:` or `.`)"
+                ),
+            );
+        }
+        let entry = ScriptEntry::Detailed(ScriptSpec {
+            command: service.command.clone(),
+            env: service.env.clone(),
+            cwd: service.cwd.clone(),
+        });
+        for message in validate_entry(&entry) {
+            report(&["services", name], format!("service `{name}`: {message}"));
+        }
+        if let Some(ready) = &service.ready {
+            for message in validate_ready_check(ready) {
+                report(
+                    &["services", name, "ready"],
+                    format!("service `{name}`: {message}"),
+                );
+            }
+        }
+    }
+
+    for key in config.env.keys() {
+        if !is_valid_env_name(key) {
+            report(
+                &["env", key],
+                format!("invalid environment variable name `{key}`"),
+            );
+        }
+    }
+
+    if let Some(count) = config.ports.as_ref().and_then(|p| p.count) {
+        if count == 0 || count > super::ports::MAX_PORTS_PER_WORKTREE {
+            report(
+                &["ports", "count"],
+                format!(
+                    "ports.count must be between 1 and {}",
+                    super::ports::MAX_PORTS_PER_WORKTREE
+                ),
+            );
+        }
+    }
+    for template in config.ports.iter().flat_map(|p| &p.templates) {
+        if !super::ports::is_worktree_relative(template) || !template.ends_with(".jean.tmpl") {
+            report(
+                &["ports", "templates"],
+                format!("template `{template}` must be a `.jean.tmpl` file inside the worktree"),
+            );
+        }
+    }
+
+    for entry in &config.copy {
+        if let Err(e) = super::worktree_copy::validate_pattern(entry.pattern()) {
+            report(
+                &["copy", entry.pattern()],
+                format!("copy `{}`: {e}", entry.pattern()),
+            );
+        }
+    }
+
+    if let Some(timeouts) = &config.timeouts {
+        for (name, value) in [
+            ("setup", timeouts.setup),
+            ("teardown", timeouts.teardown),
+            ("hooks", timeouts.hooks),
+        ] {
+            if value == Some(0) {
+                report(
+                    &["timeouts", name],
+                    format!("timeouts.{name} must be at least 1 second"),
+                );
+            }
+        }
+    }
+
+    if let Some(policy) = &config.permissions {
+        if let Err(e) = policy.validate() {
+            report(&["permissions"], format!("permissions: {e}"));
+        }
+    }
+
+    errors
+}
+
+/// Unknown keys anywhere in the file, one warning per key. They are ignored
+/// when loading.
+pub fn unknown_keys(content: &str) -> Vec<String> {
+    let Ok(root) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    let mut warnings = Vec::new();
+    let mut check = |value: Option<&serde_json::Value>, path: &[&str], known: &[&str]| {
+        let Some(map) = value.and_then(|v| v.as_object()) else {
+            return;
+        };
+        for key in map.keys().filter(|k| !known.contains(&k.as_str())) {
+            let mut key_path = path.to_vec();
+            key_path.push(key);
+            let location = find_key_line(content, &key_path)
+                .map(|line| format!("jean.json:{line}"))
+                .unwrap_or_else(|| "jean.json".to_string());
+            warnings.push(format!(
+                "{location}: unknown key `{key}` is ignored, expected one of: {}",
+                known.join(", ")
+            ));
+        }
+    };
+
+    check(Some(&root), &[], &KNOWN_KEYS);
+    if let Some(scripts) = root.get("scripts").and_then(|v| v.as_object()) {
+        for (name, entry) in scripts {
+            check(Some(entry), &["scripts", name], &SCRIPT_KEYS);
+        }
+    }
+    check(root.get("hooks"), &["hooks"], &HOOK_KEYS);
+    for hook in HOOK_KEYS {
+        check(
+            root.get("hooks").and_then(|h| h.get(hook)),
+            &["hooks", hook],
+            &SCRIPT_KEYS,
+        );
+    }
+    if let Some(services) = root.get("services").and_then(|v| v.as_object()) {
+        for (name, service) in services {
+            check(Some(service), &["services", name], &SERVICE_KEYS);
+            check(
+                service.get("ready"),
+                &["services", name, "ready"],
+                &READY_KEYS,
+            );
+        }
+    }
+    check(root.get("container"), &["container"], &CONTAINER_KEYS);
+    check(root.get("ports"), &["ports"], &PORTS_KEYS);
+    check(root.get("timeouts"), &["timeouts"], &TIMEOUTS_KEYS);
+    for entry in root
+        .get("copy")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        check(Some(entry), &["copy"], &COPY_KEYS);
+    }
+    warnings
+}
+
+/// Read and validate jean.json in a directory.
+///
+/// Returns `Ok(None)` when there is no jean.json, and every parse or
+/// validation error (one per line) otherwise. Unknown keys are only logged.
+pub fn load(dir: &str) -> Result<Option<JeanConfig>, String> {
+    let config_path = Path::new(dir).join("jean.json");
+    if !config_path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read jean.json: {e}"))?;
+    let config = parse(&content)?;
+    for warning in unknown_keys(&content) {
+        log::warn!("{warning}");
+    }
+    let errors = validate(&content, &config);
+    if errors.is_empty() {
+        Ok(Some(config))
+    } else {
+        Err(errors.join("\n"))
+    }
+}
+
+/// Environment for a script: top-level `env` overridden by the script's own
+pub fn script_env(config: &JeanConfig, entry: Option<&ScriptEntry>) -> BTreeMap<String, String> {
+    let mut env = config.env.clone();
+    if let Some(script_env) = entry.and_then(|e| e.env()) {
+        env.extend(script_env.clone());
+    }
+    env
+}
+
+/// Single shell command line for a script (for running it in a terminal):
+/// `cd`s into its working directory and exports its environment first.
+pub fn shell_command(config: &JeanConfig, entry: &ScriptEntry) -> String {
+    let mut parts = Vec::new();
+    let env = script_env(config, Some(entry));
+    if !env.is_empty() {
+        let exports = env
+            .iter()
+            .map(|(k, v)| format!("{k}={}", shell_escape(v)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        parts.push(format!("export {exports}"));
+    }
+    if let Some(cwd) = entry.cwd() {
+        parts.push(format!("cd {}", shell_escape(cwd)));
+    }
+    parts.push(entry.command().to_string());
+    parts.join(" && ")
+}
+
+fn validate_entry(entry: &ScriptEntry) -> Vec<String> {
+    let mut errors = Vec::new();
+    if entry.command().trim().is_empty() {
+        errors.push("command is empty".to_string());
+    }
+    errors.extend(entry.cwd().and_then(cwd_error));
+    if let Some(env) = entry.env() {
+        for key in env.keys() {
+            if !is_valid_env_name(key) {
+                errors.push(format!("invalid environment variable name `{key}`"));
+            }
+        }
+    }
+    errors
+}
+
+fn cwd_error(cwd: &str) -> Option<String> {
+    let path = Path::new(cwd);
+    (path.is_absolute() || path.components().any(|c| c == Component::ParentDir))
+        .then(|| format!("cwd `{cwd}` must be a path inside the worktree"))
+}
+
+/// `cwd`s of scripts, hooks and services that leave the worktree. Checked
+/// whenever jean.json is read, not only when it's saved from the settings.
+pub fn cwd_errors(config: &JeanConfig) -> Vec<String> {
+    let hooks = [
+        super::types::JeanHook::PostCheckoutPr,
+        super::types::JeanHook::PreArchive,
+        super::types::JeanHook::PostMerge,
+        super::types::JeanHook::PreCommit,
+    ];
+    let mut errors = Vec::new();
+    for (name, entry) in &config.scripts.named {
+        if let Some(error) = entry.cwd().and_then(cwd_error) {
+            errors.push(format!("script `{name}`: {error}"));
+        }
+    }
+    for hook in hooks {
+        if let Some(error) = config
+            .hooks
+            .get(hook)
+            .and_then(|e| e.cwd())
+            .and_then(cwd_error)
+        {
+            errors.push(format!("hook `{}`: {error}", hook.as_str()));
+        }
+    }
+    for (name, service) in &config.services {
+        if let Some(error) = service.cwd.as_deref().and_then(cwd_error) {
+            errors.push(format!("service `{name}`: {error}"));
+        }
+    }
+    errors
+}
+
+fn validate_ready_check(ready: &ReadyCheck) -> Vec<String> {
+    let mut errors = Vec::new();
+    if ready.url.is_none() && ready.log.is_none() {
+        errors.push("ready needs a `url` or a `log` pattern".to_string());
+    }
+    if let Some(url) = &ready.url {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            errors.push(format!(
+                "ready url `{url}` must start with http:// or https://"
+            ));
+        }
+    }
+    if let Some(pattern) = &ready.log {
+        if let Err(e) = regex::Regex::new(pattern) {
+            errors.push(format!("invalid ready log pattern: {e}"));
+        }
+    }
+    errors
+}
+
+fn is_valid_script_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'))
+}
+
+fn is_valid_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// 1-based line of a (nested) key, found by searching for each quoted key in
+/// turn after the previous one
+fn find_key_line(content: &str, path: &[&str]) -> Option<usize> {
+    let mut offset = 0;
+    for key in path {
+        let needle = format!("\"{key}\"");
+        offset += content[offset..].find(&needle)?;
+    }
+    Some(content[..offset].matches('\n').count() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_named_scripts_and_hooks() {
+        let config = parse(
+            r#"{
+  "scripts": {
+    "setup": "npm ci",
+    "dev": "npm run dev",
+    "test": { "command": "npm test", "env": { "CI": "1" }, "cwd": "packages/app" }
+  },
+  "env": { "NODE_ENV": "development" },
+  "hooks": { "pre_commit": "npm run lint" }
+}"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.scripts.setup.as_deref(), Some("npm ci"));
+        assert_eq!(config.scripts.named.len(), 2);
+        assert_eq!(config.scripts.named["test"].cwd(), Some("packages/app"));
+        assert_eq!(
+            config.hooks.pre_commit.as_ref().map(|h| h.command()),
+            Some("npm run lint")
+        );
+    }
+
+    #[test]
+    fn test_parse_error_has_line_and_column() {
+        let err = parse("{\n  \"timeouts\": {\n    \"setup\": \"soon\"\n  }\n}").unwrap_err();
+        assert!(err.starts_with("jean.json:3:"), "{err}");
+        assert!(err.contains("soon"));
+    }
+
+    #[test]
+    fn test_validate_reports_lines() {
+        let content = r#"{
+  "scripts": {
+    "bad name": "x",
+    "lint": { "command": "", "cwd": "../outside" }
+  },
+  "env": { "1BAD": "x" },
+  "extra": true
+}"#;
+        let config = parse(content).unwrap();
+        let errors = validate(content, &config);
+
+        assert!(errors
+            .iter()
+            .any(|e| e.starts_with("jean.json:3:") && e.contains("bad name")));
+        assert!(errors
+            .iter()
+            .any(|e| e.starts_with("jean.json:4:") && e.contains("command is empty")));
+        assert!(errors
+            .iter()
+            .any(|e| e.starts_with("jean.json:4:") && e.contains("../outside")));
+        assert!(errors
+            .iter()
+            .any(|e| e.starts_with("jean.json:6:") && e.contains("1BAD")));
+        assert!(!errors.iter().any(|e| e.contains("extra")));
+    }
+
+    #[test]
+    fn test_cwd_errors_cover_scripts_hooks_and_services() {
+        let config = parse(
+            r#"{
+  "scripts": { "ok": { "command": "make", "cwd": "sub/dir" }, "up": { "command": "ls", "cwd": "../.." } },
+  "hooks": { "pre_commit": { "command": "ls", "cwd": "/" } },
+  "services": { "web": { "command": "npm start", "cwd": "/etc" } }
+}"#,
+        )
+        .unwrap();
+        let errors = cwd_errors(&config);
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        assert!(errors[0].starts_with("script `up`"));
+        assert!(errors[1].starts_with("hook `pre_commit`"));
+        assert!(errors[2].starts_with("service `web`"));
+    }
+
+    #[test]
+    fn test_unknown_keys_warn_but_still_load() {
+        let content = r#"{
+  "scripts": { "setup": "npm ci" },
+  "hooks": {
+    "pre_archiv": "x",
+    "pre_commit": { "command": "npm run lint", "shell": "zsh" }
+  },
+  "services": { "api": { "command": "bun api", "ready": { "log": "up", "port": 3000 } } },
+  "extra": true
+}"#;
+        let config = parse(content).unwrap();
+        assert!(validate(content, &config).is_empty());
+        assert_eq!(config.scripts.setup.as_deref(), Some("npm ci"));
+        assert!(config.hooks.pre_commit.is_some());
+
+        let warnings = unknown_keys(content);
+        assert_eq!(warnings.len(), 4, "{warnings:?}");
+        assert!(warnings[0].starts_with("jean.json:8:") && warnings[0].contains("`extra`"));
+        assert!(warnings
+            .iter()
+            .any(|w| w.starts_with("jean.json:4:") && w.contains("`pre_archiv`")));
+        assert!(warnings
+            .iter()
+            .any(|w| w.starts_with("jean.json:5:") && w.contains("`shell`")));
+        assert!(warnings
+            .iter()
+            .any(|w| w.starts_with("jean.json:7:") && w.contains("`port`")));
+    }
+
+    #[test]
+    fn test_validate_services() {
+        let content = r#"{
+  "services": {
+    "api": { "command": "bun run api", "ready": { "url": "http://localhost:{{JEAN_PORT}}/health" } },
+    "web": { "command": "", "ready": { "log": "ready in (" } }
+  }
+}"#;
+        let config = parse(content).unwrap();
+        let errors = validate(content, &config);
+
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(errors
+            .iter()
+            .all(|e| e.starts_with("jean.json:4:") && e.contains("service `web`")));
+    }
+
+    #[test]
+    fn test_shell_command_exports_env_and_changes_dir() {
+        let config = parse(
+            r#"{ "env": { "A": "1" }, "scripts": { "t": { "command": "make", "env": { "B": "two words" }, "cwd": "sub" } } }"#,
+        )
+        .unwrap();
+        let command = shell_command(&config, &config.scripts.named["t"]);
+        assert_eq!(command, "export A='1' B='two words' && cd 'sub' && make");
+    }
+}
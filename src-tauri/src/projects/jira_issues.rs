@@ -0,0 +1,1114 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use super::github_issues::{
+    get_github_contexts_dir, load_context_references, save_context_references, slugify_issue_title,
+};
+use super::storage::load_projects_data;
+use super::types::Worktree;
+
+// =============================================================================
+// Types
+// =============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraStatus {
+    pub name: String,
+    /// Status category key: "new", "indeterminate" or "done"
+    pub category: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraUser {
+    pub display_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraIssue {
+    pub id: String,
+    pub key: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub status: JiraStatus,
+    pub issue_type: String,
+    pub priority: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub assignee: Option<JiraUser>,
+    pub created: String,
+    pub updated: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraComment {
+    pub body: String,
+    pub author: Option<JiraUser>,
+    pub created: String,
+}
+
+/// A subtask or parent of an issue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraIssueRef {
+    pub key: String,
+    pub summary: String,
+    pub status: Option<JiraStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraIssueDetail {
+    pub id: String,
+    pub key: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub status: JiraStatus,
+    pub issue_type: String,
+    pub priority: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub assignee: Option<JiraUser>,
+    pub created: String,
+    pub updated: String,
+    pub url: String,
+    pub parent: Option<JiraIssueRef>,
+    pub subtasks: Vec<JiraIssueRef>,
+    pub comments: Vec<JiraComment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraIssueListResult {
+    pub issues: Vec<JiraIssue>,
+}
+
+/// Context for creating a worktree from a Jira issue (passed from frontend)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraIssueContext {
+    pub key: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub comments: Vec<JiraComment>,
+    #[serde(default)]
+    pub subtasks: Vec<JiraIssueRef>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadedJiraIssueContext {
+    pub key: String,
+    pub summary: String,
+    pub comment_count: usize,
+    pub project_name: String,
+    pub url: Option<String>,
+}
+
+// =============================================================================
+// REST Client
+// =============================================================================
+
+/// Fields fetched for issue lists and search results
+const LIST_FIELDS: &str =
+    "summary,description,status,issuetype,priority,labels,assignee,created,updated";
+
+/// Fields fetched for a single issue
+const DETAIL_FIELDS: &str = "summary,description,status,issuetype,priority,labels,assignee,created,updated,comment,subtasks,parent";
+
+/// Jira config resolved from project settings.
+struct JiraConfig {
+    base_url: String,
+    /// Cloud API tokens authenticate with the account email; Server/DC PATs are bearer tokens
+    email: Option<String>,
+    api_token: String,
+    project_name: String,
+    jql: Option<String>,
+}
+
+/// Get the Jira config for a project.
+fn get_jira_config(app: &AppHandle, project_id: &str) -> Result<JiraConfig, String> {
+    let data = load_projects_data(app)?;
+    let project = data
+        .find_project(project_id)
+        .ok_or_else(|| format!("Project not found: {project_id}"))?;
+
+    let base_url = project
+        .jira_url
+        .clone()
+        .filter(|u| !u.is_empty())
+        .ok_or("No Jira site configured. Add one in project settings.")?;
+    let api_token = project
+        .jira_api_token
+        .clone()
+        .filter(|t| !t.is_empty())
+        .ok_or("No Jira API token configured. Add one in project settings.")?;
+
+    Ok(JiraConfig {
+        base_url,
+        email: project.jira_email.clone().filter(|e| !e.is_empty()),
+        api_token,
+        project_name: project.name.clone(),
+        jql: project.jira_jql.clone().filter(|q| !q.is_empty()),
+    })
+}
+
+async fn jira_get<T: DeserializeOwned>(
+    config: &JiraConfig,
+    path: &str,
+    query: &[(&str, &str)],
+) -> Result<T, String> {
+    let request = reqwest::Client::new()
+        .get(format!("{}{path}", config.base_url))
+        .query(query)
+        .header("Accept", "application/json");
+    let request = match &config.email {
+        Some(email) => request.basic_auth(email, Some(&config.api_token)),
+        None => request.bearer_auth(&config.api_token),
+    };
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Jira API request failed: {e}"))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let text = response.text().await.unwrap_or_default();
+        return Err(match status.as_u16() {
+            401 => "Jira credentials are invalid. Update them in project settings.".to_string(),
+            403 => format!("Jira denied access to {path}. Check the token's permissions."),
+            404 => format!("Not found on Jira: {path}"),
+            _ => format!("Jira API error ({status}): {}", jira_error_message(&text)),
+        });
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Jira response: {e}"))
+}
+
+/// Pull the human-readable messages out of a Jira error body
+fn jira_error_message(body: &str) -> String {
+    #[derive(Deserialize, Default)]
+    #[serde(default, rename_all = "camelCase")]
+    struct JrError {
+        error_messages: Vec<String>,
+        errors: std::collections::BTreeMap<String, String>,
+    }
+
+    let Ok(err) = serde_json::from_str::<JrError>(body) else {
+        return body.to_string();
+    };
+    let messages: Vec<String> = err
+        .error_messages
+        .into_iter()
+        .chain(err.errors.into_iter().map(|(k, v)| format!("{k}: {v}")))
+        .collect();
+    if messages.is_empty() {
+        body.to_string()
+    } else {
+        messages.join("; ")
+    }
+}
+
+/// Jira Cloud retired `/search` in favour of `/search/jql`; Server/DC only has the former
+fn search_path(base_url: &str) -> &'static str {
+    let host = base_url
+        .split_once("://")
+        .map_or(base_url, |(_, rest)| rest)
+        .split(['/', ':'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if host.ends_with(".atlassian.net") || host.ends_with(".jira.com") {
+        "/rest/api/2/search/jql"
+    } else {
+        "/rest/api/2/search"
+    }
+}
+
+async fn search(
+    config: &JiraConfig,
+    jql: &str,
+    max_results: u32,
+) -> Result<Vec<JiraIssue>, String> {
+    let max_results = max_results.to_string();
+    let result: JrSearchResult = jira_get(
+        config,
+        search_path(&config.base_url),
+        &[
+            ("jql", jql),
+            ("fields", LIST_FIELDS),
+            ("maxResults", &max_results),
+        ],
+    )
+    .await?;
+
+    Ok(result
+        .issues
+        .into_iter()
+        .map(|issue| to_issue(&config.base_url, issue))
+        .collect())
+}
+
+// =============================================================================
+// Response Parsing
+// =============================================================================
+
+#[derive(Deserialize)]
+struct JrSearchResult {
+    #[serde(default)]
+    issues: Vec<JrIssue>,
+}
+
+#[derive(Deserialize)]
+struct JrIssue {
+    id: String,
+    key: String,
+    #[serde(default)]
+    fields: JrFields,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct JrFields {
+    summary: String,
+    description: Option<String>,
+    status: Option<JrStatus>,
+    issuetype: Option<JrNamed>,
+    priority: Option<JrNamed>,
+    labels: Vec<String>,
+    assignee: Option<JrUser>,
+    created: String,
+    updated: String,
+    comment: Option<JrComments>,
+    subtasks: Vec<JrIssueRef>,
+    parent: Option<JrIssueRef>,
+}
+
+#[derive(Deserialize)]
+struct JrStatus {
+    name: String,
+    #[serde(rename = "statusCategory")]
+    status_category: Option<JrStatusCategory>,
+}
+
+#[derive(Deserialize)]
+struct JrStatusCategory {
+    key: String,
+}
+
+#[derive(Deserialize)]
+struct JrNamed {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct JrUser {
+    #[serde(rename = "displayName")]
+    display_name: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct JrComments {
+    comments: Vec<JrComment>,
+}
+
+#[derive(Deserialize)]
+struct JrComment {
+    #[serde(default)]
+    body: String,
+    author: Option<JrUser>,
+    #[serde(default)]
+    created: String,
+}
+
+#[derive(Deserialize)]
+struct JrIssueRef {
+    key: String,
+    #[serde(default)]
+    fields: JrRefFields,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct JrRefFields {
+    summary: String,
+    status: Option<JrStatus>,
+}
+
+fn to_status(status: Option<JrStatus>) -> JiraStatus {
+    match status {
+        Some(s) => JiraStatus {
+            name: s.name,
+            category: s
+                .status_category
+                .map(|c| c.key)
+                .unwrap_or_else(|| "indeterminate".to_string()),
+        },
+        None => JiraStatus {
+            name: "Unknown".to_string(),
+            category: "indeterminate".to_string(),
+        },
+    }
+}
+
+fn to_user(user: JrUser) -> JiraUser {
+    JiraUser {
+        display_name: user.display_name,
+    }
+}
+
+fn to_issue_ref(issue: JrIssueRef) -> JiraIssueRef {
+    JiraIssueRef {
+        key: issue.key,
+        summary: issue.fields.summary,
+        status: issue.fields.status.map(|s| to_status(Some(s))),
+    }
+}
+
+fn to_issue(base_url: &str, issue: JrIssue) -> JiraIssue {
+    let f = issue.fields;
+    JiraIssue {
+        url: format!("{base_url}/browse/{}", issue.key),
+        id: issue.id,
+        key: issue.key,
+        summary: f.summary,
+        description: f.description.filter(|d| !d.is_empty()),
+        status: to_status(f.status),
+        issue_type: f.issuetype.map(|t| t.name).unwrap_or_default(),
+        priority: f.priority.map(|p| p.name),
+        labels: f.labels,
+        assignee: f.assignee.map(to_user),
+        created: f.created,
+        updated: f.updated,
+    }
+}
+
+fn to_detail(base_url: &str, mut issue: JrIssue) -> JiraIssueDetail {
+    let comments = issue
+        .fields
+        .comment
+        .take()
+        .unwrap_or_default()
+        .comments
+        .into_iter()
+        .map(|c| JiraComment {
+            body: c.body,
+            author: c.author.map(to_user),
+            created: c.created,
+        })
+        .collect();
+    let subtasks = std::mem::take(&mut issue.fields.subtasks)
+        .into_iter()
+        .map(to_issue_ref)
+        .collect();
+    let parent = issue.fields.parent.take().map(to_issue_ref);
+
+    let base = to_issue(base_url, issue);
+    JiraIssueDetail {
+        id: base.id,
+        key: base.key,
+        summary: base.summary,
+        description: base.description,
+        status: base.status,
+        issue_type: base.issue_type,
+        priority: base.priority,
+        labels: base.labels,
+        assignee: base.assignee,
+        created: base.created,
+        updated: base.updated,
+        url: base.url,
+        parent,
+        subtasks,
+        comments,
+    }
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Normalize an issue key such as "eng-123" to "ENG-123"; None if it isn't one
+pub fn parse_issue_key(input: &str) -> Option<String> {
+    let (project, number) = input.trim().rsplit_once('-')?;
+    let mut chars = project.chars();
+    let valid_project = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    let valid_number = !number.is_empty() && number.chars().all(|c| c.is_ascii_digit());
+    (valid_project && valid_number).then(|| format!("{}-{number}", project.to_ascii_uppercase()))
+}
+
+/// Normalize the key of an issue context from the frontend. The key ends up in
+/// the branch name and the context file name, so anything else is rejected.
+pub fn normalize_jira_context(mut ctx: JiraIssueContext) -> Result<JiraIssueContext, String> {
+    ctx.key =
+        parse_issue_key(&ctx.key).ok_or_else(|| format!("Invalid Jira issue key: {}", ctx.key))?;
+    Ok(ctx)
+}
+
+/// Generate branch name from a Jira issue key and summary.
+/// The key keeps its case so Jira links the branch to the issue.
+pub fn generate_branch_name_from_jira_issue(key: &str, summary: &str) -> String {
+    let slug = slugify_issue_title(summary);
+    if slug.is_empty() {
+        key.to_string()
+    } else {
+        format!("{key}-{slug}")
+    }
+}
+
+/// Split a trailing ORDER BY clause off a JQL query
+fn split_order_by(jql: &str) -> (&str, Option<&str>) {
+    match jql.to_ascii_lowercase().rfind("order by") {
+        Some(i) => (jql[..i].trim(), Some(jql[i..].trim())),
+        None => (jql.trim(), None),
+    }
+}
+
+/// AND the project filter with extra clauses, keeping the filter's ORDER BY if it has one
+fn combine_jql(filter: Option<&str>, clauses: &[&str]) -> String {
+    let (filter_clause, order) = split_order_by(filter.unwrap_or_default());
+    let mut parts: Vec<String> = Vec::new();
+    if !filter_clause.is_empty() {
+        parts.push(format!("({filter_clause})"));
+    }
+    parts.extend(clauses.iter().map(|c| c.to_string()));
+    let order = order.unwrap_or("ORDER BY updated DESC");
+    if parts.is_empty() {
+        return order.to_string();
+    }
+    format!("{} {order}", parts.join(" AND "))
+}
+
+/// Whether a search query is already JQL rather than free text
+fn looks_like_jql(query: &str) -> bool {
+    let lower = query.to_ascii_lowercase();
+    query.contains(['=', '~', '<', '>'])
+        || lower.starts_with("order by")
+        || [" order by ", " in (", " is empty", " is not empty"]
+            .iter()
+            .any(|op| lower.contains(op))
+}
+
+/// JQL for the issue list: unresolved issues matching the project filter
+fn build_list_jql(filter: Option<&str>) -> String {
+    combine_jql(filter, &["statusCategory != Done"])
+}
+
+/// JQL for a search: raw JQL is ANDed with the project filter (its own ORDER BY
+/// wins), free text becomes a `text ~` match on unresolved issues
+fn build_search_jql(query: &str, filter: Option<&str>) -> String {
+    if looks_like_jql(query) {
+        let (clause, order) = split_order_by(query);
+        let clause = format!("({clause})");
+        let clauses: &[&str] = if clause == "()" { &[] } else { &[&clause] };
+        let combined = combine_jql(filter, clauses);
+        return match order {
+            Some(order) => match split_order_by(&combined).0 {
+                "" => order.to_string(),
+                rest => format!("{rest} {order}"),
+            },
+            None => combined,
+        };
+    }
+    let escaped = query.replace('\\', "\\\\").replace('"', "\\\"");
+    let text = format!("text ~ \"{escaped}\"");
+    combine_jql(filter, &["statusCategory != Done", &text])
+}
+
+/// Format a Jira issue as markdown context
+pub fn format_jira_issue_context_markdown(detail: &JiraIssueDetail) -> String {
+    let mut content = String::new();
+
+    content.push_str(&format!(
+        "# Jira Issue {}: {}\n\n",
+        detail.key, detail.summary
+    ));
+
+    if !detail.issue_type.is_empty() {
+        content.push_str(&format!("- **Type**: {}\n", detail.issue_type));
+    }
+    content.push_str(&format!("- **Status**: {}\n", detail.status.name));
+    if let Some(priority) = &detail.priority {
+        content.push_str(&format!("- **Priority**: {priority}\n"));
+    }
+
+    if !detail.labels.is_empty() {
+        content.push_str(&format!("- **Labels**: {}\n", detail.labels.join(", ")));
+    }
+
+    if let Some(assignee) = &detail.assignee {
+        content.push_str(&format!("- **Assignee**: {}\n", assignee.display_name));
+    }
+
+    if let Some(parent) = &detail.parent {
+        content.push_str(&format!(
+            "- **Parent**: {} {}\n",
+            parent.key, parent.summary
+        ));
+    }
+
+    if !detail.url.is_empty() {
+        content.push_str(&format!("- **URL**: {}\n", detail.url));
+    }
+
+    content.push_str("\n---\n\n");
+
+    content.push_str("## Description\n\n");
+    match detail.description.as_deref().filter(|d| !d.is_empty()) {
+        Some(desc) => content.push_str(desc),
+        None => content.push_str("*No description provided.*"),
+    }
+    content.push_str("\n\n");
+
+    if !detail.subtasks.is_empty() {
+        content.push_str("## Subtasks\n\n");
+        for subtask in &detail.subtasks {
+            let done = subtask
+                .status
+                .as_ref()
+                .is_some_and(|s| s.category == "done");
+            let status = subtask
+                .status
+                .as_ref()
+                .map(|s| format!(" ({})", s.name))
+                .unwrap_or_default();
+            content.push_str(&format!(
+                "- [{}] {}: {}{status}\n",
+                if done { "x" } else { " " },
+                subtask.key,
+                subtask.summary
+            ));
+        }
+        content.push('\n');
+    }
+
+    if !detail.comments.is_empty() {
+        content.push_str("## Comments\n\n");
+        for comment in &detail.comments {
+            let author = comment
+                .author
+                .as_ref()
+                .map(|u| u.display_name.as_str())
+                .unwrap_or("Unknown");
+            content.push_str(&format!("### {} ({})\n\n", author, comment.created));
+            content.push_str(&comment.body);
+            content.push_str("\n\n---\n\n");
+        }
+    }
+
+    content.push_str("---\n\n");
+    content.push_str("*Investigate this issue and propose a solution.*\n");
+
+    content
+}
+
+fn context_file_name(project_name: &str, key: &str) -> String {
+    format!("{project_name}-jira-{}.md", key.to_lowercase())
+}
+
+/// Write the context file for an issue and reference it from a session (or worktree)
+pub fn write_jira_issue_context(
+    app: &AppHandle,
+    project_name: &str,
+    detail: &JiraIssueDetail,
+    session_id: &str,
+) -> Result<(), String> {
+    let contexts_dir = get_github_contexts_dir(app)?;
+    std::fs::create_dir_all(&contexts_dir)
+        .map_err(|e| format!("Failed to create git-context directory: {e}"))?;
+
+    let context_file = contexts_dir.join(context_file_name(project_name, &detail.key));
+    std::fs::write(&context_file, format_jira_issue_context_markdown(detail))
+        .map_err(|e| format!("Failed to write Jira issue context file: {e}"))?;
+
+    add_jira_reference(app, project_name, &detail.key, session_id)
+}
+
+/// Keys of the loaded context files for a session and its worktree
+fn session_and_worktree_keys(
+    app: &AppHandle,
+    session_id: &str,
+    worktree_id: Option<&str>,
+) -> Result<Vec<String>, String> {
+    let mut keys = get_session_jira_refs(app, session_id)?;
+    if let Some(wt_id) = worktree_id {
+        for key in get_session_jira_refs(app, wt_id).unwrap_or_default() {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+    }
+    Ok(keys)
+}
+
+/// Read loaded context files back as (issue key, summary, content)
+fn read_loaded_contexts(
+    app: &AppHandle,
+    project_name: &str,
+    keys: Vec<String>,
+) -> Result<Vec<(String, String, String)>, String> {
+    let contexts_dir = get_github_contexts_dir(app)?;
+    let prefix = format!("{project_name}-");
+
+    Ok(keys
+        .iter()
+        // Key format: "{project_name}-{issue_key}"
+        .filter_map(|key| key.strip_prefix(&prefix))
+        .filter_map(|issue_key| {
+            let content = std::fs::read_to_string(
+                contexts_dir.join(context_file_name(project_name, issue_key)),
+            )
+            .ok()?;
+            let summary = content
+                .lines()
+                .next()
+                .and_then(|line| {
+                    line.strip_prefix("# Jira Issue ")
+                        .and_then(|rest| rest.split_once(": "))
+                        .map(|(_, summary)| summary.to_string())
+                })
+                .unwrap_or_else(|| format!("Issue {issue_key}"));
+            Some((issue_key.to_string(), summary, content))
+        })
+        .collect())
+}
+
+// =============================================================================
+// Tauri Commands
+// =============================================================================
+
+/// List unresolved Jira issues for a project (narrowed by the project's JQL filter)
+#[tauri::command]
+pub async fn list_jira_issues(
+    app: AppHandle,
+    project_id: String,
+) -> Result<JiraIssueListResult, String> {
+    log::trace!("Listing Jira issues for project {project_id}");
+
+    let config = get_jira_config(&app, &project_id)?;
+    let jql = build_list_jql(config.jql.as_deref());
+    let issues = search(&config, &jql, 100).await?;
+
+    log::trace!("Found {} Jira issues", issues.len());
+    Ok(JiraIssueListResult { issues })
+}
+
+/// Search Jira issues by key, free text or raw JQL
+#[tauri::command]
+pub async fn search_jira_issues(
+    app: AppHandle,
+    project_id: String,
+    query: String,
+) -> Result<Vec<JiraIssue>, String> {
+    log::trace!("Searching Jira issues for project {project_id}: {query}");
+
+    let config = get_jira_config(&app, &project_id)?;
+    let query = query.trim();
+
+    // An exact key jumps straight to that issue, whatever its status
+    if let Some(key) = parse_issue_key(query) {
+        let path = format!("/rest/api/2/issue/{key}");
+        if let Ok(issue) = jira_get::<JrIssue>(&config, &path, &[("fields", LIST_FIELDS)]).await {
+            return Ok(vec![to_issue(&config.base_url, issue)]);
+        }
+    }
+
+    let jql = build_search_jql(query, config.jql.as_deref());
+    let issues = search(&config, &jql, 50).await?;
+
+    log::trace!("Search returned {} Jira issues", issues.len());
+    Ok(issues)
+}
+
+/// Get a single Jira issue with comments and subtasks
+#[tauri::command]
+pub async fn get_jira_issue(
+    app: AppHandle,
+    project_id: String,
+    issue_key: String,
+) -> Result<JiraIssueDetail, String> {
+    log::trace!("Getting Jira issue {issue_key} for project {project_id}");
+
+    let config = get_jira_config(&app, &project_id)?;
+    let key = parse_issue_key(&issue_key)
+        .ok_or_else(|| format!("Invalid Jira issue key: {issue_key}"))?;
+
+    let path = format!("/rest/api/2/issue/{key}");
+    let issue: JrIssue = jira_get(&config, &path, &[("fields", DETAIL_FIELDS)]).await?;
+
+    Ok(to_detail(&config.base_url, issue))
+}
+
+/// Load/refresh Jira issue context for a session
+#[tauri::command]
+pub async fn load_jira_issue_context(
+    app: AppHandle,
+    session_id: String,
+    project_id: String,
+    issue_key: String,
+) -> Result<LoadedJiraIssueContext, String> {
+    log::trace!("Loading Jira issue {issue_key} context for session {session_id}");
+
+    let project_name = get_jira_config(&app, &project_id)?.project_name;
+    let detail = get_jira_issue(app.clone(), project_id, issue_key).await?;
+
+    write_jira_issue_context(&app, &project_name, &detail, &session_id)?;
+
+    let comment_count = detail.comments.len();
+    log::trace!(
+        "Jira issue context loaded for {} ({comment_count} comments)",
+        detail.key
+    );
+
+    Ok(LoadedJiraIssueContext {
+        key: detail.key,
+        summary: detail.summary,
+        comment_count,
+        project_name,
+        url: Some(detail.url),
+    })
+}
+
+/// List all loaded Jira issue contexts for a session
+#[tauri::command]
+pub async fn list_loaded_jira_issue_contexts(
+    app: AppHandle,
+    session_id: String,
+    worktree_id: Option<String>,
+    project_id: String,
+) -> Result<Vec<LoadedJiraIssueContext>, String> {
+    log::trace!("Listing loaded Jira issue contexts for session {session_id}");
+
+    let project_name = get_jira_config(&app, &project_id)?.project_name;
+    let keys = session_and_worktree_keys(&app, &session_id, worktree_id.as_deref())?;
+
+    Ok(read_loaded_contexts(&app, &project_name, keys)?
+        .into_iter()
+        .map(|(key, summary, content)| {
+            let comment_count = content
+                .split("## Comments")
+                .nth(1)
+                .map(|section| section.lines().filter(|l| l.starts_with("### ")).count())
+                .unwrap_or(0);
+            let url = content
+                .lines()
+                .find_map(|l| l.strip_prefix("- **URL**: "))
+                .map(|s| s.to_string());
+
+            LoadedJiraIssueContext {
+                key,
+                summary,
+                comment_count,
+                project_name: project_name.clone(),
+                url,
+            }
+        })
+        .collect())
+}
+
+/// Content of a loaded Jira issue context file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraIssueContextContent {
+    pub key: String,
+    pub summary: String,
+    pub content: String,
+}
+
+/// Return the full markdown content of each loaded Jira issue context file.
+/// Used to embed context directly into investigation prompts (Claude CLI cannot access Jira API).
+#[tauri::command]
+pub async fn get_jira_issue_context_contents(
+    app: AppHandle,
+    session_id: String,
+    worktree_id: Option<String>,
+    project_id: String,
+) -> Result<Vec<JiraIssueContextContent>, String> {
+    log::trace!("Getting Jira issue context contents for session {session_id}");
+
+    let project_name = get_jira_config(&app, &project_id)?.project_name;
+    let keys = session_and_worktree_keys(&app, &session_id, worktree_id.as_deref())?;
+
+    Ok(read_loaded_contexts(&app, &project_name, keys)?
+        .into_iter()
+        .map(|(key, summary, content)| JiraIssueContextContent {
+            key,
+            summary,
+            content,
+        })
+        .collect())
+}
+
+/// Remove a loaded Jira issue context from a session
+#[tauri::command]
+pub async fn remove_jira_issue_context(
+    app: AppHandle,
+    session_id: String,
+    project_id: String,
+    issue_key: String,
+) -> Result<(), String> {
+    log::trace!("Removing Jira issue {issue_key} context for session {session_id}");
+
+    let project_name = get_jira_config(&app, &project_id)?.project_name;
+
+    let orphaned = remove_jira_reference(&app, &project_name, &issue_key, &session_id)?;
+
+    if orphaned {
+        // Delete the context file if no more references
+        let context_file =
+            get_github_contexts_dir(&app)?.join(context_file_name(&project_name, &issue_key));
+        if context_file.exists() {
+            let _ = std::fs::remove_file(&context_file);
+        }
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+// Context Reference Tracking
+// =============================================================================
+
+/// Add a Jira issue reference for a session
+/// Key format: "{project_name}-{issue_key}"
+pub fn add_jira_reference(
+    app: &AppHandle,
+    project_name: &str,
+    issue_key: &str,
+    session_id: &str,
+) -> Result<(), String> {
+    let mut refs = load_context_references(app)?;
+    let key = format!("{project_name}-{issue_key}");
+
+    let entry = refs.jira.entry(key).or_default();
+    if !entry.sessions.contains(&session_id.to_string()) {
+        entry.sessions.push(session_id.to_string());
+    }
+    entry.orphaned_at = None;
+
+    save_context_references(app, &refs)
+}
+
+/// Remove a Jira issue reference for a session
+pub fn remove_jira_reference(
+    app: &AppHandle,
+    project_name: &str,
+    issue_key: &str,
+    session_id: &str,
+) -> Result<bool, String> {
+    let mut refs = load_context_references(app)?;
+    let key = format!("{project_name}-{issue_key}");
+
+    let orphaned = if let Some(entry) = refs.jira.get_mut(&key) {
+        entry.sessions.retain(|s| s != session_id);
+        if entry.sessions.is_empty() && entry.orphaned_at.is_none() {
+            entry.orphaned_at = Some(
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+            );
+            true
+        } else {
+            false
+        }
+    } else {
+        false
+    };
+
+    save_context_references(app, &refs)?;
+    Ok(orphaned)
+}
+
+/// Get all Jira issue reference keys held by a session
+pub fn get_session_jira_refs(app: &AppHandle, session_id: &str) -> Result<Vec<String>, String> {
+    let refs = load_context_references(app)?;
+    Ok(refs
+        .jira
+        .iter()
+        .filter(|(_, entry)| entry.sessions.contains(&session_id.to_string()))
+        .map(|(key, _)| key.clone())
+        .collect())
+}
+
+/// Jira issue keys (e.g. "ENG-123") linked to a session, its worktree, or the worktree itself
+pub fn session_jira_keys(
+    app: &AppHandle,
+    session_id: &str,
+    worktree: &Worktree,
+    project_name: &str,
+) -> Vec<String> {
+    let prefix = format!("{project_name}-");
+    let mut keys: Vec<String> = session_and_worktree_keys(app, session_id, Some(&worktree.id))
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|key| key.strip_prefix(&prefix).map(|k| k.to_string()))
+        .collect();
+
+    if let Some(key) = &worktree.jira_issue_key {
+        if !keys.contains(key) {
+            keys.push(key.clone());
+        }
+    }
+    keys
+}
+
+/// Convert a JiraIssueContext into a JiraIssueDetail for formatting
+pub fn jira_context_to_detail(ctx: &JiraIssueContext) -> JiraIssueDetail {
+    JiraIssueDetail {
+        id: String::new(),
+        key: ctx.key.clone(),
+        summary: ctx.summary.clone(),
+        description: ctx.description.clone(),
+        status: to_status(None),
+        issue_type: String::new(),
+        priority: None,
+        labels: vec![],
+        assignee: None,
+        created: String::new(),
+        updated: String::new(),
+        url: String::new(),
+        parent: None,
+        subtasks: ctx.subtasks.clone(),
+        comments: ctx.comments.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_issue_key() {
+        assert_eq!(parse_issue_key("ENG-123"), Some("ENG-123".to_string()));
+        assert_eq!(parse_issue_key(" eng-7 "), Some("ENG-7".to_string()));
+        assert_eq!(parse_issue_key("AB_2-10"), Some("AB_2-10".to_string()));
+        assert_eq!(parse_issue_key("123-45"), None);
+        assert_eq!(parse_issue_key("ENG-"), None);
+        assert_eq!(parse_issue_key("ENG-12a"), None);
+        assert_eq!(parse_issue_key("login bug"), None);
+    }
+
+    #[test]
+    fn test_generate_branch_name_from_jira_issue() {
+        assert_eq!(
+            generate_branch_name_from_jira_issue("ENG-123", "Fix the login bug"),
+            "ENG-123-fix-the-login-bug"
+        );
+        assert_eq!(
+            generate_branch_name_from_jira_issue("ENG-5", "!!!"),
+            "ENG-5"
+        );
+    }
+
+    #[test]
+    fn test_jql_building() {
+        assert_eq!(
+            build_list_jql(None),
+            "statusCategory != Done ORDER BY updated DESC"
+        );
+        assert_eq!(
+            build_list_jql(Some("project = ENG ORDER BY priority DESC")),
+            "(project = ENG) AND statusCategory != Done ORDER BY priority DESC"
+        );
+        assert_eq!(
+            build_search_jql(r#"login "sso""#, Some("project = ENG")),
+            r#"(project = ENG) AND statusCategory != Done AND text ~ "login \"sso\"" ORDER BY updated DESC"#
+        );
+        // Raw JQL stays within the project filter
+        assert_eq!(
+            build_search_jql("assignee = currentUser()", Some("project = ENG")),
+            "(project = ENG) AND (assignee = currentUser()) ORDER BY updated DESC"
+        );
+        assert_eq!(
+            build_search_jql(
+                "status = Open ORDER BY created ASC",
+                Some("project = ENG ORDER BY priority DESC")
+            ),
+            "(project = ENG) AND (status = Open) ORDER BY created ASC"
+        );
+        assert_eq!(
+            build_search_jql("order by created", None),
+            "order by created"
+        );
+        assert!(looks_like_jql("status in (Open, Done)"));
+        assert!(!looks_like_jql("crash on startup"));
+    }
+
+    #[test]
+    fn test_search_path() {
+        assert_eq!(
+            search_path("https://team.atlassian.net"),
+            "/rest/api/2/search/jql"
+        );
+        assert_eq!(
+            search_path("https://jira.example.com:8443/jira"),
+            "/rest/api/2/search"
+        );
+    }
+
+    #[test]
+    fn test_issue_detail_parsing_and_markdown() {
+        let raw: JrIssue = serde_json::from_value(serde_json::json!({
+            "id": "10001",
+            "key": "ENG-42",
+            "fields": {
+                "summary": "Crash on startup",
+                "description": "Stack trace attached",
+                "status": { "name": "In Progress", "statusCategory": { "key": "indeterminate" } },
+                "issuetype": { "name": "Bug" },
+                "priority": { "name": "High" },
+                "labels": ["backend"],
+                "assignee": { "displayName": "Sam Doe" },
+                "created": "2026-01-02T10:00:00.000+0000",
+                "updated": "2026-01-03T10:00:00.000+0000",
+                "comment": { "comments": [
+                    { "body": "Reproduced", "author": { "displayName": "Alex" }, "created": "2026-01-02T11:00:00.000+0000" }
+                ] },
+                "subtasks": [
+                    { "key": "ENG-43", "fields": { "summary": "Add test", "status": { "name": "Done", "statusCategory": { "key": "done" } } } },
+                    { "key": "ENG-44", "fields": { "summary": "Fix init", "status": { "name": "To Do", "statusCategory": { "key": "new" } } } }
+                ],
+                "parent": { "key": "ENG-1", "fields": { "summary": "Stability epic" } }
+            }
+        }))
+        .unwrap();
+
+        let detail = to_detail("https://team.atlassian.net", raw);
+        assert_eq!(detail.url, "https://team.atlassian.net/browse/ENG-42");
+        assert_eq!(detail.status.category, "indeterminate");
+        assert_eq!(detail.issue_type, "Bug");
+        assert_eq!(detail.subtasks.len(), 2);
+        assert_eq!(detail.parent.as_ref().unwrap().key, "ENG-1");
+        assert_eq!(
+            detail.comments[0].author.as_ref().unwrap().display_name,
+            "Alex"
+        );
+
+        let md = format_jira_issue_context_markdown(&detail);
+        assert!(md.starts_with("# Jira Issue ENG-42: Crash on startup\n"));
+        assert!(md.contains("- **Parent**: ENG-1 Stability epic\n"));
+        assert!(md.contains("- [x] ENG-43: Add test (Done)\n"));
+        assert!(md.contains("- [ ] ENG-44: Fix init (To Do)\n"));
+        assert!(md.contains("### Alex (2026-01-02T11:00:00.000+0000)\n\nReproduced"));
+    }
+
+    #[test]
+    fn test_jira_error_message() {
+        assert_eq!(
+            jira_error_message(r#"{"errorMessages":["Field 'foo' does not exist."],"errors":{}}"#),
+            "Field 'foo' does not exist."
+        );
+        assert_eq!(jira_error_message("Bad Gateway"), "Bad Gateway");
+    }
+}
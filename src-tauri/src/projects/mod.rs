@@ -1,19 +1,39 @@
 mod commands;
+pub mod commit_split;
+pub mod conflicts;
+pub mod container;
+pub mod forge;
 pub mod git;
 pub mod git_log;
 pub mod git_status;
 pub mod github_actions;
 pub mod github_issues;
+pub mod jean_config;
+pub mod jira_issues;
 pub mod linear_issues;
 mod names;
+pub mod ports;
+pub mod pr_lifecycle;
+pub mod pr_review;
 pub mod pr_status;
 pub mod saved_contexts;
+pub mod script_runner;
+pub mod stacks;
+pub mod staging;
 pub mod storage;
 pub mod types;
+pub mod worktree_copy;
 
 // Re-export commands for registration in lib.rs
 pub use commands::*;
+pub use commit_split::*;
+pub use conflicts::*;
 pub use github_actions::*;
 pub use github_issues::*;
+pub use jira_issues::*;
 pub use linear_issues::*;
+pub use pr_lifecycle::*;
+pub use pr_review::*;
 pub use saved_contexts::*;
+pub use stacks::*;
+pub use staging::*;
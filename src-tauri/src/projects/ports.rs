@@ -0,0 +1,352 @@
+//! Per-worktree port allocation and `.env` templating.
+//!
+//! When jean.json has a `ports` section, every worktree gets its own range of
+//! ports (persisted on `Worktree::ports`) so dev servers in parallel worktrees
+//! don't collide. Scripts, hooks and terminals see the range as `JEAN_PORT`,
+//! `JEAN_PORT_1`, ... Template files (`.env.jean.tmpl` plus `ports.templates`)
+//! are rendered into the worktree during setup with `{{JEAN_PORT}}`-style
+//! placeholders replaced.
+
+use std::collections::HashMap;
+use std::net::TcpListener;
+use std::path::{Component, Path};
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+use tauri::AppHandle;
+
+use super::git::read_jean_config;
+use super::storage::{load_projects_data, with_projects_data_mut};
+use super::types::{PortRange, PortsConfig};
+
+/// First port considered for allocation
+const PORT_RANGE_START: u16 = 40000;
+/// Last port considered for allocation
+const PORT_RANGE_END: u16 = 60000;
+/// Upper bound for `ports.count`
+pub const MAX_PORTS_PER_WORKTREE: u16 = 100;
+/// Template rendered automatically when present
+const DEFAULT_TEMPLATE: &str = ".env.jean.tmpl";
+/// Suffix stripped from template file names to get the output file
+const TEMPLATE_SUFFIX: &str = ".jean.tmpl";
+
+/// Ranges handed out for worktrees that aren't saved to storage yet, keyed by worktree ID
+static RESERVED: Lazy<Mutex<HashMap<String, PortRange>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+impl PortRange {
+    /// `JEAN_PORT`, `JEAN_PORT_1`, ... for this range
+    pub fn env_vars(&self) -> Vec<(String, String)> {
+        (0..self.count)
+            .map(|i| {
+                let key = if i == 0 {
+                    "JEAN_PORT".to_string()
+                } else {
+                    format!("JEAN_PORT_{i}")
+                };
+                (key, (self.start + i).to_string())
+            })
+            .collect()
+    }
+
+    fn overlaps(&self, other: &PortRange) -> bool {
+        let end = self.start as u32 + self.count as u32;
+        let other_end = other.start as u32 + other.count as u32;
+        (self.start as u32) < other_end && (other.start as u32) < end
+    }
+}
+
+/// Prepare a worktree that is being created: allocate its ports (when jean.json
+/// has a `ports` section) and render env templates.
+///
+/// Called before the setup script so it can use both. Returns the range to
+/// store on the new `Worktree`.
+pub fn prepare_new_worktree(
+    app: &AppHandle,
+    worktree_id: &str,
+    worktree_path: &str,
+    root_path: &str,
+    branch: &str,
+) -> Option<PortRange> {
+    let config = read_jean_config(worktree_path).or_else(|| read_jean_config(root_path));
+    let ports_config = config.as_ref().and_then(|c| c.ports.clone());
+
+    let ports = ports_config.as_ref().and_then(|pc| {
+        match allocate(app, worktree_id, requested_count(pc)) {
+            Ok(range) => Some(range),
+            Err(e) => {
+                log::warn!("Failed to allocate ports for worktree {worktree_id}: {e}");
+                None
+            }
+        }
+    });
+
+    let templates = ports_config.map(|pc| pc.templates).unwrap_or_default();
+    if let Err(e) = render_templates(worktree_path, root_path, branch, ports.as_ref(), &templates) {
+        log::warn!("Failed to render env templates in {worktree_path}: {e}");
+    }
+
+    ports
+}
+
+/// Ports for an existing worktree, allocating and persisting them when jean.json
+/// asks for ports but none (or too few) are allocated yet.
+pub fn ensure_ports(app: &AppHandle, worktree_id: &str) -> Option<PortRange> {
+    let data = load_projects_data(app).ok()?;
+    let worktree = data.find_worktree(worktree_id)?;
+    let project_path = data
+        .find_project(&worktree.project_id)
+        .map(|p| p.path.clone())
+        .unwrap_or_default();
+    let config = read_jean_config(&worktree.path).or_else(|| read_jean_config(&project_path));
+    let Some(ports_config) = config.and_then(|c| c.ports) else {
+        return worktree.ports;
+    };
+
+    let count = requested_count(&ports_config);
+    if let Some(existing) = worktree.ports.filter(|p| p.count >= count) {
+        return Some(existing);
+    }
+
+    let range = allocate(app, worktree_id, count)
+        .map_err(|e| log::warn!("Failed to allocate ports for worktree {worktree_id}: {e}"))
+        .ok()?;
+    let saved = with_projects_data_mut(app, |data| {
+        let worktree = data
+            .find_worktree_mut(worktree_id)
+            .ok_or_else(|| format!("Worktree not found: {worktree_id}"))?;
+        // Another caller may have persisted ports since we checked
+        if let Some(existing) = worktree.ports.filter(|p| p.count >= count) {
+            return Ok(existing);
+        }
+        worktree.ports = Some(range);
+        Ok(range)
+    });
+    release(worktree_id);
+    Some(saved.unwrap_or_else(|e| {
+        log::warn!("Failed to save allocated ports: {e}");
+        range
+    }))
+}
+
+/// Port env vars for a worktree identified by path (empty when it has no ports)
+pub fn env_for_path(app: &AppHandle, worktree_path: &str) -> Vec<(String, String)> {
+    let worktree_id = load_projects_data(app).ok().and_then(|data| {
+        data.worktrees
+            .iter()
+            .find(|w| w.path == worktree_path)
+            .map(|w| w.id.clone())
+    });
+    worktree_id
+        .and_then(|id| ensure_ports(app, &id))
+        .map(|ports| ports.env_vars())
+        .unwrap_or_default()
+}
+
+/// Drop the in-memory reservation for a worktree (once its ports are persisted)
+pub fn release(worktree_id: &str) {
+    if let Ok(mut reserved) = RESERVED.lock() {
+        reserved.remove(worktree_id);
+    }
+}
+
+fn requested_count(config: &PortsConfig) -> u16 {
+    config.count.unwrap_or(1).clamp(1, MAX_PORTS_PER_WORKTREE)
+}
+
+/// Find a free range that doesn't overlap any worktree's (including archived
+/// ones, which keep their ports) or any pending reservation, and reserve it.
+fn allocate(app: &AppHandle, worktree_id: &str, count: u16) -> Result<PortRange, String> {
+    let data = load_projects_data(app)?;
+    let mut reserved = RESERVED
+        .lock()
+        .map_err(|e| format!("Port reservation lock error: {e}"))?;
+
+    // Reservations for worktrees that are now persisted are no longer needed
+    reserved.retain(|id, _| data.find_worktree(id).is_none());
+
+    let used: Vec<PortRange> = data
+        .worktrees
+        .iter()
+        .filter(|w| w.id != worktree_id)
+        .filter_map(|w| w.ports)
+        .chain(
+            reserved
+                .iter()
+                .filter(|(id, _)| id.as_str() != worktree_id)
+                .map(|(_, r)| *r),
+        )
+        .collect();
+
+    let range = find_free_range(&used, count, is_port_free)
+        .ok_or_else(|| format!("No free range of {count} ports available"))?;
+    reserved.insert(worktree_id.to_string(), range);
+    log::debug!(
+        "Allocated ports {}..{} for worktree {worktree_id}",
+        range.start,
+        range.start + count - 1
+    );
+    Ok(range)
+}
+
+fn find_free_range(
+    used: &[PortRange],
+    count: u16,
+    is_free: impl Fn(u16) -> bool,
+) -> Option<PortRange> {
+    let mut start = PORT_RANGE_START;
+    while start as u32 + count as u32 - 1 <= PORT_RANGE_END as u32 {
+        let candidate = PortRange { start, count };
+        if let Some(blocking) = used.iter().find(|r| r.overlaps(&candidate)) {
+            start = blocking.start + blocking.count;
+            continue;
+        }
+        match (start..start + count).find(|p| !is_free(*p)) {
+            Some(busy) => start = busy + 1,
+            None => return Some(candidate),
+        }
+    }
+    None
+}
+
+fn is_port_free(port: u16) -> bool {
+    TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+/// Render `.env.jean.tmpl` (when present) and the configured templates.
+/// `foo.jean.tmpl` is written to `foo` next to it.
+fn render_templates(
+    worktree_path: &str,
+    root_path: &str,
+    branch: &str,
+    ports: Option<&PortRange>,
+    templates: &[String],
+) -> Result<(), String> {
+    let mut vars = vec![
+        ("JEAN_WORKSPACE_PATH".to_string(), worktree_path.to_string()),
+        ("JEAN_ROOT_PATH".to_string(), root_path.to_string()),
+        ("JEAN_BRANCH".to_string(), branch.to_string()),
+    ];
+    if let Some(ports) = ports {
+        vars.extend(ports.env_vars());
+    }
+
+    let mut paths: Vec<&str> = vec![DEFAULT_TEMPLATE];
+    paths.extend(templates.iter().map(String::as_str));
+
+    for relative in paths {
+        if !is_worktree_relative(relative) {
+            log::warn!(
+                "Env template {relative} must be a relative path inside the worktree, skipping"
+            );
+            continue;
+        }
+        let template_path = Path::new(worktree_path).join(relative);
+        if !template_path.is_file() {
+            if relative != DEFAULT_TEMPLATE {
+                log::warn!("Env template not found: {}", template_path.display());
+            }
+            continue;
+        }
+        let Some(output_name) = relative.strip_suffix(TEMPLATE_SUFFIX) else {
+            log::warn!("Env template {relative} must end with {TEMPLATE_SUFFIX}, skipping");
+            continue;
+        };
+        let content = std::fs::read_to_string(&template_path)
+            .map_err(|e| format!("Failed to read {relative}: {e}"))?;
+        let output_path = Path::new(worktree_path).join(output_name);
+        std::fs::write(&output_path, render(&content, &vars))
+            .map_err(|e| format!("Failed to write {output_name}: {e}"))?;
+        log::trace!("Rendered {relative} to {}", output_path.display());
+    }
+    Ok(())
+}
+
+/// Whether a path is relative and made of plain names only (no `..`, `.`,
+/// root or drive prefix), so joining it onto the worktree stays inside it
+pub(crate) fn is_worktree_relative(path: &str) -> bool {
+    let path = Path::new(path);
+    path.components().next().is_some()
+        && path.components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+/// Replace `{{NAME}}` placeholders; unknown placeholders are left as they are
+pub(crate) fn render(template: &str, vars: &[(String, String)]) -> String {
+    let mut output = template.to_string();
+    for (key, value) in vars {
+        output = output.replace(&format!("{{{{{key}}}}}"), value);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_vars() {
+        let range = PortRange {
+            start: 40010,
+            count: 3,
+        };
+        assert_eq!(
+            range.env_vars(),
+            vec![
+                ("JEAN_PORT".to_string(), "40010".to_string()),
+                ("JEAN_PORT_1".to_string(), "40011".to_string()),
+                ("JEAN_PORT_2".to_string(), "40012".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_free_range_skips_used_and_busy_ports() {
+        let used = vec![PortRange {
+            start: PORT_RANGE_START,
+            count: 5,
+        }];
+        let busy = PORT_RANGE_START + 6;
+        let range = find_free_range(&used, 3, |p| p != busy).unwrap();
+        assert_eq!(range.start, busy + 1);
+        assert_eq!(range.count, 3);
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let a = PortRange {
+            start: 100,
+            count: 10,
+        };
+        assert!(a.overlaps(&PortRange {
+            start: 109,
+            count: 1
+        }));
+        assert!(!a.overlaps(&PortRange {
+            start: 110,
+            count: 5
+        }));
+        assert!(a.overlaps(&PortRange {
+            start: 95,
+            count: 6
+        }));
+    }
+
+    #[test]
+    fn test_is_worktree_relative() {
+        assert!(is_worktree_relative(".env.jean.tmpl"));
+        assert!(is_worktree_relative("apps/web/.env.jean.tmpl"));
+        assert!(!is_worktree_relative("/etc/passwd.jean.tmpl"));
+        assert!(!is_worktree_relative("../other/.env.jean.tmpl"));
+        assert!(!is_worktree_relative("apps/../../x.jean.tmpl"));
+        assert!(!is_worktree_relative("./x.jean.tmpl"));
+        assert!(!is_worktree_relative(""));
+    }
+
+    #[test]
+    fn test_render_replaces_known_placeholders_only() {
+        let vars = vec![("JEAN_PORT".to_string(), "40000".to_string())];
+        assert_eq!(
+            render("PORT={{JEAN_PORT}}\nOTHER={{UNKNOWN}}\n", &vars),
+            "PORT=40000\nOTHER={{UNKNOWN}}\n"
+        );
+    }
+}
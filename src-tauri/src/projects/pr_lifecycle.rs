@@ -0,0 +1,616 @@
+//! Pull request lifecycle operations on a worktree's linked PR.
+//!
+//! Covers what happens between `gh pr create` and merging: draft/ready,
+//! reviewers, labels, assignees, milestone, auto-merge and closing. Projects
+//! can configure [`PrDefaults`] that are applied to every PR created from
+//! their worktrees (and on demand to an existing one).
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use super::storage::load_projects_data;
+use super::types::PrDefaults;
+use crate::gh_cli::config::resolve_gh_binary;
+use crate::platform::silent_command;
+
+const PR_DETAILS_FIELDS: &str =
+    "number,url,title,state,isDraft,reviewRequests,labels,assignees,milestone,autoMergeRequest";
+
+const MERGE_METHODS: [&str; 3] = ["merge", "squash", "rebase"];
+
+/// Current state of a pull request's lifecycle metadata
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PrDetails {
+    pub number: u32,
+    pub url: String,
+    pub title: String,
+    /// OPEN, CLOSED or MERGED
+    pub state: String,
+    pub is_draft: bool,
+    /// Pending review requests (user logins and team slugs)
+    pub reviewers: Vec<String>,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+    pub milestone: Option<String>,
+    /// Merge method of the pending auto-merge ("merge", "squash" or "rebase"), if enabled
+    pub auto_merge_method: Option<String>,
+}
+
+/// Metadata changes for `edit_pr_metadata`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PrMetadataChanges {
+    pub add_reviewers: Vec<String>,
+    pub remove_reviewers: Vec<String>,
+    pub add_labels: Vec<String>,
+    pub remove_labels: Vec<String>,
+    pub add_assignees: Vec<String>,
+    pub remove_assignees: Vec<String>,
+    /// Milestone title; an empty string removes the milestone
+    pub milestone: Option<String>,
+}
+
+impl PrMetadataChanges {
+    fn is_empty(&self) -> bool {
+        self.add_reviewers.is_empty()
+            && self.remove_reviewers.is_empty()
+            && self.add_labels.is_empty()
+            && self.remove_labels.is_empty()
+            && self.add_assignees.is_empty()
+            && self.remove_assignees.is_empty()
+            && self.milestone.is_none()
+    }
+}
+
+impl From<&PrDefaults> for PrMetadataChanges {
+    fn from(defaults: &PrDefaults) -> Self {
+        Self {
+            add_reviewers: defaults.reviewers.clone(),
+            add_labels: defaults.labels.clone(),
+            add_assignees: defaults.assignees.clone(),
+            milestone: defaults.milestone.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Trim entries, drop empty ones and duplicates. A leading `@` is stripped
+/// from handles (except `@me`, which gh resolves to the current user).
+fn clean_handles(values: Vec<String>, strip_at: bool) -> Vec<String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for value in values {
+        let mut value = value.trim();
+        if strip_at && value != "@me" {
+            value = value.trim_start_matches('@');
+        }
+        if !value.is_empty() && !cleaned.iter().any(|v| v == value) {
+            cleaned.push(value.to_string());
+        }
+    }
+    cleaned
+}
+
+fn validate_merge_method(method: &str) -> Result<(), String> {
+    if MERGE_METHODS.contains(&method) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid merge method '{method}' (expected merge, squash or rebase)"
+        ))
+    }
+}
+
+/// Normalize project PR defaults before saving. Returns `None` when nothing is set.
+pub(super) fn normalize_pr_defaults(defaults: PrDefaults) -> Result<Option<PrDefaults>, String> {
+    let auto_merge_method = defaults
+        .auto_merge_method
+        .map(|m| m.trim().to_lowercase())
+        .filter(|m| !m.is_empty());
+    if let Some(method) = &auto_merge_method {
+        validate_merge_method(method)?;
+    }
+    let normalized = PrDefaults {
+        draft: defaults.draft,
+        reviewers: clean_handles(defaults.reviewers, true),
+        labels: clean_handles(defaults.labels, false),
+        assignees: clean_handles(defaults.assignees, true),
+        milestone: defaults
+            .milestone
+            .map(|m| m.trim().to_string())
+            .filter(|m| !m.is_empty()),
+        auto_merge_method,
+    };
+    Ok((normalized != PrDefaults::default()).then_some(normalized))
+}
+
+/// Path, PR number and project PR defaults of a worktree's linked PR
+fn linked_pr(app: &AppHandle, worktree_id: &str) -> Result<(String, u32, PrDefaults), String> {
+    let data = load_projects_data(app)?;
+    let worktree = data
+        .find_worktree(worktree_id)
+        .ok_or_else(|| format!("Worktree not found: {worktree_id}"))?;
+    let pr_number = worktree
+        .pr_number
+        .ok_or_else(|| "This worktree has no linked pull request".to_string())?;
+    let defaults = data
+        .find_project(&worktree.project_id)
+        .and_then(|p| p.pr_defaults.clone())
+        .unwrap_or_default();
+    Ok((worktree.path.clone(), pr_number, defaults))
+}
+
+/// Run a `gh pr` subcommand in the worktree and return stdout
+fn gh_pr(gh: &Path, worktree_path: &str, args: &[String]) -> Result<String, String> {
+    let output = silent_command(gh)
+        .arg("pr")
+        .args(args)
+        .current_dir(worktree_path)
+        .output()
+        .map_err(|e| format!("Failed to run gh pr: {e}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("gh auth login") || stderr.contains("authentication") {
+            return Err("GitHub CLI not authenticated. Run 'gh auth login' first.".to_string());
+        }
+        return Err(stderr.trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Arguments for `gh pr edit`, or `None` if there is nothing to change
+fn edit_args(pr_number: u32, changes: &PrMetadataChanges) -> Option<Vec<String>> {
+    if changes.is_empty() {
+        return None;
+    }
+    let mut args = vec!["edit".to_string(), pr_number.to_string()];
+    let lists = [
+        (
+            "--add-reviewer",
+            clean_handles(changes.add_reviewers.clone(), true),
+        ),
+        (
+            "--remove-reviewer",
+            clean_handles(changes.remove_reviewers.clone(), true),
+        ),
+        (
+            "--add-label",
+            clean_handles(changes.add_labels.clone(), false),
+        ),
+        (
+            "--remove-label",
+            clean_handles(changes.remove_labels.clone(), false),
+        ),
+        (
+            "--add-assignee",
+            clean_handles(changes.add_assignees.clone(), true),
+        ),
+        (
+            "--remove-assignee",
+            clean_handles(changes.remove_assignees.clone(), true),
+        ),
+    ];
+    for (flag, values) in lists {
+        if !values.is_empty() {
+            args.push(flag.to_string());
+            args.push(values.join(","));
+        }
+    }
+    match changes.milestone.as_deref().map(str::trim) {
+        Some("") => args.push("--remove-milestone".to_string()),
+        Some(title) => {
+            args.push("--milestone".to_string());
+            args.push(title.to_string());
+        }
+        None => {}
+    }
+    (args.len() > 2).then_some(args)
+}
+
+fn parse_pr_details(json: &serde_json::Value) -> Result<PrDetails, String> {
+    let number = json["number"]
+        .as_u64()
+        .ok_or_else(|| "Missing PR number in gh output".to_string())? as u32;
+    let names = |key: &str, field: &str| -> Vec<String> {
+        json[key]
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item[field].as_str())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    // Users have a login, teams a slug (and a display name)
+    let reviewers = json["reviewRequests"]
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|r| {
+                    ["login", "slug", "name"]
+                        .iter()
+                        .filter_map(|k| r[*k].as_str())
+                        .find(|v| !v.is_empty())
+                })
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(PrDetails {
+        number,
+        url: json["url"].as_str().unwrap_or_default().to_string(),
+        title: json["title"].as_str().unwrap_or_default().to_string(),
+        state: json["state"].as_str().unwrap_or_default().to_string(),
+        is_draft: json["isDraft"].as_bool().unwrap_or(false),
+        reviewers,
+        labels: names("labels", "name"),
+        assignees: names("assignees", "login"),
+        milestone: json["milestone"]["title"].as_str().map(str::to_string),
+        auto_merge_method: json["autoMergeRequest"]["mergeMethod"]
+            .as_str()
+            .map(str::to_lowercase),
+    })
+}
+
+fn fetch_pr_details(gh: &Path, worktree_path: &str, pr_number: u32) -> Result<PrDetails, String> {
+    let stdout = gh_pr(
+        gh,
+        worktree_path,
+        &[
+            "view".to_string(),
+            pr_number.to_string(),
+            "--json".to_string(),
+            PR_DETAILS_FIELDS.to_string(),
+        ],
+    )
+    .map_err(|e| format!("Failed to load PR #{pr_number}: {e}"))?;
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout).map_err(|e| format!("Failed to parse PR details: {e}"))?;
+    parse_pr_details(&json)
+}
+
+fn set_auto_merge(
+    gh: &Path,
+    worktree_path: &str,
+    pr_number: u32,
+    method: &str,
+) -> Result<(), String> {
+    validate_merge_method(method)?;
+    gh_pr(
+        gh,
+        worktree_path,
+        &[
+            "merge".to_string(),
+            pr_number.to_string(),
+            "--auto".to_string(),
+            format!("--{method}"),
+        ],
+    )
+    .map_err(|e| format!("Failed to enable auto-merge: {e}"))?;
+    Ok(())
+}
+
+/// Apply project PR defaults (reviewers, labels, assignees, milestone and
+/// auto-merge) to a PR. Draft is only applied at creation time.
+///
+/// Each part is applied independently so that e.g. a missing label doesn't
+/// prevent reviewers from being requested; failures are returned as messages.
+pub(super) fn apply_defaults_to_pr(
+    gh: &Path,
+    worktree_path: &str,
+    pr_number: u32,
+    defaults: &PrDefaults,
+) -> Vec<String> {
+    let mut errors = Vec::new();
+    let changes = PrMetadataChanges::from(defaults);
+    let parts = [
+        PrMetadataChanges {
+            add_reviewers: changes.add_reviewers,
+            ..Default::default()
+        },
+        PrMetadataChanges {
+            add_labels: changes.add_labels,
+            ..Default::default()
+        },
+        PrMetadataChanges {
+            add_assignees: changes.add_assignees,
+            milestone: changes.milestone,
+            ..Default::default()
+        },
+    ];
+    for part in parts {
+        if let Some(args) = edit_args(pr_number, &part) {
+            if let Err(e) = gh_pr(gh, worktree_path, &args) {
+                errors.push(e);
+            }
+        }
+    }
+    if let Some(method) = &defaults.auto_merge_method {
+        if let Err(e) = set_auto_merge(gh, worktree_path, pr_number, method) {
+            errors.push(e);
+        }
+    }
+    for error in &errors {
+        log::warn!("Failed to apply PR default to #{pr_number}: {error}");
+    }
+    errors
+}
+
+/// Errors for the PR defaults a forge without `gh` doesn't apply (all but `draft`)
+pub(super) fn unsupported_defaults(defaults: &PrDefaults, forge: &str) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut unsupported = |set: bool, what: &str| {
+        if set {
+            errors.push(format!(
+                "{what} from the PR defaults are not supported on {forge}"
+            ));
+        }
+    };
+    unsupported(!defaults.reviewers.is_empty(), "Reviewers");
+    unsupported(!defaults.labels.is_empty(), "Labels");
+    unsupported(
+        !defaults.assignees.is_empty() || defaults.milestone.is_some(),
+        "Assignees and milestone",
+    );
+    unsupported(defaults.auto_merge_method.is_some(), "Auto-merge settings");
+    errors
+}
+
+// =============================================================================
+// Commands
+// =============================================================================
+
+/// Get draft state, reviewers, labels, assignees, milestone and auto-merge of
+/// the worktree's linked PR
+#[tauri::command]
+pub async fn get_pr_details(app: AppHandle, worktree_id: String) -> Result<PrDetails, String> {
+    let (worktree_path, pr_number, _) = linked_pr(&app, &worktree_id)?;
+    let gh = resolve_gh_binary(&app);
+    tokio::task::spawn_blocking(move || fetch_pr_details(&gh, &worktree_path, pr_number))
+        .await
+        .map_err(|e| format!("Task failed: {e}"))?
+}
+
+/// Mark the linked PR as ready for review, or convert it back to a draft
+#[tauri::command]
+pub async fn set_pr_draft(
+    app: AppHandle,
+    worktree_id: String,
+    draft: bool,
+) -> Result<PrDetails, String> {
+    let (worktree_path, pr_number, _) = linked_pr(&app, &worktree_id)?;
+    let gh = resolve_gh_binary(&app);
+    tokio::task::spawn_blocking(move || {
+        let mut args = vec!["ready".to_string(), pr_number.to_string()];
+        if draft {
+            args.push("--undo".to_string());
+        }
+        gh_pr(&gh, &worktree_path, &args).map_err(|e| {
+            let action = if draft {
+                "convert PR to draft"
+            } else {
+                "mark PR as ready"
+            };
+            format!("Failed to {action}: {e}")
+        })?;
+        log::info!("PR #{pr_number} draft={draft}");
+        fetch_pr_details(&gh, &worktree_path, pr_number)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {e}"))?
+}
+
+/// Add or remove reviewers, labels and assignees, or change the milestone of
+/// the linked PR
+#[tauri::command]
+pub async fn edit_pr_metadata(
+    app: AppHandle,
+    worktree_id: String,
+    changes: PrMetadataChanges,
+) -> Result<PrDetails, String> {
+    let (worktree_path, pr_number, _) = linked_pr(&app, &worktree_id)?;
+    let gh = resolve_gh_binary(&app);
+    tokio::task::spawn_blocking(move || {
+        if let Some(args) = edit_args(pr_number, &changes) {
+            gh_pr(&gh, &worktree_path, &args)
+                .map_err(|e| format!("Failed to update PR #{pr_number}: {e}"))?;
+        }
+        fetch_pr_details(&gh, &worktree_path, pr_number)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {e}"))?
+}
+
+/// Enable or disable auto-merge on the linked PR.
+///
+/// `method` defaults to the project's configured auto-merge method, then "merge".
+#[tauri::command]
+pub async fn set_pr_auto_merge(
+    app: AppHandle,
+    worktree_id: String,
+    enabled: bool,
+    method: Option<String>,
+) -> Result<PrDetails, String> {
+    let (worktree_path, pr_number, defaults) = linked_pr(&app, &worktree_id)?;
+    let gh = resolve_gh_binary(&app);
+    let method = method
+        .or(defaults.auto_merge_method)
+        .unwrap_or_else(|| "merge".to_string());
+    tokio::task::spawn_blocking(move || {
+        if enabled {
+            set_auto_merge(&gh, &worktree_path, pr_number, &method)?;
+        } else {
+            gh_pr(
+                &gh,
+                &worktree_path,
+                &[
+                    "merge".to_string(),
+                    pr_number.to_string(),
+                    "--disable-auto".to_string(),
+                ],
+            )
+            .map_err(|e| format!("Failed to disable auto-merge: {e}"))?;
+        }
+        fetch_pr_details(&gh, &worktree_path, pr_number)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {e}"))?
+}
+
+/// Close the linked PR without merging, optionally leaving a comment.
+///
+/// The branch is kept: it is still checked out in the worktree.
+#[tauri::command]
+pub async fn close_pr(
+    app: AppHandle,
+    worktree_id: String,
+    comment: Option<String>,
+) -> Result<PrDetails, String> {
+    let (worktree_path, pr_number, _) = linked_pr(&app, &worktree_id)?;
+    let gh = resolve_gh_binary(&app);
+    tokio::task::spawn_blocking(move || {
+        let mut args = vec!["close".to_string(), pr_number.to_string()];
+        if let Some(comment) = comment
+            .map(|c| c.trim().to_string())
+            .filter(|c| !c.is_empty())
+        {
+            args.push("--comment".to_string());
+            args.push(comment);
+        }
+        gh_pr(&gh, &worktree_path, &args)
+            .map_err(|e| format!("Failed to close PR #{pr_number}: {e}"))?;
+        log::info!("Closed PR #{pr_number}");
+        fetch_pr_details(&gh, &worktree_path, pr_number)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {e}"))?
+}
+
+/// Apply the project's PR defaults to the linked PR (e.g. one created outside Jean)
+#[tauri::command]
+pub async fn apply_pr_defaults(app: AppHandle, worktree_id: String) -> Result<PrDetails, String> {
+    let (worktree_path, pr_number, defaults) = linked_pr(&app, &worktree_id)?;
+    let gh = resolve_gh_binary(&app);
+    tokio::task::spawn_blocking(move || {
+        let errors = apply_defaults_to_pr(&gh, &worktree_path, pr_number, &defaults);
+        if !errors.is_empty() {
+            return Err(errors.join("\n"));
+        }
+        fetch_pr_details(&gh, &worktree_path, pr_number)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {e}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsupported_defaults() {
+        let defaults = PrDefaults {
+            draft: true,
+            labels: vec!["ai-generated".into()],
+            milestone: Some("v2".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            unsupported_defaults(&defaults, "GitLab"),
+            vec![
+                "Labels from the PR defaults are not supported on GitLab",
+                "Assignees and milestone from the PR defaults are not supported on GitLab",
+            ]
+        );
+        assert!(unsupported_defaults(&PrDefaults::default(), "GitLab").is_empty());
+    }
+
+    #[test]
+    fn test_normalize_pr_defaults() {
+        let defaults = PrDefaults {
+            draft: false,
+            reviewers: vec![" @team/backend ".into(), "alice".into(), "@alice".into()],
+            labels: vec!["ai-generated".into(), "".into()],
+            assignees: vec!["@me".into()],
+            milestone: Some("  ".into()),
+            auto_merge_method: Some("Squash".into()),
+        };
+        let normalized = normalize_pr_defaults(defaults).unwrap().unwrap();
+        assert_eq!(normalized.reviewers, vec!["team/backend", "alice"]);
+        assert_eq!(normalized.labels, vec!["ai-generated"]);
+        assert_eq!(normalized.assignees, vec!["@me"]);
+        assert_eq!(normalized.milestone, None);
+        assert_eq!(normalized.auto_merge_method.as_deref(), Some("squash"));
+
+        assert_eq!(normalize_pr_defaults(PrDefaults::default()), Ok(None));
+        let invalid = PrDefaults {
+            auto_merge_method: Some("fast-forward".into()),
+            ..Default::default()
+        };
+        assert!(normalize_pr_defaults(invalid).is_err());
+    }
+
+    #[test]
+    fn test_edit_args() {
+        let changes = PrMetadataChanges {
+            add_reviewers: vec!["@team/backend".into(), "bob".into()],
+            remove_labels: vec!["wip".into()],
+            milestone: Some(String::new()),
+            ..Default::default()
+        };
+        assert_eq!(
+            edit_args(12, &changes).unwrap(),
+            vec![
+                "edit",
+                "12",
+                "--add-reviewer",
+                "team/backend,bob",
+                "--remove-label",
+                "wip",
+                "--remove-milestone"
+            ]
+        );
+        assert_eq!(edit_args(12, &PrMetadataChanges::default()), None);
+        let blank = PrMetadataChanges {
+            add_labels: vec![" ".into()],
+            ..Default::default()
+        };
+        assert_eq!(edit_args(12, &blank), None);
+    }
+
+    #[test]
+    fn test_parse_pr_details() {
+        let json = serde_json::json!({
+            "number": 7,
+            "url": "https://github.com/o/r/pull/7",
+            "title": "Add thing",
+            "state": "OPEN",
+            "isDraft": true,
+            "reviewRequests": [
+                {"__typename": "User", "login": "alice"},
+                {"__typename": "Team", "name": "Backend", "slug": "o/backend"}
+            ],
+            "labels": [{"name": "ai-generated", "color": "ededed"}],
+            "assignees": [{"login": "bob"}],
+            "milestone": null,
+            "autoMergeRequest": {"mergeMethod": "SQUASH"}
+        });
+        let details = parse_pr_details(&json).unwrap();
+        assert!(details.is_draft);
+        assert_eq!(details.reviewers, vec!["alice", "o/backend"]);
+        assert_eq!(details.labels, vec!["ai-generated"]);
+        assert_eq!(details.assignees, vec!["bob"]);
+        assert_eq!(details.milestone, None);
+        assert_eq!(details.auto_merge_method.as_deref(), Some("squash"));
+    }
+}
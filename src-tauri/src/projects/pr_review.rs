@@ -0,0 +1,959 @@
+//! GitHub pull request reviews, in both directions.
+//!
+//! Outgoing: AI review findings are published as a PR review. Findings on
+//! lines that are part of the PR diff become inline comments (with the fix as
+//! a ```suggestion block); the rest are listed in the review body, since
+//! GitHub rejects the whole review if any comment is outside the diff.
+//!
+//! Incoming: unresolved review threads are turned into a task list for a
+//! session to work through. Once the run is done the changes are pushed, each
+//! thread gets a reply with the commit and what changed, and optionally the
+//! thread is resolved. Per-thread progress is kept on the session.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::AppHandle;
+
+use super::commands::{ReviewFinding, ReviewResponse};
+use super::git::get_repo_identifier;
+use super::storage::load_projects_data;
+use crate::chat::types::{MessageRole, ReviewThreadProgress, ReviewThreadStatus};
+use crate::chat::with_sessions_mut;
+use crate::gh_cli::config::resolve_gh_binary;
+use crate::platform::silent_command;
+
+/// Result of publishing a review
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishedReview {
+    pub review_id: u64,
+    pub html_url: String,
+    /// GitHub review state: PENDING, COMMENTED, APPROVED or CHANGES_REQUESTED
+    pub state: String,
+    /// Findings posted as inline comments
+    pub inline_count: usize,
+    /// Findings listed in the review body because their line isn't in the PR diff
+    pub body_count: usize,
+    /// Set when the verdict was downgraded to a comment (GitHub doesn't allow
+    /// approving or requesting changes on your own pull request)
+    pub downgraded: bool,
+}
+
+/// File entry from the pull request files API
+#[derive(Debug, Deserialize)]
+struct PrFile {
+    filename: String,
+    #[serde(default)]
+    patch: Option<String>,
+}
+
+/// Review response from the GitHub API
+#[derive(Debug, Deserialize)]
+struct RawReview {
+    id: u64,
+    #[serde(default)]
+    html_url: String,
+    #[serde(default)]
+    state: String,
+}
+
+/// Map the AI verdict to a GitHub review event
+fn review_event(approval_status: &str) -> &'static str {
+    match approval_status {
+        "approved" => "APPROVE",
+        "changes_requested" => "REQUEST_CHANGES",
+        _ => "COMMENT",
+    }
+}
+
+/// Lines on the new side of a patch that can take a review comment
+/// (additions and context lines)
+fn commentable_lines(patch: &str) -> HashSet<u32> {
+    let mut lines = HashSet::new();
+    let mut new_line = 0u32;
+    for line in patch.lines() {
+        if let Some(header) = line.strip_prefix("@@ ") {
+            // "@@ -a,b +c,d @@"
+            new_line = header
+                .split_whitespace()
+                .find_map(|part| part.strip_prefix('+'))
+                .and_then(|range| range.split(',').next())
+                .and_then(|start| start.parse().ok())
+                .unwrap_or(0);
+        } else if line.starts_with('+') || line.starts_with(' ') {
+            lines.insert(new_line);
+            new_line += 1;
+        }
+    }
+    lines
+}
+
+/// Normalize a finding's file path to the repo-relative form GitHub uses
+fn relative_path<'a>(file: &'a str, worktree_path: &str) -> &'a str {
+    let file = file.strip_prefix(worktree_path).unwrap_or(file);
+    file.trim_start_matches("./").trim_start_matches('/')
+}
+
+fn finding_heading(finding: &ReviewFinding) -> String {
+    format!("**[{}] {}**", finding.severity, finding.title)
+}
+
+fn inline_comment_body(finding: &ReviewFinding) -> String {
+    let mut body = format!("{}\n\n{}", finding_heading(finding), finding.description);
+    if let Some(suggestion) = finding
+        .suggestion
+        .as_deref()
+        .filter(|s| !s.trim().is_empty())
+    {
+        body.push_str(&format!(
+            "\n\n```suggestion\n{}\n```",
+            suggestion.trim_end()
+        ));
+    }
+    body
+}
+
+/// Split findings into inline comments and the review body
+fn build_review(
+    review: &ReviewResponse,
+    commentable: &HashMap<String, HashSet<u32>>,
+    worktree_path: &str,
+) -> (String, Vec<serde_json::Value>) {
+    let mut comments = Vec::new();
+    let mut other = Vec::new();
+
+    for finding in &review.findings {
+        let path = relative_path(&finding.file, worktree_path);
+        let line = finding.line.filter(|l| *l > 0);
+        match line {
+            Some(line) if commentable.get(path).is_some_and(|l| l.contains(&line)) => {
+                comments.push(json!({
+                    "path": path,
+                    "line": line,
+                    "side": "RIGHT",
+                    "body": inline_comment_body(finding),
+                }));
+            }
+            _ => other.push((finding, path)),
+        }
+    }
+
+    let mut body = review.summary.trim().to_string();
+    if !other.is_empty() {
+        body.push_str("\n\n### Other findings\n");
+        for (finding, path) in other {
+            let location = match finding.line.filter(|l| *l > 0) {
+                Some(line) => format!("`{path}:{line}`"),
+                None if path.is_empty() => String::new(),
+                None => format!("`{path}`"),
+            };
+            body.push_str(&format!(
+                "\n- {} {location}\n\n  {}\n",
+                finding_heading(finding),
+                finding.description
+            ));
+            if let Some(suggestion) = finding
+                .suggestion
+                .as_deref()
+                .filter(|s| !s.trim().is_empty())
+            {
+                body.push_str(&format!(
+                    "\n  ```\n  {}\n  ```\n",
+                    suggestion.trim_end().replace('\n', "\n  ")
+                ));
+            }
+        }
+    }
+
+    (body, comments)
+}
+
+/// Path and linked PR number of a worktree
+fn linked_pr(app: &AppHandle, worktree_id: &str) -> Result<(String, u32), String> {
+    let data = load_projects_data(app)?;
+    let worktree = data
+        .find_worktree(worktree_id)
+        .ok_or_else(|| format!("Worktree not found: {worktree_id}"))?;
+    let pr_number = worktree
+        .pr_number
+        .ok_or_else(|| "This worktree has no linked pull request".to_string())?;
+    Ok((worktree.path.clone(), pr_number))
+}
+
+/// `owner/repo` of the worktree's GitHub remote
+fn repo_slug(worktree_path: &str) -> Result<String, String> {
+    let repo_id = get_repo_identifier(worktree_path)?;
+    Ok(format!("{}/{}", repo_id.owner, repo_id.repo))
+}
+
+fn gh_error(stderr: &str, pr_number: u32) -> String {
+    if stderr.contains("gh auth login") || stderr.contains("authentication") {
+        return "GitHub CLI not authenticated. Run 'gh auth login' first.".to_string();
+    }
+    if stderr.contains("404") || stderr.contains("Not Found") {
+        return format!("PR #{pr_number} not found");
+    }
+    format!("gh api failed: {}", stderr.trim())
+}
+
+/// Commentable lines per file of the pull request
+fn pr_commentable_lines(
+    gh: &std::path::Path,
+    worktree_path: &str,
+    repo: &str,
+    pr_number: u32,
+) -> Result<HashMap<String, HashSet<u32>>, String> {
+    let endpoint = format!("/repos/{repo}/pulls/{pr_number}/files?per_page=100");
+    let output = silent_command(gh)
+        .args([
+            "api",
+            "--paginate",
+            &endpoint,
+            "--jq",
+            ".[] | {filename, patch} | tojson",
+        ])
+        .current_dir(worktree_path)
+        .output()
+        .map_err(|e| format!("Failed to run gh api: {e}"))?;
+    if !output.status.success() {
+        return Err(gh_error(
+            &String::from_utf8_lossy(&output.stderr),
+            pr_number,
+        ));
+    }
+
+    // One JSON object per line (`tojson` keeps each on a single line)
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut files = HashMap::new();
+    for line in stdout.lines().filter(|l| !l.trim().is_empty()) {
+        let file: PrFile =
+            serde_json::from_str(line).map_err(|e| format!("Failed to parse PR files: {e}"))?;
+        let lines = file
+            .patch
+            .as_deref()
+            .map(commentable_lines)
+            .unwrap_or_default();
+        files.insert(file.filename, lines);
+    }
+    Ok(files)
+}
+
+/// POST a review; `event` of `None` leaves it pending
+fn create_review(
+    gh: &std::path::Path,
+    worktree_path: &str,
+    repo: &str,
+    pr_number: u32,
+    body: &str,
+    event: Option<&str>,
+    comments: &[serde_json::Value],
+) -> Result<RawReview, String> {
+    let mut payload = json!({ "body": body, "comments": comments });
+    if let Some(event) = event {
+        payload["event"] = json!(event);
+    }
+
+    let input_path =
+        std::env::temp_dir().join(format!("jean-pr-review-{}.json", uuid::Uuid::new_v4()));
+    std::fs::write(&input_path, payload.to_string())
+        .map_err(|e| format!("Failed to write review payload: {e}"))?;
+
+    let endpoint = format!("/repos/{repo}/pulls/{pr_number}/reviews");
+    let output = silent_command(gh)
+        .args(["api", "--method", "POST", &endpoint, "--input"])
+        .arg(&input_path)
+        .current_dir(worktree_path)
+        .output();
+    let _ = std::fs::remove_file(&input_path);
+    let output = output.map_err(|e| format!("Failed to run gh api: {e}"))?;
+
+    if !output.status.success() {
+        // gh prints the API error body on stdout and a summary on stderr
+        let stderr = format!(
+            "{}\n{}",
+            String::from_utf8_lossy(&output.stderr),
+            String::from_utf8_lossy(&output.stdout)
+        );
+        return Err(gh_error(&stderr, pr_number));
+    }
+
+    serde_json::from_slice(&output.stdout).map_err(|e| format!("Failed to parse review: {e}"))
+}
+
+/// Publish AI review findings as a review on the worktree's pull request.
+///
+/// Pass only the findings to publish. With `submit` unset the review stays
+/// pending so it can be edited on GitHub before submitting; otherwise it is
+/// submitted with the verdict mapped from `approval_status`.
+#[tauri::command]
+pub async fn publish_review_to_pr(
+    app: AppHandle,
+    worktree_id: String,
+    review: ReviewResponse,
+    submit: Option<bool>,
+) -> Result<PublishedReview, String> {
+    log::trace!(
+        "Publishing {} review finding(s) for worktree {worktree_id}",
+        review.findings.len()
+    );
+    let (worktree_path, pr_number) = linked_pr(&app, &worktree_id)?;
+    let gh = resolve_gh_binary(&app);
+
+    tokio::task::spawn_blocking(move || {
+        let repo = repo_slug(&worktree_path)?;
+        let commentable = pr_commentable_lines(&gh, &worktree_path, &repo, pr_number)?;
+        let (body, comments) = build_review(&review, &commentable, &worktree_path);
+        let inline_count = comments.len();
+        let body_count = review.findings.len() - inline_count;
+
+        let event = submit
+            .unwrap_or(false)
+            .then(|| review_event(&review.approval_status));
+        let mut downgraded = false;
+        let created = match create_review(
+            &gh,
+            &worktree_path,
+            &repo,
+            pr_number,
+            &body,
+            event,
+            &comments,
+        ) {
+            Err(e)
+                if event.is_some_and(|e| e != "COMMENT") && e.contains("your own pull request") =>
+            {
+                downgraded = true;
+                create_review(
+                    &gh,
+                    &worktree_path,
+                    &repo,
+                    pr_number,
+                    &body,
+                    Some("COMMENT"),
+                    &comments,
+                )?
+            }
+            result => result?,
+        };
+
+        log::trace!(
+            "Published review {} on PR #{pr_number} ({inline_count} inline, {body_count} in body)",
+            created.id
+        );
+        Ok(PublishedReview {
+            review_id: created.id,
+            html_url: created.html_url,
+            state: created.state,
+            inline_count,
+            body_count,
+            downgraded,
+        })
+    })
+    .await
+    .map_err(|e| format!("Publish review task failed: {e}"))?
+}
+
+// =============================================================================
+// Addressing Incoming Review Threads
+// =============================================================================
+
+/// A comment in a PR review thread
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrThreadComment {
+    pub author: String,
+    pub body: String,
+    pub created_at: String,
+    pub url: String,
+}
+
+/// A review thread on a pull request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrReviewThread {
+    /// GraphQL node ID, used to reply to and resolve the thread
+    pub id: String,
+    pub path: String,
+    /// Line on the new side (None when the thread is outdated)
+    pub line: Option<u32>,
+    pub start_line: Option<u32>,
+    pub is_resolved: bool,
+    pub is_outdated: bool,
+    /// Diff context of the first comment
+    pub diff_hunk: String,
+    pub comments: Vec<PrThreadComment>,
+}
+
+/// Task list for a session run, returned by `start_addressing_review`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressReviewTask {
+    /// Message to send to the session (in build mode)
+    pub prompt: String,
+    pub threads: Vec<PrReviewThread>,
+}
+
+const REVIEW_THREADS_QUERY: &str = "query($owner: String!, $name: String!, $number: Int!) {
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) {
+      reviewThreads(first: 100) {
+        nodes {
+          id isResolved isOutdated path line startLine
+          comments(first: 50) {
+            nodes { author { login } body createdAt url diffHunk }
+          }
+        }
+      }
+    }
+  }
+}";
+
+const REPLY_MUTATION: &str = "mutation($thread: ID!, $body: String!) {
+  addPullRequestReviewThreadReply(input: { pullRequestReviewThreadId: $thread, body: $body }) {
+    comment { id }
+  }
+}";
+
+const RESOLVE_MUTATION: &str = "mutation($thread: ID!) {
+  resolveReviewThread(input: { threadId: $thread }) { thread { isResolved } }
+}";
+
+/// Outcome of replying to (and resolving) one thread
+type ThreadOutcome = (String, Result<ReviewThreadProgress, String>);
+
+/// Commit message used when the run left its changes uncommitted
+const DEFAULT_REVIEW_COMMIT_MESSAGE: &str = "Address PR review comments";
+
+/// Run a GraphQL query through `gh api graphql`. String variables are passed
+/// with `-f`, others (numbers) with `-F`.
+fn gh_graphql(
+    gh: &std::path::Path,
+    worktree_path: &str,
+    query: &str,
+    string_vars: &[(&str, &str)],
+    typed_vars: &[(&str, String)],
+) -> Result<serde_json::Value, String> {
+    let mut cmd = silent_command(gh);
+    cmd.args(["api", "graphql", "-f"])
+        .arg(format!("query={query}"))
+        .current_dir(worktree_path);
+    for (name, value) in string_vars {
+        cmd.arg("-f").arg(format!("{name}={value}"));
+    }
+    for (name, value) in typed_vars {
+        cmd.arg("-F").arg(format!("{name}={value}"));
+    }
+    let output = cmd
+        .output()
+        .map_err(|e| format!("Failed to run gh api graphql: {e}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("gh auth login") || stderr.contains("authentication") {
+            return Err("GitHub CLI not authenticated. Run 'gh auth login' first.".to_string());
+        }
+        return Err(format!("gh api graphql failed: {}", stderr.trim()));
+    }
+    serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse GraphQL response: {e}"))
+}
+
+/// Parse the review threads query response
+fn parse_review_threads(response: &serde_json::Value) -> Vec<PrReviewThread> {
+    let nodes = response
+        .pointer("/data/repository/pullRequest/reviewThreads/nodes")
+        .and_then(|n| n.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let str_at = |v: &serde_json::Value, ptr: &str| {
+        v.pointer(ptr)
+            .and_then(|s| s.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    let line_at = |v: &serde_json::Value, key: &str| {
+        v.get(key)
+            .and_then(|l| l.as_u64())
+            .and_then(|l| u32::try_from(l).ok())
+    };
+
+    nodes
+        .iter()
+        .map(|node| {
+            let comments: Vec<&serde_json::Value> = node
+                .pointer("/comments/nodes")
+                .and_then(|c| c.as_array())
+                .map(|c| c.iter().collect())
+                .unwrap_or_default();
+            PrReviewThread {
+                id: str_at(node, "/id"),
+                path: str_at(node, "/path"),
+                line: line_at(node, "line"),
+                start_line: line_at(node, "startLine"),
+                is_resolved: node["isResolved"].as_bool().unwrap_or(false),
+                is_outdated: node["isOutdated"].as_bool().unwrap_or(false),
+                diff_hunk: comments
+                    .first()
+                    .map(|c| str_at(c, "/diffHunk"))
+                    .unwrap_or_default(),
+                comments: comments
+                    .iter()
+                    .map(|c| PrThreadComment {
+                        // Deleted accounts have no author
+                        author: c
+                            .pointer("/author/login")
+                            .and_then(|l| l.as_str())
+                            .unwrap_or("ghost")
+                            .to_string(),
+                        body: str_at(c, "/body"),
+                        created_at: str_at(c, "/createdAt"),
+                        url: str_at(c, "/url"),
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+fn fetch_review_threads(
+    gh: &std::path::Path,
+    worktree_path: &str,
+    pr_number: u32,
+) -> Result<Vec<PrReviewThread>, String> {
+    let repo_id = get_repo_identifier(worktree_path)?;
+    let response = gh_graphql(
+        gh,
+        worktree_path,
+        REVIEW_THREADS_QUERY,
+        &[("owner", &repo_id.owner), ("name", &repo_id.repo)],
+        &[("number", pr_number.to_string())],
+    )?;
+    if response
+        .pointer("/data/repository/pullRequest")
+        .is_none_or(|p| p.is_null())
+    {
+        return Err(format!("PR #{pr_number} not found"));
+    }
+    Ok(parse_review_threads(&response))
+}
+
+/// Build the message asking the session to address the threads
+fn build_address_prompt(threads: &[PrReviewThread], pr_number: u32) -> String {
+    let mut prompt = format!(
+        "Address the following {} review thread(s) on PR #{pr_number}. \
+         For each thread, make the requested change, or leave the code as is if the \
+         comment doesn't call for one and explain why.\n",
+        threads.len()
+    );
+
+    for (i, thread) in threads.iter().enumerate() {
+        let location = match (thread.start_line, thread.line) {
+            (Some(start), Some(line)) if start != line => format!("{}:{start}-{line}", thread.path),
+            (_, Some(line)) => format!("{}:{line}", thread.path),
+            _ => format!("{} (outdated, line no longer in the diff)", thread.path),
+        };
+        prompt.push_str(&format!(
+            "\n## {}. Thread `{}`\n**Location:** {location}\n",
+            i + 1,
+            thread.id
+        ));
+        if !thread.diff_hunk.is_empty() {
+            prompt.push_str(&format!(
+                "\n```diff\n{}\n```\n",
+                thread.diff_hunk.trim_end()
+            ));
+        }
+        for comment in &thread.comments {
+            prompt.push_str(&format!(
+                "\n**@{}:**\n{}\n",
+                comment.author,
+                comment.body.trim()
+            ));
+        }
+    }
+
+    prompt.push_str(
+        "\nDon't commit or push; that is done after you finish. \
+         End your final reply with a fenced ```json block listing every thread above:\n\
+         ```json\n{\"threads\": [{\"id\": \"<thread id>\", \"summary\": \"<one sentence on what changed, or why nothing did>\"}]}\n```",
+    );
+    prompt
+}
+
+/// Parse the per-thread summaries from the last ```json block of the run's reply
+fn parse_thread_summaries(reply: &str) -> HashMap<String, String> {
+    #[derive(Deserialize)]
+    struct Summaries {
+        threads: Vec<Summary>,
+    }
+    #[derive(Deserialize)]
+    struct Summary {
+        id: String,
+        summary: String,
+    }
+
+    let Some(start) = reply.rfind("```json") else {
+        return HashMap::new();
+    };
+    let block = &reply[start + "```json".len()..];
+    let block = block.find("```").map_or(block, |end| &block[..end]);
+
+    serde_json::from_str::<Summaries>(block.trim())
+        .map(|s| {
+            s.threads
+                .into_iter()
+                .filter(|t| !t.summary.trim().is_empty())
+                .map(|t| (t.id, t.summary.trim().to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Reply posted on an addressed thread; cites the commit only when this step
+/// created one
+fn reply_body(summary: &str, commit_sha: Option<&str>) -> String {
+    match commit_sha {
+        Some(sha) => format!("Addressed in {}: {summary}", &sha[..sha.len().min(7)]),
+        None => summary.to_string(),
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Update the per-thread progress stored on a session
+fn update_thread_progress(
+    app: &AppHandle,
+    worktree_id: &str,
+    worktree_path: &str,
+    session_id: &str,
+    f: impl FnOnce(&mut HashMap<String, ReviewThreadProgress>),
+) -> Result<HashMap<String, ReviewThreadProgress>, String> {
+    with_sessions_mut(app, worktree_path, worktree_id, |sessions| {
+        let session = sessions
+            .find_session_mut(session_id)
+            .ok_or_else(|| format!("Session not found: {session_id}"))?;
+        f(&mut session.review_threads);
+        Ok(session.review_threads.clone())
+    })
+}
+
+/// Fetch the review threads of the worktree's linked pull request
+/// (unresolved only unless `include_resolved` is set)
+#[tauri::command]
+pub async fn get_pr_review_threads(
+    app: AppHandle,
+    worktree_id: String,
+    include_resolved: Option<bool>,
+) -> Result<Vec<PrReviewThread>, String> {
+    log::trace!("Getting review threads for worktree {worktree_id}");
+    let (worktree_path, pr_number) = linked_pr(&app, &worktree_id)?;
+    let gh = resolve_gh_binary(&app);
+
+    let mut threads =
+        tokio::task::spawn_blocking(move || fetch_review_threads(&gh, &worktree_path, pr_number))
+            .await
+            .map_err(|e| format!("Review threads task failed: {e}"))??;
+    if !include_resolved.unwrap_or(false) {
+        threads.retain(|t| !t.is_resolved);
+    }
+    log::trace!("Got {} review thread(s) for PR #{pr_number}", threads.len());
+    Ok(threads)
+}
+
+/// Start addressing review threads from a session.
+///
+/// Fetches the unresolved threads (all, or those in `thread_ids`), marks them
+/// in progress on the session and returns the task list to send to the
+/// session in build mode. Call `finish_addressing_review` after the run.
+#[tauri::command]
+pub async fn start_addressing_review(
+    app: AppHandle,
+    worktree_id: String,
+    session_id: String,
+    thread_ids: Option<Vec<String>>,
+) -> Result<AddressReviewTask, String> {
+    log::trace!("Starting to address review threads for worktree {worktree_id}");
+    let (worktree_path, pr_number) = linked_pr(&app, &worktree_id)?;
+    let gh = resolve_gh_binary(&app);
+
+    let threads = tokio::task::spawn_blocking({
+        let worktree_path = worktree_path.clone();
+        move || fetch_review_threads(&gh, &worktree_path, pr_number)
+    })
+    .await
+    .map_err(|e| format!("Review threads task failed: {e}"))??;
+    let threads: Vec<PrReviewThread> = threads
+        .into_iter()
+        .filter(|t| !t.is_resolved)
+        .filter(|t| thread_ids.as_ref().is_none_or(|ids| ids.contains(&t.id)))
+        .collect();
+    if threads.is_empty() {
+        return Err("No unresolved review threads to address".to_string());
+    }
+
+    let now = now_secs();
+    update_thread_progress(
+        &app,
+        &worktree_id,
+        &worktree_path,
+        &session_id,
+        |progress| {
+            for thread in &threads {
+                progress.insert(
+                    thread.id.clone(),
+                    ReviewThreadProgress {
+                        status: ReviewThreadStatus::InProgress,
+                        commit_sha: None,
+                        summary: None,
+                        error: None,
+                        updated_at: now,
+                    },
+                );
+            }
+        },
+    )?;
+
+    Ok(AddressReviewTask {
+        prompt: build_address_prompt(&threads, pr_number),
+        threads,
+    })
+}
+
+/// Finish addressing review threads after the session's run.
+///
+/// Commits any uncommitted changes (with `commit_message`), pushes, and
+/// replies to every in-progress thread with the summary the run reported for
+/// it, citing the commit when one was created here. Threads the run didn't
+/// report on are marked failed and get no reply. With `resolve` set, replied
+/// threads are also resolved.
+#[tauri::command]
+pub async fn finish_addressing_review(
+    app: AppHandle,
+    worktree_id: String,
+    session_id: String,
+    commit_message: Option<String>,
+    resolve: Option<bool>,
+) -> Result<HashMap<String, ReviewThreadProgress>, String> {
+    log::trace!("Finishing review threads for session {session_id}");
+    let (worktree_path, pr_number) = linked_pr(&app, &worktree_id)?;
+    let gh = resolve_gh_binary(&app);
+    let resolve = resolve.unwrap_or(false);
+
+    let sessions = crate::chat::storage::load_sessions(&app, &worktree_path, &worktree_id)?;
+    let session = sessions
+        .sessions
+        .iter()
+        .find(|s| s.id == session_id)
+        .ok_or_else(|| format!("Session not found: {session_id}"))?;
+    let pending: Vec<String> = session
+        .review_threads
+        .iter()
+        .filter(|(_, p)| p.status == ReviewThreadStatus::InProgress)
+        .map(|(id, _)| id.clone())
+        .collect();
+    if pending.is_empty() {
+        return Err("No review threads in progress for this session".to_string());
+    }
+
+    let reply = crate::chat::run_log::load_session_messages(&app, &session_id)?
+        .into_iter()
+        .rev()
+        .find(|m| m.role == MessageRole::Assistant)
+        .map(|m| m.content)
+        .unwrap_or_default();
+    let summaries = parse_thread_summaries(&reply);
+
+    let results = tokio::task::spawn_blocking({
+        let worktree_path = worktree_path.clone();
+        move || -> Result<Vec<ThreadOutcome>, String> {
+            let committed = super::git::has_uncommitted_changes(&worktree_path);
+            if committed {
+                let message = commit_message
+                    .filter(|m| !m.trim().is_empty())
+                    .unwrap_or_else(|| DEFAULT_REVIEW_COMMIT_MESSAGE.to_string());
+                super::git::commit_changes(&worktree_path, &message, true)?;
+            }
+            super::git::git_push_to_pr(&worktree_path, pr_number, &gh)?;
+            let sha = if committed {
+                Some(super::git::rev_parse(&worktree_path, "HEAD")?)
+            } else {
+                None
+            };
+
+            let results = pending
+                .into_iter()
+                .map(|id| {
+                    let Some(summary) = summaries.get(&id).cloned() else {
+                        return (id, Err("The run didn't report on this thread".to_string()));
+                    };
+                    let body = reply_body(&summary, sha.as_deref());
+                    let result = gh_graphql(
+                        &gh,
+                        &worktree_path,
+                        REPLY_MUTATION,
+                        &[("thread", &id), ("body", &body)],
+                        &[],
+                    )
+                    .and_then(|_| {
+                        if resolve {
+                            gh_graphql(
+                                &gh,
+                                &worktree_path,
+                                RESOLVE_MUTATION,
+                                &[("thread", &id)],
+                                &[],
+                            )?;
+                        }
+                        Ok(ReviewThreadProgress {
+                            status: if resolve {
+                                ReviewThreadStatus::Resolved
+                            } else {
+                                ReviewThreadStatus::Replied
+                            },
+                            commit_sha: sha.clone(),
+                            summary: Some(summary),
+                            error: None,
+                            updated_at: now_secs(),
+                        })
+                    });
+                    (id, result)
+                })
+                .collect();
+            Ok(results)
+        }
+    })
+    .await
+    .map_err(|e| format!("Review reply task failed: {e}"))??;
+
+    update_thread_progress(
+        &app,
+        &worktree_id,
+        &worktree_path,
+        &session_id,
+        |progress| {
+            for (id, result) in results {
+                let entry = match result {
+                    Ok(entry) => entry,
+                    Err(error) => {
+                        log::warn!("Failed to address review thread {id}: {error}");
+                        ReviewThreadProgress {
+                            status: ReviewThreadStatus::Failed,
+                            commit_sha: None,
+                            summary: None,
+                            error: Some(error),
+                            updated_at: now_secs(),
+                        }
+                    }
+                };
+                progress.insert(id, entry);
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(file: &str, line: Option<u32>, suggestion: Option<&str>) -> ReviewFinding {
+        ReviewFinding {
+            severity: "warning".to_string(),
+            file: file.to_string(),
+            line,
+            title: "Title".to_string(),
+            description: "Description".to_string(),
+            suggestion: suggestion.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_commentable_lines() {
+        let patch = "@@ -1,3 +1,4 @@\n a\n-b\n+B\n+C\n c\n@@ -20,2 +21,2 @@\n x\n-y\n+Y";
+        let lines = commentable_lines(patch);
+        let mut sorted: Vec<u32> = lines.into_iter().collect();
+        sorted.sort();
+        assert_eq!(sorted, vec![1, 2, 3, 4, 21, 22]);
+    }
+
+    #[test]
+    fn test_build_review_splits_inline_and_body() {
+        let review = ReviewResponse {
+            summary: "Looks fine".to_string(),
+            findings: vec![
+                finding("/wt/src/a.rs", Some(2), Some("let b = 2;")),
+                finding("/wt/src/a.rs", Some(50), None),
+                finding("./src/b.rs", Some(0), None),
+            ],
+            approval_status: "approved".to_string(),
+        };
+        let commentable = HashMap::from([("src/a.rs".to_string(), HashSet::from([1, 2, 3]))]);
+
+        let (body, comments) = build_review(&review, &commentable, "/wt");
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0]["path"], "src/a.rs");
+        assert_eq!(comments[0]["line"], 2);
+        assert!(comments[0]["body"]
+            .as_str()
+            .unwrap()
+            .ends_with("```suggestion\nlet b = 2;\n```"));
+        assert!(body.starts_with("Looks fine\n\n### Other findings\n"));
+        assert!(body.contains("`src/a.rs:50`"));
+        assert!(body.contains("`src/b.rs`"));
+        assert!(!body.contains("./src/b.rs"));
+        assert_eq!(review_event(&review.approval_status), "APPROVE");
+        assert_eq!(review_event("needs_discussion"), "COMMENT");
+    }
+
+    #[test]
+    fn test_parse_review_threads() {
+        let response = json!({"data": {"repository": {"pullRequest": {"reviewThreads": {"nodes": [
+            {"id": "T1", "isResolved": false, "isOutdated": false, "path": "src/a.rs", "line": 12, "startLine": null,
+             "comments": {"nodes": [
+                {"author": {"login": "alice"}, "body": "Rename this", "createdAt": "2026-01-01T00:00:00Z", "url": "u1", "diffHunk": "@@ -1 +1 @@"},
+                {"author": null, "body": "+1", "createdAt": "2026-01-02T00:00:00Z", "url": "u2", "diffHunk": "@@ -1 +1 @@"}
+             ]}},
+            {"id": "T2", "isResolved": true, "isOutdated": true, "path": "src/b.rs", "line": null, "startLine": null,
+             "comments": {"nodes": []}}
+        ]}}}}});
+
+        let threads = parse_review_threads(&response);
+        assert_eq!(threads.len(), 2);
+        assert_eq!(threads[0].line, Some(12));
+        assert_eq!(threads[0].diff_hunk, "@@ -1 +1 @@");
+        assert_eq!(threads[0].comments[1].author, "ghost");
+        assert!(threads[1].is_resolved && threads[1].line.is_none());
+
+        let prompt = build_address_prompt(&threads[..1], 7);
+        assert!(prompt.contains("PR #7"));
+        assert!(prompt.contains("Thread `T1`"));
+        assert!(prompt.contains("**Location:** src/a.rs:12"));
+        assert!(prompt.contains("**@alice:**\nRename this"));
+    }
+
+    #[test]
+    fn test_parse_thread_summaries() {
+        let reply = "Done.\n```json\n{\"threads\": [{\"id\": \"T1\", \"summary\": \" Renamed it \"}, {\"id\": \"T2\", \"summary\": \"\"}]}\n```\n";
+        let summaries = parse_thread_summaries(reply);
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries["T1"], "Renamed it");
+
+        assert!(parse_thread_summaries("no block here").is_empty());
+        assert!(parse_thread_summaries("```json\nnot json\n```").is_empty());
+    }
+
+    #[test]
+    fn test_reply_body_cites_only_new_commits() {
+        assert_eq!(
+            reply_body("Renamed it", Some("0123456789abcdef")),
+            "Addressed in 0123456: Renamed it"
+        );
+        assert_eq!(reply_body("Renamed it", None), "Renamed it");
+    }
+}
@@ -38,6 +38,8 @@ pub enum PrDisplayStatus {
     Draft,
     Open,
     Review,
+    /// In the merge queue (still open, may be ejected from the queue)
+    Queued,
     Merged,
     Closed,
 }
@@ -51,21 +53,217 @@ pub enum MergeableStatus {
     Unknown,
 }
 
-/// Raw response from gh pr view --json
+/// Merge state from GitHub API (why a PR can or can't be merged right now)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStateStatus {
+    /// Mergeable and passing commit status
+    Clean,
+    /// Blocked by branch protection (required reviews or checks)
+    Blocked,
+    /// Head branch is out of date with the base branch
+    Behind,
+    /// Merge conflicts
+    Dirty,
+    Draft,
+    /// Mergeable with passing commit status and pre-receive hooks
+    HasHooks,
+    /// Mergeable, but with non-passing (non-required) commit status
+    Unstable,
+    Unknown,
+}
+
+/// Auto-merge enabled on the PR
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AutoMergeStatus {
+    /// "merge", "squash" or "rebase"
+    pub merge_method: String,
+    pub enabled_by: Option<String>,
+    pub enabled_at: Option<String>,
+}
+
+/// Entry of the PR in the base branch's merge queue
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MergeQueueStatus {
+    /// 1-based position in the queue
+    pub position: Option<u32>,
+    /// QUEUED, AWAITING_CHECKS, MERGEABLE, UNMERGEABLE or LOCKED
+    pub state: String,
+    /// Estimated seconds until merged
+    pub estimated_time_to_merge: Option<u64>,
+    pub enqueued_at: Option<String>,
+}
+
+/// Check run shape used for the check status rollup
+#[derive(Debug, Clone)]
+struct StatusCheck {
+    conclusion: Option<String>,
+    status: Option<String>,
+}
+
+/// GraphQL query for the PR status. `{owner}`/`{repo}` are filled in by gh
+/// from the repository of the current directory.
+const PR_STATUS_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      state
+      isDraft
+      headRefOid
+      reviewDecision
+      mergeable
+      mergeStateStatus
+      autoMergeRequest { mergeMethod enabledAt enabledBy { login } }
+      mergeQueueEntry { position state estimatedTimeToMerge enqueuedAt }
+      baseRef {
+        branchProtectionRule { requiredApprovingReviewCount requiredStatusCheckContexts }
+      }
+      latestOpinionatedReviews(first: 100) { nodes { state } }
+      commits(last: 1) {
+        nodes {
+          commit {
+            statusCheckRollup {
+              contexts(first: 100) {
+                nodes {
+                  __typename
+                  ... on CheckRun { name status conclusion isRequired(pullRequestNumber: $number) }
+                  ... on StatusContext { context state isRequired(pullRequestNumber: $number) }
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}"#;
+
+#[derive(Debug, Clone, Deserialize)]
+struct Nodes<T> {
+    #[serde(default = "Vec::new")]
+    nodes: Vec<T>,
+}
+
+/// Raw pull request from the GraphQL response
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct GhPrViewResponse {
+struct GqlPullRequest {
     state: String,
     is_draft: bool,
+    head_ref_oid: Option<String>,
     review_decision: Option<String>,
-    status_check_rollup: Option<Vec<StatusCheck>>,
     mergeable: Option<String>,
+    merge_state_status: Option<String>,
+    auto_merge_request: Option<GqlAutoMerge>,
+    merge_queue_entry: Option<GqlMergeQueueEntry>,
+    base_ref: Option<GqlBaseRef>,
+    latest_opinionated_reviews: Option<Nodes<GqlReview>>,
+    commits: Nodes<GqlCommitNode>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct StatusCheck {
-    conclusion: Option<String>,
+#[serde(rename_all = "camelCase")]
+struct GqlAutoMerge {
+    merge_method: String,
+    enabled_at: Option<String>,
+    enabled_by: Option<GqlActor>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GqlActor {
+    login: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GqlMergeQueueEntry {
+    position: Option<u32>,
+    state: String,
+    estimated_time_to_merge: Option<u64>,
+    enqueued_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GqlBaseRef {
+    branch_protection_rule: Option<GqlProtectionRule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GqlProtectionRule {
+    required_approving_review_count: Option<u32>,
+    #[serde(default)]
+    required_status_check_contexts: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GqlReview {
+    state: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GqlCommitNode {
+    commit: GqlCommit,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GqlCommit {
+    status_check_rollup: Option<GqlRollup>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GqlRollup {
+    contexts: Nodes<GqlCheckContext>,
+}
+
+/// A CheckRun (name/status/conclusion) or a StatusContext (context/state)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GqlCheckContext {
+    name: Option<String>,
+    context: Option<String>,
     status: Option<String>,
+    conclusion: Option<String>,
+    state: Option<String>,
+    #[serde(default)]
+    is_required: bool,
+}
+
+impl GqlCheckContext {
+    fn name(&self) -> &str {
+        self.name
+            .as_deref()
+            .or(self.context.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Normalize to the check run shape used by `compute_check_status`
+    /// (status contexts only have a combined state)
+    fn to_status_check(&self) -> StatusCheck {
+        match &self.state {
+            Some(state) => match state.as_str() {
+                "PENDING" | "EXPECTED" => StatusCheck {
+                    conclusion: None,
+                    status: Some("PENDING".to_string()),
+                },
+                _ => StatusCheck {
+                    conclusion: Some(state.clone()),
+                    status: Some("COMPLETED".to_string()),
+                },
+            },
+            None => StatusCheck {
+                conclusion: self.conclusion.clone(),
+                status: self.status.clone(),
+            },
+        }
+    }
+
+    fn passed(&self) -> bool {
+        let result = self.conclusion.as_deref().or(self.state.as_deref());
+        matches!(result, Some("SUCCESS" | "NEUTRAL" | "SKIPPED"))
+    }
 }
 
 /// Processed PR status for frontend consumption
@@ -80,10 +278,20 @@ pub struct PrStatus {
     pub check_status: Option<CheckStatus>,
     pub display_status: PrDisplayStatus,
     pub mergeable: Option<MergeableStatus>,
+    pub merge_state_status: Option<MergeStateStatus>,
+    pub auto_merge: Option<AutoMergeStatus>,
+    pub merge_queue: Option<MergeQueueStatus>,
+    /// Approvals required by branch protection (None if unknown or not protected)
+    pub required_approving_review_count: Option<u32>,
+    pub approved_review_count: u32,
+    /// Required checks that haven't reported or haven't passed yet
+    pub missing_required_checks: Vec<String>,
+    /// Head commit the checks belong to
+    pub head_sha: Option<String>,
     pub checked_at: u64,
 }
 
-/// Fetch PR status using gh CLI
+/// Fetch PR status using gh CLI (GraphQL, for merge queue and branch protection details)
 pub fn get_pr_status(
     repo_path: &str,
     pr_number: u32,
@@ -93,65 +301,161 @@ pub fn get_pr_status(
 ) -> Result<PrStatus, String> {
     log::trace!("Fetching PR status for #{pr_number} in {repo_path}");
 
-    // Run gh pr view
     let output = silent_command(gh_binary)
-        .args([
-            "pr",
-            "view",
-            &pr_number.to_string(),
-            "--json",
-            "state,isDraft,reviewDecision,statusCheckRollup,mergeable",
-        ])
+        .args(["api", "graphql", "-f"])
+        .arg(format!("query={PR_STATUS_QUERY}"))
+        .args(["-F", "owner={owner}", "-F", "repo={repo}", "-F"])
+        .arg(format!("number={pr_number}"))
         .current_dir(repo_path)
         .output()
-        .map_err(|e| format!("Failed to run gh pr view: {e}"))?;
+        .map_err(|e| format!("Failed to run gh api graphql: {e}"))?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        // Handle specific errors
-        if stderr.contains("no pull requests found") || stderr.contains("Could not resolve") {
-            return Err("PR not found - may have been deleted".to_string());
-        }
-        if stderr.contains("gh auth login") || stderr.contains("authentication") {
-            return Err("GitHub CLI not authenticated".to_string());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let pr = parse_pr_response(&output.stdout, output.status.success(), &stderr)?;
+
+    let checked_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    Ok(build_pr_status(
+        pr,
+        worktree_id,
+        pr_number,
+        pr_url,
+        checked_at,
+    ))
+}
+
+/// Parse the pull request out of `gh api graphql` output.
+///
+/// A token without admin rights gets an error for `branchProtectionRule`, which
+/// makes gh exit non-zero although the rest of `data` is there, so a partial
+/// result is still used (without the branch protection details).
+fn parse_pr_response(stdout: &[u8], success: bool, stderr: &str) -> Result<GqlPullRequest, String> {
+    let response = serde_json::from_slice::<serde_json::Value>(stdout);
+    let pr_json = response
+        .as_ref()
+        .ok()
+        .and_then(|r| r.pointer("/data/repository/pullRequest"))
+        .filter(|v| !v.is_null())
+        .cloned();
+
+    if !success {
+        if pr_json.is_some() {
+            log::warn!("PR status is incomplete: {}", stderr.trim());
+        } else {
+            // Handle specific errors
+            if stderr.contains("no pull requests found") || stderr.contains("Could not resolve") {
+                return Err("PR not found - may have been deleted".to_string());
+            }
+            if stderr.contains("gh auth login") || stderr.contains("authentication") {
+                return Err("GitHub CLI not authenticated".to_string());
+            }
+            return Err(format!("gh api graphql failed: {stderr}"));
         }
-        return Err(format!("gh pr view failed: {stderr}"));
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let response: GhPrViewResponse =
-        serde_json::from_str(&stdout).map_err(|e| format!("Failed to parse gh response: {e}"))?;
+    response.map_err(|e| format!("Failed to parse gh response: {e}"))?;
+    let pr_json = pr_json.ok_or_else(|| "PR not found - may have been deleted".to_string())?;
+    serde_json::from_value(pr_json).map_err(|e| format!("Failed to parse gh response: {e}"))
+}
 
-    // Convert to PrStatus
-    let state = parse_pr_state(&response.state);
-    let review_decision = response
+/// Convert the GraphQL pull request into a `PrStatus`
+fn build_pr_status(
+    pr: GqlPullRequest,
+    worktree_id: &str,
+    pr_number: u32,
+    pr_url: &str,
+    checked_at: u64,
+) -> PrStatus {
+    let contexts = pr
+        .commits
+        .nodes
+        .into_iter()
+        .last()
+        .and_then(|n| n.commit.status_check_rollup)
+        .map(|r| r.contexts.nodes)
+        .unwrap_or_default();
+    let status_checks: Vec<StatusCheck> = contexts.iter().map(|c| c.to_status_check()).collect();
+    let protection = pr.base_ref.and_then(|b| b.branch_protection_rule);
+
+    let state = parse_pr_state(&pr.state);
+    let review_decision = pr
         .review_decision
         .as_ref()
         .and_then(|s| parse_review_decision(s));
-    let check_status = compute_check_status(&response.status_check_rollup);
-    let display_status = compute_display_status(&state, response.is_draft, &review_decision);
-    let mergeable = response
-        .mergeable
-        .as_ref()
-        .and_then(|s| parse_mergeable_status(s));
-
-    let checked_at = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_secs())
+    let check_status = compute_check_status(&Some(status_checks));
+    let merge_queue = pr.merge_queue_entry.map(|e| MergeQueueStatus {
+        position: e.position,
+        state: e.state,
+        estimated_time_to_merge: e.estimated_time_to_merge,
+        enqueued_at: e.enqueued_at,
+    });
+    let display_status = if state == PrState::Open && merge_queue.is_some() {
+        PrDisplayStatus::Queued
+    } else {
+        compute_display_status(&state, pr.is_draft, &review_decision)
+    };
+    let approved_review_count = pr
+        .latest_opinionated_reviews
+        .map(|r| r.nodes.iter().filter(|n| n.state == "APPROVED").count() as u32)
         .unwrap_or(0);
 
-    Ok(PrStatus {
+    PrStatus {
         worktree_id: worktree_id.to_string(),
         pr_number,
         pr_url: pr_url.to_string(),
         state,
-        is_draft: response.is_draft,
+        is_draft: pr.is_draft,
         review_decision,
         check_status,
         display_status,
-        mergeable,
+        mergeable: pr
+            .mergeable
+            .as_ref()
+            .and_then(|s| parse_mergeable_status(s)),
+        merge_state_status: pr
+            .merge_state_status
+            .as_ref()
+            .and_then(|s| parse_merge_state_status(s)),
+        auto_merge: pr.auto_merge_request.map(|a| AutoMergeStatus {
+            merge_method: a.merge_method.to_lowercase(),
+            enabled_by: a.enabled_by.map(|u| u.login),
+            enabled_at: a.enabled_at,
+        }),
+        merge_queue,
+        required_approving_review_count: protection
+            .as_ref()
+            .and_then(|p| p.required_approving_review_count),
+        approved_review_count,
+        missing_required_checks: missing_required_checks(
+            &contexts,
+            protection
+                .as_ref()
+                .map(|p| p.required_status_check_contexts.as_slice())
+                .unwrap_or_default(),
+        ),
+        head_sha: pr.head_ref_oid,
         checked_at,
-    })
+    }
+}
+
+/// Required checks (from branch protection or flagged required on the PR)
+/// that haven't reported or haven't passed yet, in a stable order
+fn missing_required_checks(contexts: &[GqlCheckContext], protected: &[String]) -> Vec<String> {
+    let mut required: Vec<&str> = protected.iter().map(String::as_str).collect();
+    for context in contexts.iter().filter(|c| c.is_required) {
+        if !required.contains(&context.name()) {
+            required.push(context.name());
+        }
+    }
+    required
+        .into_iter()
+        .filter(|name| !name.is_empty())
+        .filter(|name| !contexts.iter().any(|c| c.name() == *name && c.passed()))
+        .map(str::to_string)
+        .collect()
 }
 
 fn parse_pr_state(s: &str) -> PrState {
@@ -171,6 +475,20 @@ fn parse_mergeable_status(s: &str) -> Option<MergeableStatus> {
     }
 }
 
+fn parse_merge_state_status(s: &str) -> Option<MergeStateStatus> {
+    match s.to_uppercase().as_str() {
+        "CLEAN" => Some(MergeStateStatus::Clean),
+        "BLOCKED" => Some(MergeStateStatus::Blocked),
+        "BEHIND" => Some(MergeStateStatus::Behind),
+        "DIRTY" => Some(MergeStateStatus::Dirty),
+        "DRAFT" => Some(MergeStateStatus::Draft),
+        "HAS_HOOKS" => Some(MergeStateStatus::HasHooks),
+        "UNSTABLE" => Some(MergeStateStatus::Unstable),
+        "UNKNOWN" => Some(MergeStateStatus::Unknown),
+        _ => None,
+    }
+}
+
 fn parse_review_decision(s: &str) -> Option<ReviewDecision> {
     match s.to_uppercase().as_str() {
         "APPROVED" => Some(ReviewDecision::Approved),
@@ -216,7 +534,7 @@ fn compute_check_status(checks: &Option<Vec<StatusCheck>>) -> Option<CheckStatus
     }
 }
 
-fn compute_display_status(
+pub(crate) fn compute_display_status(
     state: &PrState,
     is_draft: bool,
     review_decision: &Option<ReviewDecision>,
@@ -284,6 +602,13 @@ mod tests {
             check_status: Some(CheckStatus::Success),
             display_status: PrDisplayStatus::Review,
             mergeable: Some(MergeableStatus::Mergeable),
+            merge_state_status: Some(MergeStateStatus::HasHooks),
+            auto_merge: None,
+            merge_queue: None,
+            required_approving_review_count: Some(1),
+            approved_review_count: 1,
+            missing_required_checks: vec![],
+            head_sha: None,
             checked_at: 1234567890,
         };
 
@@ -291,6 +616,7 @@ mod tests {
         assert!(json.contains("\"display_status\":\"review\""));
         assert!(json.contains("\"check_status\":\"success\""));
         assert!(json.contains("\"mergeable\":\"mergeable\""));
+        assert!(json.contains("\"merge_state_status\":\"has_hooks\""));
     }
 
     #[test]
@@ -309,4 +635,101 @@ mod tests {
         );
         assert_eq!(parse_mergeable_status("other"), None);
     }
+
+    #[test]
+    fn test_build_pr_status_queued_with_missing_checks() {
+        let pr: GqlPullRequest = serde_json::from_value(serde_json::json!({
+            "state": "OPEN",
+            "isDraft": false,
+            "reviewDecision": "APPROVED",
+            "mergeable": "MERGEABLE",
+            "mergeStateStatus": "BLOCKED",
+            "autoMergeRequest": {
+                "mergeMethod": "SQUASH",
+                "enabledAt": "2024-05-01T10:00:00Z",
+                "enabledBy": {"login": "alice"}
+            },
+            "mergeQueueEntry": {
+                "position": 2,
+                "state": "AWAITING_CHECKS",
+                "estimatedTimeToMerge": 600,
+                "enqueuedAt": "2024-05-01T10:01:00Z"
+            },
+            "baseRef": {
+                "branchProtectionRule": {
+                    "requiredApprovingReviewCount": 2,
+                    "requiredStatusCheckContexts": ["build", "deploy/preview"]
+                }
+            },
+            "latestOpinionatedReviews": {"nodes": [{"state": "APPROVED"}, {"state": "COMMENTED"}]},
+            "commits": {"nodes": [{"commit": {"statusCheckRollup": {"contexts": {"nodes": [
+                {"__typename": "CheckRun", "name": "build", "status": "COMPLETED",
+                 "conclusion": "SUCCESS", "isRequired": true},
+                {"__typename": "CheckRun", "name": "lint", "status": "IN_PROGRESS",
+                 "conclusion": null, "isRequired": true},
+                {"__typename": "StatusContext", "context": "coverage", "state": "FAILURE",
+                 "isRequired": false}
+            ]}}}}]}
+        }))
+        .unwrap();
+
+        let status = build_pr_status(pr, "wt", 5, "https://github.com/o/r/pull/5", 1);
+        assert_eq!(status.display_status, PrDisplayStatus::Queued);
+        assert_eq!(status.check_status, Some(CheckStatus::Failure));
+        assert_eq!(status.merge_state_status, Some(MergeStateStatus::Blocked));
+        assert_eq!(status.auto_merge.unwrap().merge_method, "squash");
+        assert_eq!(status.merge_queue.unwrap().position, Some(2));
+        assert_eq!(status.required_approving_review_count, Some(2));
+        assert_eq!(status.approved_review_count, 1);
+        assert_eq!(
+            status.missing_required_checks,
+            vec!["deploy/preview", "lint"]
+        );
+    }
+
+    #[test]
+    fn test_build_pr_status_without_checks_or_protection() {
+        let pr: GqlPullRequest = serde_json::from_value(serde_json::json!({
+            "state": "MERGED",
+            "isDraft": false,
+            "reviewDecision": null,
+            "mergeable": "UNKNOWN",
+            "mergeStateStatus": "UNKNOWN",
+            "autoMergeRequest": null,
+            "mergeQueueEntry": null,
+            "baseRef": null,
+            "latestOpinionatedReviews": {"nodes": []},
+            "commits": {"nodes": [{"commit": {"statusCheckRollup": null}}]}
+        }))
+        .unwrap();
+
+        let status = build_pr_status(pr, "wt", 5, "", 1);
+        assert_eq!(status.display_status, PrDisplayStatus::Merged);
+        assert_eq!(status.check_status, None);
+        assert_eq!(status.required_approving_review_count, None);
+        assert!(status.missing_required_checks.is_empty());
+    }
+
+    #[test]
+    fn test_parse_pr_response_tolerates_partial_errors() {
+        let stdout = serde_json::json!({
+            "data": {"repository": {"pullRequest": {
+                "state": "OPEN",
+                "isDraft": false,
+                "headRefOid": "abc123",
+                "baseRef": {"branchProtectionRule": null},
+                "commits": {"nodes": []}
+            }}},
+            "errors": [{"message": "Resource not accessible by integration"}]
+        })
+        .to_string();
+        let stderr = "gh: Resource not accessible by integration";
+
+        let pr = parse_pr_response(stdout.as_bytes(), false, stderr).unwrap();
+        assert_eq!(pr.head_ref_oid.as_deref(), Some("abc123"));
+        assert!(pr.base_ref.unwrap().branch_protection_rule.is_none());
+
+        let err = parse_pr_response(br#"{"data": null}"#, false, stderr).unwrap_err();
+        assert!(err.starts_with("gh api graphql failed"), "{err}");
+    }
 }
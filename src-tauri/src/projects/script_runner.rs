@@ -0,0 +1,281 @@
+//! Streaming execution of jean.json scripts (setup, teardown, hooks).
+//!
+//! Output is emitted line by line as `script:output` events while the script
+//! runs, and each run ends with a `script:finished` event. Running scripts are
+//! tracked per worktree path and script kind so they can be cancelled, and
+//! every run's process group is killed once its timeout passes.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use tauri::AppHandle;
+
+use super::types::{JeanConfig, ScriptFinishedEvent, ScriptOutputEvent};
+use crate::http_server::EmitExt;
+use crate::platform::kill_process_tree;
+
+/// Default timeout for setup scripts
+const DEFAULT_SETUP_TIMEOUT_SECS: u64 = 30 * 60;
+/// Default timeout for teardown scripts and hooks
+const DEFAULT_TIMEOUT_SECS: u64 = 10 * 60;
+/// How long to keep reading output after the script exits. Background
+/// processes it started (or ones that left its process group) can hold the
+/// pipes open indefinitely.
+const OUTPUT_DRAIN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A script process that is currently running
+struct RunningScript {
+    pid: u32,
+    cancelled: bool,
+    /// PID file inside the worktree's container, if it runs there
+    container_pid_file: Option<String>,
+}
+
+/// Running scripts keyed by (worktree path, script kind)
+static RUNNING_SCRIPTS: Lazy<Mutex<HashMap<(String, String), RunningScript>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn script_key(worktree_path: &str, kind: &str) -> (String, String) {
+    (worktree_path.to_string(), kind.to_string())
+}
+
+/// Timeout for a script kind from jean.json `timeouts`
+pub fn timeout_for(config: Option<&JeanConfig>, kind: &str) -> Duration {
+    let timeouts = config.and_then(|c| c.timeouts.as_ref());
+    let secs = match kind {
+        "setup" => timeouts
+            .and_then(|t| t.setup)
+            .unwrap_or(DEFAULT_SETUP_TIMEOUT_SECS),
+        "teardown" => timeouts
+            .and_then(|t| t.teardown)
+            .unwrap_or(DEFAULT_TIMEOUT_SECS),
+        _ => timeouts
+            .and_then(|t| t.hooks)
+            .unwrap_or(DEFAULT_TIMEOUT_SECS),
+    };
+    Duration::from_secs(secs)
+}
+
+/// Run a prepared script command, streaming its output.
+///
+/// Returns the combined output on success and an error containing it when the
+/// script fails, is cancelled or times out.
+pub fn run(
+    app: &AppHandle,
+    kind: &str,
+    worktree_path: &str,
+    mut cmd: Command,
+    timeout: Duration,
+    container_pid_file: Option<String>,
+) -> Result<String, String> {
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // Own process group so cancelling reaches the whole tree
+        cmd.process_group(0);
+    }
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("Failed to run {kind} script: {e}"))?;
+    let pid = child.id();
+
+    if let Ok(mut running) = RUNNING_SCRIPTS.lock() {
+        running.insert(
+            script_key(worktree_path, kind),
+            RunningScript {
+                pid,
+                cancelled: false,
+                container_pid_file: container_pid_file.clone(),
+            },
+        );
+    }
+
+    let output = Arc::new(Mutex::new(String::new()));
+    let (done_tx, done_rx) = mpsc::channel();
+    let reader_count = [
+        child
+            .stdout
+            .take()
+            .map(|s| Box::new(s) as Box<dyn Read + Send>),
+        child
+            .stderr
+            .take()
+            .map(|s| Box::new(s) as Box<dyn Read + Send>),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|stream| {
+        let app = app.clone();
+        let output = output.clone();
+        let kind = kind.to_string();
+        let worktree_path = worktree_path.to_string();
+        let done_tx = done_tx.clone();
+        thread::spawn(move || {
+            for line in BufReader::new(stream).lines() {
+                let Ok(line) = line else { break };
+                if let Ok(mut output) = output.lock() {
+                    output.push_str(&line);
+                    output.push('\n');
+                }
+                let event = ScriptOutputEvent {
+                    worktree_path: worktree_path.clone(),
+                    kind: kind.clone(),
+                    line,
+                };
+                let _ = app.emit_all("script:output", &event);
+            }
+            let _ = done_tx.send(());
+        });
+    })
+    .count();
+
+    let started = Instant::now();
+    let mut timed_out = false;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Ok(status),
+            Ok(None) => {}
+            Err(e) => break Err(e),
+        }
+        if !timed_out && started.elapsed() > timeout {
+            log::warn!(
+                "{kind} script in {worktree_path} timed out after {}s",
+                timeout.as_secs()
+            );
+            timed_out = true;
+            kill(app, worktree_path, pid, container_pid_file.as_deref());
+        }
+        thread::sleep(Duration::from_millis(100));
+    };
+    let drain_deadline = Instant::now() + OUTPUT_DRAIN_TIMEOUT;
+    for _ in 0..reader_count {
+        let remaining = drain_deadline.saturating_duration_since(Instant::now());
+        if done_rx.recv_timeout(remaining).is_err() {
+            log::warn!(
+                "{kind} script in {worktree_path} exited but its output is still open, not waiting for it"
+            );
+            break;
+        }
+    }
+
+    let cancelled = RUNNING_SCRIPTS
+        .lock()
+        .ok()
+        .and_then(|mut running| running.remove(&script_key(worktree_path, kind)))
+        .is_some_and(|r| r.cancelled);
+    let combined = output
+        .lock()
+        .map(|o| o.trim().to_string())
+        .unwrap_or_default();
+    let success = status.as_ref().is_ok_and(|s| s.success()) && !cancelled && !timed_out;
+
+    let finished = ScriptFinishedEvent {
+        worktree_path: worktree_path.to_string(),
+        kind: kind.to_string(),
+        success,
+        cancelled,
+        timed_out,
+    };
+    let _ = app.emit_all("script:finished", &finished);
+
+    let status = status.map_err(|e| format!("Failed to wait for {kind} script: {e}"))?;
+    if cancelled {
+        return Err(format!("{kind} script cancelled:\n{combined}"));
+    }
+    if timed_out {
+        return Err(format!(
+            "{kind} script timed out after {}s:\n{combined}",
+            timeout.as_secs()
+        ));
+    }
+    if !status.success() {
+        return Err(format!("{kind} script failed:\n{combined}"));
+    }
+    log::trace!("{kind} script completed successfully");
+    Ok(combined)
+}
+
+/// Cancel the scripts running in a worktree: only `kind` when given, else all
+/// of them. Returns the kinds that were cancelled.
+pub fn cancel(app: &AppHandle, worktree_path: &str, kind: Option<&str>) -> Vec<String> {
+    let targets: Vec<(String, u32, Option<String>)> = {
+        let Ok(mut running) = RUNNING_SCRIPTS.lock() else {
+            return Vec::new();
+        };
+        running
+            .iter_mut()
+            .filter(|((path, k), _)| path == worktree_path && kind.is_none_or(|kind| kind == k))
+            .map(|((_, k), script)| {
+                script.cancelled = true;
+                (k.clone(), script.pid, script.container_pid_file.clone())
+            })
+            .collect()
+    };
+    for (kind, pid, container_pid_file) in &targets {
+        log::trace!("Cancelling {kind} script in {worktree_path}");
+        kill(app, worktree_path, *pid, container_pid_file.as_deref());
+    }
+    targets.into_iter().map(|(kind, _, _)| kind).collect()
+}
+
+/// Whether a script of this kind is running in a worktree
+pub fn is_running(worktree_path: &str, kind: &str) -> bool {
+    RUNNING_SCRIPTS
+        .lock()
+        .is_ok_and(|running| running.contains_key(&script_key(worktree_path, kind)))
+}
+
+fn kill(app: &AppHandle, worktree_path: &str, pid: u32, container_pid_file: Option<&str>) {
+    if let Some(pid_file) = container_pid_file {
+        super::container::kill_tracked(app, worktree_path, pid_file);
+    }
+    if let Err(e) = kill_process_tree(pid) {
+        log::warn!("Failed to kill script process {pid}: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::projects::types::ScriptTimeouts;
+
+    #[test]
+    fn test_timeout_for_defaults_and_overrides() {
+        assert_eq!(
+            timeout_for(None, "setup"),
+            Duration::from_secs(DEFAULT_SETUP_TIMEOUT_SECS)
+        );
+        assert_eq!(
+            timeout_for(None, "pre_commit"),
+            Duration::from_secs(DEFAULT_TIMEOUT_SECS)
+        );
+
+        let config = JeanConfig {
+            timeouts: Some(ScriptTimeouts {
+                setup: Some(90),
+                teardown: None,
+                hooks: Some(5),
+            }),
+            ..Default::default()
+        };
+        assert_eq!(timeout_for(Some(&config), "setup"), Duration::from_secs(90));
+        assert_eq!(
+            timeout_for(Some(&config), "teardown"),
+            Duration::from_secs(DEFAULT_TIMEOUT_SECS)
+        );
+        assert_eq!(
+            timeout_for(Some(&config), "post_merge"),
+            Duration::from_secs(5)
+        );
+    }
+}
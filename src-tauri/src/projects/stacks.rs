@@ -0,0 +1,447 @@
+//! Stacked worktrees: worktrees whose branch is built on another worktree's
+//! branch instead of the project's default branch.
+//!
+//! A child records its parent (`parent_worktree_id`) and the parent commit it
+//! was last stacked on (`stack_base_commit`). Restacking replays only the
+//! child's own commits onto the parent's current tip, PRs target the parent
+//! branch, and when a parent PR merges its children are retargeted to the
+//! next branch down the stack.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use super::git;
+use super::storage::{load_projects_data, save_projects_data};
+use super::types::{Project, ProjectsData, Worktree};
+use crate::gh_cli::config::resolve_gh_binary;
+use crate::http_server::EmitExt;
+use crate::platform::silent_command;
+
+/// Result of restacking one worktree in a stack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestackOutcome {
+    pub worktree_id: String,
+    pub branch: String,
+    /// Branch the worktree was rebased onto
+    pub onto: String,
+    pub success: bool,
+    pub message: String,
+}
+
+/// Whether a worktree can still serve as a stack parent (not archived or merged)
+fn is_live(worktree: &Worktree) -> bool {
+    worktree.archived_at.is_none() && worktree.cached_pr_status.as_deref() != Some("merged")
+}
+
+/// The nearest live ancestor of a worktree, skipping merged or archived parents.
+///
+/// Returns `None` when the worktree isn't stacked or its whole stack below it
+/// has landed, in which case it belongs on the project's default branch.
+pub(crate) fn effective_parent<'a>(
+    data: &'a ProjectsData,
+    worktree: &Worktree,
+) -> Option<&'a Worktree> {
+    let mut seen = vec![worktree.id.as_str()];
+    let mut parent_id = worktree.parent_worktree_id.as_deref();
+    while let Some(id) = parent_id {
+        // Guard against cycles from hand-edited storage
+        if seen.contains(&id) {
+            return None;
+        }
+        seen.push(id);
+        let parent = data.find_worktree(id)?;
+        if is_live(parent) {
+            return Some(parent);
+        }
+        parent_id = parent.parent_worktree_id.as_deref();
+    }
+    None
+}
+
+/// Branch a worktree's PR should target: its live parent's branch, or the
+/// project's default branch for unstacked worktrees
+pub(crate) fn pr_target_branch(
+    data: &ProjectsData,
+    worktree: &Worktree,
+    project: &Project,
+) -> String {
+    effective_parent(data, worktree)
+        .map(|parent| parent.branch.clone())
+        .unwrap_or_else(|| project.default_branch.clone())
+}
+
+/// Worktrees stacked above `root_id`, parents before their children
+fn descendants<'a>(worktrees: &'a [Worktree], root_id: &str) -> Vec<&'a Worktree> {
+    let mut result: Vec<&Worktree> = Vec::new();
+    let mut queue = VecDeque::from([root_id.to_string()]);
+    while let Some(id) = queue.pop_front() {
+        for child in worktrees
+            .iter()
+            .filter(|w| w.parent_worktree_id.as_deref() == Some(id.as_str()))
+            .filter(|w| w.archived_at.is_none())
+        {
+            if child.id != root_id && !result.iter().any(|w| w.id == child.id) {
+                queue.push_back(child.id.clone());
+                result.push(child);
+            }
+        }
+    }
+    result
+}
+
+/// Create a worktree stacked on another worktree (runs in background like
+/// `create_worktree`)
+#[tauri::command]
+pub async fn create_child_worktree(
+    app: AppHandle,
+    parent_worktree_id: String,
+    custom_name: Option<String>,
+) -> Result<Worktree, String> {
+    log::trace!("Creating child worktree of: {parent_worktree_id}");
+
+    let data = load_projects_data(&app)?;
+    let parent = data
+        .find_worktree(&parent_worktree_id)
+        .ok_or_else(|| format!("Worktree not found: {parent_worktree_id}"))?;
+    let project_id = parent.project_id.clone();
+
+    super::create_worktree(
+        app,
+        project_id,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        custom_name,
+        Some(parent_worktree_id),
+    )
+    .await
+}
+
+/// Validate a stack parent for a new worktree in `project_id`
+pub(crate) fn resolve_parent<'a>(
+    data: &'a ProjectsData,
+    project_id: &str,
+    parent_worktree_id: &str,
+) -> Result<&'a Worktree, String> {
+    let parent = data
+        .find_worktree(parent_worktree_id)
+        .ok_or_else(|| format!("Worktree not found: {parent_worktree_id}"))?;
+    if parent.project_id != project_id {
+        return Err("Parent worktree belongs to a different project".to_string());
+    }
+    if !is_live(parent) {
+        return Err(format!(
+            "Cannot stack on '{}': it is archived or already merged",
+            parent.name
+        ));
+    }
+    Ok(parent)
+}
+
+/// Restack a worktree onto its parent, then restack every worktree stacked
+/// above it so the whole stack follows.
+///
+/// For a worktree that isn't stacked only its children are restacked. Children
+/// with uncommitted changes are skipped (along with everything above them)
+/// rather than having their work committed for them.
+#[tauri::command]
+pub async fn restack_worktree(
+    app: AppHandle,
+    worktree_id: String,
+    commit_message: Option<String>,
+) -> Result<Vec<RestackOutcome>, String> {
+    log::trace!("Restacking worktree: {worktree_id}");
+
+    let app_clone = app.clone();
+    tokio::task::spawn_blocking(move || restack(&app_clone, &worktree_id, commit_message))
+        .await
+        .map_err(|e| format!("Restack task failed: {e}"))?
+}
+
+fn restack(
+    app: &AppHandle,
+    worktree_id: &str,
+    commit_message: Option<String>,
+) -> Result<Vec<RestackOutcome>, String> {
+    let mut data = load_projects_data(app)?;
+    let root = data
+        .find_worktree(worktree_id)
+        .ok_or_else(|| format!("Worktree not found: {worktree_id}"))?
+        .clone();
+    let project = data
+        .find_project(&root.project_id)
+        .ok_or_else(|| format!("Project not found: {}", root.project_id))?
+        .clone();
+
+    let mut outcomes = Vec::new();
+    let mut failed: Vec<String> = Vec::new();
+
+    let is_stacked = root.parent_worktree_id.is_some() || root.stack_base_commit.is_some();
+    if is_stacked {
+        let outcome = restack_one(&mut data, &project, &root, commit_message.as_deref());
+        if !outcome.success {
+            failed.push(root.id.clone());
+        }
+        outcomes.push(outcome);
+    }
+
+    let stack: Vec<Worktree> = descendants(&data.worktrees, &root.id)
+        .into_iter()
+        .cloned()
+        .collect();
+    for child in stack {
+        let parent_failed = child
+            .parent_worktree_id
+            .as_ref()
+            .is_some_and(|id| failed.contains(id));
+        let outcome = if parent_failed {
+            skipped(&child, "Skipped: the worktree below it failed to restack")
+        } else if git::has_uncommitted_changes(&child.path) {
+            skipped(&child, "Skipped: worktree has uncommitted changes")
+        } else {
+            restack_one(&mut data, &project, &child, None)
+        };
+        if !outcome.success {
+            failed.push(child.id.clone());
+        }
+        outcomes.push(outcome);
+    }
+
+    // Merge the new stack bases into fresh storage (it may have changed meanwhile)
+    let mut latest = load_projects_data(app)?;
+    for outcome in outcomes.iter().filter(|o| o.success) {
+        if let (Some(updated), Some(wt)) = (
+            data.find_worktree(&outcome.worktree_id),
+            latest.find_worktree_mut(&outcome.worktree_id),
+        ) {
+            wt.parent_worktree_id = updated.parent_worktree_id.clone();
+            wt.stack_base_commit = updated.stack_base_commit.clone();
+        }
+    }
+    save_projects_data(app, &latest)?;
+
+    Ok(outcomes)
+}
+
+fn skipped(worktree: &Worktree, message: &str) -> RestackOutcome {
+    RestackOutcome {
+        worktree_id: worktree.id.clone(),
+        branch: worktree.branch.clone(),
+        onto: String::new(),
+        success: false,
+        message: message.to_string(),
+    }
+}
+
+/// Rebase one worktree onto its effective parent and record the new stack base
+fn restack_one(
+    data: &mut ProjectsData,
+    project: &Project,
+    worktree: &Worktree,
+    commit_message: Option<&str>,
+) -> RestackOutcome {
+    let parent = effective_parent(data, worktree).cloned();
+    let push = git::has_upstream_branch(&worktree.path);
+
+    let result = match parent {
+        Some(ref parent) => {
+            log::trace!(
+                "Restacking {} onto parent {}",
+                worktree.branch,
+                parent.branch
+            );
+            git::restack_onto(
+                &worktree.path,
+                &parent.branch,
+                worktree.stack_base_commit.as_deref(),
+                commit_message,
+                push,
+            )
+            .and_then(|message| {
+                let base = git::rev_parse(&worktree.path, &parent.branch)?;
+                Ok((parent.branch.clone(), message, Some(base)))
+            })
+        }
+        // Everything below it has landed: move onto the default branch
+        None => {
+            let onto = format!("origin/{}", project.default_branch);
+            log::trace!("Restacking {} onto {onto}", worktree.branch);
+            git::fetch_branch(&worktree.path, &project.default_branch)
+                .and_then(|_| {
+                    git::restack_onto(
+                        &worktree.path,
+                        &onto,
+                        worktree.stack_base_commit.as_deref(),
+                        commit_message,
+                        push,
+                    )
+                })
+                .map(|message| (onto.clone(), message, None))
+        }
+    };
+
+    match result {
+        Ok((onto, message, base)) => {
+            if let Some(wt) = data.find_worktree_mut(&worktree.id) {
+                if base.is_none() {
+                    wt.parent_worktree_id = None;
+                } else if let Some(ref parent) = parent {
+                    wt.parent_worktree_id = Some(parent.id.clone());
+                }
+                wt.stack_base_commit = base;
+            }
+            RestackOutcome {
+                worktree_id: worktree.id.clone(),
+                branch: worktree.branch.clone(),
+                onto,
+                success: true,
+                message,
+            }
+        }
+        Err(e) => {
+            log::warn!("Failed to restack {}: {e}", worktree.branch);
+            RestackOutcome {
+                worktree_id: worktree.id.clone(),
+                branch: worktree.branch.clone(),
+                onto: parent
+                    .map(|p| p.branch)
+                    .unwrap_or_else(|| project.default_branch.clone()),
+                success: false,
+                message: e,
+            }
+        }
+    }
+}
+
+/// Retarget the children of a worktree whose PR just merged.
+///
+/// Each child is re-parented to the next live worktree down the stack and its
+/// PR (if any) is pointed at that worktree's branch, or the project's default
+/// branch when nothing is left below it. The children's branches aren't
+/// rebased here; their `stack_base_commit` is kept so a later restack drops the
+/// merged commits cleanly.
+pub(crate) fn retarget_children(app: &AppHandle, merged_worktree_id: &str) {
+    let Ok(mut data) = load_projects_data(app) else {
+        return;
+    };
+    let Some(merged) = data.find_worktree(merged_worktree_id).cloned() else {
+        return;
+    };
+    let children: Vec<Worktree> = data
+        .worktrees
+        .iter()
+        .filter(|w| w.parent_worktree_id.as_deref() == Some(merged_worktree_id))
+        .filter(|w| w.archived_at.is_none())
+        .cloned()
+        .collect();
+    if children.is_empty() {
+        return;
+    }
+    let Some(project) = data.find_project(&merged.project_id).cloned() else {
+        return;
+    };
+
+    let new_parent = effective_parent(&data, &merged).cloned();
+    let new_base = new_parent
+        .as_ref()
+        .map(|p| p.branch.clone())
+        .unwrap_or_else(|| project.default_branch.clone());
+    let gh = resolve_gh_binary(app);
+
+    for child in &children {
+        log::trace!(
+            "Retargeting {} from merged {} to {new_base}",
+            child.branch,
+            merged.branch
+        );
+        if let Some(pr_number) = child.pr_number {
+            let output = silent_command(&gh)
+                .args(["pr", "edit", &pr_number.to_string(), "--base", &new_base])
+                .current_dir(&child.path)
+                .output();
+            match output {
+                Ok(o) if o.status.success() => {}
+                Ok(o) => log::warn!(
+                    "Failed to retarget PR #{pr_number}: {}",
+                    String::from_utf8_lossy(&o.stderr)
+                ),
+                Err(e) => log::warn!("Failed to run gh pr edit: {e}"),
+            }
+        }
+        if let Some(wt) = data.find_worktree_mut(&child.id) {
+            wt.parent_worktree_id = new_parent.as_ref().map(|p| p.id.clone());
+        }
+    }
+
+    if let Err(e) = save_projects_data(app, &data) {
+        log::error!("Failed to save retargeted stack: {e}");
+        return;
+    }
+    let _ = app.emit_all(
+        "worktrees:changed",
+        &serde_json::json!({ "project_id": project.id }),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn worktree(id: &str, parent: Option<&str>) -> Worktree {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "project_id": "p",
+            "name": id,
+            "path": format!("/tmp/{id}"),
+            "branch": id,
+            "created_at": 0,
+            "parent_worktree_id": parent,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_descendants_orders_parents_first() {
+        let worktrees = vec![
+            worktree("c", Some("b")),
+            worktree("b", Some("a")),
+            worktree("a", None),
+            worktree("d", Some("a")),
+            worktree("other", None),
+        ];
+        let ids: Vec<&str> = descendants(&worktrees, "a")
+            .iter()
+            .map(|w| w.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["b", "d", "c"]);
+        assert!(descendants(&worktrees, "c").is_empty());
+    }
+
+    #[test]
+    fn test_effective_parent_skips_merged_and_archived() {
+        let mut merged = worktree("b", Some("a"));
+        merged.cached_pr_status = Some("merged".to_string());
+        let mut archived = worktree("a", Some("root"));
+        archived.archived_at = Some(1);
+        let data = ProjectsData {
+            projects: vec![],
+            worktrees: vec![
+                worktree("root", None),
+                archived,
+                merged,
+                worktree("c", Some("b")),
+            ],
+        };
+
+        let child = data.find_worktree("c").unwrap();
+        assert_eq!(effective_parent(&data, child).unwrap().id, "root");
+        let root = data.find_worktree("root").unwrap();
+        assert!(effective_parent(&data, root).is_none());
+    }
+}
@@ -0,0 +1,549 @@
+//! Partial staging.
+//!
+//! Stage, unstage or discard changes for a whole file, selected hunks or
+//! selected lines. Hunk and line indices refer to the `DiffHunk`s and
+//! `DiffLine`s returned by `get_git_diff` with the "unstaged" (stage/discard)
+//! or "staged" (unstage) diff type. Selections are turned into a patch that
+//! is applied with `git apply`, so the index and working tree are only ever
+//! changed by git itself.
+
+use serde::{Deserialize, Serialize};
+
+use crate::platform::silent_command;
+
+/// Changes selected within one hunk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HunkSelection {
+    /// Index into `DiffFile.hunks`
+    pub hunk_index: usize,
+    /// Indices into `DiffHunk.lines` of the additions/deletions to include.
+    /// `None` selects every change in the hunk; context lines are ignored.
+    #[serde(default)]
+    pub line_indices: Option<Vec<usize>>,
+}
+
+/// What to do with the selected changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PartialOp {
+    /// Working tree -> index
+    Stage,
+    /// Index -> HEAD
+    Unstage,
+    /// Drop from the working tree (back to the index)
+    Discard,
+}
+
+impl PartialOp {
+    /// Whether the selected changes are applied in reverse
+    fn reverse(self) -> bool {
+        !matches!(self, PartialOp::Stage)
+    }
+}
+
+/// One line of a hunk in the raw diff
+#[derive(Debug, Clone)]
+struct RawLine {
+    /// '+', '-' or ' '
+    kind: char,
+    /// Line content without the prefix (keeps a trailing '\r')
+    text: String,
+    /// Followed by "\ No newline at end of file"
+    no_newline: bool,
+}
+
+#[derive(Debug, Clone)]
+struct RawHunk {
+    old_start: u32,
+    old_lines: u32,
+    new_start: u32,
+    new_lines: u32,
+    lines: Vec<RawLine>,
+}
+
+/// Split a single-file unified diff into its header lines and hunks.
+///
+/// Hunk lines are indexed the same way as `parse_unified_diff` indexes
+/// `DiffHunk.lines`, so selections from the UI line up.
+fn split_file_diff(raw: &str) -> (Vec<String>, Vec<RawHunk>) {
+    let mut header = Vec::new();
+    let mut hunks: Vec<RawHunk> = Vec::new();
+
+    for line in raw.split('\n') {
+        if line.starts_with("@@") {
+            let parse_range = |s: &str| -> (u32, u32) {
+                match s.split_once(',') {
+                    Some((start, count)) => {
+                        (start.parse().unwrap_or(0), count.parse().unwrap_or(0))
+                    }
+                    None => (s.parse().unwrap_or(0), 1),
+                }
+            };
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let (old_start, old_lines) = parse_range(parts.get(1).map_or("", |p| &p[1..]));
+            let (new_start, new_lines) = parse_range(parts.get(2).map_or("", |p| &p[1..]));
+            hunks.push(RawHunk {
+                old_start,
+                old_lines,
+                new_start,
+                new_lines,
+                lines: Vec::new(),
+            });
+            continue;
+        }
+        let Some(hunk) = hunks.last_mut() else {
+            if !line.is_empty() {
+                header.push(line.to_string());
+            }
+            continue;
+        };
+        match line.chars().next() {
+            Some(kind @ ('+' | '-' | ' ')) => hunk.lines.push(RawLine {
+                kind,
+                text: line[1..].to_string(),
+                no_newline: false,
+            }),
+            Some('\\') => {
+                if let Some(last) = hunk.lines.last_mut() {
+                    last.no_newline = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    (header, hunks)
+}
+
+/// Build a patch containing only the selected changes of a single-file diff.
+///
+/// Unselected changes are turned into context or left out depending on the
+/// direction, so the patch applies to the side it will be applied against:
+/// forward patches keep unselected deletions as context and drop unselected
+/// additions, reverse patches the other way round. Returns `None` when
+/// nothing is selected.
+fn build_partial_patch(
+    raw: &str,
+    path: &str,
+    selections: &[HunkSelection],
+    reverse: bool,
+) -> Result<Option<String>, String> {
+    let (header, hunks) = split_file_diff(raw);
+
+    let mut body = String::new();
+    let mut partial = false;
+    let mut any_selected = false;
+    // Line count change of the hunks emitted so far (new - old)
+    let mut delta: i64 = 0;
+
+    for (hunk_index, hunk) in hunks.iter().enumerate() {
+        let selection = selections.iter().find(|s| s.hunk_index == hunk_index);
+        let is_selected = |i: usize| {
+            selection.is_some_and(|s| s.line_indices.as_ref().is_none_or(|l| l.contains(&i)))
+        };
+
+        let mut lines = String::new();
+        let (mut old_count, mut new_count) = (0u32, 0u32);
+        let mut hunk_has_change = false;
+        for (i, line) in hunk.lines.iter().enumerate() {
+            let kind = match line.kind {
+                ' ' => Some(' '),
+                kind if is_selected(i) => {
+                    hunk_has_change = true;
+                    Some(kind)
+                }
+                kind => {
+                    partial = true;
+                    // Unselected change: keep the line on the side the patch is applied to
+                    match (kind, reverse) {
+                        ('-', false) | ('+', true) => Some(' '),
+                        _ => None,
+                    }
+                }
+            };
+            let Some(kind) = kind else {
+                continue;
+            };
+            if kind != '+' {
+                old_count += 1;
+            }
+            if kind != '-' {
+                new_count += 1;
+            }
+            lines.push(kind);
+            lines.push_str(&line.text);
+            lines.push('\n');
+            if line.no_newline {
+                lines.push_str("\\ No newline at end of file\n");
+            }
+        }
+
+        if !hunk_has_change {
+            partial |= hunk.lines.iter().any(|l| l.kind != ' ');
+            continue;
+        }
+        any_selected = true;
+
+        // The side the patch is applied against keeps the original position;
+        // for an empty range the start is the line before it
+        let first_line =
+            |start: u32, count: u32| i64::from(if count == 0 { start + 1 } else { start });
+        let start_for =
+            |first: i64, count: u32| (if count == 0 { first - 1 } else { first }).max(0);
+        let (old_start, new_start) = if reverse {
+            let first = first_line(hunk.new_start, hunk.new_lines);
+            (
+                start_for(first - delta, old_count),
+                start_for(first, new_count),
+            )
+        } else {
+            let first = first_line(hunk.old_start, hunk.old_lines);
+            (
+                start_for(first, old_count),
+                start_for(first + delta, new_count),
+            )
+        };
+        delta += i64::from(new_count) - i64::from(old_count);
+
+        body.push_str(&format!(
+            "@@ -{old_start},{old_count} +{new_start},{new_count} @@\n"
+        ));
+        body.push_str(&lines);
+    }
+
+    if selections.iter().any(|s| s.hunk_index >= hunks.len()) {
+        return Err("The diff has changed since it was loaded. Refresh and try again.".to_string());
+    }
+    if !any_selected {
+        return Ok(None);
+    }
+
+    // A partial change can't delete the file it applies to: deletion headers
+    // (forward) and creation headers (reverse) become plain modifications
+    let removes_file = |line: &str| {
+        if reverse {
+            line.starts_with("new file mode") || line == "--- /dev/null"
+        } else {
+            line.starts_with("deleted file mode") || line == "+++ /dev/null"
+        }
+    };
+    let mut patch = String::new();
+    for line in &header {
+        if partial && removes_file(line) {
+            if line.starts_with("---") {
+                patch.push_str(&format!("--- a/{path}\n"));
+            } else if line.starts_with("+++") {
+                patch.push_str(&format!("+++ b/{path}\n"));
+            }
+            continue;
+        }
+        if partial && line.starts_with("index ") {
+            continue;
+        }
+        patch.push_str(line);
+        patch.push('\n');
+    }
+    patch.push_str(&body);
+
+    Ok(Some(patch))
+}
+
+fn run_git(repo_path: &str, args: &[&str]) -> Result<std::process::Output, String> {
+    silent_command("git")
+        .args(args)
+        .current_dir(repo_path)
+        .output()
+        .map_err(|e| format!("Failed to run git {}: {e}", args[0]))
+}
+
+fn run_git_ok(repo_path: &str, args: &[&str]) -> Result<(), String> {
+    let output = run_git(repo_path, args)?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git {} failed: {}", args[0], stderr.trim()));
+    }
+    Ok(())
+}
+
+fn is_untracked(repo_path: &str, file_path: &str) -> Result<bool, String> {
+    let output = run_git(
+        repo_path,
+        &[
+            "ls-files",
+            "--others",
+            "--exclude-standard",
+            "--",
+            file_path,
+        ],
+    )?;
+    Ok(!String::from_utf8_lossy(&output.stdout).trim().is_empty())
+}
+
+/// Raw diff of one file for the given operation (`--unified=3` to match `get_git_diff`)
+fn file_diff(repo_path: &str, file_path: &str, op: PartialOp) -> Result<String, String> {
+    let output = if op != PartialOp::Unstage && is_untracked(repo_path, file_path)? {
+        // Exits 1 when there are differences
+        run_git(
+            repo_path,
+            &[
+                "diff",
+                "--no-index",
+                "--unified=3",
+                "--",
+                "/dev/null",
+                file_path,
+            ],
+        )?
+    } else {
+        let mut args = vec!["diff", "--unified=3"];
+        if op == PartialOp::Unstage {
+            args.push("--cached");
+        }
+        args.extend(["--", file_path]);
+        let output = run_git(repo_path, &args)?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("git diff failed: {}", stderr.trim()));
+        }
+        output
+    };
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Apply an operation to a whole file
+fn apply_to_file(repo_path: &str, file_path: &str, op: PartialOp) -> Result<(), String> {
+    match op {
+        PartialOp::Stage => run_git_ok(repo_path, &["add", "-A", "--", file_path]),
+        PartialOp::Unstage => {
+            // `reset` needs a HEAD; on an unborn branch just drop the index entry
+            run_git_ok(repo_path, &["reset", "-q", "--", file_path])
+                .or_else(|_| run_git_ok(repo_path, &["rm", "--cached", "-q", "--", file_path]))
+        }
+        PartialOp::Discard => {
+            if is_untracked(repo_path, file_path)? {
+                let target = std::path::Path::new(repo_path).join(file_path);
+                std::fs::remove_file(&target).map_err(|e| format!("Failed to remove file: {e}"))
+            } else {
+                run_git_ok(repo_path, &["checkout", "--", file_path])
+            }
+        }
+    }
+}
+
+/// Apply an operation to a file, or to the selected hunks/lines of it
+fn apply_partial(
+    repo_path: &str,
+    file_path: &str,
+    hunks: Option<&[HunkSelection]>,
+    op: PartialOp,
+) -> Result<(), String> {
+    let Some(selections) = hunks else {
+        return apply_to_file(repo_path, file_path, op);
+    };
+
+    let raw = file_diff(repo_path, file_path, op)?;
+    let Some(patch) = build_partial_patch(&raw, file_path, selections, op.reverse())? else {
+        return Err("No changes selected".to_string());
+    };
+    log::trace!("Applying partial patch ({op:?}) to {file_path}:\n{patch}");
+    apply_patch(repo_path, &patch, op != PartialOp::Discard, op.reverse())
+}
+
+fn apply_patch(repo_path: &str, patch: &str, cached: bool, reverse: bool) -> Result<(), String> {
+    let patch_path =
+        std::env::temp_dir().join(format!("jean-partial-{}.patch", uuid::Uuid::new_v4()));
+    std::fs::write(&patch_path, patch).map_err(|e| format!("Failed to write patch: {e}"))?;
+    let patch_arg = patch_path.to_string_lossy().to_string();
+
+    let mut args = vec!["apply", "--whitespace=nowarn"];
+    if cached {
+        args.push("--cached");
+    }
+    if reverse {
+        args.push("--reverse");
+    }
+    args.push(&patch_arg);
+    let result = run_git_ok(repo_path, &args);
+    let _ = std::fs::remove_file(&patch_path);
+    result
+}
+
+/// Raw diff of a file's unstaged changes, including untracked files
+pub(crate) fn unstaged_file_diff(repo_path: &str, file_path: &str) -> Result<String, String> {
+    file_diff(repo_path, file_path, PartialOp::Stage)
+}
+
+/// Stage whole hunks of a file, given its unstaged diff captured earlier.
+///
+/// Hunks of the same diff can be staged in separate calls (with commits in
+/// between) since hunks never overlap each other's context.
+pub(crate) fn stage_hunks(
+    repo_path: &str,
+    file_path: &str,
+    raw_diff: &str,
+    hunk_indices: &[usize],
+) -> Result<(), String> {
+    let selections: Vec<HunkSelection> = hunk_indices
+        .iter()
+        .map(|&hunk_index| HunkSelection {
+            hunk_index,
+            line_indices: None,
+        })
+        .collect();
+    let Some(patch) = build_partial_patch(raw_diff, file_path, &selections, false)? else {
+        return Ok(());
+    };
+    apply_patch(repo_path, &patch, true, false)
+}
+
+/// Stage a whole file (including deletions)
+pub(crate) fn stage_file(repo_path: &str, file_path: &str) -> Result<(), String> {
+    apply_to_file(repo_path, file_path, PartialOp::Stage)
+}
+
+/// Stage a file, or the selected hunks/lines of its unstaged changes
+#[tauri::command]
+pub async fn stage_changes(
+    worktree_path: String,
+    file_path: String,
+    hunks: Option<Vec<HunkSelection>>,
+) -> Result<(), String> {
+    log::trace!("Staging {file_path} in {worktree_path} (hunks: {hunks:?})");
+    tokio::task::spawn_blocking(move || {
+        apply_partial(
+            &worktree_path,
+            &file_path,
+            hunks.as_deref(),
+            PartialOp::Stage,
+        )
+    })
+    .await
+    .map_err(|e| format!("Stage task failed: {e}"))?
+}
+
+/// Unstage a file, or the selected hunks/lines of its staged changes
+#[tauri::command]
+pub async fn unstage_changes(
+    worktree_path: String,
+    file_path: String,
+    hunks: Option<Vec<HunkSelection>>,
+) -> Result<(), String> {
+    log::trace!("Unstaging {file_path} in {worktree_path} (hunks: {hunks:?})");
+    tokio::task::spawn_blocking(move || {
+        apply_partial(
+            &worktree_path,
+            &file_path,
+            hunks.as_deref(),
+            PartialOp::Unstage,
+        )
+    })
+    .await
+    .map_err(|e| format!("Unstage task failed: {e}"))?
+}
+
+/// Discard a file's unstaged changes, or the selected hunks/lines of them.
+/// Staged changes are kept; untracked files are deleted when discarded whole.
+#[tauri::command]
+pub async fn discard_changes(
+    worktree_path: String,
+    file_path: String,
+    hunks: Option<Vec<HunkSelection>>,
+) -> Result<(), String> {
+    log::trace!("Discarding changes to {file_path} in {worktree_path} (hunks: {hunks:?})");
+    tokio::task::spawn_blocking(move || {
+        apply_partial(
+            &worktree_path,
+            &file_path,
+            hunks.as_deref(),
+            PartialOp::Discard,
+        )
+    })
+    .await
+    .map_err(|e| format!("Discard task failed: {e}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODIFIED: &str = "diff --git a/f b/f
+index 1111111..2222222 100644
+--- a/f
++++ b/f
+@@ -1,4 +1,4 @@
+ a
+-b
++B
+ c
+-d
++D
+";
+
+    fn select(hunk_index: usize, lines: Option<Vec<usize>>) -> HunkSelection {
+        HunkSelection {
+            hunk_index,
+            line_indices: lines,
+        }
+    }
+
+    #[test]
+    fn test_partial_patch_forward_and_reverse() {
+        // Stage only the b -> B change: d stays as context, +D is dropped
+        let patch = build_partial_patch(MODIFIED, "f", &[select(0, Some(vec![1, 2]))], false)
+            .unwrap()
+            .unwrap();
+        // Blob ids no longer describe a partial patch
+        assert!(patch.starts_with("diff --git a/f b/f\n--- a/f\n+++ b/f\n"));
+        assert!(patch.ends_with("@@ -1,4 +1,4 @@\n a\n-b\n+B\n c\n d\n"));
+
+        // Reverse: unselected +D becomes context, -d is dropped
+        let patch = build_partial_patch(MODIFIED, "f", &[select(0, Some(vec![1, 2]))], true)
+            .unwrap()
+            .unwrap();
+        assert!(patch.ends_with("@@ -1,4 +1,4 @@\n a\n-b\n+B\n c\n D\n"));
+
+        // Only context selected -> nothing to apply
+        assert!(
+            build_partial_patch(MODIFIED, "f", &[select(0, Some(vec![0]))], false)
+                .unwrap()
+                .is_none()
+        );
+        // Stale hunk index
+        assert!(build_partial_patch(MODIFIED, "f", &[select(1, None)], false).is_err());
+    }
+
+    #[test]
+    fn test_partial_patch_new_file_and_no_newline() {
+        let new_file = "diff --git a/n b/n
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/n
+@@ -0,0 +1,3 @@
++x
++y
++z
+\\ No newline at end of file
+";
+        // Staging part of a new file keeps the creation header
+        let patch = build_partial_patch(new_file, "n", &[select(0, Some(vec![0, 2]))], false)
+            .unwrap()
+            .unwrap();
+        assert!(patch.contains("new file mode 100644\n"));
+        assert!(patch.ends_with("@@ -0,0 +1,2 @@\n+x\n+z\n\\ No newline at end of file\n"));
+
+        // Discarding part of it turns the header into a modification
+        let patch = build_partial_patch(new_file, "n", &[select(0, Some(vec![1]))], true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            patch,
+            "diff --git a/n b/n\n--- a/n\n+++ b/n\n@@ -1,2 +1,3 @@\n x\n+y\n z\n\\ No newline at end of file\n"
+        );
+
+        // Selecting everything keeps it a creation
+        let patch = build_partial_patch(new_file, "n", &[select(0, None)], true)
+            .unwrap()
+            .unwrap();
+        assert!(patch.contains("--- /dev/null\n"));
+    }
+}
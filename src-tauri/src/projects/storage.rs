@@ -189,6 +189,21 @@ pub fn save_projects_data(app: &AppHandle, data: &ProjectsData) -> Result<(), St
     save_projects_data_internal(app, data)
 }
 
+/// Atomically load, modify, and save projects data.
+/// This prevents lost updates by holding the lock for the entire operation.
+pub fn with_projects_data_mut<F, T>(app: &AppHandle, f: F) -> Result<T, String>
+where
+    F: FnOnce(&mut ProjectsData) -> Result<T, String>,
+{
+    let _lock = PROJECTS_LOCK.lock().unwrap();
+
+    let mut data = load_projects_data_internal(app)?;
+    let result = f(&mut data)?;
+    save_projects_data_internal(app, &data)?;
+
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
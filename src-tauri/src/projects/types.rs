@@ -1,6 +1,8 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
-use crate::chat::types::LabelData;
+use crate::chat::types::{LabelData, PermissionPolicy};
 
 /// Type of session (base branch or worktree)
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -28,8 +30,188 @@ pub enum MergeType {
 /// Jean configuration from jean.json
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct JeanConfig {
+    /// JSON schema reference for editor support (ignored by Jean)
+    #[serde(rename = "$schema", default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
     #[serde(default)]
     pub scripts: JeanScripts,
+    /// Environment variables for all scripts and hooks
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Lifecycle hooks
+    #[serde(default, skip_serializing_if = "JeanHooks::is_empty")]
+    pub hooks: JeanHooks,
+    /// Tool permission rules for AI sessions in this repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<PermissionPolicy>,
+    /// Run each worktree in its own container
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<ContainerConfig>,
+    /// Per-worktree port allocation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ports: Option<PortsConfig>,
+    /// Long-running processes supervised per worktree (e.g. "api", "web")
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub services: BTreeMap<String, ServiceConfig>,
+    /// Timeouts for setup/teardown scripts and hooks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeouts: Option<ScriptTimeouts>,
+    /// Untracked files and caches copied from the project root into new worktrees
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub copy: Vec<CopyEntry>,
+}
+
+/// Entry of jean.json `copy`: a glob relative to the project root, or a glob
+/// with an explicit copy method
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum CopyEntry {
+    Pattern(String),
+    Detailed(CopySpec),
+}
+
+/// Detailed form of a jean.json `copy` entry
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CopySpec {
+    pub path: String,
+    #[serde(default)]
+    pub mode: CopyMode,
+}
+
+impl CopyEntry {
+    pub fn pattern(&self) -> &str {
+        match self {
+            CopyEntry::Pattern(pattern) => pattern,
+            CopyEntry::Detailed(spec) => &spec.path,
+        }
+    }
+
+    pub fn mode(&self) -> CopyMode {
+        match self {
+            CopyEntry::Pattern(_) => CopyMode::default(),
+            CopyEntry::Detailed(spec) => spec.mode,
+        }
+    }
+}
+
+/// How a `copy` entry is brought into the worktree. Reflinks and hardlinks
+/// fall back to a plain copy when the filesystem doesn't support them.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CopyMode {
+    /// Copy-on-write clone (no extra disk space until modified)
+    #[default]
+    Reflink,
+    /// Hard links (shared with the project root; edits affect both)
+    Hardlink,
+    /// Plain copy
+    Copy,
+}
+
+impl CopyMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CopyMode::Reflink => "reflink",
+            CopyMode::Hardlink => "hardlink",
+            CopyMode::Copy => "copy",
+        }
+    }
+}
+
+/// Timeouts section of jean.json, in seconds
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct ScriptTimeouts {
+    /// Setup script (None = 30 minutes)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub setup: Option<u64>,
+    /// Teardown script (None = 10 minutes)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub teardown: Option<u64>,
+    /// Each lifecycle hook (None = 10 minutes)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<u64>,
+}
+
+/// A supervised long-running process from jean.json `services`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ServiceConfig {
+    pub command: String,
+    /// Environment variables (added to the top-level `env`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Working directory relative to the worktree
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// How to tell the service is ready (None = running means ready)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ready: Option<ReadyCheck>,
+    /// When to restart the process after it exits
+    #[serde(default)]
+    pub restart: RestartPolicy,
+    /// Give up after this many consecutive restarts (None = 5)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_restarts: Option<u32>,
+}
+
+/// Readiness check for a service: an HTTP URL answering with a non-5xx
+/// status, or a regex matched against its output
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct ReadyCheck {
+    /// URL to poll; `{{JEAN_PORT}}`-style placeholders are replaced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Regex matched against each output line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log: Option<String>,
+    /// Seconds to wait before reporting the service unhealthy (None = 60)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+/// Restart policy for a service
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RestartPolicy {
+    /// Restart when the process exits with an error
+    #[default]
+    OnFailure,
+    /// Restart whenever the process exits
+    Always,
+    /// Never restart
+    Never,
+}
+
+/// Ports section of jean.json
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct PortsConfig {
+    /// Number of ports to allocate per worktree (None = 1)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<u16>,
+    /// Template files rendered into the worktree during setup, relative to the
+    /// worktree (`.env.jean.tmpl` is always rendered when present)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub templates: Vec<String>,
+}
+
+/// Container section of jean.json
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct ContainerConfig {
+    /// Image to run (e.g. "node:22")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    /// Dockerfile to build instead of using `image` (relative to the worktree)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dockerfile: Option<String>,
+    /// "docker" or "podman" (None = whichever is installed, docker first)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<String>,
+    /// Extra arguments for `run` (e.g. ["-p", "3000:3000"]). Only honoured in
+    /// the project root's jean.json
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub run_args: Vec<String>,
+    /// Run Claude sessions inside the container, Linux hosts only (None = false)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<bool>,
 }
 
 /// Scripts section of jean.json
@@ -41,6 +223,105 @@ pub struct JeanScripts {
     pub teardown: Option<String>,
     /// Script to run the dev environment
     pub run: Option<String>,
+    /// Additional named scripts (e.g. "dev", "test", "lint", "storybook")
+    #[serde(flatten)]
+    pub named: BTreeMap<String, ScriptEntry>,
+}
+
+/// A jean.json script: a plain shell command, or a command with its own
+/// environment variables and working directory
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum ScriptEntry {
+    Command(String),
+    Detailed(ScriptSpec),
+}
+
+/// Detailed form of a jean.json script
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScriptSpec {
+    pub command: String,
+    /// Environment variables (added to the top-level `env`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Working directory relative to the worktree
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+}
+
+impl ScriptEntry {
+    pub fn command(&self) -> &str {
+        match self {
+            ScriptEntry::Command(command) => command,
+            ScriptEntry::Detailed(spec) => &spec.command,
+        }
+    }
+
+    pub fn env(&self) -> Option<&BTreeMap<String, String>> {
+        match self {
+            ScriptEntry::Command(_) => None,
+            ScriptEntry::Detailed(spec) => Some(&spec.env),
+        }
+    }
+
+    pub fn cwd(&self) -> Option<&str> {
+        match self {
+            ScriptEntry::Command(_) => None,
+            ScriptEntry::Detailed(spec) => spec.cwd.as_deref(),
+        }
+    }
+}
+
+/// Hooks section of jean.json
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct JeanHooks {
+    /// After a worktree is created from a pull request (after `setup`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_checkout_pr: Option<ScriptEntry>,
+    /// Before a worktree is archived; failure blocks archiving
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_archive: Option<ScriptEntry>,
+    /// In the project root after a worktree is merged into the base branch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_merge: Option<ScriptEntry>,
+    /// Before Jean commits changes; failure blocks the commit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_commit: Option<ScriptEntry>,
+}
+
+/// Lifecycle hook names, as used in jean.json
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JeanHook {
+    PostCheckoutPr,
+    PreArchive,
+    PostMerge,
+    PreCommit,
+}
+
+impl JeanHook {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            JeanHook::PostCheckoutPr => "post_checkout_pr",
+            JeanHook::PreArchive => "pre_archive",
+            JeanHook::PostMerge => "post_merge",
+            JeanHook::PreCommit => "pre_commit",
+        }
+    }
+}
+
+impl JeanHooks {
+    pub fn is_empty(&self) -> bool {
+        *self == JeanHooks::default()
+    }
+
+    pub fn get(&self, hook: JeanHook) -> Option<&ScriptEntry> {
+        match hook {
+            JeanHook::PostCheckoutPr => self.post_checkout_pr.as_ref(),
+            JeanHook::PreArchive => self.pre_archive.as_ref(),
+            JeanHook::PostMerge => self.post_merge.as_ref(),
+            JeanHook::PreCommit => self.pre_commit.as_ref(),
+        }
+    }
 }
 
 /// A git project that has been added to Jean, or a folder for organizing projects
@@ -93,6 +374,75 @@ pub struct Project {
     /// Linear team ID to filter issues (None = show all teams)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub linear_team_id: Option<String>,
+    /// Tool permission rules for AI sessions (combined with jean.json `permissions`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permission_policy: Option<PermissionPolicy>,
+    /// Sandbox settings for agent runs (Linux only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<SandboxConfig>,
+    /// Defaults applied to pull requests created from this project's worktrees
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pr_defaults: Option<PrDefaults>,
+    /// Forge hosting the repository (None = detect from the origin remote)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forge: Option<super::forge::ForgeKind>,
+    /// Access token for a non-GitHub forge (None = environment / forge CLI)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forge_token: Option<String>,
+    /// Web root of a self-hosted forge, e.g. "https://example.com/git"
+    /// (None = https://{remote host})
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forge_url: Option<String>,
+    /// Jira site URL (Cloud: https://team.atlassian.net, or a Server/Data Center root)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jira_url: Option<String>,
+    /// Account email for Jira Cloud API tokens (None = token is a Server/DC PAT)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jira_email: Option<String>,
+    /// Jira API token (Cloud) or personal access token (Server/Data Center)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jira_api_token: Option<String>,
+    /// JQL filter applied to issue lists, e.g. "project = ENG" (None = all projects)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jira_jql: Option<String>,
+}
+
+/// Per-project sandbox settings for agent runs
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct SandboxConfig {
+    /// Restrict agent writes to the worktree, its git dir and Jean's data dirs
+    #[serde(default)]
+    pub enabled: bool,
+    /// Block network access for commands run by the agent. Only Codex can
+    /// enforce this; Claude and OpenCode runs are refused while it is set
+    #[serde(default)]
+    pub block_network: bool,
+    /// Additional writable paths (e.g. "~/.cargo"); a leading `~/` is expanded
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_writable_paths: Vec<String>,
+}
+
+/// Per-project defaults for new pull requests
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct PrDefaults {
+    /// Open new PRs as drafts
+    #[serde(default)]
+    pub draft: bool,
+    /// Reviewers to request: GitHub logins or `org/team` slugs (leading `@` optional)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reviewers: Vec<String>,
+    /// Labels to add (e.g. "ai-generated")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// Assignees to add (`@me` assigns the authenticated user)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assignees: Vec<String>,
+    /// Milestone title
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<String>,
+    /// Enable auto-merge with this method ("merge", "squash" or "rebase")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_merge_method: Option<String>,
 }
 
 /// A git worktree created for a project
@@ -134,6 +484,9 @@ pub struct Worktree {
     /// Linear issue identifier (e.g. "ENG-123", if created from a Linear issue)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub linear_issue_identifier: Option<String>,
+    /// Jira issue key (e.g. "ENG-123", if created from a Jira issue)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jira_issue_key: Option<String>,
     /// Cached PR display status (draft, open, review, merged, closed)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cached_pr_status: Option<String>,
@@ -185,6 +538,24 @@ pub struct Worktree {
     /// Unix timestamp when worktree was last opened/viewed by the user
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub last_opened_at: Option<u64>,
+    /// Ports allocated to this worktree (when jean.json has a `ports` section)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ports: Option<PortRange>,
+    /// Worktree this one is stacked on (its branch was created from the parent's branch)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_worktree_id: Option<String>,
+    /// Parent commit the branch was last stacked on (`git rebase --onto` upstream when restacking)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack_base_commit: Option<String>,
+}
+
+/// A contiguous range of ports allocated to a worktree
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PortRange {
+    /// First port (`JEAN_PORT`)
+    pub start: u16,
+    /// Number of ports (`JEAN_PORT`, `JEAN_PORT_1` .. `JEAN_PORT_{count-1}`)
+    pub count: u16,
 }
 
 /// Container for all persisted project data
@@ -403,6 +774,26 @@ pub struct WorktreeCreatingEvent {
     pub issue_number: Option<u64>,
 }
 
+/// Event emitted for each line of output from a running jean.json script
+#[derive(Clone, Serialize)]
+pub struct ScriptOutputEvent {
+    /// Worktree the script runs in (known before the worktree is saved)
+    pub worktree_path: String,
+    /// "setup", "teardown" or a hook name
+    pub kind: String,
+    pub line: String,
+}
+
+/// Event emitted when a jean.json script finishes
+#[derive(Clone, Serialize)]
+pub struct ScriptFinishedEvent {
+    pub worktree_path: String,
+    pub kind: String,
+    pub success: bool,
+    pub cancelled: bool,
+    pub timed_out: bool,
+}
+
 /// Event emitted when worktree creation completes successfully
 #[derive(Clone, Serialize)]
 pub struct WorktreeCreatedEvent {
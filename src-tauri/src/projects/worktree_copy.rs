@@ -0,0 +1,433 @@
+//! Copy-on-create of untracked files into new worktrees.
+//!
+//! jean.json `copy` lists globs (relative to the project root) of ignored or
+//! untracked artefacts a fresh worktree lacks: `.env*`, `node_modules`,
+//! `target`, ... Matching entries are reflinked (copy-on-write clone, the
+//! default), hardlinked or copied into the new worktree before setup runs.
+//! Reflinks and hardlinks fall back to a plain copy when the filesystem
+//! doesn't support them. Paths that already exist in the worktree (tracked
+//! files) are never overwritten.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use globset::{GlobBuilder, GlobMatcher};
+use serde::Serialize;
+use tauri::AppHandle;
+
+use super::types::{CopyEntry, CopyMode, ScriptOutputEvent};
+use crate::http_server::EmitExt;
+use crate::platform::silent_command;
+
+/// How deep to look for matches when a pattern contains `**`
+const MAX_RECURSIVE_DEPTH: usize = 8;
+
+/// One path copied into a worktree
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CopiedEntry {
+    /// Path relative to the worktree
+    pub path: String,
+    /// Method actually used (after any fallback)
+    pub mode: CopyMode,
+    /// Size of the source in bytes
+    pub bytes: u64,
+}
+
+/// Copy the configured entries from the project root into a new worktree.
+///
+/// Progress is emitted as `script:output` events with kind "copy". Failures
+/// of single entries are logged and skipped.
+pub fn copy_into_worktree(
+    app: &AppHandle,
+    root_path: &str,
+    worktree_path: &str,
+    entries: &[CopyEntry],
+) -> Vec<CopiedEntry> {
+    let patterns: Vec<(GlobMatcher, CopyMode)> = entries
+        .iter()
+        .filter_map(|entry| match compile(entry.pattern()) {
+            Ok(matcher) => Some((matcher, entry.mode())),
+            Err(e) => {
+                log::warn!("Invalid copy pattern `{}`: {e}", entry.pattern());
+                None
+            }
+        })
+        .collect();
+    if patterns.is_empty() {
+        return Vec::new();
+    }
+    let max_depth = entries
+        .iter()
+        .map(|e| pattern_depth(e.pattern()))
+        .max()
+        .unwrap_or(1);
+
+    let mut matches = Vec::new();
+    find_matches(
+        Path::new(root_path),
+        "",
+        1,
+        max_depth,
+        &patterns,
+        &mut matches,
+    );
+
+    let mut copied = Vec::new();
+    for (relative, mode) in matches {
+        let src = Path::new(root_path).join(&relative);
+        let dst = Path::new(worktree_path).join(&relative);
+        if fs::symlink_metadata(&dst).is_ok() {
+            log::trace!("Skipping copy of {relative}: already exists in worktree");
+            continue;
+        }
+        match copy_entry(&src, &dst, mode) {
+            Ok(used) => {
+                let bytes = size_of(&src);
+                emit_line(
+                    app,
+                    worktree_path,
+                    format!(
+                        "Copied {relative} ({}, {})",
+                        used.as_str(),
+                        format_size(bytes)
+                    ),
+                );
+                copied.push(CopiedEntry {
+                    path: relative,
+                    mode: used,
+                    bytes,
+                });
+            }
+            Err(e) => {
+                log::warn!("Failed to copy {relative} into worktree: {e}");
+                emit_line(
+                    app,
+                    worktree_path,
+                    format!("Failed to copy {relative}: {e}"),
+                );
+            }
+        }
+    }
+
+    if !copied.is_empty() {
+        let total: u64 = copied.iter().map(|c| c.bytes).sum();
+        emit_line(
+            app,
+            worktree_path,
+            format!(
+                "Copied {} entries ({}) from the project root",
+                copied.len(),
+                format_size(total)
+            ),
+        );
+    }
+    copied
+}
+
+/// One-paragraph summary for the worktree's setup output
+pub fn summarize(copied: &[CopiedEntry]) -> Option<String> {
+    if copied.is_empty() {
+        return None;
+    }
+    let total: u64 = copied.iter().map(|c| c.bytes).sum();
+    let details = copied
+        .iter()
+        .map(|c| format!("{} ({}, {})", c.path, c.mode.as_str(), format_size(c.bytes)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(format!(
+        "Copied {} from the project root: {details}",
+        format_size(total)
+    ))
+}
+
+/// Validate a copy pattern (for jean.json validation)
+pub fn validate_pattern(pattern: &str) -> Result<(), String> {
+    if pattern.trim().is_empty() {
+        return Err("pattern is empty".to_string());
+    }
+    let path = Path::new(pattern);
+    if path.is_absolute() || pattern.split('/').any(|s| s == "..") {
+        return Err(format!("`{pattern}` must be relative to the project root"));
+    }
+    if pattern == ".git" || pattern.starts_with(".git/") {
+        return Err("the .git directory can't be copied".to_string());
+    }
+    compile(pattern).map(|_| ())
+}
+
+fn compile(pattern: &str) -> Result<GlobMatcher, String> {
+    GlobBuilder::new(pattern.trim_end_matches('/'))
+        .literal_separator(true)
+        .build()
+        .map(|g| g.compile_matcher())
+        .map_err(|e| e.to_string())
+}
+
+/// Directory levels a pattern can match at
+fn pattern_depth(pattern: &str) -> usize {
+    if pattern.contains("**") {
+        MAX_RECURSIVE_DEPTH
+    } else {
+        pattern.trim_end_matches('/').split('/').count()
+    }
+}
+
+/// Collect matching paths; matched directories aren't descended into
+fn find_matches(
+    root: &Path,
+    prefix: &str,
+    depth: usize,
+    max_depth: usize,
+    patterns: &[(GlobMatcher, CopyMode)],
+    matches: &mut Vec<(String, CopyMode)>,
+) {
+    let Ok(read_dir) = fs::read_dir(root.join(prefix)) else {
+        return;
+    };
+    let mut entries: Vec<_> = read_dir.flatten().collect();
+    entries.sort_by_key(|e| e.file_name());
+
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name == ".git" {
+            continue;
+        }
+        let relative = if prefix.is_empty() {
+            name
+        } else {
+            format!("{prefix}/{name}")
+        };
+        if let Some((_, mode)) = patterns.iter().find(|(m, _)| m.is_match(&relative)) {
+            matches.push((relative, *mode));
+            continue;
+        }
+        let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+        if is_dir && depth < max_depth {
+            find_matches(root, &relative, depth + 1, max_depth, patterns, matches);
+        }
+    }
+}
+
+/// Copy one file or directory, falling back to a plain copy. Returns the
+/// method that was used.
+fn copy_entry(src: &Path, dst: &Path, mode: CopyMode) -> io::Result<CopyMode> {
+    if let Some(parent) = dst.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    match mode {
+        CopyMode::Reflink => match reflink(src, dst) {
+            Ok(()) => return Ok(CopyMode::Reflink),
+            Err(e) => {
+                log::trace!("Reflink of {} failed, copying: {e}", src.display());
+                remove_partial(dst);
+            }
+        },
+        CopyMode::Hardlink => match hardlink_recursive(src, dst) {
+            Ok(()) => return Ok(CopyMode::Hardlink),
+            Err(e) => {
+                log::trace!("Hardlink of {} failed, copying: {e}", src.display());
+                remove_partial(dst);
+            }
+        },
+        CopyMode::Copy => {}
+    }
+    copy_recursive(src, dst)?;
+    Ok(CopyMode::Copy)
+}
+
+/// Copy-on-write clone via `cp` (APFS clonefile on macOS, FICLONE on Linux)
+fn reflink(src: &Path, dst: &Path) -> io::Result<()> {
+    let mut cmd = if cfg!(target_os = "macos") {
+        let mut cmd = silent_command("cp");
+        cmd.args(["-c", "-R", "-p"]);
+        cmd
+    } else if cfg!(target_os = "linux") {
+        let mut cmd = silent_command("cp");
+        cmd.args(["-R", "-p", "--reflink=always"]);
+        cmd
+    } else {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "reflinks are not supported on this platform",
+        ));
+    };
+    let output = cmd.arg(src).arg(dst).output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+fn hardlink_recursive(src: &Path, dst: &Path) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(src)?;
+    if metadata.file_type().is_symlink() {
+        copy_symlink(src, dst)
+    } else if metadata.is_dir() {
+        fs::create_dir_all(dst)?;
+        for entry in fs::read_dir(src)? {
+            let entry = entry?;
+            hardlink_recursive(&entry.path(), &dst.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::hard_link(src, dst)
+    }
+}
+
+fn copy_recursive(src: &Path, dst: &Path) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(src)?;
+    if metadata.file_type().is_symlink() {
+        copy_symlink(src, dst)
+    } else if metadata.is_dir() {
+        fs::create_dir_all(dst)?;
+        for entry in fs::read_dir(src)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &dst.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(src, dst).map(|_| ())
+    }
+}
+
+#[cfg(unix)]
+fn copy_symlink(src: &Path, dst: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(src)?, dst)
+}
+
+#[cfg(windows)]
+fn copy_symlink(src: &Path, dst: &Path) -> io::Result<()> {
+    // Creating symlinks needs extra privileges on Windows; copy the target instead
+    let target = fs::canonicalize(src)?;
+    copy_recursive(&target, dst)
+}
+
+fn remove_partial(dst: &Path) {
+    match fs::symlink_metadata(dst) {
+        Ok(m) if m.is_dir() => {
+            let _ = fs::remove_dir_all(dst);
+        }
+        Ok(_) => {
+            let _ = fs::remove_file(dst);
+        }
+        Err(_) => {}
+    }
+}
+
+/// Total size of a file or directory (symlinks not followed)
+fn size_of(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if metadata.is_dir() {
+        fs::read_dir(path)
+            .map(|entries| entries.flatten().map(|e| size_of(&e.path())).sum())
+            .unwrap_or(0)
+    } else {
+        metadata.len()
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+fn emit_line(app: &AppHandle, worktree_path: &str, line: String) {
+    log::trace!("{line}");
+    let event = ScriptOutputEvent {
+        worktree_path: worktree_path.to_string(),
+        kind: "copy".to_string(),
+        line,
+    };
+    let _ = app.emit_all("script:output", &event);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("jean-copy-test-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_find_matches_stops_at_matched_directories() {
+        let root = temp_dir("matches");
+        fs::write(root.join(".env"), "A=1").unwrap();
+        fs::write(root.join(".env.local"), "B=2").unwrap();
+        fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        fs::create_dir_all(root.join("packages/app/node_modules")).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+
+        let patterns = vec![
+            (compile(".env*").unwrap(), CopyMode::Copy),
+            (compile("**/node_modules").unwrap(), CopyMode::Reflink),
+        ];
+        let mut matches = Vec::new();
+        find_matches(&root, "", 1, MAX_RECURSIVE_DEPTH, &patterns, &mut matches);
+        let paths: Vec<&str> = matches.iter().map(|(p, _)| p.as_str()).collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                ".env",
+                ".env.local",
+                "node_modules",
+                "packages/app/node_modules"
+            ]
+        );
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_copy_entry_falls_back_and_copies_trees() {
+        let root = temp_dir("copy");
+        fs::create_dir_all(root.join("src/nested")).unwrap();
+        fs::write(root.join("src/nested/file.txt"), "hello").unwrap();
+
+        let used =
+            copy_entry(&root.join("src"), &root.join("dst/src"), CopyMode::Hardlink).unwrap();
+        assert!(matches!(used, CopyMode::Hardlink | CopyMode::Copy));
+        assert_eq!(
+            fs::read_to_string(root.join("dst/src/nested/file.txt")).unwrap(),
+            "hello"
+        );
+        assert_eq!(size_of(&root.join("src")), 5);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_validate_pattern() {
+        assert!(validate_pattern(".env*").is_ok());
+        assert!(validate_pattern("packages/*/node_modules").is_ok());
+        assert!(validate_pattern("../secrets").is_err());
+        assert!(validate_pattern("/etc/hosts").is_err());
+        assert!(validate_pattern(".git").is_err());
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+}
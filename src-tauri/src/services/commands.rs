@@ -0,0 +1,60 @@
+use tauri::AppHandle;
+
+use super::supervisor;
+use super::types::ServiceInfo;
+
+/// Default number of log lines returned by `get_service_logs`
+const DEFAULT_LOG_LINES: usize = 200;
+
+/// Start a jean.json service in a worktree
+#[tauri::command]
+pub async fn start_service(
+    app: AppHandle,
+    worktree_id: String,
+    name: String,
+) -> Result<ServiceInfo, String> {
+    supervisor::start_service(&app, &worktree_id, &name)
+}
+
+/// Stop a running service
+#[tauri::command]
+pub async fn stop_service(app: AppHandle, worktree_id: String, name: String) -> Result<(), String> {
+    supervisor::stop_service(&app, &worktree_id, &name)
+}
+
+/// Restart a service (re-reads jean.json)
+#[tauri::command]
+pub async fn restart_service(
+    app: AppHandle,
+    worktree_id: String,
+    name: String,
+) -> Result<ServiceInfo, String> {
+    tokio::task::spawn_blocking(move || supervisor::restart_service(&app, &worktree_id, &name))
+        .await
+        .map_err(|e| format!("Failed to restart service: {e}"))?
+}
+
+/// List the services of a worktree with their status
+#[tauri::command]
+pub async fn list_services(
+    app: AppHandle,
+    worktree_id: String,
+) -> Result<Vec<ServiceInfo>, String> {
+    supervisor::list_services(&app, &worktree_id)
+}
+
+/// Get the last lines of a service's log
+#[tauri::command]
+pub async fn get_service_logs(
+    app: AppHandle,
+    worktree_id: String,
+    name: String,
+    lines: Option<usize>,
+) -> Result<String, String> {
+    supervisor::read_service_log(
+        &app,
+        &worktree_id,
+        &name,
+        lines.unwrap_or(DEFAULT_LOG_LINES),
+    )
+}
@@ -0,0 +1,10 @@
+mod commands;
+mod supervisor;
+mod types;
+
+// Re-export commands for registration in lib.rs
+pub use commands::*;
+
+// Re-export internal functions for worktree and app lifecycle cleanup
+pub use supervisor::kill_all_services as cleanup_all_services;
+pub use supervisor::stop_worktree_services;
@@ -0,0 +1,839 @@
+//! Supervisor for long-running jean.json `services` (dev servers, workers).
+//!
+//! Each running service has a supervising thread that spawns the command,
+//! writes its output to a log file (and `service:log` events), runs the
+//! readiness check and restarts the process according to its restart policy.
+//! Every state change is emitted as a `service:status` event.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use tauri::{AppHandle, Manager};
+
+use super::types::{ServiceInfo, ServiceLogEvent, ServiceStatus};
+use crate::http_server::EmitExt;
+use crate::platform::{kill_process_tree, silent_command, terminate_process_tree};
+use crate::projects::container::{self, service_pid_file};
+use crate::projects::git::{get_user_shell, read_jean_config};
+use crate::projects::storage::load_projects_data;
+use crate::projects::types::{RestartPolicy, ServiceConfig};
+
+/// Default seconds to wait for a readiness check
+const DEFAULT_READY_TIMEOUT_SECS: u64 = 60;
+/// Default restart limit
+const DEFAULT_MAX_RESTARTS: u32 = 5;
+/// A process that ran at least this long resets the restart counter
+const STABLE_RUN: Duration = Duration::from_secs(60);
+/// Grace period between SIGTERM and SIGKILL when stopping
+const STOP_GRACE: Duration = Duration::from_secs(5);
+/// Interval between readiness URL polls
+const READY_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// (worktree_id, service name)
+type ServiceKey = (String, String);
+
+/// Supervisor state for one service
+struct ServiceProcess {
+    info: ServiceInfo,
+    /// Incremented on every start so threads of a previous start stop updating
+    generation: u64,
+    stop_requested: bool,
+    worktree_path: String,
+    in_container: bool,
+}
+
+/// Global registry of services (running or finished)
+static SERVICES: Lazy<Mutex<HashMap<ServiceKey, ServiceProcess>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+/// Everything needed to (re)spawn a service
+#[derive(Clone)]
+struct ResolvedService {
+    name: String,
+    worktree_path: String,
+    workdir: String,
+    config: ServiceConfig,
+    env: BTreeMap<String, String>,
+    ready_url: Option<String>,
+    ready_log: Option<Regex>,
+    log_path: PathBuf,
+}
+
+/// Start a service from the worktree's jean.json
+pub fn start_service(
+    app: &AppHandle,
+    worktree_id: &str,
+    name: &str,
+) -> Result<ServiceInfo, String> {
+    let service = resolve(app, worktree_id, name)?;
+    let key = (worktree_id.to_string(), name.to_string());
+
+    if let Some(parent) = service.log_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create service log directory: {e}"))?;
+    }
+
+    let generation = NEXT_GENERATION.fetch_add(1, Ordering::SeqCst);
+    let info = {
+        let mut services = SERVICES
+            .lock()
+            .map_err(|e| format!("Service registry lock error: {e}"))?;
+        if services
+            .get(&key)
+            .is_some_and(|s| s.info.status.is_active())
+        {
+            return Err(format!("Service `{name}` is already running"));
+        }
+        let info = ServiceInfo {
+            worktree_id: worktree_id.to_string(),
+            name: name.to_string(),
+            status: ServiceStatus::Starting,
+            pid: None,
+            port: service.ready_url.as_deref().and_then(url_port),
+            restarts: 0,
+            exit_code: None,
+            started_at: None,
+            message: None,
+            log_path: service.log_path.to_string_lossy().to_string(),
+        };
+        services.insert(
+            key.clone(),
+            ServiceProcess {
+                info: info.clone(),
+                generation,
+                stop_requested: false,
+                worktree_path: service.worktree_path.clone(),
+                in_container: false,
+            },
+        );
+        info
+    };
+
+    log::trace!("Starting service {name} in worktree {worktree_id}");
+    emit_status(app, &info);
+
+    let app = app.clone();
+    thread::spawn(move || supervise(app, key, generation, service));
+    Ok(info)
+}
+
+/// Stop a service: SIGTERM to its process group, SIGKILL after a grace period
+pub fn stop_service(app: &AppHandle, worktree_id: &str, name: &str) -> Result<(), String> {
+    let key = (worktree_id.to_string(), name.to_string());
+    let (pid, generation, worktree_path, in_container) = {
+        let mut services = SERVICES
+            .lock()
+            .map_err(|e| format!("Service registry lock error: {e}"))?;
+        let Some(service) = services.get_mut(&key) else {
+            return Ok(());
+        };
+        if !service.info.status.is_active() {
+            return Ok(());
+        }
+        service.stop_requested = true;
+        // Waiting to be restarted: nothing to kill, report stopped right away
+        if service.info.pid.is_none() {
+            service.info.status = ServiceStatus::Stopped;
+            service.info.message = None;
+            emit_status(app, &service.info);
+        }
+        (
+            service.info.pid,
+            service.generation,
+            service.worktree_path.clone(),
+            service.in_container,
+        )
+    };
+
+    log::trace!("Stopping service {name} in worktree {worktree_id}");
+    if in_container {
+        container::kill_tracked(app, &worktree_path, &service_pid_file(name));
+    }
+    if let Some(pid) = pid {
+        if let Err(e) = terminate_process_tree(pid) {
+            log::warn!("Failed to terminate service {name} (pid {pid}): {e}");
+        }
+        thread::spawn(move || {
+            thread::sleep(STOP_GRACE);
+            if current_pid(&key, generation) == Some(pid) {
+                log::warn!("Service {} did not exit after SIGTERM, killing", key.1);
+                let _ = kill_process_tree(pid);
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Stop a service and start it again (picking up jean.json changes)
+pub fn restart_service(
+    app: &AppHandle,
+    worktree_id: &str,
+    name: &str,
+) -> Result<ServiceInfo, String> {
+    stop_service(app, worktree_id, name)?;
+    let key = (worktree_id.to_string(), name.to_string());
+    let deadline = Instant::now() + STOP_GRACE + Duration::from_secs(1);
+    while is_active(&key) {
+        if Instant::now() > deadline {
+            return Err(format!("Service `{name}` did not stop"));
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    start_service(app, worktree_id, name)
+}
+
+/// Status of every service declared in the worktree's jean.json, plus any
+/// still tracked ones that were removed from it
+pub fn list_services(app: &AppHandle, worktree_id: &str) -> Result<Vec<ServiceInfo>, String> {
+    let (worktree_path, root_path) = worktree_paths(app, worktree_id)?;
+    let config = read_jean_config(&worktree_path).or_else(|| read_jean_config(&root_path));
+    let services = SERVICES
+        .lock()
+        .map_err(|e| format!("Service registry lock error: {e}"))?;
+
+    let mut result: Vec<ServiceInfo> = Vec::new();
+    for name in config.iter().flat_map(|c| c.services.keys()) {
+        let key = (worktree_id.to_string(), name.clone());
+        let info = match services.get(&key) {
+            Some(service) => service.info.clone(),
+            None => ServiceInfo {
+                worktree_id: worktree_id.to_string(),
+                name: name.clone(),
+                status: ServiceStatus::Stopped,
+                pid: None,
+                port: None,
+                restarts: 0,
+                exit_code: None,
+                started_at: None,
+                message: None,
+                log_path: log_path(app, worktree_id, name)
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+            },
+        };
+        result.push(info);
+    }
+    for ((id, name), service) in services.iter() {
+        if id == worktree_id && !result.iter().any(|i| &i.name == name) {
+            result.push(service.info.clone());
+        }
+    }
+    Ok(result)
+}
+
+/// Last `lines` lines of a service's log file
+pub fn read_service_log(
+    app: &AppHandle,
+    worktree_id: &str,
+    name: &str,
+    lines: usize,
+) -> Result<String, String> {
+    let path = log_path(app, worktree_id, name)?;
+    if !path.exists() {
+        return Ok(String::new());
+    }
+    let mut content = String::new();
+    File::open(&path)
+        .and_then(|mut f| f.read_to_string(&mut content))
+        .map_err(|e| format!("Failed to read service log: {e}"))?;
+    Ok(tail_lines(&content, lines))
+}
+
+/// Stop all services of a worktree (before it's archived or deleted)
+pub fn stop_worktree_services(app: &AppHandle, worktree_id: &str) {
+    let names: Vec<String> = SERVICES
+        .lock()
+        .map(|services| {
+            services
+                .iter()
+                .filter(|((id, _), s)| id == worktree_id && s.info.status.is_active())
+                .map(|((_, name), _)| name.clone())
+                .collect()
+        })
+        .unwrap_or_default();
+    for name in names {
+        if let Err(e) = stop_service(app, worktree_id, &name) {
+            log::warn!("Failed to stop service {name}: {e}");
+        }
+    }
+}
+
+/// Kill every running service (app exit). Returns the number killed.
+pub fn kill_all_services() -> usize {
+    let Ok(mut services) = SERVICES.lock() else {
+        return 0;
+    };
+    let mut killed = 0;
+    for service in services.values_mut() {
+        service.stop_requested = true;
+        if let Some(pid) = service.info.pid {
+            if kill_process_tree(pid).is_ok() {
+                killed += 1;
+            }
+        }
+    }
+    killed
+}
+
+/// Supervising thread: spawn, watch, restart
+fn supervise(app: AppHandle, key: ServiceKey, generation: u64, service: ResolvedService) {
+    let max_restarts = service.config.max_restarts.unwrap_or(DEFAULT_MAX_RESTARTS);
+    let mut restarts: u32 = 0;
+
+    loop {
+        let (mut child, in_container) = match spawn(&app, &service) {
+            Ok(spawned) => spawned,
+            Err(e) => {
+                log::error!("Failed to start service {}: {e}", service.name);
+                update(&app, &key, generation, |info| {
+                    info.status = ServiceStatus::Crashed;
+                    info.pid = None;
+                    info.message = Some(e.clone());
+                });
+                return;
+            }
+        };
+        let pid = child.id();
+        let started = Instant::now();
+        let has_ready_check = service.ready_url.is_some() || service.ready_log.is_some();
+
+        let current = update(&app, &key, generation, |info| {
+            info.status = if has_ready_check {
+                ServiceStatus::Starting
+            } else {
+                ServiceStatus::Running
+            };
+            info.pid = Some(pid);
+            info.restarts = restarts;
+            info.started_at = Some(now());
+            info.message = None;
+        });
+        if !current {
+            // Superseded by another start while spawning
+            let _ = kill_process_tree(pid);
+            return;
+        }
+        set_in_container(&key, generation, in_container);
+
+        let ready = Arc::new(AtomicBool::new(!has_ready_check));
+        let log_file = open_log(&service.log_path, restarts);
+        let readers: Vec<_> = [
+            child
+                .stdout
+                .take()
+                .map(|s| Box::new(s) as Box<dyn Read + Send>),
+            child
+                .stderr
+                .take()
+                .map(|s| Box::new(s) as Box<dyn Read + Send>),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|stream| {
+            let app = app.clone();
+            let key = key.clone();
+            let log_file = log_file.clone();
+            let ready = ready.clone();
+            let pattern = service.ready_log.clone();
+            thread::spawn(move || {
+                pipe_output(
+                    &app, &key, generation, pid, stream, log_file, ready, pattern,
+                )
+            })
+        })
+        .collect();
+
+        if has_ready_check {
+            let app = app.clone();
+            let key = key.clone();
+            let ready = ready.clone();
+            let url = service.ready_url.clone();
+            let timeout = Duration::from_secs(
+                service
+                    .config
+                    .ready
+                    .as_ref()
+                    .and_then(|r| r.timeout_secs)
+                    .unwrap_or(DEFAULT_READY_TIMEOUT_SECS),
+            );
+            thread::spawn(move || {
+                watch_readiness(&app, &key, generation, pid, url, ready, timeout)
+            });
+        }
+
+        let exit = child.wait();
+        for reader in readers {
+            let _ = reader.join();
+        }
+        let exit_code = exit.as_ref().ok().and_then(|s| s.code());
+        let success = exit.as_ref().is_ok_and(|s| s.success());
+
+        let stop_requested = SERVICES
+            .lock()
+            .ok()
+            .and_then(|s| {
+                s.get(&key)
+                    .filter(|s| s.generation == generation)
+                    .map(|s| s.stop_requested)
+            })
+            .unwrap_or(true);
+        if stop_requested {
+            update(&app, &key, generation, |info| {
+                info.status = ServiceStatus::Stopped;
+                info.pid = None;
+                info.exit_code = exit_code;
+                info.message = None;
+            });
+            log::trace!("Service {} stopped", service.name);
+            return;
+        }
+
+        if started.elapsed() >= STABLE_RUN {
+            restarts = 0;
+        }
+        let should_restart = match service.config.restart {
+            RestartPolicy::Always => true,
+            RestartPolicy::OnFailure => !success,
+            RestartPolicy::Never => false,
+        };
+        let exit_message = match exit_code {
+            Some(code) => format!("exited with code {code}"),
+            None => "was killed by a signal".to_string(),
+        };
+
+        if !should_restart || restarts >= max_restarts {
+            let message = if should_restart {
+                format!("{exit_message}; gave up after {restarts} restarts")
+            } else {
+                exit_message
+            };
+            log::warn!("Service {} {message}", service.name);
+            update(&app, &key, generation, |info| {
+                info.status = if success {
+                    ServiceStatus::Exited
+                } else {
+                    ServiceStatus::Crashed
+                };
+                info.pid = None;
+                info.exit_code = exit_code;
+                info.message = Some(message.clone());
+            });
+            return;
+        }
+
+        restarts += 1;
+        let backoff = Duration::from_secs(1 << (restarts - 1).min(5));
+        log::warn!(
+            "Service {} {exit_message}, restarting in {}s",
+            service.name,
+            backoff.as_secs()
+        );
+        let current = update(&app, &key, generation, |info| {
+            info.status = ServiceStatus::Restarting;
+            info.pid = None;
+            info.exit_code = exit_code;
+            info.restarts = restarts;
+            info.message = Some(format!(
+                "{exit_message}, restarting in {}s",
+                backoff.as_secs()
+            ));
+        });
+        if !current {
+            return;
+        }
+
+        let deadline = Instant::now() + backoff;
+        while Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(100));
+            if !is_active(&key) || services_generation(&key) != Some(generation) {
+                return;
+            }
+        }
+    }
+}
+
+/// Spawn the service process. Returns the child and whether it runs in the
+/// worktree's container.
+fn spawn(app: &AppHandle, service: &ResolvedService) -> Result<(Child, bool), String> {
+    let container = container::ensure_container(app, &service.worktree_path)?;
+    let mut cmd = if let Some(ref container) = container {
+        let pid_file = service_pid_file(&service.name);
+        // A previous instance may still run in the container (e.g. after an app restart)
+        container::kill_tracked(app, &service.worktree_path, &pid_file);
+        let env_keys: Vec<&str> = service.env.keys().map(String::as_str).collect();
+        let args = container.tracked_command_prefix(&service.workdir, &pid_file, &env_keys);
+        let mut cmd = silent_command(&args[0]);
+        cmd.args(&args[1..])
+            .args(["sh", "-c", &service.config.command]);
+        cmd
+    } else {
+        let (shell, supports_login) = get_user_shell();
+        let mut cmd = silent_command(&shell);
+        if supports_login {
+            cmd.args(["-l", "-i", "-c", &service.config.command]);
+        } else {
+            cmd.args(["-c", &service.config.command]);
+        }
+        cmd
+    };
+
+    cmd.current_dir(&service.workdir)
+        .envs(&service.env)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // Own process group so stopping reaches the whole tree
+        cmd.process_group(0);
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NEW_PROCESS_GROUP | CREATE_NO_WINDOW);
+    }
+
+    let child = cmd
+        .spawn()
+        .map_err(|e| format!("Failed to start service `{}`: {e}", service.name))?;
+    Ok((child, container.is_some()))
+}
+
+/// Copy one output stream to the log file and `service:log` events, matching
+/// the readiness pattern on the way
+#[allow(clippy::too_many_arguments)]
+fn pipe_output(
+    app: &AppHandle,
+    key: &ServiceKey,
+    generation: u64,
+    pid: u32,
+    stream: Box<dyn Read + Send>,
+    log_file: Option<Arc<Mutex<File>>>,
+    ready: Arc<AtomicBool>,
+    pattern: Option<Regex>,
+) {
+    let reader = BufReader::new(stream);
+    for line in reader.lines() {
+        let Ok(line) = line else { break };
+        if let Some(file) = &log_file {
+            if let Ok(mut file) = file.lock() {
+                let _ = writeln!(file, "{line}");
+            }
+        }
+        let event = ServiceLogEvent {
+            worktree_id: key.0.clone(),
+            name: key.1.clone(),
+            line: line.clone(),
+        };
+        let _ = app.emit_all("service:log", &event);
+
+        if let Some(pattern) = &pattern {
+            if !ready.load(Ordering::SeqCst) && pattern.is_match(&line) {
+                ready.store(true, Ordering::SeqCst);
+                mark_ready(app, key, generation, pid);
+            }
+        }
+    }
+}
+
+/// Poll the readiness URL until it answers, the log pattern matched or the
+/// timeout passed (then the service is reported unhealthy)
+fn watch_readiness(
+    app: &AppHandle,
+    key: &ServiceKey,
+    generation: u64,
+    pid: u32,
+    url: Option<String>,
+    ready: Arc<AtomicBool>,
+    timeout: Duration,
+) {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(2))
+        .build()
+        .ok();
+    let deadline = Instant::now() + timeout;
+    let mut reported_unhealthy = false;
+
+    while current_pid(key, generation) == Some(pid) {
+        if ready.load(Ordering::SeqCst) {
+            return;
+        }
+        if let (Some(url), Some(client)) = (&url, &client) {
+            let answered = client
+                .get(url)
+                .send()
+                .is_ok_and(|r| !r.status().is_server_error());
+            if answered {
+                ready.store(true, Ordering::SeqCst);
+                mark_ready(app, key, generation, pid);
+                return;
+            }
+        }
+        if !reported_unhealthy && Instant::now() > deadline {
+            reported_unhealthy = true;
+            update(app, key, generation, |info| {
+                if info.pid == Some(pid) && info.status == ServiceStatus::Starting {
+                    info.status = ServiceStatus::Unhealthy;
+                    info.message = Some(format!("not ready after {}s", timeout.as_secs()));
+                }
+            });
+        }
+        thread::sleep(READY_POLL_INTERVAL);
+    }
+}
+
+fn mark_ready(app: &AppHandle, key: &ServiceKey, generation: u64, pid: u32) {
+    update(app, key, generation, |info| {
+        if info.pid == Some(pid)
+            && matches!(
+                info.status,
+                ServiceStatus::Starting | ServiceStatus::Unhealthy
+            )
+        {
+            info.status = ServiceStatus::Healthy;
+            info.message = None;
+        }
+    });
+}
+
+/// Apply a change to a service's info and emit it, unless the service was
+/// restarted meanwhile. Returns false when this generation is outdated.
+fn update(
+    app: &AppHandle,
+    key: &ServiceKey,
+    generation: u64,
+    change: impl FnOnce(&mut ServiceInfo),
+) -> bool {
+    let info = {
+        let Ok(mut services) = SERVICES.lock() else {
+            return false;
+        };
+        let Some(service) = services.get_mut(key).filter(|s| s.generation == generation) else {
+            return false;
+        };
+        let before = service.info.status;
+        change(&mut service.info);
+        if before == ServiceStatus::Stopped && service.stop_requested {
+            // stop_service already reported it stopped; keep it that way
+            service.info.status = ServiceStatus::Stopped;
+        }
+        service.info.clone()
+    };
+    emit_status(app, &info);
+    true
+}
+
+fn emit_status(app: &AppHandle, info: &ServiceInfo) {
+    if let Err(e) = app.emit_all("service:status", info) {
+        log::error!("Failed to emit service:status event: {e}");
+    }
+}
+
+fn set_in_container(key: &ServiceKey, generation: u64, in_container: bool) {
+    if let Ok(mut services) = SERVICES.lock() {
+        if let Some(service) = services.get_mut(key).filter(|s| s.generation == generation) {
+            service.in_container = in_container;
+        }
+    }
+}
+
+fn current_pid(key: &ServiceKey, generation: u64) -> Option<u32> {
+    SERVICES
+        .lock()
+        .ok()?
+        .get(key)
+        .filter(|s| s.generation == generation)?
+        .info
+        .pid
+}
+
+fn services_generation(key: &ServiceKey) -> Option<u64> {
+    SERVICES.lock().ok()?.get(key).map(|s| s.generation)
+}
+
+fn is_active(key: &ServiceKey) -> bool {
+    SERVICES
+        .lock()
+        .ok()
+        .and_then(|s| s.get(key).map(|s| s.info.status.is_active()))
+        .unwrap_or(false)
+}
+
+/// Look up the service in jean.json and build its environment
+fn resolve(app: &AppHandle, worktree_id: &str, name: &str) -> Result<ResolvedService, String> {
+    let data = load_projects_data(app)?;
+    let worktree = data
+        .find_worktree(worktree_id)
+        .ok_or_else(|| format!("Worktree not found: {worktree_id}"))?;
+    let root_path = data
+        .find_project(&worktree.project_id)
+        .map(|p| p.path.clone())
+        .unwrap_or_default();
+    let config = read_jean_config(&worktree.path)
+        .or_else(|| read_jean_config(&root_path))
+        .ok_or_else(|| "No jean.json found".to_string())?;
+    let service_config = config
+        .services
+        .get(name)
+        .cloned()
+        .ok_or_else(|| format!("Service `{name}` is not defined in jean.json"))?;
+
+    let mut env = config.env.clone();
+    env.extend(service_config.env.clone());
+    env.insert("JEAN_WORKSPACE_PATH".to_string(), worktree.path.clone());
+    env.insert("JEAN_ROOT_PATH".to_string(), root_path);
+    env.insert("JEAN_BRANCH".to_string(), worktree.branch.clone());
+    env.insert("JEAN_SERVICE".to_string(), name.to_string());
+    if let Some(ports) = crate::projects::ports::ensure_ports(app, worktree_id) {
+        env.extend(ports.env_vars());
+    }
+
+    let vars: Vec<(String, String)> = env.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    let ready = service_config.ready.clone().unwrap_or_default();
+    let ready_url = ready
+        .url
+        .as_deref()
+        .map(|url| crate::projects::ports::render(url, &vars));
+    let ready_log = ready
+        .log
+        .as_deref()
+        .map(Regex::new)
+        .transpose()
+        .map_err(|e| format!("Invalid ready log pattern for `{name}`: {e}"))?;
+
+    let workdir = match &service_config.cwd {
+        Some(dir) => Path::new(&worktree.path)
+            .join(dir)
+            .to_string_lossy()
+            .to_string(),
+        None => worktree.path.clone(),
+    };
+
+    Ok(ResolvedService {
+        name: name.to_string(),
+        worktree_path: worktree.path.clone(),
+        workdir,
+        config: service_config,
+        env,
+        ready_url,
+        ready_log,
+        log_path: log_path(app, worktree_id, name)?,
+    })
+}
+
+fn worktree_paths(app: &AppHandle, worktree_id: &str) -> Result<(String, String), String> {
+    let data = load_projects_data(app)?;
+    let worktree = data
+        .find_worktree(worktree_id)
+        .ok_or_else(|| format!("Worktree not found: {worktree_id}"))?;
+    let root_path = data
+        .find_project(&worktree.project_id)
+        .map(|p| p.path.clone())
+        .unwrap_or_default();
+    Ok((worktree.path.clone(), root_path))
+}
+
+/// `<app data>/service-logs/<worktree id>/<name>.log`
+fn log_path(app: &AppHandle, worktree_id: &str, name: &str) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+    let file_name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    Ok(app_data_dir
+        .join("service-logs")
+        .join(worktree_id)
+        .join(format!("{file_name}.log")))
+}
+
+/// Open the log for appending: truncated on a fresh start, with a separator
+/// line on restarts
+fn open_log(path: &Path, restarts: u32) -> Option<Arc<Mutex<File>>> {
+    let mut options = OpenOptions::new();
+    options.create(true);
+    if restarts == 0 {
+        options.write(true).truncate(true);
+    } else {
+        options.append(true);
+    }
+    match options.open(path) {
+        Ok(mut file) => {
+            if restarts > 0 {
+                let _ = writeln!(file, "--- restart {restarts} ---");
+            }
+            Some(Arc::new(Mutex::new(file)))
+        }
+        Err(e) => {
+            log::warn!("Failed to open service log {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// Explicit port of a URL (`http://localhost:4102/health` -> 4102)
+fn url_port(url: &str) -> Option<u16> {
+    reqwest::Url::parse(url).ok()?.port()
+}
+
+fn tail_lines(content: &str, lines: usize) -> String {
+    let all: Vec<&str> = content.lines().collect();
+    all[all.len().saturating_sub(lines)..].join("\n")
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_port() {
+        assert_eq!(url_port("http://localhost:4102/health"), Some(4102));
+        assert_eq!(url_port("https://example.com/"), None);
+        assert_eq!(url_port("not a url"), None);
+    }
+
+    #[test]
+    fn test_tail_lines() {
+        assert_eq!(tail_lines("a\nb\nc\n", 2), "b\nc");
+        assert_eq!(tail_lines("a\nb", 10), "a\nb");
+        assert_eq!(tail_lines("", 5), "");
+    }
+
+    #[test]
+    fn test_active_statuses() {
+        assert!(ServiceStatus::Restarting.is_active());
+        assert!(ServiceStatus::Unhealthy.is_active());
+        assert!(!ServiceStatus::Crashed.is_active());
+        assert!(!ServiceStatus::Stopped.is_active());
+    }
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+/// Lifecycle state of a supervised service
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceStatus {
+    /// Process started, waiting for its readiness check
+    Starting,
+    /// Process running (no readiness check configured)
+    Running,
+    /// Readiness check passed
+    Healthy,
+    /// Process running but the readiness check timed out
+    Unhealthy,
+    /// Process exited and is about to be restarted
+    Restarting,
+    /// Process failed and won't be restarted (policy or restart limit)
+    Crashed,
+    /// Process exited successfully and won't be restarted
+    Exited,
+    /// Stopped by the user (or never started)
+    Stopped,
+}
+
+impl ServiceStatus {
+    /// Whether the service has a process running or about to be restarted
+    pub fn is_active(self) -> bool {
+        matches!(
+            self,
+            ServiceStatus::Starting
+                | ServiceStatus::Running
+                | ServiceStatus::Healthy
+                | ServiceStatus::Unhealthy
+                | ServiceStatus::Restarting
+        )
+    }
+}
+
+/// Status of a jean.json service in a worktree.
+///
+/// Also the payload of `service:status` events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceInfo {
+    pub worktree_id: String,
+    pub name: String,
+    pub status: ServiceStatus,
+    /// PID of the current process (host-side for container services)
+    pub pid: Option<u32>,
+    /// Port from the readiness URL, if it has one
+    pub port: Option<u16>,
+    /// Restarts since the last successful long-running start
+    pub restarts: u32,
+    /// Exit code of the last process that exited
+    pub exit_code: Option<i32>,
+    /// Unix timestamp when the current process was started
+    pub started_at: Option<u64>,
+    /// Human-readable detail (e.g. why it crashed)
+    pub message: Option<String>,
+    /// File the service's output is written to
+    pub log_path: String,
+}
+
+/// Event payload for a line of service output
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceLogEvent {
+    pub worktree_id: String,
+    pub name: String,
+    pub line: String,
+}
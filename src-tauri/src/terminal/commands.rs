@@ -5,7 +5,10 @@ use super::pty::{
     write_to_terminal,
 };
 use super::registry::{get_all_terminal_ids, has_terminal};
+use super::types::RunScript;
 use crate::projects::git::read_jean_config;
+use crate::projects::jean_config::shell_command;
+use crate::projects::types::ScriptEntry;
 
 /// Start a terminal
 #[tauri::command]
@@ -39,7 +42,31 @@ pub async fn start_terminal(
 /// Get the run script from jean.json for a worktree
 #[tauri::command]
 pub async fn get_run_script(worktree_path: String) -> Option<String> {
-    read_jean_config(&worktree_path).and_then(|config| config.scripts.run)
+    let config = read_jean_config(&worktree_path)?;
+    let run = ScriptEntry::Command(config.scripts.run.clone()?);
+    Some(shell_command(&config, &run))
+}
+
+/// Get all runnable scripts from jean.json for a worktree (`run` first, then named scripts)
+#[tauri::command]
+pub async fn get_run_scripts(worktree_path: String) -> Vec<RunScript> {
+    let Some(config) = read_jean_config(&worktree_path) else {
+        return Vec::new();
+    };
+    let mut scripts = Vec::new();
+    if let Some(run) = config.scripts.run.clone() {
+        scripts.push(RunScript {
+            name: "run".to_string(),
+            command: shell_command(&config, &ScriptEntry::Command(run)),
+        });
+    }
+    for (name, entry) in &config.scripts.named {
+        scripts.push(RunScript {
+            name: name.clone(),
+            command: shell_command(&config, entry),
+        });
+    }
+    scripts
 }
 
 /// Write data to a terminal (stdin)
@@ -52,8 +52,39 @@ pub fn spawn_terminal(
     let shell = get_user_shell();
     log::trace!("Using shell: {shell}");
 
+    // Worktrees with a jean.json container get their shell inside it
+    // (direct binary invocations, e.g. CLI logins, stay on the host)
+    let container = if command_args.is_none() && std::path::Path::new(&worktree_path).is_dir() {
+        crate::projects::container::ensure_container(app, &worktree_path)?
+    } else {
+        None
+    };
+
+    // Ports allocated to the worktree (JEAN_PORT, ...) when jean.json asks for them
+    let port_env = if command_args.is_none() {
+        crate::projects::ports::env_for_path(app, &worktree_path)
+    } else {
+        Vec::new()
+    };
+
     // Build command - either run a specific command or start interactive shell
-    let mut cmd = if let Some(ref run_command) = command {
+    let mut cmd = if let Some(ref container) = container {
+        let mut env_keys = vec!["TERM", "COLORTERM", "JEAN_WORKTREE_PATH"];
+        env_keys.extend(port_env.iter().map(|(k, _)| k.as_str()));
+        let args = container.exec_args(&worktree_path, true, &env_keys);
+        let mut c = CommandBuilder::new(&args[0]);
+        for arg in &args[1..] {
+            c.arg(arg);
+        }
+        c.arg("sh");
+        c.arg("-c");
+        c.arg(
+            command
+                .as_deref()
+                .unwrap_or("command -v bash >/dev/null && exec bash || exec sh"),
+        );
+        c
+    } else if let Some(ref run_command) = command {
         if let Some(ref args) = command_args {
             // Direct binary invocation — bypass shell to avoid argument mangling.
             // Validate absolute paths exist upfront for a clear error message.
@@ -101,6 +132,9 @@ pub fn spawn_terminal(
     cmd.env("TERM", "xterm-256color");
     cmd.env("COLORTERM", "truecolor");
     cmd.env("JEAN_WORKTREE_PATH", &worktree_path);
+    for (key, value) in &port_env {
+        cmd.env(key, value);
+    }
 
     // Spawn the shell
     let child = pair
@@ -27,6 +27,14 @@ pub struct TerminalStoppedEvent {
     pub signal: Option<String>,
 }
 
+/// A runnable jean.json script (`run` or a named script)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RunScript {
+    pub name: String,
+    /// Shell command line including the script's env and working directory
+    pub command: String,
+}
+
 /// Active terminal session state
 pub struct TerminalSession {
     pub terminal_id: String,
@@ -122,6 +122,7 @@ import { supportsAdaptiveThinking } from '@/lib/model-utils'
 import { copyToClipboard, copyHtmlToClipboard } from '@/lib/clipboard'
 import { useAiProviderOverview } from '@/services/ai-provider'
 import { usePrStatus, usePrStatusEvents } from '@/services/pr-status'
+import { useServiceEvents } from '@/services/services'
 import type { PrDisplayStatus, CheckStatus } from '@/types/pr-status'
 import type {
   QueuedMessage,
@@ -168,7 +169,10 @@ import { usePlanState } from './hooks/usePlanState'
 import { useActiveTodosAndAgents } from './hooks/useActiveTodosAndAgents'
 import { usePendingAttachments } from './hooks/usePendingAttachments'
 import { dedupeInFlightAssistantMessage } from './in-flight-message-dedupe'
-import { shouldShowPermissionApproval } from './permission-approval-utils'
+import {
+  isLiveApproval,
+  shouldShowPermissionApproval,
+} from './permission-approval-utils'
 
 // PERFORMANCE: Stable empty array references to prevent infinite render loops
 // When Zustand selectors return [], a new reference is created each time
@@ -472,6 +476,7 @@ export function ChatWindow({
 
   // PR status for dynamic PR button
   usePrStatusEvents() // Listen for PR status updates
+  useServiceEvents() // Keep jean.json service status current
   const { data: prStatus } = usePrStatus(activeWorktreeId ?? null)
   // Use live status if available, otherwise fall back to cached
   const displayStatus =
@@ -716,6 +721,7 @@ export function ChatWindow({
     isSending,
     executionMode,
     isCodexBackend,
+    hasLiveApprovals: pendingDenials.some(isLiveApproval),
   })
 
   // PERFORMANCE: Pre-compute last assistant message to avoid rescanning in multiple memos
@@ -1852,6 +1858,7 @@ export function ChatWindow({
     handleStreamingWorktreeYoloApproval,
     handlePermissionApproval,
     handlePermissionApprovalYolo,
+    handlePermissionApprovalForSession,
     handlePermissionDeny,
     handleFixFinding,
     handleFixAllFindings,
@@ -2356,6 +2363,11 @@ export function ChatWindow({
                               denials={pendingDenials}
                               onApprove={handlePermissionApproval}
                               onApproveYolo={handlePermissionApprovalYolo}
+                              onApproveForSession={
+                                pendingDenials.every(isLiveApproval)
+                                  ? handlePermissionApprovalForSession
+                                  : undefined
+                              }
                               onDeny={handlePermissionDeny}
                             />
                           )}
@@ -22,6 +22,8 @@ interface PermissionApprovalProps {
   onApprove: (sessionId: string, approvedPatterns: string[]) => void
   /** Callback when user approves with yolo mode (auto-approve all future tools) */
   onApproveYolo?: (sessionId: string, approvedPatterns: string[]) => void
+  /** Callback when user approves for the rest of the session (Codex/OpenCode live approvals) */
+  onApproveForSession?: (sessionId: string) => void
   /** Callback when user denies/cancels */
   onDeny?: (sessionId: string) => void
   /** Read-only mode (after approval) */
@@ -49,6 +51,14 @@ function formatToolPattern(denial: PermissionDenial): string {
     }
   }
 
+  // For Codex file changes, list the changed paths
+  if (tool_name === 'FileChange') {
+    const paths = getFileChanges(denial).map(c => c.path)
+    if (paths.length > 0) {
+      return `FileChange(${paths.join(', ')})`
+    }
+  }
+
   // For other tools, just return the tool name
   return tool_name
 }
@@ -86,9 +96,47 @@ function getToolDescription(denial: PermissionDenial): string {
     return input.file_path ? `Edit ${input.file_path}` : 'Edit file'
   }
 
+  if (
+    tool_name === 'FileChange' &&
+    typeof tool_input === 'object' &&
+    tool_input !== null
+  ) {
+    const paths = getFileChanges(denial).map(c => c.path)
+    if (paths.length === 1) return `Change ${paths[0]}`
+    if (paths.length > 1) return `Change ${paths.length} files`
+    return 'Change files'
+  }
+
   return `Use ${tool_name} tool`
 }
 
+interface ProposedFileChange {
+  path: string
+  kind?: unknown
+  diff?: string
+}
+
+/**
+ * Get the proposed file changes (with patches) of a Codex file change approval
+ */
+function getFileChanges(denial: PermissionDenial): ProposedFileChange[] {
+  if (
+    denial.tool_name !== 'FileChange' ||
+    typeof denial.tool_input !== 'object' ||
+    denial.tool_input === null
+  ) {
+    return []
+  }
+  const changes = (denial.tool_input as { changes?: unknown }).changes
+  if (!Array.isArray(changes)) return []
+  return changes.filter(
+    (c): c is ProposedFileChange =>
+      typeof c === 'object' &&
+      c !== null &&
+      typeof (c as { path?: unknown }).path === 'string'
+  )
+}
+
 /**
  * Get the command/detail text to display for a denial
  */
@@ -116,6 +164,7 @@ export function PermissionApproval({
   denials,
   onApprove,
   onApproveYolo,
+  onApproveForSession,
   onDeny,
   readOnly = false,
   approvedPatterns,
@@ -304,6 +353,18 @@ export function PermissionApproval({
                     {command}
                   </div>
                 )}
+                {getFileChanges(denial).map(change => (
+                  <div key={change.path} className="mt-1">
+                    <div className="text-xs text-foreground/80">
+                      {change.path}
+                    </div>
+                    {change.diff && (
+                      <pre className="mt-1 max-h-48 overflow-auto rounded bg-muted/50 px-2 py-1 text-xs text-foreground/80">
+                        {change.diff}
+                      </pre>
+                    )}
+                  </div>
+                ))}
                 <div className="mt-1">
                   <div className="font-medium">{denial.tool_name}</div>
                   <div className="text-xs text-muted-foreground">
@@ -349,6 +410,17 @@ export function PermissionApproval({
             </Kbd>
           </Button>
         )}
+        {onApproveForSession && (
+          <Button
+            size="sm"
+            variant="outline"
+            onClick={() => onApproveForSession(sessionId)}
+            className="gap-1"
+          >
+            <CheckCircle2 className="h-3 w-3" />
+            Approve for Session
+          </Button>
+        )}
         {onDeny && (
           <Button
             size="sm"
@@ -8,6 +8,7 @@ import {
   chatQueryKeys,
 } from '@/services/chat'
 import { projectsQueryKeys } from '@/services/projects'
+import { getWorkflowFailureContext } from '@/services/github'
 import { buildMcpConfigJson } from '@/services/mcp'
 import { resolveBackend, supportsAdaptiveThinking } from '@/lib/model-utils'
 import {
@@ -371,12 +372,16 @@ export function useInvestigateHandlers({
           ? customPrompt
           : DEFAULT_INVESTIGATE_WORKFLOW_RUN_PROMPT
 
+      const failureContext = template.includes('{failureContext}')
+        ? await getWorkflowFailureContext(detail.projectPath, detail.runId)
+        : ''
       const prompt = template
         .replace(/\{workflowName\}/g, detail.workflowName)
         .replace(/\{runUrl\}/g, detail.runUrl)
         .replace(/\{runId\}/g, detail.runId)
         .replace(/\{branch\}/g, detail.branch)
         .replace(/\{displayTitle\}/g, detail.displayTitle)
+        .replace(/\{failureContext\}/g, failureContext)
 
       const investigateModel =
         preferences?.magic_prompt_models?.investigate_workflow_run_model ??
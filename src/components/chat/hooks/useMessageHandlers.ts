@@ -20,7 +20,10 @@ import type {
   WorktreeSessions,
 } from '@/types/chat'
 import type { ReviewFinding } from '@/types/chat'
-import { formatAnswersAsNaturalLanguage } from '@/services/chat'
+import {
+  formatAnswersAsNaturalLanguage,
+  respondToLiveApprovals,
+} from '@/services/chat'
 import { parseReviewFindings, getFindingKey } from '../review-finding-utils'
 import { findPlanContent, findPlanFilePath } from '../tool-call-utils'
 import { navigateToApprovedWorktree } from '../worktree-approval-navigation'
@@ -131,6 +134,7 @@ interface MessageHandlers {
     sessionId: string,
     approvedPatterns: string[]
   ) => void
+  handlePermissionApprovalForSession: (sessionId: string) => void
   handlePermissionDeny: (sessionId: string) => void
   handleFixFinding: (
     finding: ReviewFinding,
@@ -1936,8 +1940,8 @@ export function useMessageHandlers({
 
       const backend = selectedBackends[sessionId] ?? 'claude'
 
-      // Codex path: send approval response via JSON-RPC (process is still running)
-      if (backend === 'codex') {
+      // Codex/OpenCode path: answer the pending approval request (process is still running)
+      if (backend === 'codex' || backend === 'opencode') {
         const denials = getPendingDenials(sessionId)
         const currentMode =
           useChatStore.getState().executionModes[sessionId] ??
@@ -1981,22 +1985,11 @@ export function useMessageHandlers({
           scrollToBottom(true)
         })
 
-        // Send accept for each denial that has an rpc_id
-        for (const denial of denials) {
-          if (denial.rpc_id != null) {
-            invoke('approve_codex_command', {
-              sessionId,
-              rpcId: denial.rpc_id,
-              decision: 'accept',
-            }).catch(err => {
-              console.error(
-                '[ChatWindow] Failed to approve Codex command:',
-                err
-              )
-              toast.error(`Failed to approve command: ${err}`)
-            })
-          }
-        }
+        // Send accept for each denial the backend is waiting on
+        respondToLiveApprovals(sessionId, denials, 'accept').catch(err => {
+          console.error('[ChatWindow] Failed to approve request:', err)
+          toast.error(`Failed to approve command: ${err}`)
+        })
         return
       }
 
@@ -2139,8 +2132,8 @@ export function useMessageHandlers({
 
       const backend = selectedBackends[sessionId] ?? 'claude'
 
-      // Codex path: accept current denial and switch to yolo for future messages
-      if (backend === 'codex') {
+      // Codex/OpenCode path: accept current denial and switch to yolo for future messages
+      if (backend === 'codex' || backend === 'opencode') {
         const denials = getPendingDenials(sessionId)
         const currentMode =
           useChatStore.getState().executionModes[sessionId] ??
@@ -2171,20 +2164,9 @@ export function useMessageHandlers({
           scrollToBottom(true)
         })
 
-        for (const denial of denials) {
-          if (denial.rpc_id != null) {
-            invoke('approve_codex_command', {
-              sessionId,
-              rpcId: denial.rpc_id,
-              decision: 'accept',
-            }).catch(err => {
-              console.error(
-                '[ChatWindow] Failed to approve Codex command:',
-                err
-              )
-            })
-          }
-        }
+        respondToLiveApprovals(sessionId, denials, 'accept').catch(err => {
+          console.error('[ChatWindow] Failed to approve request:', err)
+        })
         return
       }
 
@@ -2290,6 +2272,36 @@ export function useMessageHandlers({
     ]
   )
 
+  // Handle "approve for session" of live approval requests (Codex/OpenCode):
+  // the backend stops asking for the same kind of request until the session ends
+  const handlePermissionApprovalForSession = useCallback(
+    (sessionId: string) => {
+      const {
+        clearPendingDenials,
+        clearDeniedMessageContext,
+        getPendingDenials,
+        setWaitingForInput,
+      } = useChatStore.getState()
+
+      const denials = getPendingDenials(sessionId)
+      clearPendingDenials(sessionId)
+      clearDeniedMessageContext(sessionId)
+      setWaitingForInput(sessionId, false)
+
+      requestAnimationFrame(() => {
+        scrollToBottom(true)
+      })
+
+      respondToLiveApprovals(sessionId, denials, 'acceptForSession').catch(
+        err => {
+          console.error('[ChatWindow] Failed to approve request:', err)
+          toast.error(`Failed to approve command: ${err}`)
+        }
+      )
+    },
+    [scrollToBottom]
+  )
+
   // Handle permission denial (user cancels approval request)
   const handlePermissionDeny = useCallback((sessionId: string) => {
     const {
@@ -2303,20 +2315,12 @@ export function useMessageHandlers({
 
     const backend = selectedBackends[sessionId] ?? 'claude'
 
-    // For Codex: send decline response to unblock the attached process
-    if (backend === 'codex') {
+    // For Codex/OpenCode: send decline response to unblock the running process
+    if (backend === 'codex' || backend === 'opencode') {
       const denials = getPendingDenials(sessionId)
-      for (const denial of denials) {
-        if (denial.rpc_id != null) {
-          invoke('approve_codex_command', {
-            sessionId,
-            rpcId: denial.rpc_id,
-            decision: 'decline',
-          }).catch(err => {
-            console.error('[ChatWindow] Failed to decline Codex command:', err)
-          })
-        }
-      }
+      respondToLiveApprovals(sessionId, denials, 'decline').catch(err => {
+        console.error('[ChatWindow] Failed to decline request:', err)
+      })
       clearPendingDenials(sessionId)
       clearDeniedMessageContext(sessionId)
       setWaitingForInput(sessionId, false)
@@ -2623,6 +2627,7 @@ Please apply all these fixes to the respective files.`
     handlePendingPlanApprovalCallback,
     handlePermissionApproval,
     handlePermissionApprovalYolo,
+    handlePermissionApprovalForSession,
     handlePermissionDeny,
     handleFixFinding,
     handleFixAllFindings,
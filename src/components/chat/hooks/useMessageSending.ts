@@ -179,10 +179,10 @@ export function useMessageSending({
       setSelectedModel(activeSessionId, queuedMsg.model)
 
       const sessionApprovedTools = getApprovedTools(activeSessionId)
+      // The command's own tools go separately: they only apply to this run
       const mergedAllowedTools = [
         ...GIT_ALLOWED_TOOLS,
         ...(sessionApprovedTools.length > 0 ? sessionApprovedTools : []),
-        ...(queuedMsg.commandAllowedTools ?? []),
       ]
       const allowedTools =
         mergedAllowedTools.length > 0
@@ -212,6 +212,7 @@ export function useMessageSending({
           chromeEnabled: preferences?.chrome_enabled ?? false,
           aiLanguage: preferences?.ai_language,
           allowedTools,
+          commandAllowedTools: queuedMsg.commandAllowedTools,
           backend: queuedMsg.backend,
         },
         {
@@ -1,6 +1,7 @@
 import { describe, expect, it } from 'vitest'
 import {
   getCodexPermissionApprovalMode,
+  isLiveApproval,
   shouldShowPermissionApproval,
 } from './permission-approval-utils'
 
@@ -49,6 +50,25 @@ describe('permission approval utils', () => {
     ).toBe(false)
   })
 
+  it('keeps live approvals visible during opencode streaming and in yolo mode', () => {
+    expect(
+      shouldShowPermissionApproval({
+        pendingDenialsCount: 1,
+        isSending: true,
+        executionMode: 'yolo',
+        isCodexBackend: false,
+        hasLiveApprovals: true,
+      })
+    ).toBe(true)
+  })
+
+  it('detects live approval requests', () => {
+    const base = { tool_name: 'Bash', tool_use_id: 't1', tool_input: {} }
+    expect(isLiveApproval(base)).toBe(false)
+    expect(isLiveApproval({ ...base, rpc_id: 0 })).toBe(true)
+    expect(isLiveApproval({ ...base, permission_id: 'per_1' })).toBe(true)
+  })
+
   it('preserves plan mode for normal codex approvals', () => {
     expect(getCodexPermissionApprovalMode('plan', false)).toBe('plan')
   })
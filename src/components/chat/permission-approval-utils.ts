@@ -1,10 +1,12 @@
-import type { ExecutionMode } from '@/types/chat'
+import type { ExecutionMode, PermissionDenial } from '@/types/chat'
 
 interface PermissionApprovalVisibilityParams {
   pendingDenialsCount: number
   isSending: boolean
   executionMode: ExecutionMode
   isCodexBackend: boolean
+  /** Denials the running process is blocked on (Codex/OpenCode approval requests) */
+  hasLiveApprovals?: boolean
 }
 
 export function shouldShowPermissionApproval({
@@ -12,11 +14,20 @@ export function shouldShowPermissionApproval({
   isSending,
   executionMode,
   isCodexBackend,
+  hasLiveApprovals = false,
 }: PermissionApprovalVisibilityParams): boolean {
   if (pendingDenialsCount === 0) return false
-  if (executionMode === 'yolo') return false
+  if (executionMode === 'yolo' && !hasLiveApprovals) return false
 
-  return !isSending || isCodexBackend
+  return !isSending || isCodexBackend || hasLiveApprovals
+}
+
+/**
+ * Whether a denial is a live approval request (the backend process is waiting
+ * for an answer) rather than a Claude denial that requires re-sending.
+ */
+export function isLiveApproval(denial: PermissionDenial): boolean {
+  return denial.rpc_id != null || denial.permission_id != null
 }
 
 export function getCodexPermissionApprovalMode(
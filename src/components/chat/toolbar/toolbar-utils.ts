@@ -9,6 +9,11 @@ export function getPrStatusDisplay(status: PrDisplayStatus): {
       return { label: 'Draft', className: 'text-muted-foreground' }
     case 'open':
       return { label: 'Open', className: 'text-green-600 dark:text-green-500' }
+    case 'queued':
+      return {
+        label: 'Queued',
+        className: 'text-amber-600 dark:text-amber-400',
+      }
     case 'merged':
       return {
         label: 'Merged',
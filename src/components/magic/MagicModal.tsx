@@ -515,6 +515,11 @@ export function MagicModal() {
                 },
               }
             )
+            if (result.defaults_errors?.length) {
+              toast.warning('Some PR defaults could not be applied', {
+                description: result.defaults_errors.join('\n'),
+              })
+            }
           } catch (error) {
             toast.error(`Failed to create PR: ${error}`, { id: toastId })
           } finally {
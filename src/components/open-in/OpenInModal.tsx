@@ -23,6 +23,7 @@ import {
   useOpenWorktreeInTerminal,
   useOpenWorktreeInEditor,
   type GitHubRemote,
+  remoteBranchUrl,
   useProjects,
   useWorktree,
 } from '@/services/projects'
@@ -252,12 +253,12 @@ export function OpenInModal() {
           })
             .then(remotes => {
               if (!remotes || remotes.length <= 1) {
-                const url = remotes?.[0]?.url
-                if (url) openExternal(`${url}/tree/${branch}`)
+                const remote = remotes?.[0]
+                if (remote) openExternal(remoteBranchUrl(remote, branch))
               } else if (targetPath) {
                 openRemotePicker(targetPath, remoteName => {
                   const remote = remotes.find(r => r.name === remoteName)
-                  if (remote) openExternal(`${remote.url}/tree/${branch}`)
+                  if (remote) openExternal(remoteBranchUrl(remote, branch))
                 })
               }
             })
@@ -161,6 +161,11 @@ const PROMPT_SECTIONS: PromptSection[] = [
             name: '{displayTitle}',
             description: 'Commit message or PR title that triggered the run',
           },
+          {
+            name: '{failureContext}',
+            description:
+              'Failed jobs and steps, error annotations and log excerpts',
+          },
         ],
         defaultValue: DEFAULT_INVESTIGATE_WORKFLOW_RUN_PROMPT,
         defaultModel: 'opus',
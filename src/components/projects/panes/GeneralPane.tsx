@@ -39,7 +39,9 @@ import {
   useRemoveProjectAvatar,
 } from '@/services/projects'
 import { usePreferences } from '@/services/preferences'
+import { githubQueryKeys, useProjectForge } from '@/services/github'
 import { useLinearTeams, linearQueryKeys } from '@/services/linear'
+import { jiraQueryKeys } from '@/services/jira'
 import { useQueryClient } from '@tanstack/react-query'
 import {
   Select,
@@ -48,6 +50,9 @@ import {
   SelectTrigger,
   SelectValue,
 } from '@/components/ui/select'
+import type { ForgeKind } from '@/types/projects'
+
+type JiraSettingKey = 'jira_url' | 'jira_email' | 'jira_api_token' | 'jira_jql'
 
 const SettingsSection: React.FC<{
   title: string
@@ -76,8 +81,32 @@ const InlineField: React.FC<{
   </div>
 )
 
+const FORGE_LABELS: Record<ForgeKind, string> = {
+  github: 'GitHub',
+  gitlab: 'GitLab',
+  gitea: 'Gitea / Forgejo',
+}
+
+const FORGE_TOKEN_HINTS: Record<
+  Exclude<ForgeKind, 'github'>,
+  { placeholder: string; fallback: string; scope: string }
+> = {
+  gitlab: {
+    placeholder: 'glpat-...',
+    fallback: 'your glab login, or GITLAB_TOKEN for gitlab.com / GITLAB_HOST',
+    scope: 'Personal access token with the "api" scope.',
+  },
+  gitea: {
+    placeholder: 'Access token',
+    fallback:
+      'your tea login, or FORGEJO_TOKEN / GITEA_TOKEN with a matching *_HOST',
+    scope: 'Access token with read/write access to issues and repositories.',
+  },
+}
+
 export function GeneralPane({
   projectId,
+  projectPath,
 }: {
   projectId: string
   projectPath: string
@@ -110,6 +139,13 @@ export function GeneralPane({
     null
   )
   const [showLinearApiKey, setShowLinearApiKey] = useState(false)
+  const [localForgeToken, setLocalForgeToken] = useState<string | null>(null)
+  const [showForgeToken, setShowForgeToken] = useState(false)
+  const [localForgeUrl, setLocalForgeUrl] = useState<string | null>(null)
+  const [jiraDraft, setJiraDraft] = useState<
+    Partial<Record<JiraSettingKey, string>>
+  >({})
+  const [showJiraToken, setShowJiraToken] = useState(false)
 
   // Linear has access if either project key or global key is set
   const hasLinearAccess =
@@ -227,6 +263,131 @@ export function GeneralPane({
     )
   }, [projectId, updateSettings])
 
+  const jiraValue = (key: JiraSettingKey) =>
+    jiraDraft[key] ?? project?.[key] ?? ''
+
+  const jiraChanged = (
+    Object.entries(jiraDraft) as [JiraSettingKey, string][]
+  ).some(([key, value]) => value !== (project?.[key] ?? ''))
+
+  const saveJiraSettings = useCallback(
+    (values: Partial<Record<JiraSettingKey, string>>) => {
+      updateSettings.mutate(
+        {
+          projectId,
+          jiraUrl: values.jira_url?.trim(),
+          jiraEmail: values.jira_email?.trim(),
+          jiraApiToken: values.jira_api_token?.trim(),
+          jiraJql: values.jira_jql?.trim(),
+        },
+        {
+          onSuccess: () => {
+            setJiraDraft({})
+            queryClient.invalidateQueries({ queryKey: jiraQueryKeys.all })
+          },
+        }
+      )
+    },
+    [projectId, updateSettings, queryClient]
+  )
+
+  const handleSaveJira = useCallback(
+    () => saveJiraSettings(jiraDraft),
+    [saveJiraSettings, jiraDraft]
+  )
+
+  const handleDisconnectJira = useCallback(
+    () =>
+      saveJiraSettings({
+        jira_url: '',
+        jira_email: '',
+        jira_api_token: '',
+        jira_jql: '',
+      }),
+    [saveJiraSettings]
+  )
+
+  const { data: forge, isLoading: forgeLoading } = useProjectForge(projectPath)
+
+  const displayedForgeToken = localForgeToken ?? project?.forge_token ?? ''
+
+  const forgeTokenChanged =
+    localForgeToken !== null &&
+    localForgeToken !== (project?.forge_token ?? '')
+
+  const displayedForgeUrl = localForgeUrl ?? project?.forge_url ?? ''
+
+  const forgeUrlChanged =
+    localForgeUrl !== null && localForgeUrl !== (project?.forge_url ?? '')
+
+  // Issues, PRs and runs all come from the forge, so refetch everything
+  const invalidateForge = useCallback(() => {
+    queryClient.invalidateQueries({ queryKey: githubQueryKeys.all })
+  }, [queryClient])
+
+  const handleForgeChange = useCallback(
+    (value: string) => {
+      updateSettings.mutate(
+        {
+          projectId,
+          forge: value === 'auto' ? '__none__' : (value as ForgeKind),
+        },
+        { onSuccess: invalidateForge }
+      )
+    },
+    [projectId, updateSettings, invalidateForge]
+  )
+
+  const handleSaveForgeToken = useCallback(() => {
+    if (localForgeToken === null) return
+    updateSettings.mutate(
+      { projectId, forgeToken: localForgeToken.trim() },
+      {
+        onSuccess: () => {
+          setLocalForgeToken(null)
+          invalidateForge()
+        },
+      }
+    )
+  }, [localForgeToken, projectId, updateSettings, invalidateForge])
+
+  const handleClearForgeToken = useCallback(() => {
+    updateSettings.mutate(
+      { projectId, forgeToken: '' },
+      {
+        onSuccess: () => {
+          setLocalForgeToken(null)
+          invalidateForge()
+        },
+      }
+    )
+  }, [projectId, updateSettings, invalidateForge])
+
+  const handleSaveForgeUrl = useCallback(() => {
+    if (localForgeUrl === null) return
+    updateSettings.mutate(
+      { projectId, forgeUrl: localForgeUrl.trim() },
+      {
+        onSuccess: () => {
+          setLocalForgeUrl(null)
+          invalidateForge()
+        },
+      }
+    )
+  }, [localForgeUrl, projectId, updateSettings, invalidateForge])
+
+  const handleResetForgeUrl = useCallback(() => {
+    updateSettings.mutate(
+      { projectId, forgeUrl: '' },
+      {
+        onSuccess: () => {
+          setLocalForgeUrl(null)
+          invalidateForge()
+        },
+      }
+    )
+  }, [projectId, updateSettings, invalidateForge])
+
   const handleTeamChange = useCallback(
     (value: string) => {
       updateSettings.mutate(
@@ -496,6 +657,123 @@ export function GeneralPane({
         </InlineField>
       </SettingsSection>
 
+      <SettingsSection title="Code Forge">
+        <InlineField
+          label="Forge"
+          description={
+            forgeLoading
+              ? 'Detecting forge...'
+              : forge
+                ? `Issues, pull requests and CI runs come from ${FORGE_LABELS[forge.kind]} (${forge.host}/${forge.path}).`
+                : 'Could not detect a forge from the origin remote. Pick one for self-hosted instances on custom domains.'
+          }
+        >
+          <Select
+            value={project?.forge ?? 'auto'}
+            onValueChange={handleForgeChange}
+            disabled={updateSettings.isPending}
+          >
+            <SelectTrigger className="w-full">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              <SelectItem value="auto">Detect from remote</SelectItem>
+              <SelectItem value="github">GitHub</SelectItem>
+              <SelectItem value="gitlab">GitLab</SelectItem>
+              <SelectItem value="gitea">Gitea / Forgejo</SelectItem>
+            </SelectContent>
+          </Select>
+        </InlineField>
+
+        {project?.forge && project.forge !== 'github' && (
+          <InlineField
+            label="Base URL"
+            description="Web root of a self-hosted instance served under a sub-path or a different host than the remote. Leave empty to derive it from the remote."
+          >
+            <Input
+              placeholder="https://git.example.com"
+              value={displayedForgeUrl}
+              onChange={e => setLocalForgeUrl(e.target.value)}
+              className="text-sm font-mono"
+            />
+            <div className="flex items-center gap-2">
+              <Button
+                size="sm"
+                onClick={handleSaveForgeUrl}
+                disabled={!forgeUrlChanged || updateSettings.isPending}
+              >
+                {updateSettings.isPending && (
+                  <Loader2 className="h-4 w-4 animate-spin" />
+                )}
+                Save
+              </Button>
+              {project?.forge_url && (
+                <Button
+                  variant="ghost"
+                  size="sm"
+                  onClick={handleResetForgeUrl}
+                  disabled={updateSettings.isPending}
+                >
+                  <RotateCcw className="h-4 w-4" />
+                  Reset
+                </Button>
+              )}
+            </div>
+          </InlineField>
+        )}
+
+        {forge && forge.kind !== 'github' && (
+          <InlineField
+            label="Access Token"
+            description={
+              forge.authenticated && !project?.forge_token
+                ? `Using the token from ${FORGE_TOKEN_HINTS[forge.kind].fallback}. Set one here to override it for this project.`
+                : `${FORGE_TOKEN_HINTS[forge.kind].scope} Leave empty to use ${FORGE_TOKEN_HINTS[forge.kind].fallback}.`
+            }
+          >
+            <div className="flex items-center gap-2">
+              <Input
+                type={showForgeToken ? 'text' : 'password'}
+                placeholder={FORGE_TOKEN_HINTS[forge.kind].placeholder}
+                value={displayedForgeToken}
+                onChange={e => setLocalForgeToken(e.target.value)}
+                className="flex-1 text-sm font-mono"
+              />
+              <Button
+                variant="outline"
+                size="sm"
+                onClick={() => setShowForgeToken(!showForgeToken)}
+              >
+                {showForgeToken ? 'Hide' : 'Show'}
+              </Button>
+            </div>
+            <div className="flex items-center gap-2">
+              <Button
+                size="sm"
+                onClick={handleSaveForgeToken}
+                disabled={!forgeTokenChanged || updateSettings.isPending}
+              >
+                {updateSettings.isPending && (
+                  <Loader2 className="h-4 w-4 animate-spin" />
+                )}
+                Save
+              </Button>
+              {project?.forge_token && (
+                <Button
+                  variant="ghost"
+                  size="sm"
+                  onClick={handleClearForgeToken}
+                  disabled={updateSettings.isPending}
+                >
+                  <RotateCcw className="h-4 w-4" />
+                  Remove
+                </Button>
+              )}
+            </div>
+          </InlineField>
+        )}
+      </SettingsSection>
+
       <SettingsSection title="Linear Integration">
         <InlineField
           label="Project API Key Override"
@@ -587,6 +865,99 @@ export function GeneralPane({
         )}
       </SettingsSection>
 
+      <SettingsSection title="Jira Integration">
+        <InlineField
+          label="Site URL"
+          description="Jira Cloud site (https://team.atlassian.net) or the root of a Server/Data Center instance."
+        >
+          <Input
+            placeholder="https://team.atlassian.net"
+            value={jiraValue('jira_url')}
+            onChange={e =>
+              setJiraDraft(d => ({ ...d, jira_url: e.target.value }))
+            }
+            className="text-sm font-mono"
+          />
+        </InlineField>
+
+        <InlineField
+          label="Email"
+          description="Atlassian account email, required for Jira Cloud API tokens. Leave empty to use a Server/Data Center personal access token."
+        >
+          <Input
+            type="email"
+            placeholder="you@example.com"
+            value={jiraValue('jira_email')}
+            onChange={e =>
+              setJiraDraft(d => ({ ...d, jira_email: e.target.value }))
+            }
+            className="text-sm"
+          />
+        </InlineField>
+
+        <InlineField
+          label="API Token"
+          description="Jira Cloud API token, or a personal access token on Server/Data Center."
+        >
+          <div className="flex items-center gap-2">
+            <Input
+              type={showJiraToken ? 'text' : 'password'}
+              placeholder="API token"
+              value={jiraValue('jira_api_token')}
+              onChange={e =>
+                setJiraDraft(d => ({ ...d, jira_api_token: e.target.value }))
+              }
+              className="flex-1 text-sm font-mono"
+            />
+            <Button
+              variant="outline"
+              size="sm"
+              onClick={() => setShowJiraToken(!showJiraToken)}
+            >
+              {showJiraToken ? 'Hide' : 'Show'}
+            </Button>
+          </div>
+        </InlineField>
+
+        <InlineField
+          label="Issue Filter"
+          description="JQL applied to the issue list, e.g. 'project = ENG'. Resolved issues are always hidden. Leave empty to see every project."
+        >
+          <Input
+            placeholder="project = ENG"
+            value={jiraValue('jira_jql')}
+            onChange={e =>
+              setJiraDraft(d => ({ ...d, jira_jql: e.target.value }))
+            }
+            className="text-sm font-mono"
+          />
+        </InlineField>
+
+        <div className="flex items-center gap-2">
+          <Button
+            size="sm"
+            onClick={handleSaveJira}
+            disabled={!jiraChanged || updateSettings.isPending}
+          >
+            {updateSettings.isPending && (
+              <Loader2 className="h-4 w-4 animate-spin" />
+            )}
+            Save
+          </Button>
+          {project?.jira_url && (
+            <Button
+              variant="ghost"
+              size="sm"
+              onClick={handleDisconnectJira}
+              disabled={updateSettings.isPending}
+            >
+              <RotateCcw className="h-4 w-4" />
+              Disconnect
+            </Button>
+          )}
+        </div>
+      </SettingsSection>
+
       <SettingsSection title="System Prompt">
         <InlineField
           label="Custom System Prompt"
@@ -25,7 +25,8 @@ export function JeanJsonPane({
   projectId: string
   projectPath: string
 }) {
-  const { data: jeanConfig } = useJeanConfig(projectPath)
+  const { data: jeanConfig, error: jeanConfigError } =
+    useJeanConfig(projectPath)
   const saveJeanConfig = useSaveJeanConfig()
 
   const [localSetup, setLocalSetup] = useState('')
@@ -57,14 +58,24 @@ export function JeanJsonPane({
     saveJeanConfig.mutate({
       projectPath,
       config: {
+        // Keep named scripts, hooks and other sections not edited here
+        ...jeanConfig,
         scripts: {
+          ...jeanConfig?.scripts,
           setup: localSetup.trim() || null,
           teardown: localTeardown.trim() || null,
           run: localRun.trim() || null,
         },
       },
     })
-  }, [localSetup, localTeardown, localRun, projectPath, saveJeanConfig])
+  }, [
+    jeanConfig,
+    localSetup,
+    localTeardown,
+    localRun,
+    projectPath,
+    saveJeanConfig,
+  ])
 
   return (
     <div className="space-y-6">
@@ -73,6 +84,11 @@ export function JeanJsonPane({
           Scripts from jean.json — setup runs after worktree creation, teardown
           before deletion, run launches via the run command
         </p>
+        {jeanConfigError && (
+          <pre className="whitespace-pre-wrap rounded-md bg-destructive/10 p-2 text-xs text-destructive">
+            {String(jeanConfigError)}
+          </pre>
+        )}
         <div className="space-y-4">
           <div className="space-y-1.5">
             <Label htmlFor="setup-script" className="text-sm">
@@ -34,7 +34,11 @@ import { invoke } from '@/lib/transport'
 import { useUIStore } from '@/store/ui-store'
 import { useChatStore } from '@/store/chat-store'
 import { useProjectsStore } from '@/store/projects-store'
-import { useWorkflowRuns, githubQueryKeys } from '@/services/github'
+import {
+  useWorkflowRuns,
+  githubQueryKeys,
+  getWorkflowFailureContext,
+} from '@/services/github'
 import { projectsQueryKeys } from '@/services/projects'
 import {
   useCreateSession,
@@ -294,12 +298,16 @@ export function WorkflowRunsModal() {
           : DEFAULT_INVESTIGATE_WORKFLOW_RUN_PROMPT
 
       const runId = extractRunId(run.url)
+      const failureContext = template.includes('{failureContext}')
+        ? await getWorkflowFailureContext(projectPath, runId)
+        : ''
       const prompt = template
         .replace(/\{workflowName\}/g, run.workflowName)
         .replace(/\{runUrl\}/g, run.url)
         .replace(/\{runId\}/g, runId)
         .replace(/\{branch\}/g, run.headBranch)
         .replace(/\{displayTitle\}/g, run.displayTitle)
+        .replace(/\{failureContext\}/g, failureContext)
 
       // Fall back to the user's currently-selected model (like useInvestigateHandlers does)
       const storeState = useChatStore.getState()
@@ -45,7 +45,11 @@ import { useChatStore } from '@/store/chat-store'
 import { useProjectsStore } from '@/store/projects-store'
 import { chatQueryKeys } from '@/services/chat'
 import { usePreferences } from '@/services/preferences'
-import { useWorktree, type GitHubRemote } from '@/services/projects'
+import {
+  useWorktree,
+  remoteBranchUrl,
+  type GitHubRemote,
+} from '@/services/projects'
 import { useCodexCliAuth, useCodexCliStatus, useCodexUsage } from '@/services/codex-cli'
 import type { WorktreeSessions } from '@/types/chat'
 import { DEFAULT_KEYBINDINGS, formatShortcutDisplay } from '@/types/keybindings'
@@ -274,14 +278,14 @@ export function FloatingDock() {
     invoke<GitHubRemote[]>('get_github_remotes', { repoPath: targetPath })
       .then(remotes => {
         if (!remotes || remotes.length <= 1) {
-          const url = remotes?.[0]?.url
-          if (url) openExternal(`${url}/tree/${branch}`, win)
+          const remote = remotes?.[0]
+          if (remote) openExternal(remoteBranchUrl(remote, branch), win)
           else win?.close()
         } else {
           win?.close()
           useUIStore.getState().openRemotePicker(targetPath, remoteName => {
             const remote = remotes.find(r => r.name === remoteName)
-            if (remote) openExternal(`${remote.url}/tree/${branch}`)
+            if (remote) openExternal(remoteBranchUrl(remote, branch))
           })
         }
       })
@@ -36,7 +36,15 @@ export function useAutoArchiveOnMerge() {
         return
       }
 
-      // Only act on merged PRs
+      // Only act on merged PRs. A queued PR isn't merged yet: the merge queue
+      // can still eject it, so wait for the merge to land.
+      if (status.display_status === 'queued') {
+        logger.debug('PR in merge queue, waiting for merge before archiving', {
+          worktreeId: status.worktree_id,
+          position: status.merge_queue?.position,
+        })
+        return
+      }
       if (status.display_status !== 'merged') {
         return
       }
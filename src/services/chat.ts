@@ -21,6 +21,10 @@ import type {
   ExecutionMode,
   LabelData,
   QueuedMessage,
+  RunPermissionAudit,
+  PermissionDenial,
+  LiveApprovalDecision,
+  ReviewThreadProgress,
 } from '@/types/chat'
 import {
   isTauri,
@@ -30,7 +34,12 @@ import { preferencesQueryKeys } from '@/services/preferences'
 import type { AppPreferences } from '@/types/preferences'
 import { useChatStore } from '@/store/chat-store'
 import { useUIStore } from '@/store/ui-store'
-import type { ReviewResponse, Worktree } from '@/types/projects'
+import type {
+  AddressReviewTask,
+  PrReviewThread,
+  ReviewResponse,
+  Worktree,
+} from '@/types/projects'
 
 // Query keys for chat
 export const chatQueryKeys = {
@@ -1162,6 +1171,7 @@ export function useSendMessage() {
       parallelExecutionPrompt,
       aiLanguage,
       allowedTools,
+      commandAllowedTools,
       mcpConfig,
       chromeEnabled,
       customProfileName,
@@ -1178,6 +1188,7 @@ export function useSendMessage() {
       parallelExecutionPrompt?: string
       aiLanguage?: string
       allowedTools?: string[]
+      commandAllowedTools?: string[]
       mcpConfig?: string
       chromeEnabled?: boolean
       customProfileName?: string
@@ -1213,6 +1224,7 @@ export function useSendMessage() {
           parallelExecutionPrompt,
         aiLanguage,
         allowedTools,
+        commandAllowedTools,
         mcpConfig,
         chromeEnabled,
         customProfileName,
@@ -2054,6 +2066,57 @@ export async function persistDequeue(
   })
 }
 
+/**
+ * Ask the backend to start processing a session's queue if the session is idle.
+ * The backend dequeues and sends queued messages itself; clients just observe.
+ */
+export async function processMessageQueue(
+  worktreeId: string,
+  sessionId: string
+): Promise<void> {
+  await invoke('process_message_queue', { worktreeId, sessionId })
+}
+
+/**
+ * Answer approval requests a running backend is blocked on.
+ * Codex denials carry a JSON-RPC id, OpenCode denials a permission id;
+ * Claude denials have neither (they're handled by re-sending the message).
+ */
+export async function respondToLiveApprovals(
+  sessionId: string,
+  denials: PermissionDenial[],
+  decision: LiveApprovalDecision
+): Promise<void> {
+  await Promise.all(
+    denials.map(denial => {
+      if (denial.rpc_id != null) {
+        return invoke('approve_codex_command', {
+          sessionId,
+          rpcId: denial.rpc_id,
+          decision,
+        })
+      }
+      if (denial.permission_id != null) {
+        return invoke('approve_opencode_permission', {
+          sessionId,
+          permissionId: denial.permission_id,
+          decision,
+        })
+      }
+      return Promise.resolve()
+    })
+  )
+}
+
+/**
+ * Get the permission policy decisions recorded for each run of a session.
+ */
+export async function getPermissionAudit(
+  sessionId: string
+): Promise<RunPermissionAudit[]> {
+  return invoke<RunPermissionAudit[]>('get_permission_audit', { sessionId })
+}
+
 /**
  * Persist removal of a specific queued message.
  */
@@ -2085,3 +2148,122 @@ export function persistClearQueue(
     logger.error('Failed to persist clear queue', { err, sessionId })
   })
 }
+
+// ============================================================================
+// PR Review Threads
+// ============================================================================
+
+/**
+ * Hook to fetch review threads on the worktree's linked PR
+ * (unresolved only unless includeResolved is set)
+ */
+export function usePrReviewThreads(
+  worktreeId: string | null,
+  options?: { includeResolved?: boolean; enabled?: boolean }
+) {
+  const includeResolved = options?.includeResolved ?? false
+  return useQuery({
+    queryKey: [
+      ...chatQueryKeys.all,
+      'review-threads',
+      worktreeId ?? '',
+      includeResolved,
+    ],
+    queryFn: async (): Promise<PrReviewThread[]> => {
+      if (!isTauri() || !worktreeId) return []
+      return invoke<PrReviewThread[]>('get_pr_review_threads', {
+        worktreeId,
+        includeResolved,
+      })
+    },
+    enabled: !!worktreeId && (options?.enabled ?? true),
+    staleTime: 1000 * 60,
+  })
+}
+
+/**
+ * Hook to start addressing review threads from a session.
+ * Marks the threads in progress and returns the prompt to send in build mode.
+ */
+export function useStartAddressingReview() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({
+      worktreeId,
+      sessionId,
+      threadIds,
+    }: {
+      worktreeId: string
+      sessionId: string
+      threadIds?: string[]
+    }) => {
+      return invoke<AddressReviewTask>('start_addressing_review', {
+        worktreeId,
+        sessionId,
+        threadIds,
+      })
+    },
+    onSuccess: (_, { sessionId }) => {
+      queryClient.invalidateQueries({
+        queryKey: chatQueryKeys.session(sessionId),
+      })
+    },
+    onError: error => {
+      toast.error('Failed to load review threads', {
+        description: String(error),
+      })
+    },
+  })
+}
+
+/**
+ * Hook to finish addressing review threads after the session's run:
+ * commits and pushes the changes, replies to each thread with the commit
+ * and optionally resolves it.
+ */
+export function useFinishAddressingReview() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({
+      worktreeId,
+      sessionId,
+      commitMessage,
+      resolve,
+    }: {
+      worktreeId: string
+      sessionId: string
+      commitMessage?: string
+      resolve?: boolean
+    }) => {
+      return invoke<Record<string, ReviewThreadProgress>>(
+        'finish_addressing_review',
+        { worktreeId, sessionId, commitMessage, resolve }
+      )
+    },
+    onSuccess: (progress, { worktreeId, sessionId }) => {
+      queryClient.invalidateQueries({
+        queryKey: chatQueryKeys.session(sessionId),
+      })
+      queryClient.invalidateQueries({
+        queryKey: [...chatQueryKeys.all, 'review-threads', worktreeId],
+      })
+      const entries = Object.values(progress)
+      const failed = entries.filter(p => p.status === 'failed').length
+      const done = entries.filter(
+        p => p.status === 'replied' || p.status === 'resolved'
+      ).length
+      if (failed > 0) {
+        toast.warning(`Replied to ${done} review thread(s), ${failed} failed`)
+      } else {
+        toast.success(`Replied to ${done} review thread(s)`)
+      }
+    },
+    onError: error => {
+      toast.error('Failed to reply to review threads', {
+        description: String(error),
+      })
+    },
+  })
+}
@@ -0,0 +1,130 @@
+/**
+ * CI auto-fix watcher service
+ *
+ * Starts and stops the backend watcher that fixes failing PR checks from a
+ * session, and caches its 'ci-fix:update' events with TanStack Query.
+ */
+
+import { useEffect } from 'react'
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query'
+import { toast } from 'sonner'
+
+import {
+  invoke,
+  listen,
+  type UnlistenFn,
+  useWsConnectionStatus,
+} from '@/lib/transport'
+import { isTauri } from '@/services/projects'
+import type { CiFixEvent, CiFixWatcher } from '@/types/pr-status'
+
+// ============================================================================
+// Query Keys
+// ============================================================================
+
+export const ciFixQueryKeys = {
+  all: ['ci-fix'] as const,
+  watchers: () => [...ciFixQueryKeys.all, 'watchers'] as const,
+}
+
+// ============================================================================
+// Hooks
+// ============================================================================
+
+/**
+ * Hook to listen for CI auto-fix events and keep the watcher list in sync.
+ * Watchers in a final phase (passed, exhausted, stopped) are dropped.
+ */
+export function useCiFixEvents(onUpdate?: (event: CiFixEvent) => void) {
+  const queryClient = useQueryClient()
+  const wsConnected = useWsConnectionStatus()
+
+  useEffect(() => {
+    if (!isTauri()) return
+
+    const unlistenPromise: Promise<UnlistenFn> = listen<CiFixEvent>(
+      'ci-fix:update',
+      event => {
+        const update = event.payload
+        const isFinal = ['passed', 'exhausted', 'stopped'].includes(
+          update.phase
+        )
+        queryClient.setQueryData<CiFixWatcher[]>(
+          ciFixQueryKeys.watchers(),
+          old => {
+            const rest = (old ?? []).filter(
+              w => w.worktreeId !== update.worktreeId
+            )
+            return isFinal ? rest : [...rest, update]
+          }
+        )
+        onUpdate?.(update)
+      }
+    )
+
+    return () => {
+      unlistenPromise.then(unlisten => unlisten())
+    }
+  }, [queryClient, onUpdate, wsConnected])
+}
+
+/**
+ * Hook to get the active CI auto-fix watchers
+ */
+export function useCiFixWatchers() {
+  return useQuery({
+    queryKey: ciFixQueryKeys.watchers(),
+    queryFn: async (): Promise<CiFixWatcher[]> => {
+      if (!isTauri()) return []
+      return invoke<CiFixWatcher[]>('get_ci_fix_watchers')
+    },
+    staleTime: Infinity, // Kept up to date by events
+  })
+}
+
+/**
+ * Hook to start the CI auto-fix watcher for a worktree's PR
+ */
+export function useStartCiFixWatcher() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({
+      worktreeId,
+      sessionId,
+      maxAttempts,
+    }: {
+      worktreeId: string
+      sessionId: string
+      maxAttempts?: number
+    }) => {
+      return invoke<CiFixWatcher>('start_ci_fix_watcher', {
+        worktreeId,
+        sessionId,
+        maxAttempts,
+      })
+    },
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ciFixQueryKeys.watchers() })
+    },
+    onError: error => {
+      toast.error('Failed to start CI auto-fix', { description: String(error) })
+    },
+  })
+}
+
+/**
+ * Hook to stop the CI auto-fix watcher for a worktree
+ */
+export function useStopCiFixWatcher() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (worktreeId: string) => {
+      await invoke('stop_ci_fix_watcher', { worktreeId })
+    },
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ciFixQueryKeys.watchers() })
+    },
+  })
+}
@@ -0,0 +1,196 @@
+/**
+ * Conflict resolution
+ *
+ * Hooks for resolving merge/rebase conflicts hunk by hunk: load the
+ * structured conflict state, resolve hunks, mark files resolved and
+ * continue or abort the operation.
+ */
+
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query'
+import { toast } from 'sonner'
+
+import { invoke } from '@/lib/transport'
+import { isTauri } from '@/services/projects'
+import type {
+  ConflictSide,
+  ConflictState,
+  ConflictSuggestion,
+  FileConflict,
+  HunkResolution,
+} from '@/types/conflicts'
+
+// ============================================================================
+// Query Keys
+// ============================================================================
+
+export const conflictsQueryKeys = {
+  all: ['conflicts'] as const,
+  worktree: (worktreeId: string) =>
+    [...conflictsQueryKeys.all, worktreeId] as const,
+}
+
+// ============================================================================
+// Hooks
+// ============================================================================
+
+/**
+ * Hook to get the structured conflicts of a worktree
+ */
+export function useConflictState(worktreeId: string | null) {
+  return useQuery<ConflictState>({
+    queryKey: worktreeId
+      ? conflictsQueryKeys.worktree(worktreeId)
+      : ['conflicts', 'none'],
+    queryFn: async () => {
+      if (!isTauri() || !worktreeId) return { operation: null, files: [] }
+      return invoke<ConflictState>('get_conflict_state', { worktreeId })
+    },
+    enabled: !!worktreeId,
+  })
+}
+
+/**
+ * Hook to resolve one conflict hunk with ours/theirs/both/base or manual content
+ */
+export function useResolveConflictHunk() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({
+      worktreeId,
+      path,
+      hunkIndex,
+      resolution,
+    }: {
+      worktreeId: string
+      path: string
+      hunkIndex: number
+      resolution: HunkResolution
+    }) => {
+      return invoke<FileConflict>('resolve_conflict_hunk', {
+        worktreeId,
+        path,
+        hunkIndex,
+        resolution,
+      })
+    },
+    onSuccess: (file, { worktreeId }) => {
+      queryClient.setQueryData<ConflictState>(
+        conflictsQueryKeys.worktree(worktreeId),
+        old =>
+          old && {
+            ...old,
+            files: old.files.map(f => (f.path === file.path ? file : f)),
+          }
+      )
+    },
+    onError: error => {
+      toast.error('Failed to resolve conflict', { description: String(error) })
+    },
+  })
+}
+
+/**
+ * Hook to mark a file resolved, optionally taking one side for the whole file
+ */
+export function useMarkConflictResolved() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({
+      worktreeId,
+      path,
+      side,
+    }: {
+      worktreeId: string
+      path: string
+      side?: ConflictSide
+    }) => {
+      return invoke<ConflictState>('mark_conflict_resolved', {
+        worktreeId,
+        path,
+        side,
+      })
+    },
+    onSuccess: (state, { worktreeId }) => {
+      queryClient.setQueryData(conflictsQueryKeys.worktree(worktreeId), state)
+    },
+    onError: error => {
+      toast.error('Failed to mark file resolved', {
+        description: String(error),
+      })
+    },
+  })
+}
+
+/**
+ * Hook to continue or abort the merge/rebase/cherry-pick/revert in progress
+ */
+export function useConflictOperation() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({
+      worktreeId,
+      action,
+    }: {
+      worktreeId: string
+      action: 'continue' | 'abort'
+    }) => {
+      return invoke<ConflictState>(`${action}_conflict_operation`, {
+        worktreeId,
+      })
+    },
+    onSuccess: (state, { worktreeId, action }) => {
+      queryClient.setQueryData(conflictsQueryKeys.worktree(worktreeId), state)
+      if (action === 'abort') {
+        toast.success('Aborted')
+      } else if (state.files.length > 0) {
+        toast.info('Stopped on the next conflicts')
+      } else {
+        toast.success('Conflicts resolved')
+      }
+    },
+    onError: error => {
+      toast.error('Failed to continue', { description: String(error) })
+    },
+  })
+}
+
+/**
+ * Hook to ask the AI for a resolution of one conflict hunk (not applied;
+ * pass it to useResolveConflictHunk as a manual resolution to accept it)
+ */
+export function useSuggestConflictResolution() {
+  return useMutation({
+    mutationFn: async ({
+      worktreeId,
+      path,
+      hunkIndex,
+      model,
+      customProfileName,
+      reasoningEffort,
+    }: {
+      worktreeId: string
+      path: string
+      hunkIndex: number
+      model?: string
+      customProfileName?: string
+      reasoningEffort?: string
+    }) => {
+      return invoke<ConflictSuggestion>('suggest_conflict_resolution', {
+        worktreeId,
+        path,
+        hunkIndex,
+        model,
+        customProfileName,
+        reasoningEffort,
+      })
+    },
+    onError: error => {
+      toast.error('Failed to suggest a resolution', {
+        description: String(error),
+      })
+    },
+  })
+}
@@ -16,7 +16,15 @@ import {
   projectsQueryKeys,
 } from '@/services/projects'
 import type { GitPushResponse, Worktree } from '@/types/projects'
-import type { GitDiff, CommitHistoryResult } from '@/types/git-diff'
+import type {
+  GitDiff,
+  CommitHistoryResult,
+  HunkSelection,
+  RebasePlan,
+  RebasePlanResult,
+  RebaseStep,
+} from '@/types/git-diff'
+import type { CommitSplitPlan } from '@/types/commit-split'
 
 // ============================================================================
 // Types
@@ -458,12 +466,13 @@ export async function triggerImmediateRemotePoll(): Promise<void> {
  * Get detailed git diff for a worktree.
  *
  * @param worktreePath - Path to the worktree/repository
- * @param diffType - "uncommitted" for working directory changes, "branch" for changes vs base branch
+ * @param diffType - "uncommitted" for working directory changes, "staged" / "unstaged" for the
+ *   index vs HEAD / working directory vs index, "branch" for changes vs base branch
  * @param baseBranch - Base branch name (used for "branch" diff type)
  */
 export async function getGitDiff(
   worktreePath: string,
-  diffType: 'uncommitted' | 'branch',
+  diffType: 'uncommitted' | 'staged' | 'unstaged' | 'branch',
   baseBranch?: string
 ): Promise<GitDiff> {
   if (!isTauri()) {
@@ -520,6 +529,72 @@ export async function getCommitDiff(
   })
 }
 
+/**
+ * Get the commits of a worktree's branch as an all-`pick` rebase plan.
+ *
+ * @param worktreeId - Worktree to rewrite
+ * @param baseBranch - Branch to rebase against (defaults to the PR target branch)
+ */
+export async function getRebasePlan(
+  worktreeId: string,
+  baseBranch?: string
+): Promise<RebasePlan> {
+  if (!isTauri()) {
+    throw new Error('Rebase plans only available in Tauri')
+  }
+  return invoke<RebasePlan>('get_rebase_plan', {
+    worktreeId,
+    baseBranch: baseBranch ?? null,
+  })
+}
+
+/**
+ * Apply an edited rebase plan. Every commit from the plan must be listed once;
+ * reorder steps to reorder commits. On conflicts the rebase is left in
+ * progress for the conflict resolution commands.
+ */
+export async function applyRebasePlan(
+  worktreeId: string,
+  base: string,
+  steps: RebaseStep[]
+): Promise<RebasePlanResult> {
+  if (!isTauri()) {
+    throw new Error('Rebase plans only available in Tauri')
+  }
+  return invoke<RebasePlanResult>('apply_rebase_plan', {
+    worktreeId,
+    base,
+    steps,
+  })
+}
+
+/**
+ * Generate a combined commit message for commits being squashed, using the
+ * commit message magic prompt.
+ */
+export async function generateSquashMessage(
+  worktreeId: string,
+  shas: string[],
+  options?: {
+    customPrompt?: string
+    model?: string
+    customProfileName?: string
+    reasoningEffort?: string
+  }
+): Promise<string> {
+  if (!isTauri()) {
+    throw new Error('Squash messages only available in Tauri')
+  }
+  return invoke<string>('generate_squash_message', {
+    worktreeId,
+    shas,
+    customPrompt: options?.customPrompt ?? null,
+    model: options?.model ?? null,
+    customProfileName: options?.customProfileName ?? null,
+    reasoningEffort: options?.reasoningEffort ?? null,
+  })
+}
+
 /**
  * Get local branches for a repository by path.
  */
@@ -544,6 +619,102 @@ export async function revertFile(
   return invoke('revert_file', { worktreePath, filePath, fileStatus })
 }
 
+/**
+ * Stage a file, or only the selected hunks/lines of its unstaged changes.
+ * Indices refer to the "unstaged" diff from `getGitDiff`.
+ */
+export async function stageChanges(
+  worktreePath: string,
+  filePath: string,
+  hunks?: HunkSelection[]
+): Promise<void> {
+  if (!isTauri()) {
+    throw new Error('Staging only available in Tauri')
+  }
+  return invoke('stage_changes', {
+    worktreePath,
+    filePath,
+    hunks: hunks ?? null,
+  })
+}
+
+/**
+ * Unstage a file, or only the selected hunks/lines of its staged changes.
+ * Indices refer to the "staged" diff from `getGitDiff`.
+ */
+export async function unstageChanges(
+  worktreePath: string,
+  filePath: string,
+  hunks?: HunkSelection[]
+): Promise<void> {
+  if (!isTauri()) {
+    throw new Error('Unstaging only available in Tauri')
+  }
+  return invoke('unstage_changes', {
+    worktreePath,
+    filePath,
+    hunks: hunks ?? null,
+  })
+}
+
+/**
+ * Discard a file's unstaged changes, or only the selected hunks/lines of
+ * them. Staged changes are kept. Indices refer to the "unstaged" diff.
+ */
+export async function discardChanges(
+  worktreePath: string,
+  filePath: string,
+  hunks?: HunkSelection[]
+): Promise<void> {
+  if (!isTauri()) {
+    throw new Error('Discarding changes only available in Tauri')
+  }
+  return invoke('discard_changes', {
+    worktreePath,
+    filePath,
+    hunks: hunks ?? null,
+  })
+}
+
+/**
+ * Ask the AI to group the worktree's uncommitted hunks into logical commits.
+ * The plan can be adjusted (messages, hunk assignment, order) before applying.
+ */
+export async function proposeCommitSplit(
+  worktreePath: string,
+  options?: {
+    customPrompt?: string
+    model?: string
+    customProfileName?: string
+    reasoningEffort?: string
+  }
+): Promise<CommitSplitPlan> {
+  if (!isTauri()) {
+    throw new Error('Commit splitting only available in Tauri')
+  }
+  return invoke<CommitSplitPlan>('propose_commit_split', {
+    worktreePath,
+    customPrompt: options?.customPrompt ?? null,
+    model: options?.model ?? null,
+    customProfileName: options?.customProfileName ?? null,
+    reasoningEffort: options?.reasoningEffort ?? null,
+  })
+}
+
+/**
+ * Create the commits of a split plan in order. Anything staged is unstaged
+ * first. Returns the created commit hashes.
+ */
+export async function applyCommitSplit(
+  worktreePath: string,
+  plan: CommitSplitPlan
+): Promise<string[]> {
+  if (!isTauri()) {
+    throw new Error('Commit splitting only available in Tauri')
+  }
+  return invoke<string[]>('apply_commit_split', { worktreePath, plan })
+}
+
 // ============================================================================
 // Query Keys
 // ============================================================================
@@ -1,5 +1,12 @@
-import { useQuery } from '@tanstack/react-query'
-import { invoke } from '@/lib/transport'
+import { useEffect } from 'react'
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query'
+import { toast } from 'sonner'
+import {
+  invoke,
+  listen,
+  type UnlistenFn,
+  useWsConnectionStatus,
+} from '@/lib/transport'
 import { logger } from '@/lib/logger'
 import type {
   GitHubIssue,
@@ -15,7 +22,14 @@ import type {
   LoadedAdvisoryContext,
   AttachedSavedContext,
   WorkflowRunsResult,
+  WorkflowRun,
+  WorkflowRunEvent,
+  Workflow,
+  WorkflowRunDetails,
+  LogMatch,
+  FailureSnippet,
 } from '@/types/github'
+import type { ForgeInfo } from '@/types/projects'
 import { isTauri } from './projects'
 
 /**
@@ -72,6 +86,22 @@ export const githubQueryKeys = {
       projectPath,
       branch ?? '',
     ] as const,
+  workflows: (projectPath: string) =>
+    [...githubQueryKeys.all, 'workflows', projectPath] as const,
+  workflowRunDetails: (projectPath: string, runId: number) =>
+    [
+      ...githubQueryKeys.all,
+      'workflow-run-details',
+      projectPath,
+      runId,
+    ] as const,
+  workflowFailureSnippets: (projectPath: string, runId: number) =>
+    [
+      ...githubQueryKeys.all,
+      'workflow-failure-snippets',
+      projectPath,
+      runId,
+    ] as const,
   securityAlerts: (projectPath: string, state: string) =>
     [...githubQueryKeys.all, 'security-alerts', projectPath, state] as const,
   securityAlert: (projectPath: string, alertNumber: number) =>
@@ -84,6 +114,24 @@ export const githubQueryKeys = {
     [...githubQueryKeys.all, 'advisory', projectPath, ghsaId] as const,
   loadedAdvisoryContexts: (sessionId: string) =>
     [...githubQueryKeys.all, 'loaded-advisory-contexts', sessionId] as const,
+  forge: (projectPath: string) =>
+    [...githubQueryKeys.all, 'forge', projectPath] as const,
+}
+
+/**
+ * Hook to get the forge hosting a project (GitHub, GitLab), detected from its
+ * origin remote. Issues, PRs and workflow runs come from this forge.
+ */
+export function useProjectForge(projectPath: string | null) {
+  return useQuery({
+    queryKey: githubQueryKeys.forge(projectPath ?? ''),
+    queryFn: async (): Promise<ForgeInfo | null> => {
+      if (!isTauri() || !projectPath) return null
+      return invoke<ForgeInfo | null>('get_project_forge', { projectPath })
+    },
+    enabled: !!projectPath,
+    staleTime: 1000 * 60 * 10,
+  })
 }
 
 /**
@@ -1220,6 +1268,261 @@ export function useWorkflowRuns(
   })
 }
 
+/**
+ * Hook to get a workflow run with its jobs, steps, annotations and artifacts
+ */
+export function useWorkflowRunDetails(
+  projectPath: string | null,
+  runId: number | null
+) {
+  return useQuery({
+    queryKey: githubQueryKeys.workflowRunDetails(projectPath ?? '', runId ?? 0),
+    queryFn: async (): Promise<WorkflowRunDetails | null> => {
+      if (!isTauri() || !projectPath || !runId) return null
+      return invoke<WorkflowRunDetails>('get_workflow_run_details', {
+        projectPath,
+        runId,
+      })
+    },
+    enabled: !!projectPath && !!runId,
+    staleTime: 1000 * 30,
+    retry: 1,
+  })
+}
+
+/**
+ * Hook to get log excerpts around the errors of each failed job of a run
+ */
+export function useWorkflowFailureSnippets(
+  projectPath: string | null,
+  runId: number | null
+) {
+  return useQuery({
+    queryKey: githubQueryKeys.workflowFailureSnippets(
+      projectPath ?? '',
+      runId ?? 0
+    ),
+    queryFn: async (): Promise<FailureSnippet[]> => {
+      if (!isTauri() || !projectPath || !runId) return []
+      return invoke<FailureSnippet[]>('get_workflow_failure_snippets', {
+        projectPath,
+        runId,
+      })
+    },
+    enabled: !!projectPath && !!runId,
+    staleTime: 1000 * 60 * 10, // Logs of finished jobs don't change
+    retry: 1,
+  })
+}
+
+/**
+ * Get the full log of a workflow job (cached by the backend)
+ */
+export async function getWorkflowJobLog(
+  projectPath: string,
+  jobId: number
+): Promise<string> {
+  return invoke<string>('get_workflow_job_log', { projectPath, jobId })
+}
+
+/**
+ * Search a workflow job's log with a regex (context lines around each match)
+ */
+export async function searchWorkflowJobLog(
+  projectPath: string,
+  jobId: number,
+  pattern: string,
+  options?: { contextLines?: number; ignoreCase?: boolean }
+): Promise<LogMatch[]> {
+  return invoke<LogMatch[]>('search_workflow_job_log', {
+    projectPath,
+    jobId,
+    pattern,
+    contextLines: options?.contextLines,
+    ignoreCase: options?.ignoreCase,
+  })
+}
+
+/**
+ * Get a markdown summary of why a run failed, for the {failureContext}
+ * variable of the investigate-workflow-run prompt. Returns a note instead of
+ * throwing, so the investigation can still start without it.
+ */
+export async function getWorkflowFailureContext(
+  projectPath: string | null | undefined,
+  runId: string | number
+): Promise<string> {
+  const id = Number(runId)
+  if (!projectPath || !id) return 'Failure details unavailable.'
+  try {
+    return await invoke<string>('get_workflow_failure_context', {
+      projectPath,
+      runId: id,
+    })
+  } catch (error) {
+    logger.warn('Failed to load workflow failure context', { error, runId })
+    return 'Failure details unavailable.'
+  }
+}
+
+/**
+ * Hook to list the workflows of a repository with their dispatch inputs
+ */
+export function useWorkflows(projectPath: string | null) {
+  return useQuery({
+    queryKey: githubQueryKeys.workflows(projectPath ?? ''),
+    queryFn: async (): Promise<Workflow[]> => {
+      if (!isTauri() || !projectPath) return []
+      return invoke<Workflow[]>('list_workflows', { projectPath })
+    },
+    enabled: !!projectPath,
+    staleTime: 1000 * 60 * 5,
+    retry: 1,
+  })
+}
+
+/**
+ * Hook to dispatch a workflow_dispatch workflow on a branch
+ * (default: the current branch of projectPath)
+ */
+export function useDispatchWorkflow() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({
+      projectPath,
+      workflow,
+      branch,
+      inputs,
+    }: {
+      projectPath: string
+      workflow: string
+      branch?: string
+      inputs?: Record<string, string>
+    }) => {
+      return invoke<WorkflowRun | null>('dispatch_workflow', {
+        projectPath,
+        workflow,
+        branch,
+        inputs,
+      })
+    },
+    onSuccess: (run, { projectPath, workflow }) => {
+      queryClient.invalidateQueries({
+        queryKey: [...githubQueryKeys.all, 'workflow-runs', projectPath],
+      })
+      toast.success(`Started ${run?.workflowName ?? workflow}`, {
+        description: run
+          ? undefined
+          : 'The run will appear in the list shortly.',
+      })
+    },
+    onError: error => {
+      toast.error('Failed to start workflow', { description: String(error) })
+    },
+  })
+}
+
+/**
+ * Hook to re-run a workflow run (failed jobs only unless failedOnly is false)
+ */
+export function useRerunWorkflowRun() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({
+      projectPath,
+      runId,
+      failedOnly,
+    }: {
+      projectPath: string
+      runId: number
+      failedOnly?: boolean
+    }) => {
+      return invoke<WorkflowRun>('rerun_workflow_run', {
+        projectPath,
+        runId,
+        failedOnly,
+      })
+    },
+    onSuccess: (_, { projectPath }) => {
+      queryClient.invalidateQueries({
+        queryKey: [...githubQueryKeys.all, 'workflow-runs', projectPath],
+      })
+    },
+    onError: error => {
+      toast.error('Failed to re-run workflow', { description: String(error) })
+    },
+  })
+}
+
+/**
+ * Hook to cancel a queued or in-progress workflow run
+ */
+export function useCancelWorkflowRun() {
+  return useMutation({
+    mutationFn: async ({
+      projectPath,
+      runId,
+    }: {
+      projectPath: string
+      runId: number
+    }) => {
+      await invoke('cancel_workflow_run', { projectPath, runId })
+    },
+    onError: error => {
+      toast.error('Failed to cancel workflow run', {
+        description: String(error),
+      })
+    },
+  })
+}
+
+/**
+ * Hook to listen for live updates of workflow runs started from Jean
+ * ('workflow-run:update') and refresh the cached run lists and details.
+ */
+export function useWorkflowRunEvents(
+  onUpdate?: (event: WorkflowRunEvent) => void
+) {
+  const queryClient = useQueryClient()
+  const wsConnected = useWsConnectionStatus()
+
+  useEffect(() => {
+    if (!isTauri()) return
+
+    const unlistenPromise: Promise<UnlistenFn> = listen<WorkflowRunEvent>(
+      'workflow-run:update',
+      event => {
+        const { projectPath, run } = event.payload
+        queryClient.setQueriesData<WorkflowRunsResult>(
+          {
+            queryKey: [...githubQueryKeys.all, 'workflow-runs', projectPath],
+          },
+          old =>
+            old && {
+              ...old,
+              runs: old.runs.some(r => r.databaseId === run.databaseId)
+                ? old.runs.map(r => (r.databaseId === run.databaseId ? run : r))
+                : [run, ...old.runs],
+            }
+        )
+        queryClient.invalidateQueries({
+          queryKey: githubQueryKeys.workflowRunDetails(
+            projectPath,
+            run.databaseId
+          ),
+        })
+        onUpdate?.(event.payload)
+      }
+    )
+
+    return () => {
+      unlistenPromise.then(unlisten => unlisten())
+    }
+  }, [queryClient, onUpdate, wsConnected])
+}
+
 // =============================================================================
 // Attached Saved Context Hooks and Functions
 // =============================================================================
@@ -0,0 +1,219 @@
+import { useQuery } from '@tanstack/react-query'
+import { invoke } from '@/lib/transport'
+import { logger } from '@/lib/logger'
+import type {
+  JiraIssue,
+  JiraIssueDetail,
+  JiraIssueListResult,
+  LoadedJiraIssueContext,
+} from '@/types/jira'
+import { isTauri, useProjects } from './projects'
+
+function useHasJiraAccess(projectId: string | null): boolean {
+  const { data: projects } = useProjects()
+  const project = projects?.find(p => p.id === projectId)
+
+  return !!project?.jira_url?.trim() && !!project?.jira_api_token?.trim()
+}
+
+/**
+ * Check if an error is a Jira site/credentials configuration error.
+ */
+export function isJiraAuthError(error: unknown): boolean {
+  if (!error) return false
+  const message = error instanceof Error ? error.message : String(error)
+  const lower = message.toLowerCase()
+
+  return (
+    lower.includes('no jira site configured') ||
+    lower.includes('no jira api token') ||
+    lower.includes('jira credentials are invalid')
+  )
+}
+
+// Query keys for Jira
+export const jiraQueryKeys = {
+  all: ['jira'] as const,
+  issues: (projectId: string) =>
+    [...jiraQueryKeys.all, 'issues', projectId] as const,
+  issueSearch: (projectId: string, query: string) =>
+    [...jiraQueryKeys.all, 'issue-search', projectId, query] as const,
+  issue: (projectId: string, key: string) =>
+    [...jiraQueryKeys.all, 'issue', projectId, key] as const,
+  loadedContexts: (sessionId: string) =>
+    [...jiraQueryKeys.all, 'loaded-contexts', sessionId] as const,
+}
+
+/**
+ * Hook to list unresolved Jira issues for a project (narrowed by its JQL filter)
+ */
+export function useJiraIssues(
+  projectId: string | null,
+  options?: { enabled?: boolean }
+) {
+  const hasJiraAccess = useHasJiraAccess(projectId)
+
+  return useQuery({
+    queryKey: jiraQueryKeys.issues(projectId ?? ''),
+    queryFn: async (): Promise<JiraIssueListResult> => {
+      if (!isTauri() || !projectId || !hasJiraAccess) {
+        return { issues: [] }
+      }
+
+      try {
+        logger.debug('Fetching Jira issues', { projectId })
+        const result = await invoke<JiraIssueListResult>('list_jira_issues', {
+          projectId,
+        })
+        logger.info('Jira issues loaded', { count: result.issues.length })
+        return result
+      } catch (error) {
+        logger.error('Failed to load Jira issues', { error, projectId })
+        throw error
+      }
+    },
+    enabled: (options?.enabled ?? true) && !!projectId && hasJiraAccess,
+    staleTime: 1000 * 60 * 2,
+    gcTime: 1000 * 60 * 10,
+    retry: 1,
+  })
+}
+
+/**
+ * Hook to search Jira issues by key, free text or raw JQL
+ */
+export function useSearchJiraIssues(
+  projectId: string | null,
+  query: string,
+  options?: { enabled?: boolean }
+) {
+  const hasJiraAccess = useHasJiraAccess(projectId)
+
+  return useQuery({
+    queryKey: jiraQueryKeys.issueSearch(projectId ?? '', query),
+    queryFn: async (): Promise<JiraIssue[]> => {
+      if (!isTauri() || !projectId || !query.trim() || !hasJiraAccess) {
+        return []
+      }
+
+      try {
+        logger.debug('Searching Jira issues', { projectId, query })
+        const result = await invoke<JiraIssue[]>('search_jira_issues', {
+          projectId,
+          query,
+        })
+        logger.info('Jira issue search returned', { count: result.length })
+        return result
+      } catch (error) {
+        logger.error('Failed to search Jira issues', { error, projectId })
+        throw error
+      }
+    },
+    enabled:
+      (options?.enabled ?? true) &&
+      !!projectId &&
+      !!query.trim() &&
+      hasJiraAccess,
+    staleTime: 1000 * 60 * 1,
+    gcTime: 1000 * 60 * 5,
+    retry: 1,
+  })
+}
+
+/**
+ * Hook to fetch a single Jira issue with comments and subtasks
+ */
+export function useJiraIssue(
+  projectId: string | null,
+  issueKey: string | null,
+  options?: { enabled?: boolean }
+) {
+  const hasJiraAccess = useHasJiraAccess(projectId)
+
+  return useQuery({
+    queryKey: jiraQueryKeys.issue(projectId ?? '', issueKey ?? ''),
+    queryFn: async (): Promise<JiraIssueDetail | null> => {
+      if (!isTauri() || !projectId || !issueKey || !hasJiraAccess) {
+        return null
+      }
+
+      try {
+        logger.debug('Fetching Jira issue', { projectId, issueKey })
+        return await invoke<JiraIssueDetail>('get_jira_issue', {
+          projectId,
+          issueKey,
+        })
+      } catch (error) {
+        logger.error('Failed to load Jira issue', { error, issueKey })
+        throw error
+      }
+    },
+    enabled:
+      (options?.enabled ?? true) && !!projectId && !!issueKey && hasJiraAccess,
+    staleTime: 1000 * 60 * 2,
+    gcTime: 1000 * 60 * 10,
+    retry: 1,
+  })
+}
+
+/**
+ * Hook to list loaded Jira issue contexts for a session
+ */
+export function useLoadedJiraIssueContexts(
+  sessionId: string | null,
+  worktreeId: string | null,
+  projectId: string | null,
+  options?: { enabled?: boolean }
+) {
+  const hasJiraAccess = useHasJiraAccess(projectId)
+
+  return useQuery({
+    queryKey: jiraQueryKeys.loadedContexts(sessionId ?? ''),
+    queryFn: async (): Promise<LoadedJiraIssueContext[]> => {
+      if (!isTauri() || !sessionId || !projectId || !hasJiraAccess) {
+        return []
+      }
+
+      try {
+        return await invoke<LoadedJiraIssueContext[]>(
+          'list_loaded_jira_issue_contexts',
+          { sessionId, worktreeId, projectId }
+        )
+      } catch (error) {
+        logger.error('Failed to load Jira contexts', { error, sessionId })
+        return []
+      }
+    },
+    enabled:
+      (options?.enabled ?? true) &&
+      !!sessionId &&
+      !!projectId &&
+      hasJiraAccess,
+    staleTime: 1000 * 60 * 2,
+    gcTime: 1000 * 60 * 10,
+    retry: 1,
+  })
+}
+
+/**
+ * Filter Jira issues by search query (client-side)
+ */
+export function filterJiraIssues(
+  issues: JiraIssue[],
+  query: string
+): JiraIssue[] {
+  if (!query.trim()) return issues
+
+  const lowerQuery = query.toLowerCase().trim()
+
+  return issues.filter(issue => {
+    // Match by key (e.g., "ENG-123")
+    if (issue.key.toLowerCase().includes(lowerQuery)) return true
+    // Match by summary
+    if (issue.summary.toLowerCase().includes(lowerQuery)) return true
+    // Match by description
+    if (issue.description?.toLowerCase().includes(lowerQuery)) return true
+
+    return false
+  })
+}
@@ -0,0 +1,145 @@
+/**
+ * Pull request lifecycle service
+ *
+ * Draft/ready, reviewers, labels, assignees, milestone, auto-merge and closing
+ * for a worktree's linked PR. Every mutation returns the refreshed PR details,
+ * which are written back into the query cache.
+ */
+
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query'
+import { toast } from 'sonner'
+
+import { invoke } from '@/lib/transport'
+import { isTauri, projectsQueryKeys } from '@/services/projects'
+import type {
+  PrDetails,
+  PrMergeMethod,
+  PrMetadataChanges,
+} from '@/types/projects'
+
+// ============================================================================
+// Query Keys
+// ============================================================================
+
+export const prLifecycleQueryKeys = {
+  all: ['pr-lifecycle'] as const,
+  details: (worktreeId: string) =>
+    [...prLifecycleQueryKeys.all, 'details', worktreeId] as const,
+}
+
+// ============================================================================
+// Hooks
+// ============================================================================
+
+/**
+ * Hook to get draft state, reviewers, labels, assignees, milestone and
+ * auto-merge of the worktree's linked PR
+ */
+export function usePrDetails(worktreeId: string | null, enabled = true) {
+  return useQuery({
+    queryKey: prLifecycleQueryKeys.details(worktreeId ?? ''),
+    queryFn: async (): Promise<PrDetails | null> => {
+      if (!isTauri() || !worktreeId) return null
+      return invoke<PrDetails>('get_pr_details', { worktreeId })
+    },
+    enabled: enabled && !!worktreeId,
+    staleTime: 1000 * 30,
+    retry: 1,
+  })
+}
+
+/**
+ * Shared mutation wiring: cache the returned details, toast on error
+ */
+function usePrLifecycleMutation<TVars extends { worktreeId: string }>(
+  command: string,
+  errorTitle: string
+) {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (vars: TVars) => invoke<PrDetails>(command, vars),
+    onSuccess: (details, { worktreeId }) => {
+      queryClient.setQueryData(
+        prLifecycleQueryKeys.details(worktreeId),
+        details
+      )
+    },
+    onError: error => {
+      toast.error(errorTitle, { description: String(error) })
+    },
+  })
+}
+
+/**
+ * Hook to mark the linked PR as ready for review (draft: false) or convert it
+ * back to a draft (draft: true)
+ */
+export function useSetPrDraft() {
+  return usePrLifecycleMutation<{ worktreeId: string; draft: boolean }>(
+    'set_pr_draft',
+    'Failed to update draft state'
+  )
+}
+
+/**
+ * Hook to add/remove reviewers, labels and assignees or change the milestone
+ */
+export function useEditPrMetadata() {
+  return usePrLifecycleMutation<{
+    worktreeId: string
+    changes: PrMetadataChanges
+  }>('edit_pr_metadata', 'Failed to update pull request')
+}
+
+/**
+ * Hook to enable or disable auto-merge. The method defaults to the project's
+ * configured auto-merge method, then "merge".
+ */
+export function useSetPrAutoMerge() {
+  return usePrLifecycleMutation<{
+    worktreeId: string
+    enabled: boolean
+    method?: PrMergeMethod
+  }>('set_pr_auto_merge', 'Failed to update auto-merge')
+}
+
+/**
+ * Hook to close the linked PR without merging
+ */
+export function useClosePr() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({
+      worktreeId,
+      comment,
+    }: {
+      worktreeId: string
+      comment?: string
+    }) => invoke<PrDetails>('close_pr', { worktreeId, comment }),
+    onSuccess: (details, { worktreeId }) => {
+      queryClient.setQueryData(
+        prLifecycleQueryKeys.details(worktreeId),
+        details
+      )
+      queryClient.invalidateQueries({ queryKey: projectsQueryKeys.all })
+      toast.success(`Closed PR #${details.number}`)
+    },
+    onError: error => {
+      toast.error('Failed to close pull request', {
+        description: String(error),
+      })
+    },
+  })
+}
+
+/**
+ * Hook to apply the project's PR defaults to the linked PR
+ */
+export function useApplyPrDefaults() {
+  return usePrLifecycleMutation<{ worktreeId: string }>(
+    'apply_pr_defaults',
+    'Failed to apply PR defaults'
+  )
+}
@@ -1,4 +1,4 @@
-import { useEffect } from 'react'
+import { useEffect, useState } from 'react'
 import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query'
 import { invoke, useWsConnectionStatus, setAppDataDir } from '@/lib/transport'
 import { listen, type UnlistenFn } from '@/lib/transport'
@@ -20,6 +20,14 @@ import type {
   WorktreePermanentlyDeletedEvent,
   WorktreePathExistsEvent,
   WorktreeBranchExistsEvent,
+  SandboxConfig,
+  PrDefaults,
+  ForgeKind,
+  ScriptOutputEvent,
+  ScriptFinishedEvent,
+  RestackOutcome,
+  ReviewResponse,
+  PublishedReview,
 } from '@/types/projects'
 import { useProjectsStore } from '@/store/projects-store'
 import { useChatStore } from '@/store/chat-store'
@@ -27,7 +35,9 @@ import { useUIStore } from '@/store/ui-store'
 import { getFileManagerName } from '@/lib/platform'
 
 import type { AppPreferences } from '@/types/preferences'
+import type { PermissionPolicy } from '@/types/chat'
 import type { AdvisoryContext } from '@/types/github'
+import type { JiraIssueContext } from '@/types/jira'
 import { hasBackend } from '@/lib/environment'
 import { openExternal, preOpenWindow } from '@/lib/platform'
 
@@ -456,7 +466,9 @@ export function useCreateWorktree() {
       securityContext,
       advisoryContext,
       linearContext,
+      jiraContext,
       customName,
+      parentWorktreeId,
       background: _background,
     }: {
       projectId: string
@@ -516,8 +528,12 @@ export function useCreateWorktree() {
           createdAt: string
         }[]
       }
+      /** Jira issue context to pass when creating a worktree from a Jira issue */
+      jiraContext?: JiraIssueContext
       /** Custom worktree name (used when retrying after path conflict) */
       customName?: string
+      /** Stack the new worktree on this worktree (branches off its branch) */
+      parentWorktreeId?: string
       /** When true, skip auto-navigation (CMD+Click from new session modal) */
       background?: boolean
     }): Promise<Worktree> => {
@@ -532,7 +548,9 @@ export function useCreateWorktree() {
         prNumber: prContext?.number,
         securityAlertNumber: securityContext?.number,
         advisoryGhsaId: advisoryContext?.ghsaId,
+        jiraIssueKey: jiraContext?.key,
         customName,
+        parentWorktreeId,
       })
       const worktree = await invoke<Worktree>('create_worktree', {
         projectId,
@@ -542,7 +560,9 @@ export function useCreateWorktree() {
         securityContext,
         advisoryContext,
         linearContext,
+        jiraContext,
         customName,
+        parentWorktreeId,
       })
       return worktree
     },
@@ -1895,6 +1915,21 @@ export function useOpenBranchOnGitHub() {
 export interface GitHubRemote {
   name: string
   url: string
+  forge: ForgeKind
+}
+
+/**
+ * Web page of a branch on a remote (each forge lays out its URLs differently)
+ */
+export function remoteBranchUrl(remote: GitHubRemote, branch: string): string {
+  switch (remote.forge) {
+    case 'gitlab':
+      return `${remote.url}/-/tree/${branch}`
+    case 'gitea':
+      return `${remote.url}/src/branch/${branch}`
+    default:
+      return `${remote.url}/tree/${branch}`
+  }
 }
 
 /**
@@ -2036,11 +2071,121 @@ export function useOpenWorktreeInEditor() {
  * Jean.json config shape
  */
 export interface JeanConfig {
+  $schema?: string
   scripts: {
     setup: string | null
     teardown: string | null
     run: string | null
+    /** Additional named scripts (e.g. "dev", "test", "lint") */
+    [name: string]: ScriptEntry | null
+  }
+  /** Environment variables for all scripts and hooks */
+  env?: Record<string, string>
+  hooks?: JeanHooks
+  permissions?: PermissionPolicy
+  container?: ContainerConfig
+  ports?: PortsConfig
+  services?: Record<string, ServiceConfig>
+  /** Timeouts in seconds (defaults: setup 1800, teardown 600, hooks 600) */
+  timeouts?: {
+    setup?: number
+    teardown?: number
+    hooks?: number
+  }
+  /**
+   * Untracked files and caches copied from the project root into new worktrees,
+   * e.g. [".env*", { "path": "node_modules", "mode": "reflink" }]
+   */
+  copy?: CopyEntry[]
+}
+
+/**
+ * A jean.json `copy` entry: a glob relative to the project root, or a glob with
+ * a copy method (reflinks and hardlinks fall back to a plain copy)
+ */
+export type CopyEntry =
+  | string
+  | { path: string; mode?: 'reflink' | 'hardlink' | 'copy' }
+
+/**
+ * A supervised long-running process from jean.json `services`
+ */
+export interface ServiceConfig {
+  command: string
+  env?: Record<string, string>
+  /** Working directory relative to the worktree */
+  cwd?: string
+  /** Readiness check: an HTTP URL (non-5xx answer) or a regex on the output */
+  ready?: {
+    url?: string
+    log?: string
+    /** Seconds before the service is reported unhealthy (default: 60) */
+    timeout_secs?: number
   }
+  /** Default: on_failure */
+  restart?: 'on_failure' | 'always' | 'never'
+  /** Give up after this many consecutive restarts (default: 5) */
+  max_restarts?: number
+}
+
+/**
+ * A jean.json script: a shell command, or a command with env and working directory
+ */
+export type ScriptEntry =
+  | string
+  | {
+      command: string
+      env?: Record<string, string>
+      /** Working directory relative to the worktree */
+      cwd?: string
+    }
+
+/**
+ * Lifecycle hooks section of jean.json
+ */
+export interface JeanHooks {
+  /** After a worktree is created from a pull request (after setup) */
+  post_checkout_pr?: ScriptEntry
+  /** Before a worktree is archived; failure blocks archiving */
+  pre_archive?: ScriptEntry
+  /** In the project root after a worktree is merged into the base branch */
+  post_merge?: ScriptEntry
+  /** Before Jean commits changes; failure blocks the commit */
+  pre_commit?: ScriptEntry
+}
+
+/**
+ * A runnable jean.json script with its env and working directory applied
+ */
+export interface RunScript {
+  name: string
+  command: string
+}
+
+/**
+ * Container section of jean.json (one container per worktree)
+ */
+export interface ContainerConfig {
+  /** Image to run, e.g. "node:22" */
+  image?: string
+  /** Dockerfile to build instead of `image` (relative to the worktree) */
+  dockerfile?: string
+  /** "docker" or "podman" (default: whichever is installed) */
+  runtime?: 'docker' | 'podman'
+  /** Extra arguments for `run`, e.g. ["-p", "3000:3000"] */
+  run_args?: string[]
+  /** Run Claude sessions inside the container (default: true) */
+  agent?: boolean
+}
+
+/**
+ * Ports section of jean.json (a unique port range per worktree)
+ */
+export interface PortsConfig {
+  /** Ports per worktree, exposed as JEAN_PORT, JEAN_PORT_1, ... (default: 1) */
+  count?: number
+  /** `*.jean.tmpl` files rendered during setup (`.env.jean.tmpl` always is) */
+  templates?: string[]
 }
 
 /**
@@ -2089,6 +2234,193 @@ export function useSaveJeanConfig() {
   })
 }
 
+/**
+ * Hook to re-run the jean.json setup script on an existing worktree
+ */
+export function useRerunSetupScript() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({ worktreeId }: { worktreeId: string }) => {
+      return invoke<Worktree>('rerun_setup_script', { worktreeId })
+    },
+    onSuccess: worktree => {
+      queryClient.invalidateQueries({
+        queryKey: projectsQueryKeys.worktrees(worktree.project_id),
+      })
+      queryClient.invalidateQueries({
+        queryKey: [...projectsQueryKeys.all, 'worktree', worktree.id],
+      })
+      if (worktree.setup_success === false) {
+        toast.error('Setup script failed')
+      } else {
+        toast.success('Setup script finished')
+      }
+    },
+    onError: error => {
+      toast.error('Failed to run setup script', {
+        description: String(error),
+      })
+    },
+  })
+}
+
+/**
+ * Hook to restack a worktree onto its parent and rebase every worktree
+ * stacked above it
+ */
+export function useRestackWorktree() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({
+      worktreeId,
+      commitMessage,
+    }: {
+      worktreeId: string
+      projectId: string
+      commitMessage?: string
+    }) => {
+      return invoke<RestackOutcome[]>('restack_worktree', {
+        worktreeId,
+        commitMessage,
+      })
+    },
+    onSuccess: (outcomes, { projectId }) => {
+      queryClient.invalidateQueries({
+        queryKey: projectsQueryKeys.worktrees(projectId),
+      })
+      const failed = outcomes.filter(o => !o.success)
+      if (failed.length > 0) {
+        const restacked = outcomes.length - failed.length
+        toast.error(`Restacked ${restacked} of ${outcomes.length} branches`, {
+          description: failed.map(o => `${o.branch}: ${o.message}`).join('\n'),
+        })
+      } else {
+        toast.success(`${outcomes.length} branch(es) restacked`)
+      }
+    },
+    onError: error => {
+      toast.error('Failed to restack', { description: String(error) })
+    },
+  })
+}
+
+/**
+ * Publish selected AI review findings as a review on the worktree's PR.
+ * Without `submit` the review stays pending on GitHub.
+ */
+export function usePublishReviewToPr() {
+  return useMutation({
+    mutationFn: async ({
+      worktreeId,
+      review,
+      submit,
+    }: {
+      worktreeId: string
+      review: ReviewResponse
+      submit?: boolean
+    }) => {
+      return invoke<PublishedReview>('publish_review_to_pr', {
+        worktreeId,
+        review,
+        submit,
+      })
+    },
+    onSuccess: result => {
+      const where =
+        result.bodyCount > 0
+          ? `${result.inlineCount} inline, ${result.bodyCount} in the review body`
+          : `${result.inlineCount} inline comment(s)`
+      toast.success(
+        result.state === 'PENDING'
+          ? 'Pending review created on GitHub'
+          : 'Review published on GitHub',
+        {
+          description: result.downgraded
+            ? `${where}. Posted as a comment since you can't approve or request changes on your own PR.`
+            : where,
+        }
+      )
+    },
+    onError: error => {
+      toast.error('Failed to publish review', { description: String(error) })
+    },
+  })
+}
+
+/**
+ * Hook to cancel a setup/teardown script or hook running in a worktree
+ * (every running one when no kind is given)
+ */
+export function useCancelJeanScript() {
+  return useMutation({
+    mutationFn: async ({
+      worktreePath,
+      kind,
+    }: {
+      worktreePath: string
+      kind?: string
+    }) => {
+      return invoke<boolean>('cancel_jean_script', { worktreePath, kind })
+    },
+    onError: error => {
+      toast.error('Failed to cancel script', { description: String(error) })
+    },
+  })
+}
+
+/**
+ * Hook to follow the output of jean.json scripts running in a worktree.
+ *
+ * Lines accumulate while a script runs and reset when the next one starts.
+ */
+export function useScriptOutput(worktreePath: string | null) {
+  const [state, setState] = useState<{
+    kind: string | null
+    lines: string[]
+    running: boolean
+    finished: ScriptFinishedEvent | null
+  }>({ kind: null, lines: [], running: false, finished: null })
+  const wsConnected = useWsConnectionStatus()
+
+  useEffect(() => {
+    if (!isTauri() || !worktreePath) return
+
+    const unlistenPromises: Promise<UnlistenFn>[] = [
+      listen<ScriptOutputEvent>('script:output', event => {
+        const { worktree_path, kind, line } = event.payload
+        if (worktree_path !== worktreePath) return
+        setState(prev =>
+          prev.running && prev.kind === kind
+            ? { ...prev, lines: [...prev.lines, line] }
+            : { kind, lines: [line], running: true, finished: null }
+        )
+      }),
+      listen<ScriptFinishedEvent>('script:finished', event => {
+        if (event.payload.worktree_path !== worktreePath) return
+        setState(prev => ({
+          ...prev,
+          kind: event.payload.kind,
+          running: false,
+          finished: event.payload,
+        }))
+      }),
+    ]
+
+    const unlistens: UnlistenFn[] = []
+    Promise.all(unlistenPromises).then(fns => {
+      unlistens.push(...fns)
+    })
+
+    return () => {
+      unlistens.forEach(unlisten => unlisten())
+    }
+  }, [worktreePath, wsConnected])
+
+  return state
+}
+
 /**
  * Hook to get the run script from jean.json for a worktree
  */
@@ -2110,6 +2442,21 @@ export function useRunScript(worktreePath: string | null) {
   })
 }
 
+/**
+ * Hook to get all runnable jean.json scripts for a worktree (`run` first, then named scripts)
+ */
+export function useRunScripts(worktreePath: string | null) {
+  return useQuery<RunScript[]>({
+    queryKey: ['run-script', worktreePath, 'all'],
+    queryFn: async () => {
+      if (!isTauri() || !worktreePath) return []
+      return invoke<RunScript[]>('get_run_scripts', { worktreePath })
+    },
+    enabled: !!worktreePath,
+    staleTime: 30_000,
+  })
+}
+
 /**
  * Hook to commit changes in a worktree
  */
@@ -2376,6 +2723,16 @@ export function useUpdateProjectSettings() {
       worktreesDir,
       linearApiKey,
       linearTeamId,
+      permissionPolicy,
+      sandbox,
+      prDefaults,
+      forge,
+      forgeToken,
+      forgeUrl,
+      jiraUrl,
+      jiraEmail,
+      jiraApiToken,
+      jiraJql,
     }: {
       projectId: string
       defaultBranch?: string
@@ -2387,6 +2744,17 @@ export function useUpdateProjectSettings() {
       worktreesDir?: string
       linearApiKey?: string
       linearTeamId?: string
+      permissionPolicy?: PermissionPolicy
+      sandbox?: SandboxConfig
+      prDefaults?: PrDefaults
+      /** Forge kind, or '__none__' to detect it from the remote */
+      forge?: ForgeKind | '__none__'
+      forgeToken?: string
+      forgeUrl?: string
+      jiraUrl?: string
+      jiraEmail?: string
+      jiraApiToken?: string
+      jiraJql?: string
     }): Promise<Project> => {
       if (!isTauri()) {
         throw new Error('Not in Tauri context')
@@ -2404,6 +2772,16 @@ export function useUpdateProjectSettings() {
         worktreesDir,
         linearApiKey,
         linearTeamId,
+        permissionPolicy,
+        sandbox,
+        prDefaults,
+        forge,
+        forgeToken,
+        forgeUrl,
+        jiraUrl,
+        jiraEmail,
+        jiraApiToken,
+        jiraJql,
       })
       logger.info('Project settings updated', { project })
       return project
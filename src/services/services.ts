@@ -0,0 +1,134 @@
+/**
+ * Service supervisor
+ *
+ * Hooks for the long-running processes declared in jean.json `services`.
+ * Status changes arrive as `service:status` events and are merged into the
+ * query cache.
+ */
+
+import { useEffect } from 'react'
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query'
+import { toast } from 'sonner'
+
+import {
+  invoke,
+  listen,
+  type UnlistenFn,
+  useWsConnectionStatus,
+} from '@/lib/transport'
+import { isTauri } from '@/services/projects'
+import type { ServiceInfo } from '@/types/services'
+
+// ============================================================================
+// Query Keys
+// ============================================================================
+
+export const servicesQueryKeys = {
+  all: ['services'] as const,
+  worktree: (worktreeId: string) =>
+    [...servicesQueryKeys.all, worktreeId] as const,
+  logs: (worktreeId: string, name: string) =>
+    [...servicesQueryKeys.all, worktreeId, 'logs', name] as const,
+}
+
+// ============================================================================
+// Hooks
+// ============================================================================
+
+/**
+ * Hook to get the services of a worktree with their status
+ */
+export function useServices(worktreeId: string | null) {
+  return useQuery<ServiceInfo[]>({
+    queryKey: worktreeId
+      ? servicesQueryKeys.worktree(worktreeId)
+      : ['services', 'none'],
+    queryFn: async () => {
+      if (!isTauri() || !worktreeId) return []
+      return invoke<ServiceInfo[]>('list_services', { worktreeId })
+    },
+    enabled: !!worktreeId,
+    staleTime: 30_000,
+  })
+}
+
+/**
+ * Hook to listen for `service:status` events and keep the cache current
+ */
+export function useServiceEvents() {
+  const queryClient = useQueryClient()
+  const wsConnected = useWsConnectionStatus()
+
+  useEffect(() => {
+    if (!isTauri()) return
+
+    const unlistenPromise = listen<ServiceInfo>('service:status', event => {
+      const info = event.payload
+      queryClient.setQueryData<ServiceInfo[]>(
+        servicesQueryKeys.worktree(info.worktree_id),
+        old => {
+          if (!old) return [info]
+          return old.some(s => s.name === info.name)
+            ? old.map(s => (s.name === info.name ? info : s))
+            : [...old, info]
+        }
+      )
+    })
+
+    let unlisten: UnlistenFn | undefined
+    unlistenPromise.then(fn => {
+      unlisten = fn
+    })
+
+    return () => {
+      unlisten?.()
+    }
+  }, [queryClient, wsConnected])
+}
+
+/**
+ * Hook to get the last lines of a service's log
+ */
+export function useServiceLogs(
+  worktreeId: string | null,
+  name: string | null,
+  lines?: number
+) {
+  return useQuery<string>({
+    queryKey:
+      worktreeId && name
+        ? servicesQueryKeys.logs(worktreeId, name)
+        : ['services', 'logs', 'none'],
+    queryFn: async () => {
+      if (!isTauri() || !worktreeId || !name) return ''
+      return invoke<string>('get_service_logs', { worktreeId, name, lines })
+    },
+    enabled: !!worktreeId && !!name,
+  })
+}
+
+type ServiceAction = 'start' | 'stop' | 'restart'
+
+/**
+ * Hook to start, stop or restart a service
+ */
+export function useServiceAction() {
+  return useMutation({
+    mutationFn: async ({
+      worktreeId,
+      name,
+      action,
+    }: {
+      worktreeId: string
+      name: string
+      action: ServiceAction
+    }) => {
+      await invoke(`${action}_service`, { worktreeId, name })
+    },
+    onError: (error, { name, action }) => {
+      toast.error(`Failed to ${action} ${name}`, {
+        description: String(error),
+      })
+    },
+  })
+}
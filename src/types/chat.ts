@@ -116,6 +116,25 @@ export interface DeniedMessageContext {
   thinking_level: string
 }
 
+/** Status of a PR review thread being addressed by a session */
+export type ReviewThreadStatus =
+  | 'in_progress'
+  | 'replied'
+  | 'resolved'
+  | 'failed'
+
+/** Progress on one PR review thread, persisted on the session */
+export interface ReviewThreadProgress {
+  status: ReviewThreadStatus
+  /** Commit the reply points at */
+  commit_sha?: string | null
+  /** What changed, as reported by the run */
+  summary?: string | null
+  error?: string | null
+  /** Unix timestamp (seconds) */
+  updated_at: number
+}
+
 /**
  * A chat session within a worktree (supports multiple sessions per worktree)
  */
@@ -165,6 +184,8 @@ export interface Session {
   submitted_answers?: Record<string, QuestionAnswer[]>
   /** Finding keys that have been marked as fixed */
   fixed_findings?: string[]
+  /** Progress on addressing PR review threads: thread id -> progress */
+  review_threads?: Record<string, ReviewThreadProgress>
   /** Pending permission denials awaiting user approval */
   pending_permission_denials?: PermissionDenial[]
   /** Original message context for re-send after permission approval */
@@ -394,8 +415,15 @@ export interface PermissionDenial {
   tool_input: unknown
   /** JSON-RPC request ID (Codex only — used to respond to approval requests) */
   rpc_id?: number
+  /** Permission request ID (OpenCode only — used to answer the pending request) */
+  permission_id?: string
 }
 
+/**
+ * Decision for a live approval request (Codex JSON-RPC / OpenCode permission)
+ */
+export type LiveApprovalDecision = 'accept' | 'acceptForSession' | 'decline'
+
 /**
  * Event payload for permission denied from Rust
  * Sent when Claude CLI returns permission_denials (tools that require approval)
@@ -406,6 +434,60 @@ export interface PermissionDeniedEvent {
   denials: PermissionDenial[]
 }
 
+// ============================================================================
+// Permission Policy Types
+// ============================================================================
+
+/** Decision a permission policy rule makes for a matching tool call */
+export type PolicyAction = 'allow' | 'deny' | 'ask'
+
+/**
+ * A single permission rule. All specified matchers must match for the rule to apply.
+ */
+export interface PolicyRule {
+  action: PolicyAction
+  /** Tool name or glob (e.g., "Bash", "Edit", "mcp__*"). Omitted = any tool */
+  tool?: string
+  /** Regex matched against shell commands (e.g., "^git push") */
+  command?: string
+  /** Glob matched against file paths, relative to the worktree */
+  path?: string
+}
+
+/** Tool permission policy (project settings and/or jean.json `permissions`) */
+export interface PermissionPolicy {
+  rules: PolicyRule[]
+}
+
+/** A permission decision recorded on a run */
+export interface PermissionAuditEntry {
+  timestamp: number
+  /** Backend that requested the permission (claude, codex, opencode) */
+  backend: string
+  tool_name: string
+  /** Command or file paths the decision applied to */
+  target?: string
+  /** Decision taken (undefined = no rule matched, execution mode default applied) */
+  action?: PolicyAction
+  /** Index of the matching rule in the effective policy */
+  rule_index?: number
+}
+
+/** Permission decisions of a single run */
+export interface RunPermissionAudit {
+  run_id: string
+  user_message_id: string
+  started_at: number
+  entries: PermissionAuditEntry[]
+}
+
+/** Event payload for chat:permission_decision */
+export interface PermissionDecisionEvent {
+  session_id: string
+  worktree_id: string
+  entry: PermissionAuditEntry
+}
+
 // ============================================================================
 // AskUserQuestion Types
 // ============================================================================
@@ -0,0 +1,31 @@
+/**
+ * Types for splitting uncommitted changes into several logical commits
+ */
+
+/** A unit of change that can be assigned to a commit */
+export interface SplitHunk {
+  /** Stable id used in the plan: `path#index`, or `path` for a whole file */
+  id: string
+  path: string
+  /** Index into the file's hunks; null for files committed whole (binary, mode-only) */
+  hunkIndex: number | null
+  /** Hunk header, or the file status for whole files */
+  header: string
+  additions: number
+  deletions: number
+}
+
+/** One proposed commit */
+export interface SplitCommit {
+  message: string
+  /** Ids of the `SplitHunk`s in this commit */
+  hunkIds: string[]
+}
+
+/** A proposed (or user-adjusted) split of the uncommitted changes */
+export interface CommitSplitPlan {
+  /** Commits in the order they will be created */
+  commits: SplitCommit[]
+  /** Every hunk of the uncommitted changes; unassigned hunks stay uncommitted */
+  hunks: SplitHunk[]
+}
@@ -0,0 +1,69 @@
+/** Git operation that stopped on conflicts */
+export type ConflictOperation = 'merge' | 'rebase' | 'cherry_pick' | 'revert'
+
+/** How a file is unmerged (from the `git status` XY code) */
+export type ConflictStatus =
+  | 'both_modified'
+  | 'both_added'
+  | 'deleted_by_us'
+  | 'deleted_by_them'
+  | 'added_by_us'
+  | 'added_by_them'
+  | 'both_deleted'
+
+/** One conflict region (<<<<<<< .. >>>>>>>) in a working file */
+export interface ConflictHunk {
+  /** Position among the file's remaining hunks */
+  index: number
+  /** 1-based line of the <<<<<<< marker */
+  start_line: number
+  /** 1-based line of the >>>>>>> marker */
+  end_line: number
+  ours: string
+  /** Common ancestor version, when known */
+  base: string | null
+  theirs: string
+}
+
+/** An unmerged file with its three versions and remaining conflict hunks */
+export interface FileConflict {
+  path: string
+  status: ConflictStatus
+  /** Binary files have no contents or hunks; resolve them by taking a side */
+  binary: boolean
+  ours_label: string | null
+  theirs_label: string | null
+  /** Stage 1 (common ancestor) */
+  base: string | null
+  /** Stage 2 (current branch; the upstream during a rebase) */
+  ours: string | null
+  /** Stage 3 (incoming changes; the replayed commit during a rebase) */
+  theirs: string | null
+  /** Conflict hunks still in the working file */
+  hunks: ConflictHunk[]
+}
+
+/** Conflicts in a worktree and the operation that produced them */
+export interface ConflictState {
+  /** null when no merge/rebase/cherry-pick/revert is in progress */
+  operation: ConflictOperation | null
+  files: FileConflict[]
+}
+
+/** Resolution for a single conflict hunk */
+export type HunkResolution =
+  | { kind: 'ours' }
+  | { kind: 'theirs' }
+  | { kind: 'both' }
+  | { kind: 'base' }
+  /** Hand-edited or AI-suggested content */
+  | { kind: 'manual'; content: string }
+
+/** Side to take for a whole file */
+export type ConflictSide = 'ours' | 'theirs'
+
+/** AI-suggested content for a conflict hunk */
+export interface ConflictSuggestion {
+  content: string
+  explanation: string | null
+}
@@ -2,6 +2,8 @@
  * Git diff types for displaying GitHub-style diffs
  */
 
+import type { ConflictState } from './conflicts'
+
 /** A single line in a diff hunk */
 export interface DiffLine {
   /** Line type: "context", "addition", "deletion" */
@@ -73,10 +75,48 @@ export interface CommitHistoryResult {
   hasMore: boolean
 }
 
+/** Changes selected within one hunk for partial stage/unstage/discard */
+export interface HunkSelection {
+  /** Index into `DiffFile.hunks` */
+  hunk_index: number
+  /**
+   * Indices into `DiffHunk.lines` of the additions/deletions to include.
+   * Omit to select every change in the hunk.
+   */
+  line_indices?: number[]
+}
+
+/** What to do with a commit in a rebase plan */
+export type RebaseAction = 'pick' | 'reword' | 'squash' | 'fixup' | 'drop'
+
+/** One commit in a rebase plan, in the order it should be applied */
+export interface RebaseStep {
+  sha: string
+  action: RebaseAction
+  /**
+   * Full commit message. Required for `reword`; on a `squash` it replaces the
+   * combined message of the squashed commit.
+   */
+  message?: string
+}
+
+/** The commits on a branch that a rebase plan can rewrite */
+export interface RebasePlan {
+  /** Commit the plan is applied onto (merge base with the target branch) */
+  base: string
+  /** Oldest first, all `pick`, with their current messages */
+  steps: RebaseStep[]
+}
+
+/** Outcome of applying a rebase plan */
+export type RebasePlanResult =
+  | { status: 'Success'; head: string }
+  | { status: 'Conflict'; message: string; state: ConflictState }
+
 /** Complete diff response */
 export interface GitDiff {
   /** Type of diff: "uncommitted", "branch", or "commit" */
-  diff_type: 'uncommitted' | 'branch' | 'commit'
+  diff_type: 'uncommitted' | 'staged' | 'unstaged' | 'branch' | 'commit'
   /** Base ref (e.g., "origin/main" or "HEAD") */
   base_ref: string
   /** Target ref (e.g., "HEAD" or "working directory") */
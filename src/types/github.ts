@@ -243,6 +243,8 @@ export interface WorkflowRun {
   createdAt: string
   url: string
   workflowName: string
+  /** Run attempt, incremented by each re-run */
+  attempt: number
 }
 
 export interface WorkflowRunsResult {
@@ -250,6 +252,115 @@ export interface WorkflowRunsResult {
   failedCount: number
 }
 
+/** Input of a workflow_dispatch trigger */
+export interface WorkflowInput {
+  name: string
+  description: string | null
+  required: boolean
+  default: string | null
+  /** "string" | "boolean" | "choice" | "number" | "environment" */
+  inputType: string
+  /** Allowed values of a choice input */
+  options: string[]
+}
+
+export interface Workflow {
+  id: number
+  name: string
+  /** e.g. .github/workflows/ci.yml */
+  path: string
+  state: string // "active" | "disabled_manually" | ...
+  /** Has a workflow_dispatch trigger (in the local workflow file) */
+  dispatchable: boolean
+  inputs: WorkflowInput[]
+}
+
+/** Live update of a run started from Jean ('workflow-run:update') */
+export interface WorkflowRunEvent {
+  projectPath: string
+  run: WorkflowRun
+}
+
+export interface WorkflowStep {
+  number: number
+  name: string
+  status: string
+  conclusion: string | null
+  startedAt: string | null
+  completedAt: string | null
+  durationSecs: number | null
+}
+
+/** Check annotation pinned to a file and line */
+export interface WorkflowAnnotation {
+  path: string
+  startLine: number | null
+  endLine: number | null
+  level: string // "notice" | "warning" | "failure"
+  title: string | null
+  message: string
+}
+
+export interface WorkflowJob {
+  databaseId: number
+  name: string
+  status: string
+  conclusion: string | null
+  startedAt: string | null
+  completedAt: string | null
+  durationSecs: number | null
+  url: string
+  steps: WorkflowStep[]
+  /** Only fetched for jobs that didn't succeed */
+  annotations: WorkflowAnnotation[]
+}
+
+export interface WorkflowArtifact {
+  id: number
+  name: string
+  sizeInBytes: number
+  expired: boolean
+}
+
+export interface WorkflowRunDetails {
+  databaseId: number
+  name: string
+  workflowName: string
+  displayTitle: string
+  status: string
+  conclusion: string | null
+  event: string
+  headBranch: string
+  headSha: string
+  attempt: number
+  createdAt: string
+  updatedAt: string
+  durationSecs: number | null
+  url: string
+  jobs: WorkflowJob[]
+  artifacts: WorkflowArtifact[]
+}
+
+/** Job log line matching a search */
+export interface LogMatch {
+  /** 1-based line number in the job log */
+  lineNumber: number
+  step: string | null
+  line: string
+  before: string[]
+  after: string[]
+}
+
+/** Excerpt of a failed job's log around an error */
+export interface FailureSnippet {
+  jobId: number
+  jobName: string
+  step: string | null
+  /** 1-based line number of the first line */
+  startLine: number
+  lines: string[]
+}
+
 // =============================================================================
 // Attached Saved Context Types
 // =============================================================================
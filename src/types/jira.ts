@@ -0,0 +1,77 @@
+/**
+ * Jira issue types (Jira Cloud and Server/Data Center)
+ */
+
+export interface JiraStatus {
+  name: string
+  /** Status category key: "new" | "indeterminate" | "done" */
+  category: string
+}
+
+export interface JiraUser {
+  displayName: string
+}
+
+export interface JiraIssue {
+  id: string
+  /** e.g., "ENG-123" */
+  key: string
+  summary: string
+  /** Jira wiki markup */
+  description?: string
+  status: JiraStatus
+  issueType: string
+  priority?: string
+  labels: string[]
+  assignee?: JiraUser
+  created: string
+  updated: string
+  url: string
+}
+
+export interface JiraComment {
+  body: string
+  author?: JiraUser
+  created: string
+}
+
+/**
+ * A subtask or parent of an issue
+ */
+export interface JiraIssueRef {
+  key: string
+  summary: string
+  status?: JiraStatus
+}
+
+export interface JiraIssueDetail extends JiraIssue {
+  parent?: JiraIssueRef
+  subtasks: JiraIssueRef[]
+  comments: JiraComment[]
+}
+
+export interface JiraIssueListResult {
+  issues: JiraIssue[]
+}
+
+/**
+ * Context passed to create_worktree when creating a worktree from a Jira issue
+ */
+export interface JiraIssueContext {
+  key: string
+  summary: string
+  description?: string
+  comments: JiraComment[]
+  subtasks?: JiraIssueRef[]
+}
+
+/**
+ * Loaded Jira issue context info (from backend)
+ */
+export interface LoadedJiraIssueContext {
+  key: string
+  summary: string
+  commentCount: number
+  projectName: string
+  url?: string
+}
@@ -19,13 +19,55 @@ export type CheckStatus = 'success' | 'failure' | 'pending' | 'error'
 /**
  * High-level display status for UI
  */
-export type PrDisplayStatus = 'draft' | 'open' | 'review' | 'merged' | 'closed'
+export type PrDisplayStatus =
+  | 'draft'
+  | 'open'
+  | 'review'
+  /** In the merge queue (still open, may be ejected from the queue) */
+  | 'queued'
+  | 'merged'
+  | 'closed'
 
 /**
  * PR merge conflict status from GitHub API
  */
 export type MergeableStatus = 'mergeable' | 'conflicting' | 'unknown'
 
+/**
+ * Merge state from GitHub API (why a PR can or can't be merged right now)
+ */
+export type MergeStateStatus =
+  | 'clean'
+  | 'blocked'
+  | 'behind'
+  | 'dirty'
+  | 'draft'
+  | 'has_hooks'
+  | 'unstable'
+  | 'unknown'
+
+/**
+ * Auto-merge enabled on the PR
+ */
+export interface AutoMergeStatus {
+  merge_method: 'merge' | 'squash' | 'rebase'
+  enabled_by: string | null
+  enabled_at: string | null
+}
+
+/**
+ * Entry of the PR in the base branch's merge queue
+ */
+export interface MergeQueueStatus {
+  /** 1-based position in the queue */
+  position: number | null
+  /** QUEUED, AWAITING_CHECKS, MERGEABLE, UNMERGEABLE or LOCKED */
+  state: string
+  /** Estimated seconds until merged */
+  estimated_time_to_merge: number | null
+  enqueued_at: string | null
+}
+
 /**
  * PR status event from the backend
  */
@@ -39,5 +81,55 @@ export interface PrStatusEvent {
   check_status: CheckStatus | null
   display_status: PrDisplayStatus
   mergeable: MergeableStatus | null
+  merge_state_status: MergeStateStatus | null
+  auto_merge: AutoMergeStatus | null
+  merge_queue: MergeQueueStatus | null
+  /** Approvals required by branch protection (null if unknown) */
+  required_approving_review_count: number | null
+  approved_review_count: number
+  /** Required checks that haven't reported or haven't passed yet */
+  missing_required_checks: string[]
+  /** Head commit the checks belong to */
+  head_sha: string | null
   checked_at: number
 }
+
+/**
+ * CI auto-fix watcher phase
+ */
+export type CiFixPhase =
+  | 'watching'
+  | 'fetching_logs'
+  | 'fixing'
+  | 'pushing'
+  | 'pushed'
+  | 'passed'
+  | 'exhausted'
+  | 'error'
+  | 'stopped'
+
+/**
+ * CI auto-fix watcher state
+ */
+export interface CiFixWatcher {
+  worktreeId: string
+  /** Session the fix runs are sent to */
+  sessionId: string
+  maxAttempts: number
+  attempts: number
+  phase: CiFixPhase
+  /** Head commit whose failure was last handled */
+  lastHandledSha: string | null
+  /** Commit pushed by the last attempt */
+  lastFixSha: string | null
+  message: string | null
+  lastCheckedAt: number
+}
+
+/**
+ * CI auto-fix event from the backend ('ci-fix:update')
+ */
+export interface CiFixEvent extends CiFixWatcher {
+  /** Failed workflow runs the current attempt is based on */
+  runIds: number[]
+}
@@ -288,9 +288,16 @@ Investigate the failed GitHub Actions workflow run for "{workflowName}" on branc
 </context>
 
 
+<failure_context>
+
+{failureContext}
+
+</failure_context>
+
+
 <instructions>
 
-1. Use the GitHub CLI to fetch the workflow run logs: \`gh run view {runId} --log-failed\`
+1. Start from the failed jobs, steps and log excerpts above. If you need more, fetch the full logs with \`gh run view {runId} --log-failed\`
 2. Read the error output carefully to identify the failure cause
 3. Explore the relevant code in the codebase to understand the context
 4. Determine if this is a code issue, configuration issue, or flaky test
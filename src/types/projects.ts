@@ -1,4 +1,4 @@
-import type { LabelData } from '@/types/chat'
+import type { LabelData, PermissionPolicy } from '@/types/chat'
 
 /**
  * Type of session (base branch or worktree)
@@ -55,8 +55,79 @@ export interface Project {
   linear_api_key?: string | null
   /** Linear team ID to filter issues (undefined/null = show all teams) */
   linear_team_id?: string | null
+  /** Tool permission rules for AI sessions (combined with jean.json `permissions`) */
+  permission_policy?: PermissionPolicy | null
+  /** Sandbox settings for agent runs (Linux only) */
+  sandbox?: SandboxConfig | null
+  /** Defaults applied to pull requests created from this project's worktrees */
+  pr_defaults?: PrDefaults | null
+  /** Forge hosting the repository (undefined = detect from the origin remote) */
+  forge?: ForgeKind | null
+  /** Access token for a non-GitHub forge (undefined = environment / forge CLI) */
+  forge_token?: string | null
+  /** Web root of a self-hosted forge, e.g. "https://example.com/git" */
+  forge_url?: string | null
+  /** Jira site URL (Cloud: https://team.atlassian.net, or a Server/Data Center root) */
+  jira_url?: string | null
+  /** Account email for Jira Cloud API tokens (undefined = token is a Server/DC PAT) */
+  jira_email?: string | null
+  /** Jira API token (Cloud) or personal access token (Server/Data Center) */
+  jira_api_token?: string | null
+  /** JQL filter applied to issue lists, e.g. "project = ENG" */
+  jira_jql?: string | null
 }
 
+/** Code forge hosting a repository */
+export type ForgeKind = 'github' | 'gitlab' | 'gitea'
+
+/**
+ * Forge of a project, detected from its origin remote
+ */
+export interface ForgeInfo {
+  kind: ForgeKind
+  host: string
+  /** Project path on the forge, e.g. "group/subgroup/app" */
+  path: string
+  webUrl: string
+  /** Whether an access token was found (always true for GitHub, which uses gh) */
+  authenticated: boolean
+}
+
+/**
+ * Per-project sandbox settings for agent runs
+ */
+export interface SandboxConfig {
+  /** Restrict agent writes to the worktree, its git dir and Jean's data dirs */
+  enabled: boolean
+  /**
+   * Block network access for commands run by the agent. Only Codex can enforce
+   * this; Claude and OpenCode runs are refused while it is set
+   */
+  block_network: boolean
+  /** Additional writable paths (e.g. "~/.cargo") */
+  extra_writable_paths?: string[]
+}
+
+/**
+ * Per-project defaults for new pull requests
+ */
+export interface PrDefaults {
+  /** Open new PRs as drafts */
+  draft: boolean
+  /** Reviewers to request: GitHub logins or `org/team` slugs (leading `@` optional) */
+  reviewers?: string[]
+  /** Labels to add (e.g. "ai-generated") */
+  labels?: string[]
+  /** Assignees to add (`@me` assigns the authenticated user) */
+  assignees?: string[]
+  /** Milestone title */
+  milestone?: string | null
+  /** Enable auto-merge with this method */
+  auto_merge_method?: PrMergeMethod | null
+}
+
+export type PrMergeMethod = 'merge' | 'squash' | 'rebase'
+
 /**
  * Check if a project entry is a folder
  */
@@ -98,6 +169,8 @@ export interface Worktree {
   issue_number?: number
   /** Linear issue identifier (e.g. "ENG-123", if created from a Linear issue) */
   linear_issue_identifier?: string
+  /** Jira issue key (e.g. "ENG-123", if created from a Jira issue) */
+  jira_issue_key?: string
   /** Cached PR display status (draft, open, review, merged, closed) */
   cached_pr_status?: string
   /** Cached CI check status (success, failure, pending, error) */
@@ -132,6 +205,34 @@ export interface Worktree {
   archived_at?: number
   /** Unix timestamp when worktree was last opened/viewed by the user */
   last_opened_at?: number
+  /** Ports allocated to this worktree (when jean.json has a `ports` section) */
+  ports?: PortRange
+  /** Worktree this one is stacked on (its branch was created from the parent's branch) */
+  parent_worktree_id?: string
+  /** Parent commit the branch was last stacked on */
+  stack_base_commit?: string
+}
+
+/**
+ * A contiguous port range allocated to a worktree
+ */
+export interface PortRange {
+  /** First port (JEAN_PORT) */
+  start: number
+  /** Number of ports (JEAN_PORT, JEAN_PORT_1, ...) */
+  count: number
+}
+
+/**
+ * Result of restacking one worktree in a stack
+ */
+export interface RestackOutcome {
+  worktree_id: string
+  branch: string
+  /** Branch the worktree was rebased onto */
+  onto: string
+  success: boolean
+  message: string
 }
 
 // =============================================================================
@@ -161,6 +262,27 @@ export interface WorktreeCreateErrorEvent {
   error: string
 }
 
+// =============================================================================
+// jean.json Script Events (from Rust backend)
+// =============================================================================
+
+/** Event payload for a line of output from a running setup/teardown script or hook */
+export interface ScriptOutputEvent {
+  worktree_path: string
+  /** "setup", "teardown" or a hook name */
+  kind: string
+  line: string
+}
+
+/** Event payload when a setup/teardown script or hook finishes */
+export interface ScriptFinishedEvent {
+  worktree_path: string
+  kind: string
+  success: boolean
+  cancelled: boolean
+  timed_out: boolean
+}
+
 // =============================================================================
 // Worktree Deletion Events (from Rust backend)
 // =============================================================================
@@ -290,6 +412,8 @@ export interface CreatePrResponse {
   title: string
   /** Whether this PR already existed (was linked, not newly created) */
   existing: boolean
+  /** Project PR defaults (reviewers, labels, ...) that failed to apply */
+  defaults_errors?: string[]
 }
 
 /** Response from detecting an existing PR for the current branch */
@@ -309,6 +433,35 @@ export interface MergePrResponse {
   message: string
 }
 
+/** Lifecycle metadata of a worktree's linked PR */
+export interface PrDetails {
+  number: number
+  url: string
+  title: string
+  /** OPEN, CLOSED or MERGED */
+  state: string
+  isDraft: boolean
+  /** Pending review requests (user logins and team slugs) */
+  reviewers: string[]
+  labels: string[]
+  assignees: string[]
+  milestone: string | null
+  /** Merge method of the pending auto-merge, if enabled */
+  autoMergeMethod: PrMergeMethod | null
+}
+
+/** Metadata changes for `edit_pr_metadata` */
+export interface PrMetadataChanges {
+  addReviewers?: string[]
+  removeReviewers?: string[]
+  addLabels?: string[]
+  removeLabels?: string[]
+  addAssignees?: string[]
+  removeAssignees?: string[]
+  /** Milestone title; an empty string removes the milestone */
+  milestone?: string
+}
+
 // =============================================================================
 // AI-Powered Commit Creation
 // =============================================================================
@@ -374,6 +527,48 @@ export interface ReviewResponse {
   approval_status: 'approved' | 'changes_requested' | 'needs_discussion'
 }
 
+/** Result of publishing AI review findings as a GitHub PR review */
+export interface PublishedReview {
+  reviewId: number
+  htmlUrl: string
+  /** GitHub review state: PENDING, COMMENTED, APPROVED or CHANGES_REQUESTED */
+  state: string
+  /** Findings posted as inline comments */
+  inlineCount: number
+  /** Findings listed in the review body because their line isn't in the PR diff */
+  bodyCount: number
+  /** The verdict was posted as a comment (own pull request) */
+  downgraded: boolean
+}
+
+/** A comment in a PR review thread */
+export interface PrThreadComment {
+  author: string
+  body: string
+  createdAt: string
+  url: string
+}
+
+/** A review thread on a pull request */
+export interface PrReviewThread {
+  /** GraphQL node ID */
+  id: string
+  path: string
+  /** Line on the new side (null when outdated) */
+  line: number | null
+  startLine: number | null
+  isResolved: boolean
+  isOutdated: boolean
+  diffHunk: string
+  comments: PrThreadComment[]
+}
+
+/** Task list to send to a session in build mode */
+export interface AddressReviewTask {
+  prompt: string
+  threads: PrReviewThread[]
+}
+
 // =============================================================================
 // Release Notes
 // =============================================================================
@@ -0,0 +1,42 @@
+/**
+ * Lifecycle state of a jean.json service
+ */
+export type ServiceStatus =
+  | 'starting'
+  | 'running'
+  | 'healthy'
+  | 'unhealthy'
+  | 'restarting'
+  | 'crashed'
+  | 'exited'
+  | 'stopped'
+
+/**
+ * Status of a service in a worktree (also the `service:status` event payload)
+ */
+export interface ServiceInfo {
+  worktree_id: string
+  name: string
+  status: ServiceStatus
+  /** PID of the current process */
+  pid: number | null
+  /** Port from the readiness URL, if it has one */
+  port: number | null
+  /** Restarts since the last stable start */
+  restarts: number
+  exit_code: number | null
+  /** Unix timestamp when the current process was started */
+  started_at: number | null
+  /** Why the service crashed or is unhealthy */
+  message: string | null
+  log_path: string
+}
+
+/**
+ * Payload of `service:log` events (one line of service output)
+ */
+export interface ServiceLogEvent {
+  worktree_id: string
+  name: string
+  line: string
+}